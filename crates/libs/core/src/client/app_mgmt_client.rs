@@ -0,0 +1,379 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{
+        IFabricApplicationManagementClient10, IFabricApplicationUpgradeProgressResult2,
+    },
+    FabricTypes::{
+        FABRIC_APPLICATION_DESCRIPTION, FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        FABRIC_APPLICATION_UPGRADE_DESCRIPTION, FABRIC_DELETE_APPLICATION_DESCRIPTION,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE,
+        FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION, FABRIC_URI,
+    },
+};
+
+use crate::{
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        ApplicationDescription, ApplicationUpdateDescription, ApplicationUpgradeDescription,
+        ApplicationUpgradeProgress, DeleteApplicationDescription,
+        ProvisionApplicationTypeDescription, UnprovisionApplicationTypeDescription, Uri,
+    },
+};
+
+// Application Management Client
+#[derive(Debug, Clone)]
+pub struct ApplicationManagementClient {
+    com: IFabricApplicationManagementClient10,
+}
+
+impl ApplicationManagementClient {
+    pub fn get_com(&self) -> IFabricApplicationManagementClient10 {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl ApplicationManagementClient {
+    fn provision_application_type_internal(
+        &self,
+        desc: &FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginProvisionApplicationType3(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndProvisionApplicationType3(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn unprovision_application_type_internal(
+        &self,
+        desc: &FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUnprovisionApplicationType2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUnprovisionApplicationType2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn create_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_application_internal(
+        &self,
+        desc: &FABRIC_DELETE_APPLICATION_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteApplication2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteApplication2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn upgrade_application_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_UPGRADE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpgradeApplication(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpgradeApplication(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_upgrade_progress_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricApplicationUpgradeProgressResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationUpgradeProgress(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationUpgradeProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_next_application_upgrade_domain_internal(
+        &self,
+        progress: &IFabricApplicationUpgradeProgressResult2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveNextApplicationUpgradeDomain(progress, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndMoveNextApplicationUpgradeDomain(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn rollback_application_upgrade_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRollbackApplicationUpgrade(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRollbackApplicationUpgrade(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricApplicationManagementClient10> for ApplicationManagementClient {
+    fn from(com: IFabricApplicationManagementClient10) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ApplicationManagementClient> for IFabricApplicationManagementClient10 {
+    fn from(value: ApplicationManagementClient) -> Self {
+        value.com
+    }
+}
+
+// public implementation block
+
+impl ApplicationManagementClient {
+    /// Provisions an application type with the cluster, from an application package
+    /// copied to the image store or from an sfpkg in an external store.
+    /// An application type must be provisioned before applications of this type can be created.
+    pub async fn provision_application_type(
+        &self,
+        desc: &ProvisionApplicationTypeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            let ffi_raw = desc_raw.as_ffi();
+            self.provision_application_type_internal(
+                &ffi_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Removes an application type version from the cluster.
+    /// All application instances of this type version must be deleted first.
+    pub async fn unprovision_application_type(
+        &self,
+        desc: &UnprovisionApplicationTypeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw: FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION = desc.into();
+            self.unprovision_application_type_internal(
+                &raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Creates an application instance of a provisioned application type.
+    /// Default services declared in the application manifest are created as well.
+    pub async fn create_application(
+        &self,
+        desc: &ApplicationDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.create_application_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Updates the application capacity settings of an application instance.
+    pub async fn update_application(
+        &self,
+        desc: &ApplicationUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.update_application_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Deletes an application instance and all of its services.
+    pub async fn delete_application(
+        &self,
+        desc: &DeleteApplicationDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let raw: FABRIC_DELETE_APPLICATION_DESCRIPTION = desc.into();
+            self.delete_application_internal(&raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts upgrading an application instance to the target application type version.
+    /// The call returns once the upgrade is accepted; use get_application_upgrade_progress()
+    /// to monitor the upgrade.
+    pub async fn upgrade_application(
+        &self,
+        desc: &ApplicationUpgradeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.upgrade_application_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_application_upgrade_progress(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationUpgradeProgress> {
+        self.get_application_upgrade_progress_internal(
+            name.as_raw(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map(ApplicationUpgradeProgress::from_com)
+        .map_err(crate::Error::from)
+    }
+
+    /// Moves an UnmonitoredManual upgrade to the next upgrade domain.
+    /// progress must be the latest result of get_application_upgrade_progress().
+    pub async fn move_next_application_upgrade_domain(
+        &self,
+        progress: &ApplicationUpgradeProgress,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.move_next_application_upgrade_domain_internal(
+            progress.as_com(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Rolls back an in progress application upgrade to the previous application type version.
+    pub async fn rollback_application_upgrade(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.rollback_application_upgrade_internal(
+            name.as_raw(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+}
@@ -0,0 +1,397 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricClusterManagementClient4, IFabricUpgradeProgressResult2},
+    FabricTypes::{FABRIC_NODE_DEACTIVATION_INTENT, FABRIC_UPGRADE_DESCRIPTION},
+};
+
+use crate::{
+    PCWSTR, WString,
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{FabricUpgradeDescription, FabricUpgradeProgress, NodeDeactivationIntent},
+};
+
+// Cluster Management Client
+#[derive(Debug, Clone)]
+pub struct ClusterManagementClient {
+    com: IFabricClusterManagementClient4,
+}
+
+impl ClusterManagementClient {
+    pub fn get_com(&self) -> IFabricClusterManagementClient4 {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl ClusterManagementClient {
+    fn deactivate_node_internal(
+        &self,
+        node_name: PCWSTR,
+        intent: FABRIC_NODE_DEACTIVATION_INTENT,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeactivateNode(node_name, intent, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeactivateNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn activate_node_internal(
+        &self,
+        node_name: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginActivateNode(node_name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndActivateNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn node_state_removed_internal(
+        &self,
+        node_name: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginNodeStateRemoved(node_name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndNodeStateRemoved(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn recover_partitions_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginRecoverPartitions(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndRecoverPartitions(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn provision_fabric_internal(
+        &self,
+        code_file_path: PCWSTR,
+        cluster_manifest_file_path: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginProvisionFabric(
+                    code_file_path,
+                    cluster_manifest_file_path,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndProvisionFabric(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn unprovision_fabric_internal(
+        &self,
+        code_version: PCWSTR,
+        config_version: PCWSTR,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUnprovisionFabric(
+                    code_version,
+                    config_version,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndUnprovisionFabric(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn upgrade_fabric_internal(
+        &self,
+        desc: &FABRIC_UPGRADE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpgradeFabric(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpgradeFabric(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_fabric_upgrade_progress_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricUpgradeProgressResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetFabricUpgradeProgress(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetFabricUpgradeProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_next_fabric_upgrade_domain_internal(
+        &self,
+        progress: &IFabricUpgradeProgressResult2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveNextFabricUpgradeDomain(progress, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndMoveNextFabricUpgradeDomain(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn rollback_fabric_upgrade_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRollbackFabricUpgrade(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRollbackFabricUpgrade(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricClusterManagementClient4> for ClusterManagementClient {
+    fn from(com: IFabricClusterManagementClient4) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ClusterManagementClient> for IFabricClusterManagementClient4 {
+    fn from(value: ClusterManagementClient) -> Self {
+        value.com
+    }
+}
+
+fn get_pcwstr_from_opt_ref(opt: Option<&WString>) -> PCWSTR {
+    opt.map_or(PCWSTR::null(), |s| s.as_pcwstr())
+}
+
+// public implementation block
+
+impl ClusterManagementClient {
+    /// Deactivates a node with the given intent. SF moves the replicas off the node
+    /// according to the intent. The node is in Disabling state until the deactivation
+    /// completes, which can be observed via the node query.
+    /// Deactivating an already deactivated node with a higher intent is allowed.
+    pub async fn deactivate_node(
+        &self,
+        node_name: &WString,
+        intent: NodeDeactivationIntent,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.deactivate_node_internal(
+            node_name.as_pcwstr(),
+            intent.into(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Reverts a previous deactivate_node() so the node can host replicas again.
+    pub async fn activate_node(
+        &self,
+        node_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.activate_node_internal(
+            node_name.as_pcwstr(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Notifies SF that the state on a down node has been permanently lost,
+    /// so that replicas on it are considered lost.
+    /// WARNING: Calling this for a node whose state is not lost can cause data loss.
+    pub async fn node_state_removed(
+        &self,
+        node_name: &WString,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.node_state_removed_internal(
+            node_name.as_pcwstr(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Recovers all partitions that are stuck in quorum loss.
+    /// This should only be used when the down replicas cannot be brought back.
+    pub async fn recover_partitions(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.recover_partitions_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map_err(crate::Error::from)
+    }
+
+    /// Provisions the fabric code package and/or cluster manifest
+    /// in the image store so that they can be used for an upgrade.
+    /// Paths are relative to the image store root.
+    pub async fn provision_fabric(
+        &self,
+        code_file_path: Option<&WString>,
+        cluster_manifest_file_path: Option<&WString>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.provision_fabric_internal(
+            get_pcwstr_from_opt_ref(code_file_path),
+            get_pcwstr_from_opt_ref(cluster_manifest_file_path),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Unprovisions a fabric code and/or config version that is no longer in use.
+    pub async fn unprovision_fabric(
+        &self,
+        code_version: Option<&WString>,
+        config_version: Option<&WString>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.unprovision_fabric_internal(
+            get_pcwstr_from_opt_ref(code_version),
+            get_pcwstr_from_opt_ref(config_version),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts upgrading the cluster to the provisioned code and/or config version.
+    /// The call returns once the upgrade is accepted; use get_fabric_upgrade_progress()
+    /// to monitor the upgrade.
+    pub async fn upgrade_fabric(
+        &self,
+        desc: &FabricUpgradeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.upgrade_fabric_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_fabric_upgrade_progress(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<FabricUpgradeProgress> {
+        self.get_fabric_upgrade_progress_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map(FabricUpgradeProgress::from_com)
+            .map_err(crate::Error::from)
+    }
+
+    /// Moves an UnmonitoredManual fabric upgrade to the next upgrade domain.
+    /// progress must be the latest result of get_fabric_upgrade_progress().
+    pub async fn move_next_fabric_upgrade_domain(
+        &self,
+        progress: &FabricUpgradeProgress,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.move_next_fabric_upgrade_domain_internal(
+            progress.as_com(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Rolls back an in progress fabric upgrade to the previous version.
+    pub async fn rollback_fabric_upgrade(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.rollback_fabric_upgrade_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map_err(crate::Error::from)
+    }
+}
@@ -0,0 +1,885 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{collections::VecDeque, ffi::c_void, time::Duration};
+
+use mssf_com::{
+    FabricClient::{
+        IFabricChaosDescriptionResult, IFabricChaosEventsSegmentResult, IFabricChaosReportResult,
+        IFabricChaosScheduleDescriptionResult, IFabricFaultManagementClient,
+        IFabricMovePrimaryResult, IFabricMoveSecondaryResult,
+        IFabricPartitionDataLossProgressResult, IFabricPartitionQuorumLossProgressResult,
+        IFabricPartitionRestartProgressResult, IFabricRestartDeployedCodePackageResult,
+        IFabricRestartNodeResult, IFabricStartNodeResult, IFabricStopNodeResult,
+        IFabricTestManagementClient4,
+    },
+    FabricTypes::{
+        FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION, FABRIC_CHAOS_EVENTS_SEGMENT_DESCRIPTION,
+        FABRIC_CHAOS_EVENTS_SEGMENT_FILTER, FABRIC_CHAOS_REPORT_FILTER,
+        FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION, FABRIC_GET_CHAOS_REPORT_DESCRIPTION,
+        FABRIC_MOVE_PRIMARY_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME, FABRIC_MOVE_PRIMARY_DESCRIPTION2,
+        FABRIC_MOVE_SECONDARY_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME, FABRIC_MOVE_SECONDARY_DESCRIPTION2,
+        FABRIC_QUERY_PAGING_DESCRIPTION,
+        FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME,
+        FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2,
+        FABRIC_RESTART_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_RESTART_NODE_DESCRIPTION2,
+        FABRIC_START_CHAOS_DESCRIPTION, FABRIC_START_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_START_NODE_DESCRIPTION2,
+        FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION,
+        FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION, FABRIC_START_PARTITION_RESTART_DESCRIPTION,
+        FABRIC_STOP_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+        FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_STOP_NODE_DESCRIPTION2,
+    },
+};
+
+use crate::{
+    GUID, WString,
+    runtime::executor::BoxedCancelToken,
+    strings::get_pcwstr_from_opt,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        CancelTestCommandDescription, ChaosDescription, ChaosEvent, ChaosEventsFilter,
+        ChaosEventsSegment, ChaosParameters, ChaosReport, ChaosScheduleDescription,
+        DeployedCodePackageResult, MovePrimaryDescription, MovePrimaryResult,
+        MoveSecondaryDescription, MoveSecondaryResult, NodeResult, PagedQueryDescription,
+        PartitionFaultProgress, RestartDeployedCodePackageDescription, RestartNodeDescription,
+        StartNodeDescription, StartPartitionDataLossDescription,
+        StartPartitionQuorumLossDescription, StartPartitionRestartDescription, StopNodeDescription,
+    },
+};
+
+/// Fault Management Client
+/// Injects faults into nodes, code packages and partitions.
+/// Partition faults are started with a caller chosen operation id, and the
+/// progress is polled with the same id until it reaches a terminal state.
+#[derive(Debug, Clone)]
+pub struct FaultManagementClient {
+    com: IFabricFaultManagementClient,
+    test_com: IFabricTestManagementClient4,
+}
+
+impl FaultManagementClient {
+    pub(crate) fn new(
+        com: IFabricFaultManagementClient,
+        test_com: IFabricTestManagementClient4,
+    ) -> Self {
+        Self { com, test_com }
+    }
+
+    pub fn get_com(&self) -> IFabricFaultManagementClient {
+        self.com.clone()
+    }
+
+    pub fn get_test_com(&self) -> IFabricTestManagementClient4 {
+        self.test_com.clone()
+    }
+}
+
+// internal implementation block
+
+impl FaultManagementClient {
+    fn restart_node_internal(
+        &self,
+        desc: &FABRIC_RESTART_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricRestartNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginRestartNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndRestartNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_node_internal(
+        &self,
+        desc: &FABRIC_START_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricStartNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStartNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStartNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn stop_node_internal(
+        &self,
+        desc: &FABRIC_STOP_NODE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricStopNodeResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStopNode(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStopNode(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn restart_deployed_code_package_internal(
+        &self,
+        desc: &FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricRestartDeployedCodePackageResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginRestartDeployedCodePackage(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndRestartDeployedCodePackage(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_primary_internal(
+        &self,
+        desc: &FABRIC_MOVE_PRIMARY_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricMovePrimaryResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginMovePrimary(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndMovePrimary(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn move_secondary_internal(
+        &self,
+        desc: &FABRIC_MOVE_SECONDARY_DESCRIPTION2,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricMoveSecondaryResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginMoveSecondary(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndMoveSecondary(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_partition_data_loss_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionDataLoss(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionDataLoss(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_data_loss_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricPartitionDataLossProgressResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionDataLossProgress(operation_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetPartitionDataLossProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_partition_quorum_loss_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionQuorumLoss(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionQuorumLoss(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_quorum_loss_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricPartitionQuorumLossProgressResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionQuorumLossProgress(
+                    operation_id,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndGetPartitionQuorumLossProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_partition_restart_internal(
+        &self,
+        desc: &FABRIC_START_PARTITION_RESTART_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginStartPartitionRestart(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndStartPartitionRestart(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_restart_progress_internal(
+        &self,
+        operation_id: GUID,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricPartitionRestartProgressResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionRestartProgress(operation_id, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetPartitionRestartProgress(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn cancel_test_command_internal(
+        &self,
+        desc: &FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCancelTestCommand(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCancelTestCommand(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn start_chaos_internal(
+        &self,
+        desc: &FABRIC_START_CHAOS_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStartChaos(desc, timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStartChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn stop_chaos_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginStopChaos(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndStopChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricChaosDescriptionResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetChaos(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndGetChaos(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_schedule_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricChaosScheduleDescriptionResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetChaosSchedule(timeout_milliseconds, callback) },
+            move |ctx| unsafe { com2.EndGetChaosSchedule(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn set_chaos_schedule_internal(
+        &self,
+        desc: &FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginSetChaosSchedule(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndSetChaosSchedule(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_events_internal(
+        &self,
+        desc: &FABRIC_CHAOS_EVENTS_SEGMENT_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricChaosEventsSegmentResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetChaosEvents(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetChaosEvents(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_chaos_report_internal(
+        &self,
+        desc: &FABRIC_GET_CHAOS_REPORT_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricChaosReportResult>> {
+        let com1 = &self.test_com;
+        let com2 = self.test_com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetChaosReport(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetChaosReport(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+// public implementation block
+
+impl FaultManagementClient {
+    /// Restarts a node. The returned node instance is the one before restart.
+    pub async fn restart_node(
+        &self,
+        desc: &RestartNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        {
+            let inner = FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_RESTART_NODE_DESCRIPTION2 {
+                Kind: FABRIC_RESTART_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.restart_node_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map(|r| NodeResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts a node that was stopped by stop_node.
+    pub async fn start_node(
+        &self,
+        desc: &StartNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        {
+            let inner = FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_START_NODE_DESCRIPTION2 {
+                Kind: FABRIC_START_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.start_node_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map(|r| NodeResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Stops a node. The node stays down until start_node is called.
+    pub async fn stop_node(
+        &self,
+        desc: &StopNodeDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeResult> {
+        {
+            let inner = FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_STOP_NODE_DESCRIPTION2 {
+                Kind: FABRIC_STOP_NODE_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.stop_node_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map(|r| NodeResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn restart_deployed_code_package(
+        &self,
+        desc: &RestartDeployedCodePackageDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedCodePackageResult> {
+        {
+            let inner =
+                FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION2 {
+                Kind: FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.restart_deployed_code_package_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|r| DeployedCodePackageResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn move_primary(
+        &self,
+        desc: &MovePrimaryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<MovePrimaryResult> {
+        {
+            let inner = FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_MOVE_PRIMARY_DESCRIPTION2 {
+                Kind: FABRIC_MOVE_PRIMARY_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.move_primary_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map(|r| MovePrimaryResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn move_secondary(
+        &self,
+        desc: &MoveSecondaryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<MoveSecondaryResult> {
+        {
+            let inner = FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME::from(desc);
+            let desc_raw = FABRIC_MOVE_SECONDARY_DESCRIPTION2 {
+                Kind: FABRIC_MOVE_SECONDARY_DESCRIPTION_KIND_USING_NODE_NAME,
+                Value: &inner as *const _ as *mut c_void,
+            };
+            self.move_secondary_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map(|r| MoveSecondaryResult::from(unsafe { r.get_Result().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts inducing data loss on a partition.
+    /// The call returns once the command is accepted.
+    pub async fn start_partition_data_loss(
+        &self,
+        desc: &StartPartitionDataLossDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let selector = desc.partition_selector.build_raw();
+            let desc_raw = desc.as_ffi(&selector);
+            self.start_partition_data_loss_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_partition_data_loss_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionFaultProgress> {
+        self.get_partition_data_loss_progress_internal(
+            operation_id,
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map(|r| PartitionFaultProgress::from(unsafe { r.get_Progress().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts inducing quorum loss on a partition.
+    pub async fn start_partition_quorum_loss(
+        &self,
+        desc: &StartPartitionQuorumLossDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let selector = desc.partition_selector.build_raw();
+            let desc_raw = desc.as_ffi(&selector);
+            self.start_partition_quorum_loss_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_partition_quorum_loss_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionFaultProgress> {
+        self.get_partition_quorum_loss_progress_internal(
+            operation_id,
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map(|r| PartitionFaultProgress::from(unsafe { r.get_Progress().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts restarting some or all replicas of a partition.
+    pub async fn start_partition_restart(
+        &self,
+        desc: &StartPartitionRestartDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let selector = desc.partition_selector.build_raw();
+            let desc_raw = desc.as_ffi(&selector);
+            self.start_partition_restart_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_partition_restart_progress(
+        &self,
+        operation_id: GUID,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionFaultProgress> {
+        self.get_partition_restart_progress_internal(
+            operation_id,
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map(|r| PartitionFaultProgress::from(unsafe { r.get_Progress().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Cancels a running partition fault command.
+    pub async fn cancel_test_command(
+        &self,
+        desc: &CancelTestCommandDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION::from(desc);
+            self.cancel_test_command_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Starts chaos in the cluster. Fails if chaos is already running.
+    pub async fn start_chaos(
+        &self,
+        parameters: &ChaosParameters,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let params_raw = parameters.build_raw();
+            let desc_raw = FABRIC_START_CHAOS_DESCRIPTION {
+                ChaosParameters: params_raw.as_ffi() as *const _ as *mut _,
+                Reserved: std::ptr::null_mut(),
+            };
+            self.start_chaos_internal(&desc_raw, timeout.as_millis() as u32, cancellation_token)
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Stops chaos, and the chaos schedule if one is active.
+    pub async fn stop_chaos(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.stop_chaos_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map_err(crate::Error::from)
+    }
+
+    pub async fn get_chaos(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosDescription> {
+        self.get_chaos_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map(|r| {
+                ChaosDescription::from(unsafe { r.get_ChaosDescriptionResult().as_ref().unwrap() })
+            })
+            .map_err(crate::Error::from)
+    }
+
+    pub async fn get_chaos_schedule(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosScheduleDescription> {
+        self.get_chaos_schedule_internal(timeout.as_millis() as u32, cancellation_token)
+            .await?
+            .map(|r| {
+                ChaosScheduleDescription::from(unsafe {
+                    r.get_ChaosScheduleDescriptionResult().as_ref().unwrap()
+                })
+            })
+            .map_err(crate::Error::from)
+    }
+
+    /// The version in desc must match the version returned by get_chaos_schedule.
+    pub async fn set_chaos_schedule(
+        &self,
+        desc: &ChaosScheduleDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.set_chaos_schedule_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets one page of chaos events.
+    /// SF requires the filter for the first page, and the continuation token
+    /// in paging for the following pages.
+    pub async fn get_chaos_events_segment(
+        &self,
+        filter: Option<&ChaosEventsFilter>,
+        paging: &PagedQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosEventsSegment> {
+        {
+            let filter_raw = filter.map(FABRIC_CHAOS_EVENTS_SEGMENT_FILTER::from);
+            let paging_raw = FABRIC_QUERY_PAGING_DESCRIPTION {
+                ContinuationToken: get_pcwstr_from_opt(&paging.continuation_token),
+                MaxResults: paging.max_results.unwrap_or(0),
+                Reserved: std::ptr::null_mut(),
+            };
+            let desc_raw = FABRIC_CHAOS_EVENTS_SEGMENT_DESCRIPTION {
+                Filter: filter_raw
+                    .as_ref()
+                    .map_or(std::ptr::null(), |f| f as *const _),
+                PagingDescription: &paging_raw,
+                Reserved: std::ptr::null_mut(),
+            };
+            self.get_chaos_events_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(ChaosEventsSegment::from)
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets all chaos events within the filter, following continuation tokens.
+    /// Pages of at most max_results events are fetched lazily, each with the given timeout.
+    pub fn get_chaos_events(
+        &self,
+        filter: ChaosEventsFilter,
+        max_results: Option<i32>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> ChaosEventStream {
+        ChaosEventStream {
+            client: self.clone(),
+            filter,
+            max_results,
+            timeout,
+            cancellation_token,
+            buffered: VecDeque::new(),
+            continuation_token: None,
+            finished: false,
+        }
+    }
+
+    /// Gets the chaos status and a page of the event history.
+    /// Either the filter or the continuation token of the previous report must be set.
+    pub async fn get_chaos_report(
+        &self,
+        filter: Option<&ChaosEventsFilter>,
+        continuation_token: Option<&WString>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ChaosReport> {
+        {
+            let mut filter_raw = filter.map(FABRIC_CHAOS_REPORT_FILTER::from);
+            let desc_raw = FABRIC_GET_CHAOS_REPORT_DESCRIPTION {
+                Filter: filter_raw
+                    .as_mut()
+                    .map_or(std::ptr::null_mut(), |f| f as *mut _),
+                ContinuationToken: continuation_token
+                    .map_or(crate::PCWSTR::null(), |t| t.as_pcwstr()),
+                Reserved: std::ptr::null_mut(),
+            };
+            self.get_chaos_report_internal(
+                &desc_raw,
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(ChaosReport::from)
+        .map_err(crate::Error::from)
+    }
+}
+
+/// Chaos events returned by FaultManagementClient::get_chaos_events.
+/// Next pages are fetched from SF when the buffered page is drained.
+pub struct ChaosEventStream {
+    client: FaultManagementClient,
+    filter: ChaosEventsFilter,
+    max_results: Option<i32>,
+    timeout: Duration,
+    cancellation_token: Option<BoxedCancelToken>,
+    buffered: VecDeque<ChaosEvent>,
+    continuation_token: Option<WString>,
+    finished: bool,
+}
+
+impl ChaosEventStream {
+    /// Returns None when all pages are consumed.
+    /// After an error is returned the stream ends.
+    pub async fn next(&mut self) -> Option<crate::Result<ChaosEvent>> {
+        loop {
+            if let Some(event) = self.buffered.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.finished {
+                return None;
+            }
+            let paging = PagedQueryDescription {
+                continuation_token: self.continuation_token.take(),
+                max_results: self.max_results,
+            };
+            // The filter is only needed for the first page.
+            let filter = match paging.continuation_token {
+                Some(_) => None,
+                None => Some(&self.filter),
+            };
+            let segment = self
+                .client
+                .get_chaos_events_segment(
+                    filter,
+                    &paging,
+                    self.timeout,
+                    self.cancellation_token.clone(),
+                )
+                .await;
+            match segment {
+                Ok(segment) => {
+                    self.buffered.extend(segment.iter());
+                    self.continuation_token = segment.get_continuation_token();
+                    self.finished = self.continuation_token.is_none();
+                }
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
@@ -3,29 +3,51 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
+use std::time::Duration;
+
 use mssf_com::{
-    FabricClient::IFabricHealthClient4,
+    FabricClient::{
+        IFabricApplicationHealthResult, IFabricClusterHealthResult,
+        IFabricDeployedApplicationHealthResult, IFabricDeployedServicePackageHealthResult,
+        IFabricGetClusterHealthChunkResult, IFabricHealthClient4, IFabricNodeHealthResult,
+        IFabricPartitionHealthResult, IFabricReplicaHealthResult, IFabricServiceHealthResult,
+    },
     FabricTypes::{
-        FABRIC_APPLICATION_HEALTH_REPORT, FABRIC_CLUSTER_HEALTH_REPORT,
-        FABRIC_DEPLOYED_APPLICATION_HEALTH_REPORT, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_REPORT,
-        FABRIC_HEALTH_INFORMATION, FABRIC_HEALTH_REPORT, FABRIC_HEALTH_REPORT_KIND_APPLICATION,
+        FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION, FABRIC_APPLICATION_HEALTH_REPORT,
+        FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION, FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_CLUSTER_HEALTH_REPORT, FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_APPLICATION_HEALTH_REPORT,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_REPORT, FABRIC_HEALTH_INFORMATION,
+        FABRIC_HEALTH_REPORT, FABRIC_HEALTH_REPORT_KIND_APPLICATION,
         FABRIC_HEALTH_REPORT_KIND_CLUSTER, FABRIC_HEALTH_REPORT_KIND_DEPLOYED_APPLICATION,
         FABRIC_HEALTH_REPORT_KIND_DEPLOYED_SERVICE_PACKAGE, FABRIC_HEALTH_REPORT_KIND_INVALID,
         FABRIC_HEALTH_REPORT_KIND_NODE, FABRIC_HEALTH_REPORT_KIND_PARTITION,
         FABRIC_HEALTH_REPORT_KIND_SERVICE, FABRIC_HEALTH_REPORT_KIND_STATEFUL_SERVICE_REPLICA,
-        FABRIC_HEALTH_REPORT_KIND_STATELESS_SERVICE_INSTANCE, FABRIC_NODE_HEALTH_REPORT,
-        FABRIC_PARTITION_HEALTH_REPORT, FABRIC_SERVICE_HEALTH_REPORT,
+        FABRIC_HEALTH_REPORT_KIND_STATELESS_SERVICE_INSTANCE, FABRIC_NODE_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_NODE_HEALTH_REPORT, FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_PARTITION_HEALTH_REPORT, FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION,
+        FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION, FABRIC_SERVICE_HEALTH_REPORT,
         FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_REPORT,
         FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_REPORT, FABRIC_URI,
     },
 };
 
-use crate::types::HealthReport;
+use crate::{
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        ApplicationHealth, ApplicationHealthQueryDescription, ClusterHealth, ClusterHealthChunk,
+        ClusterHealthChunkQueryDescription, ClusterHealthQueryDescription,
+        DeployedApplicationHealth, DeployedApplicationHealthQueryDescription,
+        DeployedServicePackageHealth, DeployedServicePackageHealthQueryDescription, HealthReport,
+        NodeHealth, NodeHealthQueryDescription, PartitionHealth, PartitionHealthQueryDescription,
+        ReplicaHealth, ReplicaHealthQueryDescription, ServiceHealth, ServiceHealthQueryDescription,
+    },
+};
 
 /// Provides functionality to perform health related operations, like report and query health.
 /// See C# API [here](https://docs.microsoft.com/en-us/dotnet/api/system.fabric.fabricclient.healthclient?view=azure-dotnet).
-///
-/// TODO: Implement full functionality of the HealthClient.
 #[derive(Debug, Clone)]
 pub struct HealthClient {
     com: IFabricHealthClient4,
@@ -43,6 +65,162 @@ impl From<HealthClient> for IFabricHealthClient4 {
     }
 }
 
+// internal implementation block
+impl HealthClient {
+    fn get_cluster_health_internal(
+        &self,
+        desc: &FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricClusterHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_node_health_internal(
+        &self,
+        desc: &FABRIC_NODE_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricNodeHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetNodeHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetNodeHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_health_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricApplicationHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_health_internal(
+        &self,
+        desc: &FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricServiceHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_partition_health_internal(
+        &self,
+        desc: &FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricPartitionHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetPartitionHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetPartitionHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_replica_health_internal(
+        &self,
+        desc: &FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricReplicaHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetReplicaHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetReplicaHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_application_health_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricDeployedApplicationHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedApplicationHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedApplicationHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_service_package_health_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricDeployedServicePackageHealthResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedServicePackageHealth2(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedServicePackageHealth2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_cluster_health_chunk_internal(
+        &self,
+        desc: &FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetClusterHealthChunkResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterHealthChunk(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterHealthChunk(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
 // Public implementation block
 impl HealthClient {
     /// Reports health on a Service Fabric entity. See C# API [here](https://docs.microsoft.com/en-us/dotnet/api/system.fabric.fabricclient.healthclient.reporthealth?view=azure-dotnet).
@@ -192,4 +370,199 @@ impl HealthClient {
             }
         }.map_err(crate::Error::from)
     }
+
+    /// Gets the health of the cluster, including the health states of nodes and applications
+    /// selected by the filters.
+    pub async fn get_cluster_health(
+        &self,
+        desc: &ClusterHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ClusterHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_cluster_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| ClusterHealth::from(unsafe { res.get_ClusterHealth().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of a node.
+    pub async fn get_node_health(
+        &self,
+        desc: &NodeHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_node_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| NodeHealth::from(unsafe { res.get_NodeHealth().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of an application, including its services and deployed applications.
+    pub async fn get_application_health(
+        &self,
+        desc: &ApplicationHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_application_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| {
+            ApplicationHealth::from(unsafe { res.get_ApplicationHealth().as_ref().unwrap() })
+        })
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of a service and its partitions.
+    pub async fn get_service_health(
+        &self,
+        desc: &ServiceHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_service_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| ServiceHealth::from(unsafe { res.get_ServiceHealth().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of a partition and its replicas.
+    pub async fn get_partition_health(
+        &self,
+        desc: &PartitionHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PartitionHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_partition_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| PartitionHealth::from(unsafe { res.get_PartitionHealth().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of a stateful replica or stateless instance.
+    pub async fn get_replica_health(
+        &self,
+        desc: &ReplicaHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ReplicaHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_replica_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| ReplicaHealth::from(unsafe { res.get_ReplicaHealth().as_ref().unwrap() }))
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of an application deployed on a node.
+    pub async fn get_deployed_application_health(
+        &self,
+        desc: &DeployedApplicationHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedApplicationHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_deployed_application_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| {
+            DeployedApplicationHealth::from(unsafe {
+                res.get_DeployedApplicationHealth().as_ref().unwrap()
+            })
+        })
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the health of a service package deployed on a node.
+    pub async fn get_deployed_service_package_health(
+        &self,
+        desc: &DeployedServicePackageHealthQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedServicePackageHealth> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_deployed_service_package_health_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| {
+            DeployedServicePackageHealth::from(unsafe {
+                res.get_DeployedServicePackageHealth().as_ref().unwrap()
+            })
+        })
+        .map_err(crate::Error::from)
+    }
+
+    /// Gets the cluster health with the nodes and applications that match the filters.
+    /// Unlike get_cluster_health, children can be filtered by name and the result is paged
+    /// by SF if too large, see the total counts in the chunk lists.
+    pub async fn get_cluster_health_chunk(
+        &self,
+        desc: &ClusterHealthChunkQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ClusterHealthChunk> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_cluster_health_chunk_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(|res| {
+            ClusterHealthChunk::from(unsafe { res.get_ClusterHealthChunk().as_ref().unwrap() })
+        })
+        .map_err(crate::Error::from)
+    }
 }
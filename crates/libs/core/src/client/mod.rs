@@ -10,9 +10,12 @@ use crate::{
 use connection::{ClientConnectionEventHandlerBridge, LambdaClientConnectionNotificationHandler};
 use health_client::HealthClient;
 use mssf_com::FabricClient::{
-    IFabricClientConnectionEventHandler, IFabricClientSettings2, IFabricHealthClient4,
-    IFabricPropertyManagementClient2, IFabricQueryClient10, IFabricServiceManagementClient6,
-    IFabricServiceNotificationEventHandler,
+    IFabricApplicationManagementClient10, IFabricClientConnectionEventHandler,
+    IFabricClientSettings2, IFabricClusterManagementClient4, IFabricFaultManagementClient,
+    IFabricHealthClient4, IFabricPropertyManagementClient2, IFabricQueryClient10,
+    IFabricRepairManagementClient2, IFabricServiceGroupManagementClient4,
+    IFabricServiceManagementClient6, IFabricServiceNotificationEventHandler,
+    IFabricTestManagementClient4,
 };
 use notification::{
     LambdaServiceNotificationHandler, ServiceNotificationEventHandler,
@@ -27,14 +30,30 @@ mod connection;
 mod notification;
 
 // Export public client modules
+mod app_mgmt_client;
+mod cluster_mgmt_client;
+mod fault_mgmt_client;
 pub mod health_client;
 mod property_client;
 pub mod query_client;
+mod query_stream;
+mod repair_mgmt_client;
+mod resolution;
+mod svc_group_mgmt_client;
 pub mod svc_mgmt_client;
 // reexport
+pub use app_mgmt_client::ApplicationManagementClient;
+pub use cluster_mgmt_client::ClusterManagementClient;
 pub use connection::GatewayInformationResult;
+pub use fault_mgmt_client::{ChaosEventStream, FaultManagementClient};
 pub use notification::ServiceNotification;
 pub use property_client::PropertyManagementClient;
+pub use query_stream::QueryStream;
+pub use repair_mgmt_client::RepairManagementClient;
+pub use resolution::{
+    ServicePartitionResolutionChangeHandle, ServicePartitionResolutionChangeHandler,
+};
+pub use svc_group_mgmt_client::ServiceGroupManagementClient;
 
 #[cfg(test)]
 mod tests;
@@ -270,6 +289,11 @@ pub struct FabricClient {
     service_client: ServiceManagementClient,
     query_client: QueryClient,
     health_client: HealthClient,
+    app_client: ApplicationManagementClient,
+    cluster_client: ClusterManagementClient,
+    // Optional interfaces, which older runtimes may not implement,
+    // are cast from this when requested.
+    com: IFabricPropertyManagementClient2,
 }
 
 impl FabricClient {
@@ -289,11 +313,22 @@ impl FabricClient {
             .unwrap();
         let com_query_client = com.clone().cast::<IFabricQueryClient10>().unwrap();
         let com_health_client = com.clone().cast::<IFabricHealthClient4>().unwrap();
+        let com_app_client = com
+            .clone()
+            .cast::<IFabricApplicationManagementClient10>()
+            .unwrap();
+        let com_cluster_client = com
+            .clone()
+            .cast::<IFabricClusterManagementClient4>()
+            .unwrap();
         Self {
             property_client: PropertyManagementClient::from(com_property_client),
             service_client: ServiceManagementClient::from(com_service_client),
             query_client: QueryClient::from(com_query_client),
             health_client: HealthClient::from(com_health_client),
+            app_client: ApplicationManagementClient::from(com_app_client),
+            cluster_client: ClusterManagementClient::from(com_cluster_client),
+            com,
         }
     }
 
@@ -316,4 +351,43 @@ impl FabricClient {
     pub fn get_health_manager(&self) -> &HealthClient {
         &self.health_client
     }
+
+    /// Get the client for managing application types, applications and application upgrades.
+    pub fn get_application_manager(&self) -> &ApplicationManagementClient {
+        &self.app_client
+    }
+
+    /// Get the client for managing nodes, fabric versions and cluster upgrades.
+    pub fn get_cluster_manager(&self) -> &ClusterManagementClient {
+        &self.cluster_client
+    }
+
+    /// Get the client for injecting node, code package and partition faults.
+    /// Fails with E_NOINTERFACE if the runtime does not support fault injection.
+    pub fn get_fault_manager(&self) -> crate::Result<FaultManagementClient> {
+        let com = self.com.cast::<IFabricFaultManagementClient>()?;
+        let test_com = self.com.cast::<IFabricTestManagementClient4>()?;
+        Ok(FaultManagementClient::new(com, test_com))
+    }
+
+    /// Get the client for creating and driving repair tasks.
+    /// Fails with E_NOINTERFACE if the runtime does not support repair tasks.
+    pub fn get_repair_manager(&self) -> crate::Result<RepairManagementClient> {
+        let com = self.com.cast::<IFabricRepairManagementClient2>()?;
+        Ok(RepairManagementClient::from(com))
+    }
+
+    /// Get the client for managing service groups.
+    /// Fails with E_NOINTERFACE if the runtime does not support service groups.
+    pub fn get_service_group_manager(&self) -> crate::Result<ServiceGroupManagementClient> {
+        let com = self.com.cast::<IFabricServiceGroupManagementClient4>()?;
+        Ok(ServiceGroupManagementClient::from(com))
+    }
+
+    /// Get the settings currently in effect for this client,
+    /// i.e. the SF defaults with the ones passed to the builder applied.
+    pub fn get_settings(&self) -> crate::Result<FabricClientSettings> {
+        let com = self.com.cast::<IFabricClientSettings2>()?;
+        FabricClientSettings::get(&com)
+    }
 }
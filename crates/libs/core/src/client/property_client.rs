@@ -7,9 +7,14 @@ use std::time::Duration;
 
 use crate::{
     WString,
+    error::ErrorCode,
     runtime::executor::BoxedCancelToken,
     sync::{FabricReceiver, fabric_begin_end_proxy},
-    types::{NameEnumerationResult, PropertyMetadataResult, PropertyValueResult, Uri},
+    types::{
+        NameEnumerationResult, NamedPropertyMetadata, PropertyBatchOperation, PropertyBatchResult,
+        PropertyEnumerationResult, PropertyMetadataResult, PropertyValue, PropertyValueResult,
+        PutCustomPropertyOperation, Uri,
+    },
 };
 use mssf_com::{
     FabricClient::{
@@ -309,9 +314,6 @@ impl PropertyManagementClient {
         )
     }
 
-    // TODO: implement this
-    // Batch operations are not supported yet.
-    #[allow(dead_code)]
     fn submit_property_batch_internal(
         &self,
         name: &Uri,
@@ -335,8 +337,6 @@ impl PropertyManagementClient {
         )
     }
 
-    // TODO: implement this
-    #[allow(dead_code)]
     fn enumerate_properties_internal(
         &self,
         name: &Uri,
@@ -362,8 +362,6 @@ impl PropertyManagementClient {
         )
     }
 
-    // TODO: implement this
-    #[allow(dead_code)]
     fn put_custom_property_operation_internal(
         &self,
         name: &Uri,
@@ -625,4 +623,160 @@ impl PropertyManagementClient {
         .map_err(|e| e.into())
         .map(PropertyValueResult::from_com)
     }
+
+    /// Submits a batch of property operations on a SF name.
+    /// The batch is applied atomically: if any operation fails, for example a
+    /// CheckSequence or CheckValue, none of the writes take effect and the
+    /// result reports the index of the failed operation.
+    /// This can be used for optimistic concurrency, i.e. check and set.
+    pub async fn submit_property_batch(
+        &self,
+        name: &Uri,
+        operations: &[PropertyBatchOperation],
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PropertyBatchResult> {
+        {
+            let batch_raw = PropertyBatchOperation::build_batch_raw(operations);
+            self.submit_property_batch_internal(
+                name,
+                batch_raw.as_ffi(),
+                timeout.as_millis().try_into().unwrap(),
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(|e| e.into())
+        .map(|(index, com)| PropertyBatchResult::from_com(com, index))
+    }
+
+    /// Enumerates properties of a SF name, one page at a time.
+    /// Pass the previous result to continue the enumeration, until its
+    /// status reports finished.
+    pub async fn enumerate_properties(
+        &self,
+        name: &Uri,
+        include_values: bool,
+        prev: Option<&PropertyEnumerationResult>,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<PropertyEnumerationResult> {
+        self.enumerate_properties_internal(
+            name,
+            include_values,
+            prev.map(|x| x.as_com()),
+            timeout.as_millis().try_into().unwrap(),
+            cancellation_token,
+        )
+        .await?
+        .map_err(|e| e.into())
+        .map(PropertyEnumerationResult::from_com)
+    }
+
+    /// Enumerates all properties with values of a SF name, following the
+    /// continuation until the enumeration is finished.
+    /// The timeout applies to each page.
+    pub async fn enumerate_all_properties(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Vec<(NamedPropertyMetadata, PropertyValue)>> {
+        let mut properties = Vec::new();
+        let mut prev: Option<PropertyEnumerationResult> = None;
+        loop {
+            let page = self
+                .enumerate_properties(
+                    name,
+                    true,
+                    prev.as_ref(),
+                    timeout,
+                    cancellation_token.clone(),
+                )
+                .await?;
+            for i in 0..page.get_property_count() {
+                let property = page.get_property(i)?;
+                let value = property.get_value()?;
+                let (meta, _) = property.get_named_property();
+                properties.push((meta, value));
+            }
+            if page.get_enumeration_status().is_finished() {
+                break;
+            }
+            prev = Some(page);
+        }
+        Ok(properties)
+    }
+
+    /// Put a property with a user defined custom type id to a SF name.
+    /// If the operation has a sequence number, the put only succeeds if the
+    /// property has that sequence number, otherwise FABRIC_E_PROPERTY_CHECK_FAILED
+    /// is returned. A failure of the put itself is then returned as E_FAIL.
+    pub async fn put_custom_property(
+        &self,
+        name: &Uri,
+        operation: &PutCustomPropertyOperation,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        if let Some(sequence_number) = operation.sequence_number {
+            // The single put api has no sequence check, so send both in a batch.
+            let batch = [
+                PropertyBatchOperation::CheckSequence {
+                    property_name: operation.property_name.clone(),
+                    sequence_number,
+                },
+                PropertyBatchOperation::PutCustom {
+                    property_name: operation.property_name.clone(),
+                    value: operation.value.clone(),
+                    custom_type_id: operation.custom_type_id.clone(),
+                },
+            ];
+            let res = self
+                .submit_property_batch(name, &batch, timeout, cancellation_token)
+                .await?;
+            return sequenced_put_result(res.get_failed_operation_index());
+        }
+        {
+            let operation_raw = operation.build_raw();
+            self.put_custom_property_operation_internal(
+                name,
+                operation_raw.as_ffi(),
+                timeout.as_millis().try_into().unwrap(),
+                cancellation_token,
+            )
+        }
+        .await??;
+        Ok(())
+    }
+}
+
+/// Maps the failed operation index of the [CheckSequence, PutCustom] batch of
+/// put_custom_property. Only a failed check is a sequence number conflict.
+fn sequenced_put_result(failed_operation_index: Option<u32>) -> crate::Result<()> {
+    match failed_operation_index {
+        None => Ok(()),
+        Some(0) => Err(ErrorCode::FABRIC_E_PROPERTY_CHECK_FAILED.into()),
+        // The put itself failed. The batch result does not carry the reason.
+        Some(_) => Err(ErrorCode::E_FAIL.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sequenced_put_result;
+    use crate::ErrorCode;
+
+    #[test]
+    fn test_sequenced_put_result() {
+        assert!(sequenced_put_result(None).is_ok());
+        let code = |i| {
+            sequenced_put_result(Some(i))
+                .unwrap_err()
+                .try_as_fabric_error_code()
+                .unwrap()
+        };
+        assert_eq!(code(0), ErrorCode::FABRIC_E_PROPERTY_CHECK_FAILED);
+        assert_eq!(code(1), ErrorCode::E_FAIL);
+    }
 }
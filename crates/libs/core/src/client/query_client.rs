@@ -6,29 +6,73 @@ use std::{ffi::c_void, time::Duration};
 
 use mssf_com::{
     FabricClient::{
+        IFabricGetApplicationListResult2, IFabricGetApplicationLoadInformationResult,
+        IFabricGetApplicationTypePagedListResult, IFabricGetClusterLoadInformationResult,
+        IFabricGetDeployedApplicationPagedListResult, IFabricGetDeployedCodePackageListResult,
+        IFabricGetDeployedReplicaListResult, IFabricGetDeployedServicePackageListResult,
         IFabricGetDeployedServiceReplicaDetailResult, IFabricGetNodeListResult2,
-        IFabricGetPartitionListResult2, IFabricGetPartitionLoadInformationResult,
-        IFabricGetReplicaListResult2, IFabricQueryClient10,
+        IFabricGetNodeLoadInformationResult, IFabricGetPartitionListResult2,
+        IFabricGetPartitionLoadInformationResult, IFabricGetProvisionedCodeVersionListResult,
+        IFabricGetProvisionedConfigVersionListResult, IFabricGetReplicaListResult2,
+        IFabricGetReplicaLoadInformationResult, IFabricGetServiceListResult2,
+        IFabricGetServiceTypeListResult, IFabricGetUnplacedReplicaInformationResult,
+        IFabricQueryClient10,
     },
     FabricTypes::{
-        FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION, FABRIC_NODE_QUERY_DESCRIPTION,
+        FABRIC_APPLICATION_DEFINITION_KIND_FILTER_DEFAULT,
+        FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_APPLICATION_QUERY_DESCRIPTION, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX1,
+        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX2, FABRIC_APPLICATION_QUERY_DESCRIPTION_EX3,
+        FABRIC_APPLICATION_QUERY_DESCRIPTION_EX4, FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION, FABRIC_NODE_QUERY_DESCRIPTION,
         FABRIC_NODE_QUERY_DESCRIPTION_EX1, FABRIC_NODE_QUERY_DESCRIPTION_EX2,
-        FABRIC_NODE_QUERY_DESCRIPTION_EX3, FABRIC_PARTITION_LOAD_INFORMATION_QUERY_DESCRIPTION,
-        FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION, FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_NODE_QUERY_DESCRIPTION_EX3, FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION,
+        FABRIC_PARTITION_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION, FABRIC_QUERY_PAGING_DESCRIPTION,
+        FABRIC_QUERY_SERVICE_REPLICA_STATUS_FILTER_DEFAULT,
+        FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION, FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION_EX1,
+        FABRIC_SERVICE_QUERY_DESCRIPTION, FABRIC_SERVICE_QUERY_DESCRIPTION_EX1,
+        FABRIC_SERVICE_QUERY_DESCRIPTION_EX2, FABRIC_SERVICE_QUERY_DESCRIPTION_EX3,
+        FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION, FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION_EX1,
+        FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION_EX2, FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION,
+        FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION, FABRIC_URI,
+        PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION,
+        PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION_EX1,
     },
 };
 
 use crate::{
+    WString,
+    client::{QueryStream, query_stream::QueryPage},
     runtime::executor::BoxedCancelToken,
     sync::{FabricReceiver, fabric_begin_end_proxy},
 };
 use crate::{
     strings::get_pcwstr_from_opt,
     types::{
-        DeployedServiceReplicaDetailQueryDescription, DeployedServiceReplicaDetailQueryResult,
-        NodeList, NodeQueryDescription, PartitionLoadInformation,
-        PartitionLoadInformationQueryDescription, ServicePartitionList,
-        ServicePartitionQueryDescription, ServiceReplicaList, ServiceReplicaQueryDescription,
+        ApplicationList, ApplicationLoadInformation, ApplicationLoadInformationQueryDescription,
+        ApplicationQueryDescription, ApplicationQueryResult, ApplicationType, ApplicationTypeList,
+        ClusterLoadInformation, DeployedApplication, DeployedApplicationList,
+        DeployedCodePackageList, DeployedCodePackageQueryDescription, DeployedServicePackageList,
+        DeployedServicePackageQueryDescription, DeployedServiceReplicaDetailQueryDescription,
+        DeployedServiceReplicaDetailQueryResult, DeployedServiceReplicaList,
+        DeployedServiceReplicaQueryDescription, Node, NodeList, NodeLoadInformation,
+        NodeLoadInformationQueryDescription, NodeQueryDescription,
+        PagedApplicationTypeQueryDescription, PagedDeployedApplicationQueryDescription,
+        PartitionLoadInformation, PartitionLoadInformationQueryDescription,
+        ProvisionedCodeVersionList, ProvisionedCodeVersionQueryDescription,
+        ProvisionedConfigVersionList, ProvisionedConfigVersionQueryDescription,
+        ReplicaLoadInformation, ReplicaLoadInformationQueryDescription, ServiceList,
+        ServicePartitionList, ServicePartitionQueryDescription, ServicePartitionQueryResult,
+        ServiceQueryDescription, ServiceQueryResult, ServiceReplicaList,
+        ServiceReplicaQueryDescription, ServiceReplicaQueryResult, ServiceTypeList,
+        ServiceTypeQueryDescription, UnplacedReplicaInformation,
+        UnplacedReplicaInformationQueryDescription, Uri,
     },
 };
 
@@ -127,6 +171,264 @@ impl QueryClient {
             cancellation_token,
         )
     }
+
+    fn get_application_type_list_internal(
+        &self,
+        desc: &PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetApplicationTypePagedListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationTypePagedList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationTypePagedList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_list_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetApplicationListResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationList2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_list_internal(
+        &self,
+        desc: &FABRIC_SERVICE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetServiceListResult2>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceList2(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_type_list_internal(
+        &self,
+        desc: &FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetServiceTypeListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceTypeList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceTypeList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_application_list_internal(
+        &self,
+        desc: &FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetDeployedApplicationPagedListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedApplicationPagedList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedApplicationPagedList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_service_package_list_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetDeployedServicePackageListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedServicePackageList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedServicePackageList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_code_package_list_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetDeployedCodePackageListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedCodePackageList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedCodePackageList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_deployed_replica_list_internal(
+        &self,
+        desc: &FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetDeployedReplicaListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetDeployedReplicaList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetDeployedReplicaList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_node_load_information_internal(
+        &self,
+        desc: &FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetNodeLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetNodeLoadInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetNodeLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_cluster_load_information_internal(
+        &self,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetClusterLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetClusterLoadInformation(timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetClusterLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_replica_load_information_internal(
+        &self,
+        desc: &FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetReplicaLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetReplicaLoadInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetReplicaLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_application_load_information_internal(
+        &self,
+        desc: &FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetApplicationLoadInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetApplicationLoadInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetApplicationLoadInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_unplaced_replica_information_internal(
+        &self,
+        desc: &FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetUnplacedReplicaInformationResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetUnplacedReplicaInformation(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetUnplacedReplicaInformation(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_provisioned_code_version_list_internal(
+        &self,
+        desc: &FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetProvisionedCodeVersionListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetProvisionedFabricCodeVersionList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetProvisionedFabricCodeVersionList(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_provisioned_config_version_list_internal(
+        &self,
+        desc: &FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetProvisionedConfigVersionListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetProvisionedFabricConfigVersionList(
+                    desc,
+                    timeout_milliseconds,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndGetProvisionedFabricConfigVersionList(ctx) },
+            cancellation_token,
+        )
+    }
 }
 
 impl From<IFabricQueryClient10> for QueryClient {
@@ -188,7 +490,12 @@ impl QueryClient {
         cancellation_token: Option<BoxedCancelToken>,
     ) -> crate::Result<ServicePartitionList> {
         let com = {
-            let raw: FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION = desc.into();
+            let ex1 = FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION_EX1 {
+                ContinuationToken: get_pcwstr_from_opt(&desc.continuation_token),
+                Reserved: std::ptr::null_mut(),
+            };
+            let mut raw: FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION = desc.into();
+            raw.Reserved = std::ptr::addr_of!(ex1) as *mut c_void;
             let mili = timeout.as_millis() as u32;
             self.get_partition_list_internal(&raw, mili, cancellation_token)
         }
@@ -203,7 +510,16 @@ impl QueryClient {
         cancellation_token: Option<BoxedCancelToken>,
     ) -> crate::Result<ServiceReplicaList> {
         let com = {
-            let raw: FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION = desc.into();
+            let ex2 = FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION_EX2 {
+                ContinuationToken: get_pcwstr_from_opt(&desc.continuation_token),
+                Reserved: std::ptr::null_mut(),
+            };
+            let ex1 = FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION_EX1 {
+                ReplicaStatusFilter: FABRIC_QUERY_SERVICE_REPLICA_STATUS_FILTER_DEFAULT.0 as u32,
+                Reserved: std::ptr::addr_of!(ex2) as *mut c_void,
+            };
+            let mut raw: FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION = desc.into();
+            raw.Reserved = std::ptr::addr_of!(ex1) as *mut c_void;
             let mili = timeout.as_millis() as u32;
             self.get_replica_list_internal(&raw, mili, cancellation_token)
         }
@@ -240,4 +556,456 @@ impl QueryClient {
         .await??;
         Ok(DeployedServiceReplicaDetailQueryResult::new(com))
     }
+
+    // List application types provisioned in the cluster
+    pub async fn get_application_type_list(
+        &self,
+        desc: &PagedApplicationTypeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationTypeList> {
+        let com = {
+            let ex1 = PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION_EX1 {
+                ApplicationTypeVersionFilter: get_pcwstr_from_opt(
+                    &desc.application_type_version_filter,
+                ),
+                Reserved: std::ptr::null_mut(),
+            };
+            let raw = PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION {
+                ApplicationTypeNameFilter: get_pcwstr_from_opt(&desc.application_type_name_filter),
+                MaxResults: desc.paged_query.max_results.unwrap_or(0),
+                ContinuationToken: get_pcwstr_from_opt(&desc.paged_query.continuation_token),
+                ExcludeApplicationParameters: desc.exclude_application_parameters,
+                Reserved: std::ptr::addr_of!(ex1) as *mut c_void,
+            };
+            let mili = timeout.as_millis() as u32;
+            self.get_application_type_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ApplicationTypeList::new(com))
+    }
+
+    // List applications in the cluster
+    pub async fn get_application_list(
+        &self,
+        desc: &ApplicationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationList> {
+        let com = {
+            let ex4 = FABRIC_APPLICATION_QUERY_DESCRIPTION_EX4 {
+                MaxResults: desc.paged_query.max_results.unwrap_or(0),
+                Reserved: std::ptr::null_mut(),
+            };
+            let ex3 = FABRIC_APPLICATION_QUERY_DESCRIPTION_EX3 {
+                ApplicationDefinitionKindFilter: FABRIC_APPLICATION_DEFINITION_KIND_FILTER_DEFAULT.0
+                    as u32,
+                Reserved: std::ptr::addr_of!(ex4) as *mut c_void,
+            };
+            let ex2 = FABRIC_APPLICATION_QUERY_DESCRIPTION_EX2 {
+                ApplicationTypeNameFilter: get_pcwstr_from_opt(&desc.application_type_name_filter),
+                ExcludeApplicationParameters: desc.exclude_application_parameters,
+                Reserved: std::ptr::addr_of!(ex3) as *mut c_void,
+            };
+            let ex1 = FABRIC_APPLICATION_QUERY_DESCRIPTION_EX1 {
+                ContinuationToken: get_pcwstr_from_opt(&desc.paged_query.continuation_token),
+                Reserved: std::ptr::addr_of!(ex2) as *mut c_void,
+            };
+            let raw = FABRIC_APPLICATION_QUERY_DESCRIPTION {
+                ApplicationNameFilter: opt_uri(&desc.application_name_filter),
+                Reserved: std::ptr::addr_of!(ex1) as *mut c_void,
+            };
+            let mili = timeout.as_millis() as u32;
+            self.get_application_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ApplicationList::new(com))
+    }
+
+    // List services of an application
+    pub async fn get_service_list(
+        &self,
+        desc: &ServiceQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceList> {
+        let com = {
+            let ex3 = FABRIC_SERVICE_QUERY_DESCRIPTION_EX3 {
+                MaxResults: desc.paged_query.max_results.unwrap_or(0),
+                Reserved: std::ptr::null_mut(),
+            };
+            let ex2 = FABRIC_SERVICE_QUERY_DESCRIPTION_EX2 {
+                ServiceTypeNameFilter: get_pcwstr_from_opt(&desc.service_type_name_filter),
+                Reserved: std::ptr::addr_of!(ex3) as *mut c_void,
+            };
+            let ex1 = FABRIC_SERVICE_QUERY_DESCRIPTION_EX1 {
+                ContinuationToken: get_pcwstr_from_opt(&desc.paged_query.continuation_token),
+                Reserved: std::ptr::addr_of!(ex2) as *mut c_void,
+            };
+            let raw = FABRIC_SERVICE_QUERY_DESCRIPTION {
+                ApplicationName: desc.application_name.as_raw(),
+                ServiceNameFilter: opt_uri(&desc.service_name_filter),
+                Reserved: std::ptr::addr_of!(ex1) as *mut c_void,
+            };
+            let mili = timeout.as_millis() as u32;
+            self.get_service_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ServiceList::new(com))
+    }
+
+    // List applications deployed on a node
+    pub async fn get_deployed_application_list(
+        &self,
+        desc: &PagedDeployedApplicationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedApplicationList> {
+        let com = {
+            let paging = FABRIC_QUERY_PAGING_DESCRIPTION {
+                ContinuationToken: get_pcwstr_from_opt(&desc.paged_query.continuation_token),
+                MaxResults: desc.paged_query.max_results.unwrap_or(0),
+                Reserved: std::ptr::null_mut(),
+            };
+            let raw = FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION {
+                NodeName: desc.node_name.as_pcwstr(),
+                ApplicationNameFilter: opt_uri(&desc.application_name_filter),
+                IncludeHealthState: desc.include_health_state,
+                PagingDescription: &paging,
+                Reserved: std::ptr::null_mut(),
+            };
+            let mili = timeout.as_millis() as u32;
+            self.get_deployed_application_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(DeployedApplicationList::new(com))
+    }
+
+    // List service types registered in a provisioned application type.
+    pub async fn get_service_type_list(
+        &self,
+        desc: &ServiceTypeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceTypeList> {
+        let com = {
+            let raw: FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_service_type_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ServiceTypeList::new(com))
+    }
+
+    // List service packages of an application deployed on a node.
+    pub async fn get_deployed_service_package_list(
+        &self,
+        desc: &DeployedServicePackageQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedServicePackageList> {
+        let com = {
+            let raw: FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_deployed_service_package_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(DeployedServicePackageList::new(com))
+    }
+
+    // List code packages of an application deployed on a node.
+    pub async fn get_deployed_code_package_list(
+        &self,
+        desc: &DeployedCodePackageQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedCodePackageList> {
+        let com = {
+            let raw: FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_deployed_code_package_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(DeployedCodePackageList::new(com))
+    }
+
+    // List replicas of an application deployed on a node.
+    pub async fn get_deployed_replica_list(
+        &self,
+        desc: &DeployedServiceReplicaQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<DeployedServiceReplicaList> {
+        let com = {
+            let raw: FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_deployed_replica_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(DeployedServiceReplicaList::new(com))
+    }
+
+    pub async fn get_node_load_information(
+        &self,
+        desc: &NodeLoadInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<NodeLoadInformation> {
+        let com = {
+            let raw: FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_node_load_information_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(NodeLoadInformation::new(com))
+    }
+
+    pub async fn get_cluster_load_information(
+        &self,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ClusterLoadInformation> {
+        let com = self
+            .get_cluster_load_information_internal(timeout.as_millis() as u32, cancellation_token)
+            .await??;
+        Ok(ClusterLoadInformation::new(com))
+    }
+
+    pub async fn get_replica_load_information(
+        &self,
+        desc: &ReplicaLoadInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ReplicaLoadInformation> {
+        let com = {
+            let raw: FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_replica_load_information_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ReplicaLoadInformation::new(com))
+    }
+
+    pub async fn get_application_load_information(
+        &self,
+        desc: &ApplicationLoadInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ApplicationLoadInformation> {
+        let com = {
+            let raw: FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_application_load_information_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ApplicationLoadInformation::new(com))
+    }
+
+    // Explains why replicas of a partition could not be placed.
+    pub async fn get_unplaced_replica_information(
+        &self,
+        desc: &UnplacedReplicaInformationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<UnplacedReplicaInformation> {
+        let com = {
+            let raw: FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_unplaced_replica_information_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(UnplacedReplicaInformation::from(com))
+    }
+
+    pub async fn get_provisioned_fabric_code_version_list(
+        &self,
+        desc: &ProvisionedCodeVersionQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ProvisionedCodeVersionList> {
+        let com = {
+            let raw: FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_provisioned_code_version_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ProvisionedCodeVersionList::new(com))
+    }
+
+    pub async fn get_provisioned_fabric_config_version_list(
+        &self,
+        desc: &ProvisionedConfigVersionQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ProvisionedConfigVersionList> {
+        let com = {
+            let raw: FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION = desc.into();
+            let mili = timeout.as_millis() as u32;
+            self.get_provisioned_config_version_list_internal(&raw, mili, cancellation_token)
+        }
+        .await??;
+        Ok(ProvisionedConfigVersionList::new(com))
+    }
+
+    // Paginated query streams.
+    // Each page is fetched with the given per page timeout, and
+    // max_results in the description (where supported) limits the page size.
+    // The cancellation token applies to the whole stream.
+
+    pub fn get_node_list_stream(
+        &self,
+        desc: NodeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<Node> {
+        self.query_stream(
+            desc,
+            |d| &mut d.paged_query.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_node_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_partition_list_stream(
+        &self,
+        desc: ServicePartitionQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<ServicePartitionQueryResult> {
+        self.query_stream(
+            desc,
+            |d| &mut d.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_partition_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_replica_list_stream(
+        &self,
+        desc: ServiceReplicaQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<ServiceReplicaQueryResult> {
+        self.query_stream(
+            desc,
+            |d| &mut d.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_replica_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_application_type_list_stream(
+        &self,
+        desc: PagedApplicationTypeQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<ApplicationType> {
+        self.query_stream(
+            desc,
+            |d| &mut d.paged_query.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_application_type_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_application_list_stream(
+        &self,
+        desc: ApplicationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<ApplicationQueryResult> {
+        self.query_stream(
+            desc,
+            |d| &mut d.paged_query.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_application_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_service_list_stream(
+        &self,
+        desc: ServiceQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<ServiceQueryResult> {
+        self.query_stream(
+            desc,
+            |d| &mut d.paged_query.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_service_list(&d, timeout, ct).await },
+        )
+    }
+
+    pub fn get_deployed_application_list_stream(
+        &self,
+        desc: PagedDeployedApplicationQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> QueryStream<DeployedApplication> {
+        self.query_stream(
+            desc,
+            |d| &mut d.paged_query.continuation_token,
+            cancellation_token,
+            move |qc, d, ct| async move { qc.get_deployed_application_list(&d, timeout, ct).await },
+        )
+    }
+
+    /// Builds the stream of a paged query.
+    /// token returns the continuation token field of the description,
+    /// and fetch_page gets the page of the description.
+    fn query_stream<D, L, Fut>(
+        &self,
+        mut desc: D,
+        token: fn(&mut D) -> &mut Option<WString>,
+        cancellation_token: Option<BoxedCancelToken>,
+        fetch_page: impl Fn(QueryClient, D, Option<BoxedCancelToken>) -> Fut + Send + 'static,
+    ) -> QueryStream<L::Item>
+    where
+        D: Clone + Send + 'static,
+        L: PagedList,
+        Fut: Future<Output = crate::Result<L>> + Send + 'static,
+    {
+        let qc = self.clone();
+        QueryStream::new(
+            token(&mut desc).clone(),
+            cancellation_token.clone(),
+            move |continuation_token| {
+                let mut desc = desc.clone();
+                *token(&mut desc) = continuation_token;
+                let page = fetch_page(qc.clone(), desc, cancellation_token.clone());
+                Box::pin(async move { Ok(page.await?.into_page()) })
+            },
+        )
+    }
+}
+
+/// A page of a paged query result.
+trait PagedList {
+    type Item;
+    fn into_page(self) -> QueryPage<Self::Item>;
+}
+
+macro_rules! impl_paged_list {
+    ($list:ty, $item:ty) => {
+        impl PagedList for $list {
+            type Item = $item;
+            fn into_page(self) -> QueryPage<$item> {
+                let token = self.get_paging_status().map(|s| s.continuation_token);
+                (self.iter().collect(), token)
+            }
+        }
+    };
+}
+
+impl_paged_list!(NodeList, Node);
+impl_paged_list!(ServicePartitionList, ServicePartitionQueryResult);
+impl_paged_list!(ServiceReplicaList, ServiceReplicaQueryResult);
+impl_paged_list!(ApplicationTypeList, ApplicationType);
+impl_paged_list!(ApplicationList, ApplicationQueryResult);
+impl_paged_list!(ServiceList, ServiceQueryResult);
+impl_paged_list!(DeployedApplicationList, DeployedApplication);
+
+fn opt_uri(uri: &Option<Uri>) -> FABRIC_URI {
+    uri.as_ref()
+        .map_or(FABRIC_URI(std::ptr::null_mut()), |u| u.as_raw())
 }
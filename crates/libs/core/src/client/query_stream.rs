@@ -0,0 +1,191 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::{Stream, future::BoxFuture};
+
+use crate::{ErrorCode, WString, runtime::executor::BoxedCancelToken};
+
+/// Items of one page and the continuation token of the next page.
+pub(crate) type QueryPage<T> = (Vec<T>, Option<WString>);
+
+type PageFetcher<T> =
+    Box<dyn FnMut(Option<WString>) -> BoxFuture<'static, crate::Result<QueryPage<T>>> + Send>;
+
+/// Stream of query results spanning all pages of a paged SF query.
+/// A page is fetched from SF when the buffered page is drained, using the
+/// continuation token returned with the previous page.
+/// After an error is returned the stream ends.
+pub struct QueryStream<T> {
+    fetch: PageFetcher<T>,
+    cancellation_token: Option<BoxedCancelToken>,
+    buffered: VecDeque<T>,
+    pending: Option<BoxFuture<'static, crate::Result<QueryPage<T>>>>,
+    continuation_token: Option<WString>,
+    finished: bool,
+}
+
+impl<T> QueryStream<T> {
+    /// fetch is called with the continuation token of the page to get,
+    /// which is None for the first page.
+    pub(crate) fn new(
+        continuation_token: Option<WString>,
+        cancellation_token: Option<BoxedCancelToken>,
+        fetch: impl FnMut(Option<WString>) -> BoxFuture<'static, crate::Result<QueryPage<T>>>
+        + Send
+        + 'static,
+    ) -> Self {
+        Self {
+            fetch: Box::new(fetch),
+            cancellation_token,
+            buffered: VecDeque::new(),
+            pending: None,
+            continuation_token,
+            finished: false,
+        }
+    }
+}
+
+impl<T: Unpin> QueryStream<T> {
+    /// Returns the next item, or None after the last page is consumed.
+    pub async fn next(&mut self) -> Option<crate::Result<T>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T: Unpin> Stream for QueryStream<T> {
+    type Item = crate::Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.finished {
+                return Poll::Ready(None);
+            }
+            let pending = match this.pending.as_mut() {
+                Some(p) => p,
+                None => {
+                    // A cancelled token also fails the in-flight page, but a
+                    // page should not be started after cancellation.
+                    if this
+                        .cancellation_token
+                        .as_ref()
+                        .is_some_and(|t| t.is_cancelled())
+                    {
+                        this.finished = true;
+                        return Poll::Ready(Some(Err(ErrorCode::E_ABORT.into())));
+                    }
+                    let token = this.continuation_token.take();
+                    this.pending.insert((this.fetch)(token))
+                }
+            };
+            let page = std::task::ready!(pending.as_mut().poll(cx));
+            this.pending = None;
+            match page {
+                Ok((items, token)) => {
+                    this.buffered.extend(items);
+                    // SF returns an empty token on the last page.
+                    this.continuation_token = token.filter(|t| !t.is_empty());
+                    this.finished = this.continuation_token.is_none();
+                }
+                Err(e) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::sync::SimpleCancelToken;
+
+    async fn collect<T: Unpin>(mut s: QueryStream<T>) -> Vec<crate::Result<T>> {
+        let mut res = Vec::new();
+        while let Some(item) = s.next().await {
+            res.push(item);
+        }
+        res
+    }
+
+    #[tokio::test]
+    async fn test_query_stream_follows_tokens() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let calls2 = calls.clone();
+        let s = QueryStream::new(None, None, move |token: Option<WString>| {
+            calls2.lock().unwrap().push(token.clone());
+            let page = match token.map(|t| t.to_string_lossy()) {
+                None => (vec![1, 2], Some(WString::from("p2"))),
+                Some(t) if t == "p2" => (vec![], Some(WString::from("p3"))),
+                // last page has an empty token.
+                _ => (vec![3], Some(WString::new())),
+            };
+            Box::pin(async move { Ok(page) })
+        });
+        let items = collect(s)
+            .await
+            .into_iter()
+            .map(|r| r.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![None, Some(WString::from("p2")), Some(WString::from("p3"))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_stream_ends_on_error() {
+        let s = QueryStream::new(None, None, move |token: Option<WString>| {
+            Box::pin(async move {
+                match token {
+                    None => Ok((vec![1], Some(WString::from("next")))),
+                    Some(_) => Err(ErrorCode::E_FAIL.into()),
+                }
+            })
+        });
+        let items = collect(s).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert_eq!(
+            items[1].as_ref().unwrap_err(),
+            &crate::Error::from(ErrorCode::E_FAIL)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_stream_stops_when_cancelled() {
+        let token = SimpleCancelToken::new();
+        let token2 = token.clone();
+        let s = QueryStream::new(
+            None,
+            Some(Box::new(token.clone())),
+            move |_: Option<WString>| {
+                // Cancel while the first page is being fetched.
+                token2.cancel();
+                Box::pin(async move { Ok((vec![1], Some(WString::from("next")))) })
+            },
+        );
+        let items = collect(s).await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert_eq!(
+            items[1].as_ref().unwrap_err(),
+            &crate::Error::from(ErrorCode::E_ABORT)
+        );
+    }
+}
@@ -0,0 +1,310 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricGetRepairTaskListResult, IFabricRepairManagementClient2},
+    FabricTypes::{
+        FABRIC_REPAIR_APPROVE_DESCRIPTION, FABRIC_REPAIR_CANCEL_DESCRIPTION,
+        FABRIC_REPAIR_DELETE_DESCRIPTION, FABRIC_REPAIR_TASK,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION, FABRIC_REPAIR_TASK_QUERY_DESCRIPTION,
+    },
+};
+
+use crate::{
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        RepairApproveDescription, RepairCancelDescription, RepairDeleteDescription, RepairTask,
+        RepairTaskHealthPolicyUpdateDescription, RepairTaskList, RepairTaskQueryDescription,
+    },
+};
+
+/// Repair Management Client
+/// Talks to the repair manager system service, which needs to be enabled in the cluster.
+/// The mutating apis return the new version of the repair task, which is needed
+/// for the next update of the same task.
+#[derive(Debug, Clone)]
+pub struct RepairManagementClient {
+    com: IFabricRepairManagementClient2,
+}
+
+impl RepairManagementClient {
+    pub fn get_com(&self) -> IFabricRepairManagementClient2 {
+        self.com.clone()
+    }
+}
+
+// internal implementation block
+
+impl RepairManagementClient {
+    fn create_repair_task_internal(
+        &self,
+        task: &FABRIC_REPAIR_TASK,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateRepairTask(task, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn cancel_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_CANCEL_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCancelRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCancelRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn force_approve_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_APPROVE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginForceApproveRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndForceApproveRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_repair_task_internal(
+        &self,
+        desc: &FABRIC_REPAIR_DELETE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteRepairTask(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteRepairTask(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_repair_execution_state_internal(
+        &self,
+        task: &FABRIC_REPAIR_TASK,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateRepairExecutionState(task, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateRepairExecutionState(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_repair_task_health_policy_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<i64>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateRepairTaskHealthPolicy(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateRepairTaskHealthPolicy(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_repair_task_list_internal(
+        &self,
+        desc: &FABRIC_REPAIR_TASK_QUERY_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricGetRepairTaskListResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetRepairTaskList(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetRepairTaskList(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+impl From<IFabricRepairManagementClient2> for RepairManagementClient {
+    fn from(com: IFabricRepairManagementClient2) -> Self {
+        Self { com }
+    }
+}
+
+impl From<RepairManagementClient> for IFabricRepairManagementClient2 {
+    fn from(value: RepairManagementClient) -> Self {
+        value.com
+    }
+}
+
+// public implementation block
+
+impl RepairManagementClient {
+    /// Creates a repair task. Returns the version of the created task.
+    pub async fn create_repair_task(
+        &self,
+        task: &RepairTask,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let task_raw = task.build_raw();
+            self.create_repair_task_internal(
+                task_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Requests the cancellation of a repair task.
+    pub async fn cancel_repair_task(
+        &self,
+        desc: &RepairCancelDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let desc_raw = desc.build_raw();
+            self.cancel_repair_task_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Approves a repair task in Preparing state without waiting for the safety checks.
+    pub async fn force_approve_repair_task(
+        &self,
+        desc: &RepairApproveDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let desc_raw = desc.build_raw();
+            self.force_approve_repair_task_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn delete_repair_task(
+        &self,
+        desc: &RepairDeleteDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.delete_repair_task_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Used by the repair executor to move the task through its state machine.
+    /// Typically the task is read from get_repair_task_list, modified and passed in.
+    pub async fn update_repair_execution_state(
+        &self,
+        task: &RepairTask,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let task_raw = task.build_raw();
+            self.update_repair_execution_state_internal(
+                task_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn update_repair_task_health_policy(
+        &self,
+        desc: &RepairTaskHealthPolicyUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        {
+            let desc_raw = desc.build_raw();
+            self.update_repair_task_health_policy_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_repair_task_list(
+        &self,
+        desc: &RepairTaskQueryDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<RepairTaskList> {
+        {
+            let desc_raw = desc.build_raw();
+            self.get_repair_task_list_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map(RepairTaskList::from)
+        .map_err(crate::Error::from)
+    }
+}
@@ -0,0 +1,156 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use mssf_com::FabricClient::{
+    IFabricResolvedServicePartitionResult, IFabricServiceManagementClient,
+    IFabricServiceManagementClient6, IFabricServicePartitionResolutionChangeHandler,
+    IFabricServicePartitionResolutionChangeHandler_Impl,
+};
+
+use crate::ErrorCode;
+
+use super::svc_mgmt_client::ResolvedServicePartition;
+
+/// Receives the push based resolution changes of a service partition registered with
+/// `ServiceManagementClient::register_service_partition_resolution_change_handler`.
+/// SF invokes the handler on its own threads, and the handler should return quickly.
+/// Closures of the matching signature implement this trait.
+pub trait ServicePartitionResolutionChangeHandler: Send + Sync + 'static {
+    /// Called with the latest resolved partition, or with the error SF hit
+    /// while resolving, e.g. the service does not exist (yet).
+    fn on_change(&self, result: crate::Result<ResolvedServicePartition>);
+}
+
+impl<T> ServicePartitionResolutionChangeHandler for T
+where
+    T: Fn(crate::Result<ResolvedServicePartition>) + Send + Sync + 'static,
+{
+    fn on_change(&self, result: crate::Result<ResolvedServicePartition>) {
+        self(result)
+    }
+}
+
+// Bridge implementation for the resolution change handler to turn rust code into SF com object.
+#[windows_core::implement(IFabricServicePartitionResolutionChangeHandler)]
+#[allow(non_camel_case_types)] // Suppress lint for _Impl struct
+pub struct ServicePartitionResolutionChangeHandlerBridge<T>
+where
+    T: ServicePartitionResolutionChangeHandler,
+{
+    inner: T,
+}
+
+impl<T> ServicePartitionResolutionChangeHandlerBridge<T>
+where
+    T: ServicePartitionResolutionChangeHandler,
+{
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn new_com(inner: T) -> IFabricServicePartitionResolutionChangeHandler {
+        Self::new(inner).into()
+    }
+}
+
+impl<T> IFabricServicePartitionResolutionChangeHandler_Impl
+    for ServicePartitionResolutionChangeHandlerBridge_Impl<T>
+where
+    T: ServicePartitionResolutionChangeHandler,
+{
+    fn OnChange(
+        &self,
+        _source: windows_core::Ref<IFabricServiceManagementClient>,
+        _handlerid: i64,
+        partition: windows_core::Ref<IFabricResolvedServicePartitionResult>,
+        error: crate::HRESULT,
+    ) {
+        let result = if error.is_err() {
+            Err(crate::Error::from(error))
+        } else {
+            partition
+                .ok()
+                .map(|com| ResolvedServicePartition::from(com.clone()))
+                .map_err(|_| ErrorCode::E_POINTER.into())
+        };
+        self.inner.on_change(result);
+    }
+}
+
+/// Handle to a registered resolution change handler.
+/// The handler is unregistered when the handle is dropped.
+#[derive(Debug)]
+pub struct ServicePartitionResolutionChangeHandle {
+    com: IFabricServiceManagementClient6,
+    id: i64,
+}
+
+impl ServicePartitionResolutionChangeHandle {
+    pub(crate) fn new(com: IFabricServiceManagementClient6, id: i64) -> Self {
+        Self { com, id }
+    }
+
+    /// The id SF assigned to the registration.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+}
+
+impl Drop for ServicePartitionResolutionChangeHandle {
+    fn drop(&mut self) {
+        // Nothing can be done if unregister fails, e.g. the client is already closed
+        // in which case SF has dropped the handler anyway.
+        let _ = unsafe {
+            self.com
+                .UnregisterServicePartitionResolutionChangeHandler(self.id)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use mssf_com::FabricClient::{
+        IFabricResolvedServicePartitionResult, IFabricServiceManagementClient,
+    };
+
+    use super::ServicePartitionResolutionChangeHandlerBridge;
+    use crate::{ErrorCode, client::svc_mgmt_client::ResolvedServicePartition};
+
+    #[test]
+    fn test_resolution_change_bridge_errors() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_cp = received.clone();
+        let com = ServicePartitionResolutionChangeHandlerBridge::new_com(
+            move |res: crate::Result<ResolvedServicePartition>| {
+                received_cp.lock().unwrap().push(res.unwrap_err());
+            },
+        );
+        unsafe {
+            com.OnChange(
+                None::<&IFabricServiceManagementClient>,
+                1,
+                None::<&IFabricResolvedServicePartitionResult>,
+                ErrorCode::FABRIC_E_SERVICE_DOES_NOT_EXIST.into(),
+            );
+            // Success without a partition is reported as an error too.
+            com.OnChange(
+                None::<&IFabricServiceManagementClient>,
+                1,
+                None::<&IFabricResolvedServicePartitionResult>,
+                crate::HRESULT(0),
+            );
+        }
+        let received = received.lock().unwrap();
+        assert_eq!(
+            *received,
+            vec![
+                crate::Error::from(ErrorCode::FABRIC_E_SERVICE_DOES_NOT_EXIST),
+                crate::Error::from(ErrorCode::E_POINTER),
+            ]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::time::Duration;
+
+use mssf_com::{
+    FabricClient::{IFabricServiceGroupDescriptionResult, IFabricServiceGroupManagementClient4},
+    FabricTypes::{
+        FABRIC_SERVICE_GROUP_DESCRIPTION, FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION, FABRIC_URI,
+    },
+};
+
+use crate::{
+    runtime::executor::BoxedCancelToken,
+    sync::{FabricReceiver, fabric_begin_end_proxy},
+    types::{
+        ServiceGroupDescription, ServiceGroupUpdateDescriptionRaw, ServiceUpdateDescription, Uri,
+    },
+};
+
+/// Manages service groups, i.e. services whose replicas host several member services.
+#[derive(Debug, Clone)]
+pub struct ServiceGroupManagementClient {
+    com: IFabricServiceGroupManagementClient4,
+}
+
+impl ServiceGroupManagementClient {
+    pub fn get_com(&self) -> IFabricServiceGroupManagementClient4 {
+        self.com.clone()
+    }
+}
+
+impl From<IFabricServiceGroupManagementClient4> for ServiceGroupManagementClient {
+    fn from(com: IFabricServiceGroupManagementClient4) -> Self {
+        Self { com }
+    }
+}
+
+impl From<ServiceGroupManagementClient> for IFabricServiceGroupManagementClient4 {
+    fn from(value: ServiceGroupManagementClient) -> Self {
+        value.com
+    }
+}
+
+// internal implementation block
+
+impl ServiceGroupManagementClient {
+    fn create_service_group_internal(
+        &self,
+        desc: &FABRIC_SERVICE_GROUP_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginCreateServiceGroup(desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndCreateServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn update_service_group_internal(
+        &self,
+        name: FABRIC_URI,
+        desc: &FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginUpdateServiceGroup(name, desc, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndUpdateServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn delete_service_group_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<()>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginDeleteServiceGroup(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndDeleteServiceGroup(ctx) },
+            cancellation_token,
+        )
+    }
+
+    fn get_service_group_description_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricServiceGroupDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceGroupDescription(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceGroupDescription(ctx) },
+            cancellation_token,
+        )
+    }
+}
+
+// public implementation block
+
+impl ServiceGroupManagementClient {
+    /// Creates the group service together with all its members.
+    pub async fn create_service_group(
+        &self,
+        desc: &ServiceGroupDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = desc.build_raw();
+            self.create_service_group_internal(
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    /// Updates the group service, e.g. its replica or instance count.
+    /// Members of an existing group cannot be added or removed.
+    pub async fn update_service_group(
+        &self,
+        name: &Uri,
+        desc: &ServiceUpdateDescription,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        {
+            let desc_raw = ServiceGroupUpdateDescriptionRaw::new(desc);
+            self.update_service_group_internal(
+                name.as_raw(),
+                desc_raw.as_ffi(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+        }
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn delete_service_group(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.delete_service_group_internal(
+            name.as_raw(),
+            timeout.as_millis() as u32,
+            cancellation_token,
+        )
+        .await?
+        .map_err(crate::Error::from)
+    }
+
+    pub async fn get_service_group_description(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<ServiceGroupDescription> {
+        let com = self
+            .get_service_group_description_internal(
+                name.as_raw(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+            .await??;
+        ServiceGroupDescription::try_from(&com)
+    }
+}
@@ -7,7 +7,10 @@ use std::{ffi::c_void, time::Duration};
 
 use crate::{PCWSTR, WString, runtime::executor::BoxedCancelToken, types::Uri};
 use mssf_com::{
-    FabricClient::{IFabricResolvedServicePartitionResult, IFabricServiceManagementClient6},
+    FabricClient::{
+        IFabricResolvedServicePartitionResult, IFabricServiceDescriptionResult,
+        IFabricServiceManagementClient6,
+    },
     FabricTypes::{
         FABRIC_PARTITION_KEY_TYPE, FABRIC_PARTITION_KEY_TYPE_INT64,
         FABRIC_PARTITION_KEY_TYPE_INVALID, FABRIC_PARTITION_KEY_TYPE_NONE,
@@ -25,6 +28,11 @@ use mssf_com::{
 
 use crate::sync::{FabricReceiver, fabric_begin_end_proxy};
 
+use super::resolution::{
+    ServicePartitionResolutionChangeHandle, ServicePartitionResolutionChangeHandler,
+    ServicePartitionResolutionChangeHandlerBridge,
+};
+
 use crate::{
     iter::{FabricIter, FabricListAccessor},
     strings::WStringWrap,
@@ -197,6 +205,23 @@ impl ServiceManagementClient {
             cancellation_token,
         )
     }
+
+    fn get_service_description_internal(
+        &self,
+        name: FABRIC_URI,
+        timeout_milliseconds: u32,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> FabricReceiver<crate::WinResult<IFabricServiceDescriptionResult>> {
+        let com1 = &self.com;
+        let com2 = self.com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginGetServiceDescription(name, timeout_milliseconds, callback)
+            },
+            move |ctx| unsafe { com2.EndGetServiceDescription(ctx) },
+            cancellation_token,
+        )
+    }
 }
 
 impl From<IFabricServiceManagementClient6> for ServiceManagementClient {
@@ -381,6 +406,50 @@ impl ServiceManagementClient {
         .await?
         .map_err(crate::Error::from)
     }
+
+    /// Gets the description of an existing service.
+    /// The result can be compared with the desired description to decide
+    /// whether update_service needs to be called.
+    pub async fn get_service_description(
+        &self,
+        name: &Uri,
+        timeout: Duration,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<crate::types::ServiceDescription> {
+        let com = self
+            .get_service_description_internal(
+                name.as_raw(),
+                timeout.as_millis() as u32,
+                cancellation_token,
+            )
+            .await??;
+        crate::types::ServiceDescription::try_from(&com)
+    }
+
+    /// Registers a handler that SF calls whenever the resolution of the partition
+    /// owning the key changes, so that callers get push updates instead of polling
+    /// resolve_service_partition().
+    /// The handler is unregistered when the returned handle is dropped.
+    pub fn register_service_partition_resolution_change_handler(
+        &self,
+        name: &Uri,
+        key_type: &PartitionKeyType,
+        handler: impl ServicePartitionResolutionChangeHandler,
+    ) -> crate::Result<ServicePartitionResolutionChangeHandle> {
+        let callback = ServicePartitionResolutionChangeHandlerBridge::new_com(handler);
+        let id = unsafe {
+            self.com.RegisterServicePartitionResolutionChangeHandler(
+                name.as_raw(),
+                key_type.into(),
+                key_type.get_raw_opt().unwrap_or(std::ptr::null()),
+                &callback,
+            )
+        }?;
+        Ok(ServicePartitionResolutionChangeHandle::new(
+            self.com.clone(),
+            id,
+        ))
+    }
 }
 
 // Handle to the registered service notification filter
@@ -391,7 +460,7 @@ pub struct FilterIdHandle {
 }
 
 // see ComFabricClient.cpp for conversion details in cpp
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum PartitionKeyType {
     Int64(i64),
     Invalid,
@@ -8,7 +8,7 @@ use crate::Interface;
 use mssf_com::FabricCommon::{IFabricAsyncOperationCallback, IFabricAsyncOperationContext};
 use mssf_com::FabricRuntime::IFabricRuntime;
 
-pub use self::runtime_wrapper::Runtime;
+pub use self::runtime_wrapper::{Runtime, ServiceGroupFactoryBuilder};
 
 pub mod config;
 pub mod error;
@@ -16,6 +16,8 @@ pub mod error;
 pub mod executor;
 pub mod node_context;
 
+pub mod operation_proxy;
+
 pub mod package_change;
 
 pub mod runtime_wrapper;
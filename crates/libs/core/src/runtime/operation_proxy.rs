@@ -0,0 +1,277 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// operation_proxy wraps the operation com objects exchanged with the SF state replicator.
+
+use mssf_com::FabricRuntime::{
+    IFabricOperation, IFabricOperationDataStream, IFabricOperationStream, IFabricOperationStream2,
+};
+
+use crate::{
+    Interface,
+    error::ErrorCode,
+    runtime::executor::BoxedCancelToken,
+    sync::fabric_begin_end_proxy,
+    types::{FaultType, OperationData, OperationType},
+};
+
+use super::stateful::OperationDataStream;
+
+/// Operations received by a secondary from the StateReplicator,
+/// i.e. the copy stream or the replication stream.
+#[derive(Debug, Clone)]
+pub struct OperationStream {
+    com_impl: IFabricOperationStream,
+    is_copy: bool,
+}
+
+impl OperationStream {
+    pub(crate) fn new(com_impl: IFabricOperationStream, is_copy: bool) -> Self {
+        Self { com_impl, is_copy }
+    }
+
+    pub fn get_com(&self) -> &IFabricOperationStream {
+        &self.com_impl
+    }
+
+    /// Gets the next operation, or None at the end of the stream.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", err)
+    )]
+    pub async fn next(
+        &self,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<Option<Operation>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetOperation(callback) },
+            move |ctx| unsafe { com2.EndGetOperation(ctx) },
+            cancellation_token,
+        );
+        match rx.await? {
+            Ok(com) => Operation::new(com, self.is_copy).map(Some),
+            // SF returns a null operation at the end of the stream.
+            Err(e) if e.code() == crate::HRESULT(0) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Reports a fault while processing the stream, i.e. the operation cannot be applied.
+    /// SF then restarts the replica (permanent) or closes and reopens it (transient).
+    /// Requires the replicator to be created with
+    /// FABRIC_REPLICATOR_SETTINGS_STREAM_FAULTS_AND_END_OF_STREAM_OPERATION_ACK flag.
+    pub fn report_fault(&self, fault_type: FaultType) -> crate::Result<()> {
+        let com2 = self.com_impl.cast::<IFabricOperationStream2>()?;
+        unsafe { com2.ReportFault(fault_type.into()) }.map_err(crate::Error::from)
+    }
+}
+
+/// An operation received from an OperationStream.
+/// The data is owned by SF and lives as long as the operation.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    com_impl: IFabricOperation,
+    operation_type: OperationType,
+    sequence_number: i64,
+    atomic_group_id: i64,
+    // Points into the com object, which is immutable and kept alive by com_impl.
+    buffers: Vec<&'static [u8]>,
+}
+
+impl Operation {
+    fn new(com_impl: IFabricOperation, is_copy: bool) -> crate::Result<Self> {
+        let metadata = unsafe { com_impl.get_Metadata().as_ref() }.ok_or(ErrorCode::E_POINTER)?;
+        let operation_type = OperationType::from_raw(metadata.Type, is_copy);
+        let sequence_number = metadata.SequenceNumber;
+        let atomic_group_id = metadata.AtomicGroupId;
+
+        let mut count = 0;
+        let raw = unsafe { com_impl.GetData(&mut count) }?;
+        let buffers = if raw.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(raw, count as usize) }
+                .iter()
+                .map(|b| {
+                    if b.Buffer.is_null() {
+                        &[][..]
+                    } else {
+                        unsafe { std::slice::from_raw_parts(b.Buffer, b.BufferSize as usize) }
+                    }
+                })
+                .collect()
+        };
+        Ok(Self {
+            com_impl,
+            operation_type,
+            sequence_number,
+            atomic_group_id,
+            buffers,
+        })
+    }
+
+    pub fn get_com(&self) -> &IFabricOperation {
+        &self.com_impl
+    }
+
+    pub fn operation_type(&self) -> OperationType {
+        self.operation_type
+    }
+
+    pub fn sequence_number(&self) -> i64 {
+        self.sequence_number
+    }
+
+    /// Id of the atomic group the operation belongs to, if the type is an atomic group one.
+    pub fn atomic_group_id(&self) -> i64 {
+        self.atomic_group_id
+    }
+
+    /// The data buffers of the operation.
+    pub fn data(&self) -> &[&[u8]] {
+        &self.buffers
+    }
+
+    /// Copies the data buffers out of the operation.
+    pub fn to_operation_data(&self) -> OperationData {
+        OperationData::from(self.buffers.iter().map(|b| b.to_vec()).collect::<Vec<_>>())
+    }
+
+    /// Acknowledges the operation after it is applied to the state.
+    /// Required unless the replicator settings disable service acks.
+    pub fn acknowledge(&self) -> crate::Result<()> {
+        unsafe { self.com_impl.Acknowledge() }.map_err(crate::Error::from)
+    }
+}
+
+/// Proxy of the copy context stream sent by a secondary, passed to
+/// StateProvider::get_copy_state on the primary.
+#[derive(Debug, Clone)]
+pub struct OperationDataStreamProxy {
+    com_impl: IFabricOperationDataStream,
+}
+
+impl From<IFabricOperationDataStream> for OperationDataStreamProxy {
+    fn from(com_impl: IFabricOperationDataStream) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl OperationDataStream for OperationDataStreamProxy {
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<Option<OperationData>> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginGetNext(callback) },
+            move |ctx| unsafe { com2.EndGetNext(ctx) },
+            Some(cancellation_token),
+        );
+        match rx.await? {
+            Ok(com) => OperationData::try_from(&com).map(Some),
+            // Null data marks the end of the stream.
+            Err(e) if e.code() == crate::HRESULT(0) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::{
+        FabricRuntime::{IFabricOperation, IFabricOperation_Impl},
+        FabricTypes::{
+            FABRIC_OPERATION_DATA_BUFFER, FABRIC_OPERATION_METADATA,
+            FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION, FABRIC_OPERATION_TYPE_NORMAL,
+        },
+    };
+
+    use super::Operation;
+    use crate::types::{OperationData, OperationType};
+
+    #[windows_core::implement(IFabricOperation)]
+    struct TestOperation {
+        metadata: FABRIC_OPERATION_METADATA,
+        data: Vec<Vec<u8>>,
+        buffers: Vec<FABRIC_OPERATION_DATA_BUFFER>,
+    }
+
+    impl TestOperation {
+        fn new_com(metadata: FABRIC_OPERATION_METADATA, data: Vec<Vec<u8>>) -> IFabricOperation {
+            let buffers = data
+                .iter()
+                .map(|b| FABRIC_OPERATION_DATA_BUFFER {
+                    BufferSize: b.len() as u32,
+                    Buffer: b.as_ptr() as *mut u8,
+                })
+                .collect();
+            TestOperation {
+                metadata,
+                data,
+                buffers,
+            }
+            .into()
+        }
+    }
+
+    impl IFabricOperation_Impl for TestOperation_Impl {
+        fn get_Metadata(&self) -> *mut FABRIC_OPERATION_METADATA {
+            &self.metadata as *const _ as *mut _
+        }
+
+        fn GetData(&self, count: *mut u32) -> crate::WinResult<*mut FABRIC_OPERATION_DATA_BUFFER> {
+            assert_eq!(self.data.len(), self.buffers.len());
+            unsafe { count.write(self.buffers.len() as u32) };
+            Ok(self.buffers.as_ptr() as *mut _)
+        }
+
+        fn Acknowledge(&self) -> crate::WinResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_operation() {
+        let com = TestOperation::new_com(
+            FABRIC_OPERATION_METADATA {
+                Type: FABRIC_OPERATION_TYPE_NORMAL,
+                SequenceNumber: 3,
+                ..Default::default()
+            },
+            vec![b"ab".to_vec(), Vec::new()],
+        );
+        let op = Operation::new(com.clone(), true).unwrap();
+        assert_eq!(op.operation_type(), OperationType::Copy);
+        assert_eq!(op.sequence_number(), 3);
+        assert_eq!(op.data(), &[&b"ab"[..], &[][..]]);
+        // The data outlives the original com reference.
+        let op2 = op.clone();
+        drop(com);
+        drop(op);
+        assert_eq!(
+            op2.to_operation_data(),
+            OperationData::from(vec![b"ab".to_vec(), Vec::new()])
+        );
+        op2.acknowledge().unwrap();
+
+        let com = TestOperation::new_com(
+            FABRIC_OPERATION_METADATA {
+                Type: FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION,
+                SequenceNumber: 4,
+                AtomicGroupId: 9,
+                ..Default::default()
+            },
+            Vec::new(),
+        );
+        let op = Operation::new(com, false).unwrap();
+        assert!(op.operation_type().is_atomic_group());
+        assert_eq!(op.atomic_group_id(), 9);
+        assert!(op.data().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::WString;
 /// safe wrapping for runtime
 use mssf_com::FabricRuntime::{
-    IFabricRuntime, IFabricStatefulServiceFactory, IFabricStatelessServiceFactory,
+    IFabricRuntime, IFabricServiceGroupFactoryBuilder, IFabricStatefulServiceFactory,
+    IFabricStatelessServiceFactory,
 };
 
 use super::{
@@ -58,4 +59,69 @@ where
         }
         .map_err(crate::Error::from)
     }
+
+    /// Starts building the factory of a service group type.
+    /// Members are added to the builder, which is then registered under the group type name.
+    pub fn create_service_group_factory_builder(
+        &self,
+    ) -> crate::Result<ServiceGroupFactoryBuilder<'_, E>> {
+        let com = unsafe { self.com_impl.CreateServiceGroupFactoryBuilder() }?;
+        Ok(ServiceGroupFactoryBuilder { runtime: self, com })
+    }
+}
+
+/// Registers the factories of the member service types of a service group.
+/// The member type names are the ServiceTypeName of the members in the
+/// ServiceGroupType section of the service manifest.
+pub struct ServiceGroupFactoryBuilder<'a, E>
+where
+    E: Executor,
+{
+    runtime: &'a Runtime<E>,
+    com: IFabricServiceGroupFactoryBuilder,
+}
+
+impl<E> ServiceGroupFactoryBuilder<'_, E>
+where
+    E: Executor,
+{
+    pub fn add_stateless_service_factory(
+        self,
+        member_service_type: &WString,
+        factory: impl StatelessServiceFactory + 'static,
+    ) -> crate::Result<Self> {
+        let bridge: IFabricStatelessServiceFactory =
+            StatelessServiceFactoryBridge::create(factory, self.runtime.rt.clone()).into();
+        unsafe {
+            self.com
+                .AddStatelessServiceFactory(member_service_type.as_pcwstr(), &bridge)
+        }?;
+        Ok(self)
+    }
+
+    pub fn add_stateful_service_factory(
+        self,
+        member_service_type: &WString,
+        factory: impl StatefulServiceFactory + 'static,
+    ) -> crate::Result<Self> {
+        let bridge: IFabricStatefulServiceFactory =
+            StatefulServiceFactoryBridge::create(factory, self.runtime.rt.clone()).into();
+        unsafe {
+            self.com
+                .AddStatefulServiceFactory(member_service_type.as_pcwstr(), &bridge)
+        }?;
+        Ok(self)
+    }
+
+    /// Registers the group factory with the runtime.
+    /// SF creates the members with the added factories when a replica of the group is placed.
+    pub fn register(self, service_group_type_name: &WString) -> crate::Result<()> {
+        let factory = unsafe { self.com.ToServiceGroupFactory() }?;
+        unsafe {
+            self.runtime
+                .com_impl
+                .RegisterServiceGroupFactory(service_group_type_name.as_pcwstr(), &factory)
+        }
+        .map_err(crate::Error::from)
+    }
 }
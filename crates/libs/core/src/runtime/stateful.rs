@@ -8,9 +8,11 @@
 use crate::runtime::executor::BoxedCancelToken;
 use crate::types::ReplicaRole;
 
-use crate::types::{Epoch, OpenMode, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode};
+use crate::types::{
+    Epoch, OpenMode, OperationData, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode,
+};
 
-use super::stateful_proxy::StatefulServicePartition;
+use super::{operation_proxy::OperationDataStreamProxy, stateful_proxy::StatefulServicePartition};
 
 /// Represents a stateful service factory that is responsible for creating replicas
 /// of a specific type of stateful service. Stateful service factories are registered with
@@ -36,9 +38,8 @@ pub trait LocalStatefulServiceReplica: Send + Sync + 'static {
     /// Opens an initialized service replica so that additional actions can be taken.
     /// Returns PrimaryReplicator that is used by the stateful service.
     /// Note:
-    /// Most user calls IFabricStatefulServicePartition.CreateReplicator instead of
-    /// writing their own replicator (TODO: not supported in mssf yet),
-    /// or use FabricCreateKeyValueStoreReplica.
+    /// Most user calls StatefulServicePartition::create_replicator instead of
+    /// writing their own replicator, or use FabricCreateKeyValueStoreReplica.
     async fn open(
         &self,
         openmode: OpenMode,
@@ -245,3 +246,88 @@ pub trait LocalPrimaryReplicator: Replicator {
     /// SF does not call remove_replica on the replica where build_replica is still running.
     fn remove_replica(&self, replicaid: i64) -> crate::Result<()>;
 }
+
+/// Stream of operation data produced by rust code, i.e. the copy context of a
+/// secondary or the copy state that the primary sends to a secondary.
+#[trait_variant::make(OperationDataStream: Send)]
+pub trait LocalOperationDataStream: Send + Sync + 'static {
+    /// Returns the next operation data, or None if the stream has ended.
+    async fn get_next(
+        &self,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<Option<OperationData>>;
+}
+
+/// The state of a stateful service that is replicated by the SF state replicator.
+/// The state replicator is created by StatefulServicePartition::create_replicator,
+/// which calls back into the state provider to manage the state.
+/// IFabricStateProvider com interface wrapper.
+#[trait_variant::make(StateProvider: Send)]
+pub trait LocalStateProvider: Send + Sync + 'static {
+    /// Indicates to a replica that the configuration of a replica set has changed due to
+    /// a change or attempted change to the primary replica.
+    /// previous_epoch_last_sequence_number is the last LSN of the previous epoch.
+    /// Operations after it that are not yet committed must be discarded.
+    async fn update_epoch(
+        &self,
+        epoch: &Epoch,
+        previous_epoch_last_sequence_number: i64,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<()>;
+
+    /// Returns the LSN of the last operation the state provider has applied.
+    fn get_last_committed_sequence_number(&self) -> crate::Result<i64>;
+
+    /// Called on the primary after a quorum loss. The state provider may restore
+    /// its state, e.g. from a backup. Returns true if the state was changed,
+    /// in which case SF rebuilds the other replicas from this one.
+    async fn on_data_loss(&self, cancellation_token: BoxedCancelToken) -> crate::Result<bool>;
+
+    /// Called on the secondary being built, returns the context that is sent
+    /// to the primary, e.g. the last LSN the secondary has.
+    /// None means the secondary sends no context.
+    fn get_copy_context(&self) -> crate::Result<Option<impl OperationDataStream>>;
+
+    /// Called on the primary, returns the state up to up_to_sequence_number to
+    /// copy into a secondary being built. Operations after up_to_sequence_number
+    /// reach the secondary in the replication stream.
+    /// copy_context is the context from the secondary, if it sent one.
+    fn get_copy_state(
+        &self,
+        up_to_sequence_number: i64,
+        copy_context: Option<OperationDataStreamProxy>,
+    ) -> crate::Result<impl OperationDataStream>;
+}
+
+/// State provider that supports atomic groups, i.e. sets of operations that are
+/// replicated and then committed or rolled back as a whole.
+/// Pass it to StatefulServicePartition::create_atomic_group_replicator.
+/// IFabricAtomicGroupStateProvider com interface wrapper.
+#[trait_variant::make(AtomicGroupStateProvider: Send)]
+pub trait LocalAtomicGroupStateProvider: StateProvider {
+    /// Applies the operations of the atomic group, which has been committed
+    /// at commit_sequence_number.
+    async fn atomic_group_commit(
+        &self,
+        atomic_group_id: i64,
+        commit_sequence_number: i64,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<()>;
+
+    /// Discards the operations of the atomic group, which has been rolled back
+    /// at rollback_sequence_number.
+    async fn atomic_group_rollback(
+        &self,
+        atomic_group_id: i64,
+        rollback_sequence_number: i64,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<()>;
+
+    /// Undoes the committed groups after from_commit_sequence_number,
+    /// e.g. when a secondary has committed operations that the new primary did not.
+    async fn atomic_group_undo_progress(
+        &self,
+        from_commit_sequence_number: i64,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<()>;
+}
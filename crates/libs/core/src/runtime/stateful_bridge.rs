@@ -16,28 +16,37 @@ use windows_core::implement;
 use mssf_com::{
     FabricCommon::IFabricStringResult,
     FabricRuntime::{
-        IFabricPrimaryReplicator, IFabricPrimaryReplicator_Impl, IFabricReplicator,
-        IFabricReplicator_Impl, IFabricReplicatorCatchupSpecificQuorum,
-        IFabricReplicatorCatchupSpecificQuorum_Impl, IFabricStatefulServiceFactory,
+        IFabricAtomicGroupStateProvider, IFabricAtomicGroupStateProvider_Impl,
+        IFabricOperationData, IFabricOperationData_Impl, IFabricOperationDataStream,
+        IFabricOperationDataStream_Impl, IFabricPrimaryReplicator, IFabricPrimaryReplicator_Impl,
+        IFabricReplicator, IFabricReplicator_Impl, IFabricReplicatorCatchupSpecificQuorum,
+        IFabricReplicatorCatchupSpecificQuorum_Impl, IFabricStateProvider,
+        IFabricStateProvider_Impl, IFabricStatefulServiceFactory,
         IFabricStatefulServiceFactory_Impl, IFabricStatefulServicePartition,
         IFabricStatefulServicePartition3, IFabricStatefulServiceReplica,
         IFabricStatefulServiceReplica_Impl,
     },
     FabricTypes::{
-        FABRIC_EPOCH, FABRIC_REPLICA_INFORMATION, FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_ROLE,
-        FABRIC_REPLICA_SET_CONFIGURATION, FABRIC_REPLICA_SET_QUORUM_MODE, FABRIC_URI,
+        FABRIC_EPOCH, FABRIC_OPERATION_DATA_BUFFER, FABRIC_REPLICA_INFORMATION,
+        FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_ROLE, FABRIC_REPLICA_SET_CONFIGURATION,
+        FABRIC_REPLICA_SET_QUORUM_MODE, FABRIC_URI,
     },
 };
 
 use crate::{
+    ErrorCode,
     strings::WStringWrap,
     sync::BridgeContext,
-    types::{Epoch, OpenMode, ReplicaInformation, ReplicaRole, ReplicaSetConfig},
+    types::{Epoch, OpenMode, OperationData, ReplicaInformation, ReplicaRole, ReplicaSetConfig},
 };
 
 use super::{
     executor::Executor,
-    stateful::{PrimaryReplicator, Replicator, StatefulServiceFactory, StatefulServiceReplica},
+    operation_proxy::OperationDataStreamProxy,
+    stateful::{
+        AtomicGroupStateProvider, OperationDataStream, PrimaryReplicator, Replicator,
+        StateProvider, StatefulServiceFactory, StatefulServiceReplica,
+    },
 };
 // bridges from rs into com
 
@@ -723,3 +732,454 @@ where
 }
 
 // endregion: IFabricStatefulServiceReplicaBridge
+
+// region: IFabricOperationDataBridge
+
+/// Exposes rust owned operation data to SF.
+#[implement(IFabricOperationData)]
+pub struct IFabricOperationDataBridge {
+    // Buffers point into the data, which is never modified.
+    _data: OperationData,
+    buffers: Vec<FABRIC_OPERATION_DATA_BUFFER>,
+}
+
+impl IFabricOperationDataBridge {
+    pub fn create(data: OperationData) -> IFabricOperationDataBridge {
+        let buffers = data
+            .buffers
+            .iter()
+            .map(|b| FABRIC_OPERATION_DATA_BUFFER {
+                BufferSize: b.len() as u32,
+                Buffer: b.as_ptr() as *mut u8,
+            })
+            .collect();
+        IFabricOperationDataBridge {
+            _data: data,
+            buffers,
+        }
+    }
+}
+
+impl IFabricOperationData_Impl for IFabricOperationDataBridge_Impl {
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn GetData(&self, count: *mut u32) -> crate::WinResult<*mut FABRIC_OPERATION_DATA_BUFFER> {
+        if count.is_null() {
+            return Err(ErrorCode::E_POINTER.into());
+        }
+        unsafe { count.write(self.buffers.len() as u32) };
+        Ok(self.buffers.as_ptr() as *mut _)
+    }
+}
+
+// endregion: IFabricOperationDataBridge
+
+// region: IFabricOperationDataStreamBridge
+
+/// Bridge for the copy context and copy state streams implemented in rust.
+#[implement(IFabricOperationDataStream)]
+pub struct IFabricOperationDataStreamBridge<E, S>
+where
+    E: Executor,
+    S: OperationDataStream,
+{
+    inner: Arc<S>,
+    rt: E,
+}
+
+impl<E, S> IFabricOperationDataStreamBridge<E, S>
+where
+    E: Executor,
+    S: OperationDataStream,
+{
+    pub fn create(stream: S, rt: E) -> IFabricOperationDataStreamBridge<E, S> {
+        IFabricOperationDataStreamBridge {
+            inner: Arc::new(stream),
+            rt,
+        }
+    }
+}
+
+impl<E, S> IFabricOperationDataStream_Impl for IFabricOperationDataStreamBridge_Impl<E, S>
+where
+    E: Executor,
+    S: OperationDataStream,
+{
+    fn BeginGetNext(
+        &self,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .get_next(token)
+                .await
+                .map(|data| {
+                    data.map(|d| IFabricOperationData::from(IFabricOperationDataBridge::create(d)))
+                })
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    fn EndGetNext(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<IFabricOperationData> {
+        // SF expects a null operation data at the end of the stream.
+        // The generated shim only writes the out param on Ok, so null cannot be
+        // returned as a value. An empty error maps to S_OK and leaves the out
+        // param unwritten. This is safe because callers pass it in as null:
+        // the SF replicator reads the stream through a ComPointer reset by
+        // InitializationAddress(), and windows-rs callers zero the out param
+        // before the call. Both read the unwritten null as end of stream.
+        BridgeContext::<crate::WinResult<Option<IFabricOperationData>>>::result(context)??
+            .ok_or(crate::WinError::empty())
+    }
+}
+
+// endregion: IFabricOperationDataStreamBridge
+
+// region: IFabricStateProviderBridge
+
+/// Bridge for the state provider of the SF state replicator.
+#[implement(IFabricStateProvider)]
+pub struct IFabricStateProviderBridge<E, P>
+where
+    E: Executor,
+    P: StateProvider,
+{
+    inner: Arc<P>,
+    rt: E,
+}
+
+impl<E, P> IFabricStateProviderBridge<E, P>
+where
+    E: Executor,
+    P: StateProvider,
+{
+    pub fn create(provider: P, rt: E) -> IFabricStateProviderBridge<E, P> {
+        IFabricStateProviderBridge {
+            inner: Arc::new(provider),
+            rt,
+        }
+    }
+
+    fn create_from_atomic_group_provider(
+        provider: Arc<P>,
+        rt: E,
+    ) -> IFabricStateProviderBridge<E, P> {
+        IFabricStateProviderBridge {
+            inner: provider,
+            rt,
+        }
+    }
+}
+
+impl<E, P> IFabricStateProvider_Impl for IFabricStateProviderBridge_Impl<E, P>
+where
+    E: Executor,
+    P: StateProvider,
+{
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginUpdateEpoch(
+        &self,
+        epoch: *const FABRIC_EPOCH,
+        previousepochlastsequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let epoch2: Epoch = unsafe { epoch.as_ref().unwrap().into() };
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .update_epoch(&epoch2, previousepochlastsequencenumber, token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndUpdateEpoch(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        BridgeContext::result(context)?
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn GetLastCommittedSequenceNumber(&self) -> crate::WinResult<i64> {
+        self.inner
+            .get_last_committed_sequence_number()
+            .map_err(crate::WinError::from)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginOnDataLoss(
+        &self,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .on_data_loss(token)
+                .await
+                .map(u8::from)
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndOnDataLoss(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        BridgeContext::result(context)?
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", err)
+    )]
+    fn GetCopyContext(&self) -> crate::WinResult<IFabricOperationDataStream> {
+        // SF treats a null copy context as no copy context. It is returned as
+        // an unwritten out param with S_OK, which is safe for the same reason
+        // as in IFabricOperationDataStreamBridge::EndGetNext.
+        self.inner
+            .get_copy_context()?
+            .map(|s| IFabricOperationDataStreamBridge::create(s, self.rt.clone()).into())
+            .ok_or(crate::WinError::empty())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", err)
+    )]
+    fn GetCopyState(
+        &self,
+        uptosequencenumber: i64,
+        copycontextstream: windows_core::Ref<IFabricOperationDataStream>,
+    ) -> crate::WinResult<IFabricOperationDataStream> {
+        let copy_context = copycontextstream
+            .as_ref()
+            .map(|s| OperationDataStreamProxy::from(s.clone()));
+        let stream = self
+            .inner
+            .get_copy_state(uptosequencenumber, copy_context)?;
+        Ok(IFabricOperationDataStreamBridge::create(stream, self.rt.clone()).into())
+    }
+}
+
+// endregion: IFabricStateProviderBridge
+
+// region: IFabricAtomicGroupStateProviderBridge
+
+/// Bridge for the state provider with atomic group support.
+/// SF queries the provider for IFabricAtomicGroupStateProvider to enable atomic groups.
+#[implement(IFabricAtomicGroupStateProvider, IFabricStateProvider)]
+pub struct IFabricAtomicGroupStateProviderBridge<E, P>
+where
+    E: Executor,
+    P: AtomicGroupStateProvider,
+{
+    inner: Arc<P>,
+    rt: E,
+    provider: IFabricStateProvider,
+}
+
+impl<E, P> IFabricAtomicGroupStateProviderBridge<E, P>
+where
+    E: Executor,
+    P: AtomicGroupStateProvider,
+{
+    pub fn create(provider: P, rt: E) -> IFabricAtomicGroupStateProviderBridge<E, P> {
+        let inner = Arc::new(provider);
+        let provider_bridge = IFabricStateProviderBridge::create_from_atomic_group_provider(
+            inner.clone(),
+            rt.clone(),
+        );
+        IFabricAtomicGroupStateProviderBridge {
+            inner,
+            rt,
+            provider: provider_bridge.into(),
+        }
+    }
+}
+
+impl<E, P> IFabricAtomicGroupStateProvider_Impl for IFabricAtomicGroupStateProviderBridge_Impl<E, P>
+where
+    E: Executor,
+    P: AtomicGroupStateProvider,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginAtomicGroupCommit(
+        &self,
+        atomicgroupid: i64,
+        commitsequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .atomic_group_commit(atomicgroupid, commitsequencenumber, token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndAtomicGroupCommit(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        BridgeContext::result(context)?
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginAtomicGroupRollback(
+        &self,
+        atomicgroupid: i64,
+        rollbackequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .atomic_group_rollback(atomicgroupid, rollbackequencenumber, token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndAtomicGroupRollback(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        BridgeContext::result(context)?
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginUndoProgress(
+        &self,
+        fromcommitsequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .atomic_group_undo_progress(fromcommitsequencenumber, token)
+                .await
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndUndoProgress(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        BridgeContext::result(context)?
+    }
+}
+
+impl<E, P> IFabricStateProvider_Impl for IFabricAtomicGroupStateProviderBridge_Impl<E, P>
+where
+    E: Executor,
+    P: AtomicGroupStateProvider,
+{
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    fn BeginUpdateEpoch(
+        &self,
+        epoch: *const FABRIC_EPOCH,
+        previousepochlastsequencenumber: i64,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        unsafe {
+            self.provider.BeginUpdateEpoch(
+                epoch,
+                previousepochlastsequencenumber,
+                callback.as_ref(),
+            )
+        }
+    }
+
+    fn EndUpdateEpoch(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<()> {
+        unsafe { self.provider.EndUpdateEpoch(context.as_ref()) }
+    }
+
+    fn GetLastCommittedSequenceNumber(&self) -> crate::WinResult<i64> {
+        unsafe { self.provider.GetLastCommittedSequenceNumber() }
+    }
+
+    fn BeginOnDataLoss(
+        &self,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        unsafe { self.provider.BeginOnDataLoss(callback.as_ref()) }
+    }
+
+    fn EndOnDataLoss(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        unsafe { self.provider.EndOnDataLoss(context.as_ref()) }
+    }
+
+    fn GetCopyContext(&self) -> crate::WinResult<IFabricOperationDataStream> {
+        unsafe { self.provider.GetCopyContext() }
+    }
+
+    fn GetCopyState(
+        &self,
+        uptosequencenumber: i64,
+        copycontextstream: windows_core::Ref<IFabricOperationDataStream>,
+    ) -> crate::WinResult<IFabricOperationDataStream> {
+        unsafe {
+            self.provider
+                .GetCopyState(uptosequencenumber, copycontextstream.as_ref())
+        }
+    }
+}
+
+// endregion: IFabricAtomicGroupStateProviderBridge
@@ -10,8 +10,10 @@ use std::ffi::c_void;
 
 use crate::{Interface, WString, runtime::executor::BoxedCancelToken};
 use mssf_com::FabricRuntime::{
-    IFabricPrimaryReplicator, IFabricReplicator, IFabricReplicatorCatchupSpecificQuorum,
-    IFabricStatefulServicePartition3, IFabricStatefulServiceReplica,
+    IFabricAtomicGroupStateReplicator, IFabricOperationData, IFabricPrimaryReplicator,
+    IFabricReplicator, IFabricReplicatorCatchupSpecificQuorum, IFabricServiceGroupPartition,
+    IFabricStateProvider, IFabricStateReplicator, IFabricStatefulServicePartition3,
+    IFabricStatefulServiceReplica,
 };
 
 use crate::{
@@ -20,12 +22,26 @@ use crate::{
     sync::fabric_begin_end_proxy,
     types::{
         FaultType, HealthInformation, LoadMetric, LoadMetricListRef, MoveCost, ReplicaRole,
-        ServicePartitionAccessStatus, ServicePartitionInformation,
+        ServicePartitionAccessStatus, ServicePartitionInformation, Uri,
     },
 };
 
-use super::stateful::{PrimaryReplicator, Replicator, StatefulServiceReplica};
-use crate::types::{Epoch, OpenMode, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode};
+use super::{
+    executor::Executor,
+    operation_proxy::OperationStream,
+    stateful::{
+        AtomicGroupStateProvider, PrimaryReplicator, Replicator, StateProvider,
+        StatefulServiceReplica,
+    },
+    stateful_bridge::{
+        IFabricAtomicGroupStateProviderBridge, IFabricOperationDataBridge,
+        IFabricStateProviderBridge,
+    },
+};
+use crate::types::{
+    Epoch, OpenMode, OperationData, ReplicaInformation, ReplicaSetConfig, ReplicaSetQuorumMode,
+    ReplicatorSettings,
+};
 
 pub struct StatefulServiceReplicaProxy {
     com_impl: IFabricStatefulServiceReplica,
@@ -401,10 +417,58 @@ impl StatefulServicePartition {
             .map_err(crate::Error::from)
     }
 
-    /// TODO: not implemented
-    /// Creates a FabricReplicator with the specified settings and returns it to the replica.
-    pub fn create_replicator(&self) -> crate::Result<()> {
-        Err(ErrorCode::E_NOTIMPL.into())
+    /// Creates the SF system replicator with the specified settings, using the default settings
+    /// if None. The replicator keeps the state provider and calls it on executor rt.
+    /// The returned PrimaryReplicatorProxy is to be returned from StatefulServiceReplica::open,
+    /// and the StateReplicator is used by the replica to replicate and pump operations.
+    pub fn create_replicator<E: Executor>(
+        &self,
+        state_provider: impl StateProvider,
+        settings: Option<&ReplicatorSettings>,
+        rt: E,
+    ) -> crate::Result<(StateReplicator, PrimaryReplicatorProxy)> {
+        let provider: IFabricStateProvider =
+            IFabricStateProviderBridge::create(state_provider, rt).into();
+        self.create_replicator_inner(&provider, settings)
+    }
+
+    /// Same as create_replicator, and enables atomic groups in the replicator.
+    pub fn create_atomic_group_replicator<E: Executor>(
+        &self,
+        state_provider: impl AtomicGroupStateProvider,
+        settings: Option<&ReplicatorSettings>,
+        rt: E,
+    ) -> crate::Result<(AtomicGroupStateReplicator, PrimaryReplicatorProxy)> {
+        let provider: IFabricStateProvider =
+            IFabricAtomicGroupStateProviderBridge::create(state_provider, rt).into();
+        let (state_replicator, primary) = self.create_replicator_inner(&provider, settings)?;
+        Ok((
+            AtomicGroupStateReplicator::try_from(state_replicator)?,
+            primary,
+        ))
+    }
+
+    fn create_replicator_inner(
+        &self,
+        provider: &IFabricStateProvider,
+        settings: Option<&ReplicatorSettings>,
+    ) -> crate::Result<(StateReplicator, PrimaryReplicatorProxy)> {
+        let raw_settings = settings.map(|s| s.get_raw());
+        let settings_ptr = raw_settings
+            .as_ref()
+            .map_or(std::ptr::null(), |s| s as *const _);
+        let mut replicator = None;
+        let state_replicator = unsafe {
+            self.com_impl
+                .CreateReplicator(provider, settings_ptr, &mut replicator)
+        }?;
+        let primary: IFabricPrimaryReplicator = replicator
+            .ok_or(crate::Error::from(ErrorCode::E_POINTER))?
+            .cast()?;
+        Ok((
+            StateReplicator::from(state_replicator),
+            PrimaryReplicatorProxy::new(primary),
+        ))
     }
 
     /// Reports load for the current replica in the partition.
@@ -453,6 +517,20 @@ impl StatefulServicePartition {
         let healthinfo_ref = &healthinfo.into();
         unsafe { self.com_impl.ReportReplicaHealth(healthinfo_ref) }.map_err(crate::Error::from)
     }
+
+    /// Resolves a member service of the service group this partition belongs to.
+    /// member_name is the full member name, i.e. "fabric:/app/group#member".
+    /// T is the COM interface of the member, e.g. the instance or replica created by its factory.
+    /// Fails if the service is not a service group.
+    pub fn resolve_member<T: Interface>(&self, member_name: &Uri) -> crate::Result<T> {
+        let group = self.com_impl.cast::<IFabricServiceGroupPartition>()?;
+        let raw = unsafe { group.ResolveMember(member_name.as_raw(), &T::IID) }?;
+        if raw.is_null() {
+            return Err(ErrorCode::E_POINTER.into());
+        }
+        // SAFETY: ResolveMember returns an owned reference of the requested interface.
+        Ok(unsafe { T::from_raw(raw) })
+    }
 }
 
 impl From<&IFabricStatefulServicePartition3> for StatefulServicePartition {
@@ -462,3 +540,186 @@ impl From<&IFabricStatefulServicePartition3> for StatefulServicePartition {
         }
     }
 }
+
+/// Handle to the SF system replicator created by StatefulServicePartition::create_replicator.
+#[derive(Debug, Clone)]
+pub struct StateReplicator {
+    com_impl: IFabricStateReplicator,
+}
+
+impl From<IFabricStateReplicator> for StateReplicator {
+    fn from(com_impl: IFabricStateReplicator) -> Self {
+        Self { com_impl }
+    }
+}
+
+impl StateReplicator {
+    pub fn get_com(&self) -> &IFabricStateReplicator {
+        &self.com_impl
+    }
+
+    /// Replicates the data to the secondaries and returns the sequence number
+    /// once the operation is acknowledged by a quorum.
+    /// Only the primary can replicate.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn replicate(
+        &self,
+        data: OperationData,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let data: IFabricOperationData = IFabricOperationDataBridge::create(data).into();
+        // SF also returns the sequence number in End, so the one from Begin is not used.
+        let mut sequence_number = 0;
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginReplicate(&data, callback, &mut sequence_number) },
+            move |ctx| unsafe { com2.EndReplicate(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
+
+    /// Stream of the replication operations on a secondary.
+    pub fn get_replication_stream(&self) -> crate::Result<OperationStream> {
+        unsafe { self.com_impl.GetReplicationStream() }
+            .map(|com| OperationStream::new(com, false))
+            .map_err(crate::Error::from)
+    }
+
+    /// Stream of the copy operations when a secondary is built.
+    pub fn get_copy_stream(&self) -> crate::Result<OperationStream> {
+        unsafe { self.com_impl.GetCopyStream() }
+            .map(|com| OperationStream::new(com, true))
+            .map_err(crate::Error::from)
+    }
+
+    pub fn update_replicator_settings(&self, settings: &ReplicatorSettings) -> crate::Result<()> {
+        let raw = settings.get_raw();
+        unsafe { self.com_impl.UpdateReplicatorSettings(&raw) }.map_err(crate::Error::from)
+    }
+}
+
+/// The SF system replicator created with atomic group support by
+/// StatefulServicePartition::create_atomic_group_replicator.
+/// Operations replicated in a group are applied by the state provider
+/// only after the group is committed.
+#[derive(Debug, Clone)]
+pub struct AtomicGroupStateReplicator {
+    com_impl: IFabricAtomicGroupStateReplicator,
+    state_replicator: StateReplicator,
+}
+
+impl TryFrom<StateReplicator> for AtomicGroupStateReplicator {
+    type Error = crate::Error;
+
+    fn try_from(state_replicator: StateReplicator) -> Result<Self, Self::Error> {
+        Ok(Self {
+            com_impl: state_replicator.get_com().cast()?,
+            state_replicator,
+        })
+    }
+}
+
+impl AtomicGroupStateReplicator {
+    pub fn get_com(&self) -> &IFabricAtomicGroupStateReplicator {
+        &self.com_impl
+    }
+
+    /// The replicator for operations outside of atomic groups, and for the streams.
+    pub fn state_replicator(&self) -> &StateReplicator {
+        &self.state_replicator
+    }
+
+    /// Creates a new atomic group and returns its id.
+    pub fn create_atomic_group(&self) -> crate::Result<i64> {
+        unsafe { self.com_impl.CreateAtomicGroup() }.map_err(crate::Error::from)
+    }
+
+    /// Replicates the data as part of the atomic group and returns its sequence number.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn replicate_atomic_group_operation(
+        &self,
+        atomic_group_id: i64,
+        data: OperationData,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let data: IFabricOperationData = IFabricOperationDataBridge::create(data).into();
+        let mut sequence_number = 0;
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginReplicateAtomicGroupOperation(
+                    atomic_group_id,
+                    &data,
+                    callback,
+                    &mut sequence_number,
+                )
+            },
+            move |ctx| unsafe { com2.EndReplicateAtomicGroupOperation(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
+
+    /// Commits the atomic group and returns the commit sequence number.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn replicate_atomic_group_commit(
+        &self,
+        atomic_group_id: i64,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let mut sequence_number = 0;
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginReplicateAtomicGroupCommit(
+                    atomic_group_id,
+                    callback,
+                    &mut sequence_number,
+                )
+            },
+            move |ctx| unsafe { com2.EndReplicateAtomicGroupCommit(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
+
+    /// Rolls back the atomic group and returns the rollback sequence number.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn replicate_atomic_group_rollback(
+        &self,
+        atomic_group_id: i64,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<i64> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let mut sequence_number = 0;
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginReplicateAtomicGroupRollback(
+                    atomic_group_id,
+                    callback,
+                    &mut sequence_number,
+                )
+            },
+            move |ctx| unsafe { com2.EndReplicateAtomicGroupRollback(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
+}
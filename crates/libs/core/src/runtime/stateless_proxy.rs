@@ -3,12 +3,16 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::types::{
-    FaultType, HealthInformation, LoadMetric, LoadMetricListRef, MoveCost,
-    ServicePartitionInformation,
+use crate::{
+    ErrorCode,
+    types::{
+        FaultType, HealthInformation, LoadMetric, LoadMetricListRef, MoveCost,
+        ServicePartitionInformation, Uri,
+    },
 };
 use mssf_com::FabricRuntime::{
-    IFabricStatelessServicePartition, IFabricStatelessServicePartition3,
+    IFabricServiceGroupPartition, IFabricStatelessServicePartition,
+    IFabricStatelessServicePartition3,
 };
 use windows_core::Interface;
 // wrap of com interface
@@ -70,4 +74,18 @@ impl StatelessServicePartition {
         let healthinfo_ref = &healthinfo.into();
         unsafe { self.com_impl.ReportInstanceHealth(healthinfo_ref) }.map_err(crate::Error::from)
     }
+
+    /// Resolves a member service of the service group this partition belongs to.
+    /// member_name is the full member name, i.e. "fabric:/app/group#member".
+    /// T is the COM interface of the member, e.g. the instance or replica created by its factory.
+    /// Fails if the service is not a service group.
+    pub fn resolve_member<T: Interface>(&self, member_name: &Uri) -> crate::Result<T> {
+        let group = self.com_impl.cast::<IFabricServiceGroupPartition>()?;
+        let raw = unsafe { group.ResolveMember(member_name.as_raw(), &T::IID) }?;
+        if raw.is_null() {
+            return Err(ErrorCode::E_POINTER.into());
+        }
+        // SAFETY: ResolveMember returns an owned reference of the requested interface.
+        Ok(unsafe { T::from_raw(raw) })
+    }
 }
@@ -3,18 +3,25 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use std::ffi::c_void;
+use std::{ffi::c_void, sync::Arc};
 
 use crate::{PCWSTR, WString};
 use mssf_com::{
     FabricRuntime::{
-        IFabricKeyValueStoreReplica2, IFabricStoreEventHandler, IFabricStoreEventHandler_Impl,
+        IFabricKeyValueStoreReplica6, IFabricStoreEventHandler, IFabricStoreEventHandler_Impl,
+        IFabricStorePostBackupHandler, IFabricStorePostBackupHandler_Impl,
+    },
+    FabricTypes::{
+        FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND, FABRIC_STORE_BACKUP_INFO,
     },
-    FabricTypes::{FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND},
 };
 use windows_core::implement;
 
-use crate::types::{EseLocalStoreSettings, LocalStoreKind, ReplicatorSettings};
+use crate::{
+    runtime::executor::{BoxedCancelToken, Executor},
+    sync::BridgeContext,
+    types::{EseLocalStoreSettings, LocalStoreKind, ReplicatorSettings, StoreBackupInfo},
+};
 
 #[implement(IFabricStoreEventHandler)]
 pub struct DummyStoreEventHandler {}
@@ -35,7 +42,7 @@ pub fn create_com_key_value_store_replica(
     localstorekind: LocalStoreKind,
     localstoresettings: Option<&EseLocalStoreSettings>,
     storeeventhandler: &IFabricStoreEventHandler,
-) -> crate::Result<IFabricKeyValueStoreReplica2> {
+) -> crate::Result<IFabricKeyValueStoreReplica6> {
     let kind: FABRIC_LOCAL_STORE_KIND = localstorekind.into();
     let local_settings: Option<FABRIC_ESE_LOCAL_STORE_SETTINGS> =
         localstoresettings.map(|x| x.get_raw());
@@ -45,7 +52,7 @@ pub fn create_com_key_value_store_replica(
         None => std::ptr::null(),
     };
     crate::API_TABLE
-        .fabric_create_key_value_store_replica::<IFabricKeyValueStoreReplica2>(
+        .fabric_create_key_value_store_replica::<IFabricKeyValueStoreReplica6>(
             PCWSTR::from_raw(storename.as_ptr()),
             partitionid,
             replicaid,
@@ -56,3 +63,82 @@ pub fn create_com_key_value_store_replica(
         )
         .map_err(crate::Error::from)
 }
+
+/// Called after the backup files of KVStoreProxy::backup are written,
+/// e.g. to upload them to remote storage.
+#[trait_variant::make(StorePostBackupHandler: Send)]
+pub trait LocalStorePostBackupHandler: Send + Sync + 'static {
+    /// Returns true if the backup is handled, in which case the store truncates its logs and
+    /// the next incremental backup continues the chain. Returns false to keep the logs, and
+    /// the next backup must then be a full one.
+    async fn post_backup(
+        &self,
+        info: StoreBackupInfo,
+        cancellation_token: BoxedCancelToken,
+    ) -> crate::Result<bool>;
+}
+
+/// Bridge to pass a rust post backup handler to KVStoreProxy::backup.
+#[implement(IFabricStorePostBackupHandler)]
+pub struct IFabricStorePostBackupHandlerBridge<E, H>
+where
+    E: Executor,
+    H: StorePostBackupHandler,
+{
+    inner: Arc<H>,
+    rt: E,
+}
+
+impl<E, H> IFabricStorePostBackupHandlerBridge<E, H>
+where
+    E: Executor,
+    H: StorePostBackupHandler,
+{
+    pub fn create(handler: H, rt: E) -> IFabricStorePostBackupHandlerBridge<E, H> {
+        IFabricStorePostBackupHandlerBridge {
+            inner: Arc::new(handler),
+            rt,
+        }
+    }
+}
+
+impl<E, H> IFabricStorePostBackupHandler_Impl for IFabricStorePostBackupHandlerBridge_Impl<E, H>
+where
+    E: Executor,
+    H: StorePostBackupHandler,
+{
+    #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn BeginPostBackup(
+        &self,
+        info: *const FABRIC_STORE_BACKUP_INFO,
+        callback: windows_core::Ref<super::IFabricAsyncOperationCallback>,
+    ) -> crate::WinResult<super::IFabricAsyncOperationContext> {
+        let info = StoreBackupInfo::from(
+            unsafe { info.as_ref() }.ok_or(crate::WinError::from(crate::ErrorCode::E_POINTER))?,
+        );
+        let inner = self.inner.clone();
+        let (ctx, token) = BridgeContext::make(callback);
+        ctx.spawn(&self.rt, async move {
+            inner
+                .post_backup(info, token)
+                .await
+                .map(u8::from)
+                .map_err(crate::WinError::from)
+        })
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, ret(level = "debug"), err)
+    )]
+    fn EndPostBackup(
+        &self,
+        context: windows_core::Ref<super::IFabricAsyncOperationContext>,
+    ) -> crate::WinResult<u8> {
+        BridgeContext::result(context)?
+    }
+}
@@ -3,22 +3,32 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::{PCWSTR, runtime::executor::BoxedCancelToken};
+use std::{ffi::c_void, time::SystemTime};
+
+use crate::{Interface, PCWSTR, WString, runtime::executor::BoxedCancelToken};
 use mssf_com::{
     FabricRuntime::{
-        IFabricKeyValueStoreItemResult, IFabricKeyValueStoreReplica2, IFabricTransaction,
+        IFabricKeyValueStoreItemEnumerator, IFabricKeyValueStoreItemEnumerator2,
+        IFabricKeyValueStoreItemMetadataEnumerator, IFabricKeyValueStoreItemMetadataEnumerator2,
+        IFabricKeyValueStoreItemMetadataResult, IFabricKeyValueStoreItemResult,
+        IFabricKeyValueStoreReplica6, IFabricStorePostBackupHandler, IFabricTransaction,
     },
     FabricTypes::{FABRIC_KEY_VALUE_STORE_ITEM, FABRIC_KEY_VALUE_STORE_ITEM_METADATA},
 };
 
 use crate::sync::fabric_begin_end_proxy;
 
-use crate::types::TransactionIsolationLevel;
+use super::{
+    executor::Executor,
+    store::{IFabricStorePostBackupHandlerBridge, StorePostBackupHandler},
+};
+
+use crate::types::{KeyValueStoreRestoreSettings, StoreBackupOption, TransactionIsolationLevel};
 
 // wrapp for kv store
 #[derive(Clone)]
 pub struct KVStoreProxy {
-    com_impl: IFabricKeyValueStoreReplica2,
+    com_impl: IFabricKeyValueStoreReplica6,
 }
 
 pub struct TransactionProxy {
@@ -42,6 +52,17 @@ impl KVStoreItemProxy {
         unsafe { std::slice::from_raw_parts(item.Value, meta.ValueSizeInBytes as usize) }
     }
 
+    /// The lsn of the last modification of the item.
+    pub fn sequence_number(&self) -> i64 {
+        Self::get_meta_inner(self.get_item_inner()).SequenceNumber
+    }
+
+    pub fn last_modified_utc(&self) -> SystemTime {
+        Self::get_meta_inner(self.get_item_inner())
+            .LastModifiedUtc
+            .into()
+    }
+
     fn get_item_inner(&self) -> &FABRIC_KEY_VALUE_STORE_ITEM {
         unsafe { self.com_impl.get_Item().as_ref().unwrap() }
     }
@@ -50,11 +71,112 @@ impl KVStoreItemProxy {
     }
 }
 
+/// Metadata of an item, without reading the value.
+pub struct KVStoreItemMetadataProxy {
+    com_impl: IFabricKeyValueStoreItemMetadataResult,
+}
+
+impl KVStoreItemMetadataProxy {
+    pub fn key(&self) -> &[u16] {
+        unsafe { self.get_meta_inner().Key.as_wide() }
+    }
+
+    pub fn value_size(&self) -> usize {
+        self.get_meta_inner().ValueSizeInBytes as usize
+    }
+
+    /// The lsn of the last modification of the item.
+    pub fn sequence_number(&self) -> i64 {
+        self.get_meta_inner().SequenceNumber
+    }
+
+    pub fn last_modified_utc(&self) -> SystemTime {
+        self.get_meta_inner().LastModifiedUtc.into()
+    }
+
+    fn get_meta_inner(&self) -> &FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+        unsafe { self.com_impl.get_Metadata().as_ref().unwrap() }
+    }
+}
+
+/// Iterator of the items of an enumeration in key order.
+/// The enumeration reads from the transaction it was created with, which
+/// must be kept open while iterating.
+pub struct KVStoreItemIter {
+    com_impl: IFabricKeyValueStoreItemEnumerator2,
+}
+
+impl TryFrom<IFabricKeyValueStoreItemEnumerator> for KVStoreItemIter {
+    type Error = crate::Error;
+
+    fn try_from(value: IFabricKeyValueStoreItemEnumerator) -> Result<Self, Self::Error> {
+        Ok(Self {
+            com_impl: value.cast()?,
+        })
+    }
+}
+
+impl Iterator for KVStoreItemIter {
+    type Item = crate::Result<KVStoreItemProxy>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match unsafe { self.com_impl.TryMoveNext() } {
+            Ok(0) => None,
+            Ok(_) => unsafe { self.com_impl.get_Current() }
+                .map(|com_impl| Ok(KVStoreItemProxy { com_impl })),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+/// Iterator of the item metadata of an enumeration in key order.
+/// Same as KVStoreItemIter, but the values are not read.
+pub struct KVStoreItemMetadataIter {
+    com_impl: IFabricKeyValueStoreItemMetadataEnumerator2,
+}
+
+impl TryFrom<IFabricKeyValueStoreItemMetadataEnumerator> for KVStoreItemMetadataIter {
+    type Error = crate::Error;
+
+    fn try_from(value: IFabricKeyValueStoreItemMetadataEnumerator) -> Result<Self, Self::Error> {
+        Ok(Self {
+            com_impl: value.cast()?,
+        })
+    }
+}
+
+impl Iterator for KVStoreItemMetadataIter {
+    type Item = crate::Result<KVStoreItemMetadataProxy>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match unsafe { self.com_impl.TryMoveNext() } {
+            Ok(0) => None,
+            Ok(_) => unsafe { self.com_impl.get_Current() }
+                .map(|com_impl| Ok(KVStoreItemMetadataProxy { com_impl })),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+// The Try* apis return a null result if the key does not exist, which the
+// generated code turns into an empty error.
+fn null_to_none<T>(res: crate::WinResult<T>) -> crate::Result<Option<T>> {
+    match res {
+        Ok(t) => Ok(Some(t)),
+        Err(e) if e.code() == crate::HRESULT(0) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
 impl KVStoreProxy {
-    pub fn new(com_impl: IFabricKeyValueStoreReplica2) -> KVStoreProxy {
+    pub fn new(com_impl: IFabricKeyValueStoreReplica6) -> KVStoreProxy {
         KVStoreProxy { com_impl }
     }
 
+    pub fn get_com(&self) -> &IFabricKeyValueStoreReplica6 {
+        &self.com_impl
+    }
+
     pub fn create_transaction(&self) -> crate::Result<TransactionProxy> {
         let tx = unsafe { self.com_impl.CreateTransaction() }?;
         Ok(TransactionProxy { com_impl: tx })
@@ -68,6 +190,16 @@ impl KVStoreProxy {
         .map_err(crate::Error::from)
     }
 
+    /// Same as add, but returns false instead of erroring if the key exists.
+    pub fn try_add(&self, tx: &TransactionProxy, key: &[u16], value: &[u8]) -> crate::Result<bool> {
+        unsafe {
+            self.com_impl
+                .TryAdd(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()), value)
+        }
+        .map(|b| b != 0)
+        .map_err(crate::Error::from)
+    }
+
     pub fn get(&self, tx: &TransactionProxy, key: &[u16]) -> crate::Result<KVStoreItemProxy> {
         let com = unsafe {
             self.com_impl
@@ -76,6 +208,95 @@ impl KVStoreProxy {
         Ok(KVStoreItemProxy { com_impl: com })
     }
 
+    /// Same as get, but returns None instead of erroring if the key does not exist.
+    pub fn try_get(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+    ) -> crate::Result<Option<KVStoreItemProxy>> {
+        null_to_none(unsafe {
+            self.com_impl
+                .TryGet(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        })
+        .map(|com| com.map(|com_impl| KVStoreItemProxy { com_impl }))
+    }
+
+    pub fn get_metadata(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+    ) -> crate::Result<KVStoreItemMetadataProxy> {
+        let com = unsafe {
+            self.com_impl
+                .GetMetadata(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        }?;
+        Ok(KVStoreItemMetadataProxy { com_impl: com })
+    }
+
+    /// Same as get_metadata, but returns None instead of erroring if the key does not exist.
+    pub fn try_get_metadata(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+    ) -> crate::Result<Option<KVStoreItemMetadataProxy>> {
+        null_to_none(unsafe {
+            self.com_impl
+                .TryGetMetadata(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        })
+        .map(|com| com.map(|com_impl| KVStoreItemMetadataProxy { com_impl }))
+    }
+
+    pub fn contains(&self, tx: &TransactionProxy, key: &[u16]) -> crate::Result<bool> {
+        unsafe {
+            self.com_impl
+                .Contains(&tx.com_impl, PCWSTR::from_raw(key.as_ptr()))
+        }
+        .map(|b| b != 0)
+        .map_err(crate::Error::from)
+    }
+
+    // check sequence number is the lsn that last time the key got modified.
+    // if lsn does not match the update will error out.
+    // specify 0 to ignore check.
+    pub fn update(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+        value: &[u8],
+        checksequencenumber: i64,
+    ) -> crate::Result<()> {
+        unsafe {
+            self.com_impl.Update(
+                &tx.com_impl,
+                PCWSTR::from_raw(key.as_ptr()),
+                value,
+                checksequencenumber,
+            )
+        }
+        .map_err(crate::Error::from)
+    }
+
+    /// Same as update, but returns false instead of erroring if the key does not exist.
+    /// A sequence number mismatch is still an error.
+    pub fn try_update(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+        value: &[u8],
+        checksequencenumber: i64,
+    ) -> crate::Result<bool> {
+        unsafe {
+            self.com_impl.TryUpdate(
+                &tx.com_impl,
+                PCWSTR::from_raw(key.as_ptr()),
+                value,
+                checksequencenumber,
+            )
+        }
+        .map(|b| b != 0)
+        .map_err(crate::Error::from)
+    }
+
     // check sequence number is the lsn that last time the key got modified.
     // if lsn does not match the remove will error out.
     // specify 0 to ignore check.
@@ -94,6 +315,165 @@ impl KVStoreProxy {
         }
         .map_err(crate::Error::from)
     }
+
+    /// Same as remove, but returns false instead of erroring if the key does not exist.
+    /// A sequence number mismatch is still an error.
+    pub fn try_remove(
+        &self,
+        tx: &TransactionProxy,
+        key: &[u16],
+        checksequencenumber: i64,
+    ) -> crate::Result<bool> {
+        unsafe {
+            self.com_impl.TryRemove(
+                &tx.com_impl,
+                PCWSTR::from_raw(key.as_ptr()),
+                checksequencenumber,
+            )
+        }
+        .map(|b| b != 0)
+        .map_err(crate::Error::from)
+    }
+
+    /// Enumerates all items in key order.
+    pub fn enumerate(&self, tx: &TransactionProxy) -> crate::Result<KVStoreItemIter> {
+        unsafe { self.com_impl.Enumerate(&tx.com_impl) }?.try_into()
+    }
+
+    /// Enumerates the items starting at key_prefix in key order.
+    /// With strict_prefix only the keys starting with key_prefix are returned,
+    /// otherwise the enumeration continues to the last key, i.e. a range scan.
+    pub fn enumerate_by_key(
+        &self,
+        tx: &TransactionProxy,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemIter> {
+        unsafe {
+            self.com_impl.EnumerateByKey2(
+                &tx.com_impl,
+                PCWSTR::from_raw(key_prefix.as_ptr()),
+                strict_prefix,
+            )
+        }?
+        .try_into()
+    }
+
+    /// Enumerates the metadata of all items in key order.
+    pub fn enumerate_metadata(
+        &self,
+        tx: &TransactionProxy,
+    ) -> crate::Result<KVStoreItemMetadataIter> {
+        unsafe { self.com_impl.EnumerateMetadata(&tx.com_impl) }?.try_into()
+    }
+
+    /// Same as enumerate_by_key, but for the metadata of the items.
+    pub fn enumerate_metadata_by_key(
+        &self,
+        tx: &TransactionProxy,
+        key_prefix: &[u16],
+        strict_prefix: bool,
+    ) -> crate::Result<KVStoreItemMetadataIter> {
+        unsafe {
+            self.com_impl.EnumerateMetadataByKey2(
+                &tx.com_impl,
+                PCWSTR::from_raw(key_prefix.as_ptr()),
+                strict_prefix,
+            )
+        }?
+        .try_into()
+    }
+
+    /// Backs up the store into backup_dir, which must be empty for full backups.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn backup(
+        &self,
+        backup_dir: &WString,
+        option: StoreBackupOption,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        self.backup_inner(backup_dir, option, None, cancellation_token)
+            .await
+    }
+
+    /// Same as backup, and calls the post backup handler on executor rt
+    /// once the backup files are written.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn backup_with_handler<E: Executor>(
+        &self,
+        backup_dir: &WString,
+        option: StoreBackupOption,
+        post_backup_handler: impl StorePostBackupHandler,
+        rt: E,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        let handler: IFabricStorePostBackupHandler =
+            IFabricStorePostBackupHandlerBridge::create(post_backup_handler, rt).into();
+        self.backup_inner(backup_dir, option, Some(&handler), cancellation_token)
+            .await
+    }
+
+    async fn backup_inner(
+        &self,
+        backup_dir: &WString,
+        option: StoreBackupOption,
+        handler: Option<&IFabricStorePostBackupHandler>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let rx = fabric_begin_end_proxy(
+            move |callback| unsafe {
+                com1.BeginBackup(
+                    PCWSTR::from_raw(backup_dir.as_ptr()),
+                    option.into(),
+                    handler,
+                    callback,
+                )
+            },
+            move |ctx| unsafe { com2.EndBackup(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
+
+    /// Restores the store from the backups in backup_dir.
+    /// Incremental backups must be placed in the same dir as the full backup they follow.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, level = "debug", ret, err)
+    )]
+    pub async fn restore(
+        &self,
+        backup_dir: &WString,
+        settings: Option<&KeyValueStoreRestoreSettings>,
+        cancellation_token: Option<BoxedCancelToken>,
+    ) -> crate::Result<()> {
+        let com1 = &self.com_impl;
+        let com2 = self.com_impl.clone();
+        let rx = fabric_begin_end_proxy(
+            move |callback| {
+                let dir = PCWSTR::from_raw(backup_dir.as_ptr());
+                match settings {
+                    Some(settings) => {
+                        let (mut raw, ex1) = settings.get_raw_parts();
+                        raw.Reserved = std::ptr::addr_of!(ex1) as *mut c_void;
+                        unsafe { com1.BeginRestore2(dir, &raw, callback) }
+                    }
+                    None => unsafe { com1.BeginRestore(dir, callback) },
+                }
+            },
+            move |ctx| unsafe { com2.EndRestore(ctx) },
+            cancellation_token,
+        );
+        rx.await?.map_err(crate::Error::from)
+    }
 }
 
 impl TransactionProxy {
@@ -128,3 +508,509 @@ impl TransactionProxy {
         unsafe { self.com_impl.Rollback() };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use mssf_com::{
+        FabricCommon::{
+            IFabricAsyncOperationCallback, IFabricAsyncOperationContext, IFabricStringResult,
+        },
+        FabricRuntime::{
+            IFabricKeyValueStoreItemEnumerator, IFabricKeyValueStoreItemEnumerator_Impl,
+            IFabricKeyValueStoreItemEnumerator2, IFabricKeyValueStoreItemEnumerator2_Impl,
+            IFabricKeyValueStoreItemMetadataEnumerator, IFabricKeyValueStoreItemMetadataResult,
+            IFabricKeyValueStoreItemResult, IFabricKeyValueStoreItemResult_Impl,
+            IFabricKeyValueStoreReplica_Impl, IFabricKeyValueStoreReplica2_Impl,
+            IFabricKeyValueStoreReplica3_Impl, IFabricKeyValueStoreReplica4_Impl,
+            IFabricKeyValueStoreReplica5_Impl, IFabricKeyValueStoreReplica6,
+            IFabricKeyValueStoreReplica6_Impl, IFabricReplicator, IFabricStatefulServicePartition,
+            IFabricStatefulServiceReplica_Impl, IFabricStorePostBackupHandler, IFabricTransaction,
+            IFabricTransaction_Impl, IFabricTransactionBase, IFabricTransactionBase_Impl,
+        },
+        FabricTypes::{
+            FABRIC_EPOCH, FABRIC_KEY_VALUE_STORE_ITEM, FABRIC_KEY_VALUE_STORE_ITEM_METADATA,
+            FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS, FABRIC_KEY_VALUE_STORE_TRANSACTION_SETTINGS,
+            FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_ROLE, FABRIC_REPLICATOR_SETTINGS,
+            FABRIC_STORE_BACKUP_OPTION, FABRIC_TRANSACTION_ISOLATION_LEVEL,
+            FABRIC_TRANSACTION_ISOLATION_LEVEL_DEFAULT,
+        },
+    };
+    use windows_core::{PCWSTR, Ref};
+
+    use super::KVStoreProxy;
+    use crate::{ErrorCode, GUID, WString, WinError, WinResult};
+
+    // Keys the test store does not have, or fails on.
+    const MISSING: &str = "missing";
+    const FAILING: &str = "failing";
+
+    fn not_impl<T>() -> WinResult<T> {
+        Err(ErrorCode::E_NOTIMPL.into())
+    }
+
+    fn check_key(key: &PCWSTR) -> WinResult<bool> {
+        match String::from_utf16_lossy(unsafe { key.as_wide() }).as_str() {
+            MISSING => Ok(false),
+            FAILING => Err(ErrorCode::FABRIC_E_SEQUENCE_NUMBER_CHECK_FAILED.into()),
+            _ => Ok(true),
+        }
+    }
+
+    #[windows_core::implement(IFabricKeyValueStoreItemResult)]
+    struct TestItem {
+        _key: WString,
+        _value: Vec<u8>,
+        _meta: Box<FABRIC_KEY_VALUE_STORE_ITEM_METADATA>,
+        item: Box<FABRIC_KEY_VALUE_STORE_ITEM>,
+    }
+
+    impl TestItem {
+        fn new_com(key: &str) -> IFabricKeyValueStoreItemResult {
+            let key = WString::from(key);
+            let mut value = b"value".to_vec();
+            let meta = Box::new(FABRIC_KEY_VALUE_STORE_ITEM_METADATA {
+                Key: key.as_pcwstr(),
+                ValueSizeInBytes: value.len() as i32,
+                SequenceNumber: 1,
+                ..Default::default()
+            });
+            let item = Box::new(FABRIC_KEY_VALUE_STORE_ITEM {
+                Metadata: meta.as_ref(),
+                Value: value.as_mut_ptr(),
+                ..Default::default()
+            });
+            TestItem {
+                _key: key,
+                _value: value,
+                _meta: meta,
+                item,
+            }
+            .into()
+        }
+    }
+
+    impl IFabricKeyValueStoreItemResult_Impl for TestItem_Impl {
+        fn get_Item(&self) -> *mut FABRIC_KEY_VALUE_STORE_ITEM {
+            self.item.as_ref() as *const _ as *mut _
+        }
+    }
+
+    /// Enumerator of the given items, failing after them if fail is set.
+    #[windows_core::implement(IFabricKeyValueStoreItemEnumerator2)]
+    struct TestEnumerator {
+        items: Vec<IFabricKeyValueStoreItemResult>,
+        fail: bool,
+        pos: AtomicUsize,
+    }
+
+    impl IFabricKeyValueStoreItemEnumerator_Impl for TestEnumerator_Impl {
+        fn MoveNext(&self) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn get_Current(&self) -> Option<IFabricKeyValueStoreItemResult> {
+            let pos = self.pos.load(Ordering::Relaxed);
+            self.items.get(pos.checked_sub(1)?).cloned()
+        }
+    }
+
+    impl IFabricKeyValueStoreItemEnumerator2_Impl for TestEnumerator_Impl {
+        fn TryMoveNext(&self) -> WinResult<u8> {
+            let pos = self.pos.load(Ordering::Relaxed);
+            if pos < self.items.len() {
+                self.pos.store(pos + 1, Ordering::Relaxed);
+                Ok(1)
+            } else if self.fail {
+                Err(ErrorCode::FABRIC_E_OBJECT_CLOSED.into())
+            } else {
+                Ok(0)
+            }
+        }
+    }
+
+    #[windows_core::implement(IFabricTransaction)]
+    struct TestTransaction {
+        id: GUID,
+    }
+
+    impl IFabricTransactionBase_Impl for TestTransaction_Impl {
+        fn get_Id(&self) -> *mut GUID {
+            &self.id as *const _ as *mut _
+        }
+
+        fn get_IsolationLevel(&self) -> FABRIC_TRANSACTION_ISOLATION_LEVEL {
+            FABRIC_TRANSACTION_ISOLATION_LEVEL_DEFAULT
+        }
+    }
+
+    impl IFabricTransaction_Impl for TestTransaction_Impl {
+        fn BeginCommit(
+            &self,
+            _: u32,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndCommit(&self, _: Ref<IFabricAsyncOperationContext>) -> WinResult<i64> {
+            not_impl()
+        }
+
+        fn Rollback(&self) {}
+    }
+
+    /// Store with the Try* and enumeration apis only.
+    #[windows_core::implement(IFabricKeyValueStoreReplica6)]
+    #[derive(Default)]
+    struct TestStore {
+        strict_prefix: Mutex<Vec<bool>>,
+    }
+
+    impl IFabricStatefulServiceReplica_Impl for TestStore_Impl {
+        fn BeginOpen(
+            &self,
+            _: FABRIC_REPLICA_OPEN_MODE,
+            _: Ref<IFabricStatefulServicePartition>,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndOpen(&self, _: Ref<IFabricAsyncOperationContext>) -> WinResult<IFabricReplicator> {
+            not_impl()
+        }
+
+        fn BeginChangeRole(
+            &self,
+            _: FABRIC_REPLICA_ROLE,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndChangeRole(
+            &self,
+            _: Ref<IFabricAsyncOperationContext>,
+        ) -> WinResult<IFabricStringResult> {
+            not_impl()
+        }
+
+        fn BeginClose(
+            &self,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndClose(&self, _: Ref<IFabricAsyncOperationContext>) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn Abort(&self) {}
+    }
+
+    impl IFabricKeyValueStoreReplica_Impl for TestStore_Impl {
+        fn GetCurrentEpoch(&self, _: *mut FABRIC_EPOCH) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn UpdateReplicatorSettings(&self, _: *const FABRIC_REPLICATOR_SETTINGS) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn CreateTransaction(&self) -> WinResult<IFabricTransaction> {
+            Ok(TestTransaction { id: GUID::zeroed() }.into())
+        }
+
+        fn Add(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+            _: i32,
+            _: *const u8,
+        ) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn Remove(&self, _: Ref<IFabricTransactionBase>, _: &PCWSTR, _: i64) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn Update(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+            _: i32,
+            _: *const u8,
+            _: i64,
+        ) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn Get(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemResult> {
+            not_impl()
+        }
+
+        fn GetMetadata(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemMetadataResult> {
+            not_impl()
+        }
+
+        fn Contains(&self, _: Ref<IFabricTransactionBase>, _: &PCWSTR) -> WinResult<u8> {
+            not_impl()
+        }
+
+        fn Enumerate(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+        ) -> WinResult<IFabricKeyValueStoreItemEnumerator> {
+            not_impl()
+        }
+
+        fn EnumerateByKey(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemEnumerator> {
+            not_impl()
+        }
+
+        fn EnumerateMetadata(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+        ) -> WinResult<IFabricKeyValueStoreItemMetadataEnumerator> {
+            not_impl()
+        }
+
+        fn EnumerateMetadataByKey(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemMetadataEnumerator> {
+            not_impl()
+        }
+    }
+
+    impl IFabricKeyValueStoreReplica2_Impl for TestStore_Impl {
+        fn Backup(&self, _: &PCWSTR) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn Restore(&self, _: &PCWSTR) -> WinResult<()> {
+            not_impl()
+        }
+
+        fn CreateTransaction2(
+            &self,
+            _: *const FABRIC_KEY_VALUE_STORE_TRANSACTION_SETTINGS,
+        ) -> WinResult<IFabricTransaction> {
+            not_impl()
+        }
+    }
+
+    impl IFabricKeyValueStoreReplica3_Impl for TestStore_Impl {
+        fn BeginBackup(
+            &self,
+            _: &PCWSTR,
+            _: FABRIC_STORE_BACKUP_OPTION,
+            _: Ref<IFabricStorePostBackupHandler>,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndBackup(&self, _: Ref<IFabricAsyncOperationContext>) -> WinResult<()> {
+            not_impl()
+        }
+    }
+
+    impl IFabricKeyValueStoreReplica4_Impl for TestStore_Impl {
+        fn BeginRestore(
+            &self,
+            _: &PCWSTR,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+
+        fn EndRestore(&self, _: Ref<IFabricAsyncOperationContext>) -> WinResult<()> {
+            not_impl()
+        }
+    }
+
+    impl IFabricKeyValueStoreReplica5_Impl for TestStore_Impl {
+        fn TryAdd(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+            _: i32,
+            _: *const u8,
+        ) -> WinResult<u8> {
+            not_impl()
+        }
+
+        fn TryRemove(&self, _: Ref<IFabricTransactionBase>, key: &PCWSTR, _: i64) -> WinResult<u8> {
+            check_key(key).map(u8::from)
+        }
+
+        fn TryUpdate(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            key: &PCWSTR,
+            _: i32,
+            _: *const u8,
+            _: i64,
+        ) -> WinResult<u8> {
+            check_key(key).map(u8::from)
+        }
+
+        fn TryGet(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            key: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemResult> {
+            match check_key(key)? {
+                true => Ok(TestItem::new_com("a")),
+                // Null result, as SF returns for a missing key.
+                false => Err(WinError::empty()),
+            }
+        }
+
+        fn TryGetMetadata(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            key: &PCWSTR,
+        ) -> WinResult<IFabricKeyValueStoreItemMetadataResult> {
+            match check_key(key)? {
+                true => not_impl(),
+                false => Err(WinError::empty()),
+            }
+        }
+
+        fn EnumerateByKey2(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            keyprefix: &PCWSTR,
+            strictprefix: bool,
+        ) -> WinResult<IFabricKeyValueStoreItemEnumerator> {
+            self.strict_prefix.lock().unwrap().push(strictprefix);
+            let enumerator: IFabricKeyValueStoreItemEnumerator2 = TestEnumerator {
+                items: vec![TestItem::new_com("a"), TestItem::new_com("b")],
+                fail: check_key(keyprefix).is_err(),
+                pos: AtomicUsize::new(0),
+            }
+            .into();
+            Ok(enumerator.into())
+        }
+
+        fn EnumerateMetadataByKey2(
+            &self,
+            _: Ref<IFabricTransactionBase>,
+            _: &PCWSTR,
+            _: bool,
+        ) -> WinResult<IFabricKeyValueStoreItemMetadataEnumerator> {
+            not_impl()
+        }
+    }
+
+    impl IFabricKeyValueStoreReplica6_Impl for TestStore_Impl {
+        fn BeginRestore2(
+            &self,
+            _: &PCWSTR,
+            _: *const FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS,
+            _: Ref<IFabricAsyncOperationCallback>,
+        ) -> WinResult<IFabricAsyncOperationContext> {
+            not_impl()
+        }
+    }
+
+    fn key(k: &str) -> WString {
+        WString::from(k)
+    }
+
+    fn is_seq_check_failed(e: crate::Error) -> bool {
+        e.try_as_fabric_error_code() == Ok(ErrorCode::FABRIC_E_SEQUENCE_NUMBER_CHECK_FAILED)
+    }
+
+    #[test]
+    fn test_try_apis() {
+        let com: IFabricKeyValueStoreReplica6 = TestStore::default().into();
+        let kv = KVStoreProxy::new(com);
+        let tx = kv.create_transaction().unwrap();
+
+        let item = kv.try_get(&tx, key("a").as_wide()).unwrap().unwrap();
+        assert_eq!(item.key(), key("a").as_wide());
+        assert_eq!(item.val(), b"value");
+        assert!(kv.try_get(&tx, key(MISSING).as_wide()).unwrap().is_none());
+        assert!(is_seq_check_failed(
+            kv.try_get(&tx, key(FAILING).as_wide()).err().unwrap()
+        ));
+
+        assert!(
+            kv.try_get_metadata(&tx, key(MISSING).as_wide())
+                .unwrap()
+                .is_none()
+        );
+        assert!(is_seq_check_failed(
+            kv.try_get_metadata(&tx, key(FAILING).as_wide())
+                .err()
+                .unwrap()
+        ));
+
+        assert!(kv.try_update(&tx, key("a").as_wide(), b"v", 1).unwrap());
+        assert!(!kv.try_update(&tx, key(MISSING).as_wide(), b"v", 1).unwrap());
+        assert!(is_seq_check_failed(
+            kv.try_update(&tx, key(FAILING).as_wide(), b"v", 1)
+                .unwrap_err()
+        ));
+
+        assert!(kv.try_remove(&tx, key("a").as_wide(), 1).unwrap());
+        assert!(!kv.try_remove(&tx, key(MISSING).as_wide(), 1).unwrap());
+        assert!(is_seq_check_failed(
+            kv.try_remove(&tx, key(FAILING).as_wide(), 1).unwrap_err()
+        ));
+    }
+
+    #[test]
+    fn test_enumerate_by_key() {
+        let store = TestStore::default();
+        let com: IFabricKeyValueStoreReplica6 = store.into();
+        let kv = KVStoreProxy::new(com.clone());
+        let tx = kv.create_transaction().unwrap();
+
+        // The end of the stream ends the iteration.
+        let keys = kv
+            .enumerate_by_key(&tx, key("a").as_wide(), true)
+            .unwrap()
+            .map(|item| String::from_utf16_lossy(item.unwrap().key()))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec!["a", "b"]);
+
+        // Errors are yielded.
+        let mut iter = kv
+            .enumerate_by_key(&tx, key(FAILING).as_wide(), false)
+            .unwrap();
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_ok());
+        let e = iter.next().unwrap().err().unwrap();
+        assert_eq!(
+            e.try_as_fabric_error_code(),
+            Ok(ErrorCode::FABRIC_E_OBJECT_CLOSED)
+        );
+
+        let store: &TestStore = unsafe { windows_core::AsImpl::as_impl(&com) };
+        assert_eq!(*store.strict_prefix.lock().unwrap(), vec![true, false]);
+    }
+}
@@ -9,8 +9,9 @@ use crate::{
     PCWSTR, WString,
     iter::{FabricIter, FabricListAccessor},
 };
-use mssf_com::FabricCommon::{
-    IFabricStringListResult, IFabricStringResult, IFabricStringResult_Impl,
+use mssf_com::{
+    FabricCommon::{IFabricStringListResult, IFabricStringResult, IFabricStringResult_Impl},
+    FabricTypes::FABRIC_STRING_LIST,
 };
 use windows_core::implement;
 
@@ -148,6 +149,57 @@ impl FabricListAccessor<PCWSTR> for FabricStringListAccessor<'_> {
 pub(crate) type FabricStringListAccessorIter<'a> =
     FabricIter<'a, PCWSTR, WString, FabricStringListAccessor<'a>>;
 
+/// Copies the strings out of a FABRIC_STRING_LIST. Null list yields an empty vec.
+pub(crate) fn fabric_string_list_to_vec(list: *const FABRIC_STRING_LIST) -> Vec<WString> {
+    match unsafe { list.as_ref() } {
+        Some(l) if !l.Items.is_null() => {
+            let accessor = FabricStringListAccessor {
+                itemcount: l.Count,
+                first_str: l.Items as *mut PCWSTR,
+                phantom: PhantomData,
+            };
+            FabricStringListAccessorIter::new(&accessor, &accessor).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Owns the pointer array of a FABRIC_STRING_LIST passed to SF.
+/// The strings themselves are borrowed from the rust strings.
+pub(crate) struct FabricStringListRaw<'a> {
+    internal: Box<FABRIC_STRING_LIST>,
+    _items: Vec<PCWSTR>,
+    _phantom: PhantomData<&'a WString>,
+}
+
+impl<'a> FabricStringListRaw<'a> {
+    pub(crate) fn new(strs: &'a [WString]) -> Self {
+        let items = strs.iter().map(|s| s.as_pcwstr()).collect::<Vec<_>>();
+        let internal = Box::new(FABRIC_STRING_LIST {
+            Count: items.len() as u32,
+            Items: items.as_ptr(),
+        });
+        Self {
+            internal,
+            _items: items,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn as_ffi(&self) -> &FABRIC_STRING_LIST {
+        self.internal.as_ref()
+    }
+
+    /// Null for an empty list, so that SF treats it as not specified.
+    pub(crate) fn as_ptr_or_null(&self) -> *const FABRIC_STRING_LIST {
+        if self.internal.Count == 0 {
+            std::ptr::null()
+        } else {
+            self.as_ffi()
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::strings::WStringWrap;
@@ -0,0 +1,742 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use mssf_com::{
+    FabricClient::IFabricApplicationUpgradeProgressResult2,
+    FabricTypes::{
+        FABRIC_APPLICATION_CAPACITY_DESCRIPTION, FABRIC_APPLICATION_DESCRIPTION,
+        FABRIC_APPLICATION_DESCRIPTION_EX1, FABRIC_APPLICATION_METRIC_DESCRIPTION,
+        FABRIC_APPLICATION_METRIC_LIST, FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_AUTOMATIC,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_DEFAULT,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_INVALID,
+        FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_MANUAL, FABRIC_APPLICATION_PARAMETER,
+        FABRIC_APPLICATION_PARAMETER_LIST, FABRIC_APPLICATION_UPDATE_DESCRIPTION,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES,
+        FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_NONE, FABRIC_APPLICATION_UPGRADE_DESCRIPTION,
+        FABRIC_APPLICATION_UPGRADE_KIND_ROLLING, FABRIC_APPLICATION_UPGRADE_STATE,
+        FABRIC_APPLICATION_UPGRADE_STATE_FAILED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_COMPLETED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_PENDING,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS,
+        FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_PENDING,
+        FABRIC_DELETE_APPLICATION_DESCRIPTION,
+        FABRIC_EXTERNAL_STORE_PROVISION_APPLICATION_TYPE_DESCRIPTION,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE,
+        FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_EX1,
+        FABRIC_PROVISION_APPLICATION_TYPE_KIND_EXTERNAL_STORE,
+        FABRIC_PROVISION_APPLICATION_TYPE_KIND_IMAGE_STORE_PATH,
+        FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION,
+    },
+};
+
+use super::upgrade::{RollingUpgradePolicyDescriptionRaw, UpgradeDomainStatusListRef};
+use crate::{
+    WString,
+    strings::WStringWrap,
+    types::{RollingUpgradeMode, RollingUpgradePolicyDescription, UpgradeDomainStatus, Uri},
+};
+
+// FABRIC_APPLICATION_PARAMETER
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplicationParameter {
+    pub name: WString,
+    pub value: WString,
+}
+
+/// Holder for FABRIC_APPLICATION_PARAMETER_LIST memories.
+pub(crate) struct ApplicationParameterListRaw<'a> {
+    _items: Vec<FABRIC_APPLICATION_PARAMETER>,
+    list: Box<FABRIC_APPLICATION_PARAMETER_LIST>,
+    phantom: PhantomData<&'a ApplicationParameter>,
+}
+
+impl ApplicationParameterListRaw<'_> {
+    pub(crate) fn new(params: &[ApplicationParameter]) -> ApplicationParameterListRaw<'_> {
+        let mut items = params
+            .iter()
+            .map(|p| FABRIC_APPLICATION_PARAMETER {
+                Name: p.name.as_pcwstr(),
+                Value: p.value.as_pcwstr(),
+                Reserved: std::ptr::null_mut(),
+            })
+            .collect::<Vec<_>>();
+        let list = Box::new(FABRIC_APPLICATION_PARAMETER_LIST {
+            Count: items.len() as u32,
+            Items: items.as_mut_ptr(),
+        });
+        ApplicationParameterListRaw {
+            _items: items,
+            list,
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn as_ffi(&self) -> &FABRIC_APPLICATION_PARAMETER_LIST {
+        self.list.as_ref()
+    }
+}
+
+// FABRIC_APPLICATION_METRIC_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApplicationMetricDescription {
+    pub name: WString,
+    pub node_reservation_capacity: u32,
+    pub maximum_node_capacity: u32,
+    pub total_application_capacity: u32,
+}
+
+/// Holder for FABRIC_APPLICATION_METRIC_LIST memories.
+struct ApplicationMetricListRaw<'a> {
+    _items: Vec<FABRIC_APPLICATION_METRIC_DESCRIPTION>,
+    list: Box<FABRIC_APPLICATION_METRIC_LIST>,
+    phantom: PhantomData<&'a ApplicationMetricDescription>,
+}
+
+impl ApplicationMetricListRaw<'_> {
+    fn new(metrics: &[ApplicationMetricDescription]) -> ApplicationMetricListRaw<'_> {
+        let mut items = metrics
+            .iter()
+            .map(|m| FABRIC_APPLICATION_METRIC_DESCRIPTION {
+                Name: m.name.as_pcwstr(),
+                NodeReservationCapacity: m.node_reservation_capacity,
+                MaximumNodeCapacity: m.maximum_node_capacity,
+                TotalApplicationCapacity: m.total_application_capacity,
+                Reserved: std::ptr::null_mut(),
+            })
+            .collect::<Vec<_>>();
+        let list = Box::new(FABRIC_APPLICATION_METRIC_LIST {
+            Count: items.len() as u32,
+            Capacities: items.as_mut_ptr(),
+        });
+        ApplicationMetricListRaw {
+            _items: items,
+            list,
+            phantom: PhantomData,
+        }
+    }
+
+    fn as_ffi(&self) -> &FABRIC_APPLICATION_METRIC_LIST {
+        self.list.as_ref()
+    }
+}
+
+// FABRIC_APPLICATION_CAPACITY_DESCRIPTION
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApplicationCapacityDescription {
+    pub maximum_nodes: u32,
+    pub minimum_nodes: u32,
+    pub metrics: Vec<ApplicationMetricDescription>,
+}
+
+struct ApplicationCapacityDescriptionRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_CAPACITY_DESCRIPTION>,
+    _metrics: ApplicationMetricListRaw<'a>,
+}
+
+impl ApplicationCapacityDescription {
+    fn build_raw(&self) -> ApplicationCapacityDescriptionRaw<'_> {
+        let metrics = ApplicationMetricListRaw::new(&self.metrics);
+        let internal = Box::new(FABRIC_APPLICATION_CAPACITY_DESCRIPTION {
+            MaximumNodes: self.maximum_nodes,
+            MinimumNodes: self.minimum_nodes,
+            Metrics: metrics.as_ffi(),
+            Reserved: std::ptr::null_mut(),
+        });
+        ApplicationCapacityDescriptionRaw {
+            internal,
+            _metrics: metrics,
+        }
+    }
+}
+
+/// FABRIC_APPLICATION_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ApplicationDescription {
+    application_name: Uri,
+    application_type_name: WString,
+    application_type_version: WString,
+    application_parameters: Vec<ApplicationParameter>,
+    // ex1
+    application_capacity: Option<ApplicationCapacityDescription>,
+}
+
+impl ApplicationDescription {
+    /// application_name is the name of the application instance, i.e. fabric:/MyApp.
+    /// The application type must have been provisioned before the application is created.
+    pub fn new(
+        application_name: Uri,
+        application_type_name: WString,
+        application_type_version: WString,
+    ) -> Self {
+        Self {
+            application_name,
+            application_type_name,
+            application_type_version,
+            application_parameters: Vec::new(),
+            application_capacity: None,
+        }
+    }
+
+    /// Overrides a parameter declared in the application manifest.
+    pub fn with_application_parameter(mut self, name: WString, value: WString) -> Self {
+        self.application_parameters
+            .push(ApplicationParameter { name, value });
+        self
+    }
+
+    pub fn with_application_capacity(mut self, capacity: ApplicationCapacityDescription) -> Self {
+        self.application_capacity = Some(capacity);
+        self
+    }
+
+    pub(crate) fn build_raw(&self) -> ApplicationDescriptionRaw<'_> {
+        let params = ApplicationParameterListRaw::new(&self.application_parameters);
+        let capacity = self.application_capacity.as_ref().map(|c| c.build_raw());
+        let ex1 = Box::new(FABRIC_APPLICATION_DESCRIPTION_EX1 {
+            ApplicationCapacity: capacity
+                .as_ref()
+                .map_or(std::ptr::null(), |c| c.internal.as_ref() as *const _),
+            Reserved: std::ptr::null_mut(),
+        });
+        let internal = Box::new(FABRIC_APPLICATION_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            ApplicationTypeName: self.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: self.application_type_version.as_pcwstr(),
+            ApplicationParameters: params.as_ffi(),
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+        ApplicationDescriptionRaw {
+            internal,
+            _internal_ex1: ex1,
+            _params: params,
+            _capacity: capacity,
+        }
+    }
+}
+
+pub(crate) struct ApplicationDescriptionRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_DESCRIPTION>,
+    _internal_ex1: Box<FABRIC_APPLICATION_DESCRIPTION_EX1>,
+    _params: ApplicationParameterListRaw<'a>,
+    _capacity: Option<ApplicationCapacityDescriptionRaw<'a>>,
+}
+
+impl ApplicationDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_APPLICATION_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+// FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS
+bitflags::bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq)]
+    pub struct ApplicationUpdateDescriptionFlags: u32 {
+        const FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_NONE = FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_NONE.0 as u32;
+        const FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES = FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES.0 as u32;
+        const FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES = FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES.0 as u32;
+        const FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS = FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS.0 as u32;
+    }
+}
+
+/// FABRIC_APPLICATION_UPDATE_DESCRIPTION
+/// Only the fields set via the with_* setters are updated.
+#[derive(Debug, Clone)]
+pub struct ApplicationUpdateDescription {
+    flags: ApplicationUpdateDescriptionFlags,
+    application_name: Uri,
+    remove_application_capacity: bool,
+    maximum_nodes: u32,
+    minimum_nodes: u32,
+    metrics: Vec<ApplicationMetricDescription>,
+}
+
+impl ApplicationUpdateDescription {
+    pub fn new(application_name: Uri) -> Self {
+        Self {
+            flags: ApplicationUpdateDescriptionFlags::default(),
+            application_name,
+            remove_application_capacity: false,
+            maximum_nodes: 0,
+            minimum_nodes: 0,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Removes all application capacity settings.
+    pub fn with_remove_application_capacity(mut self, remove: bool) -> Self {
+        self.remove_application_capacity = remove;
+        self
+    }
+
+    pub fn with_maximum_nodes(mut self, maximum_nodes: u32) -> Self {
+        self.flags |=
+            ApplicationUpdateDescriptionFlags::FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES;
+        self.maximum_nodes = maximum_nodes;
+        self
+    }
+
+    pub fn with_minimum_nodes(mut self, minimum_nodes: u32) -> Self {
+        self.flags |=
+            ApplicationUpdateDescriptionFlags::FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES;
+        self.minimum_nodes = minimum_nodes;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: Vec<ApplicationMetricDescription>) -> Self {
+        self.flags |=
+            ApplicationUpdateDescriptionFlags::FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_METRICS;
+        self.metrics = metrics;
+        self
+    }
+
+    pub(crate) fn build_raw(&self) -> ApplicationUpdateDescriptionRaw<'_> {
+        let metrics = ApplicationMetricListRaw::new(&self.metrics);
+        let internal = Box::new(FABRIC_APPLICATION_UPDATE_DESCRIPTION {
+            Flags: self.flags.bits(),
+            ApplicationName: self.application_name.as_raw(),
+            RemoveApplicationCapacity: self.remove_application_capacity,
+            MaximumNodes: self.maximum_nodes,
+            MinimumNodes: self.minimum_nodes,
+            Metrics: metrics.as_ffi(),
+            Reserved: std::ptr::null_mut(),
+        });
+        ApplicationUpdateDescriptionRaw {
+            internal,
+            _metrics: metrics,
+        }
+    }
+}
+
+pub(crate) struct ApplicationUpdateDescriptionRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_UPDATE_DESCRIPTION>,
+    _metrics: ApplicationMetricListRaw<'a>,
+}
+
+impl ApplicationUpdateDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_APPLICATION_UPDATE_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+// FABRIC_DELETE_APPLICATION_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct DeleteApplicationDescription {
+    pub application_name: Uri,
+    /// Deletes the application without waiting for the services to close gracefully.
+    pub force_delete: bool,
+}
+
+impl From<&DeleteApplicationDescription> for FABRIC_DELETE_APPLICATION_DESCRIPTION {
+    /// The lifetime of the SF raw type returned must match the
+    /// original struct.
+    fn from(value: &DeleteApplicationDescription) -> Self {
+        Self {
+            ApplicationName: value.application_name.as_raw(),
+            ForceDelete: value.force_delete,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ApplicationPackageCleanupPolicy {
+    Invalid,
+    /// Uses the cluster wide setting.
+    #[default]
+    Default,
+    /// Removes the package from the image store after a successful provision.
+    Automatic,
+    /// Keeps the package in the image store.
+    Manual,
+}
+
+impl From<ApplicationPackageCleanupPolicy> for FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY {
+    fn from(value: ApplicationPackageCleanupPolicy) -> Self {
+        match value {
+            ApplicationPackageCleanupPolicy::Invalid => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_INVALID
+            }
+            ApplicationPackageCleanupPolicy::Default => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_DEFAULT
+            }
+            ApplicationPackageCleanupPolicy::Automatic => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_AUTOMATIC
+            }
+            ApplicationPackageCleanupPolicy::Manual => {
+                FABRIC_APPLICATION_PACKAGE_CLEANUP_POLICY_MANUAL
+            }
+        }
+    }
+}
+
+// FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ImageStoreProvisionApplicationTypeDescription {
+    /// Relative path of the application package in the image store.
+    pub build_path: WString,
+    /// When true the call returns once the request is accepted and
+    /// provisioning continues in the background.
+    pub is_async: bool,
+    // ex1
+    pub application_package_cleanup_policy: ApplicationPackageCleanupPolicy,
+}
+
+// FABRIC_EXTERNAL_STORE_PROVISION_APPLICATION_TYPE_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ExternalStoreProvisionApplicationTypeDescription {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    /// Http(s) uri of the sfpkg to download.
+    pub application_package_download_uri: WString,
+    pub is_async: bool,
+}
+
+// FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE
+#[derive(Debug, Clone)]
+pub enum ProvisionApplicationTypeDescription {
+    ImageStorePath(ImageStoreProvisionApplicationTypeDescription),
+    ExternalStore(ExternalStoreProvisionApplicationTypeDescription),
+}
+
+pub(crate) enum ProvisionApplicationTypeDescriptionRaw<'a> {
+    ImageStorePath {
+        internal: Box<FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION>,
+        _internal_ex1: Box<FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_EX1>,
+        phantom: PhantomData<&'a ImageStoreProvisionApplicationTypeDescription>,
+    },
+    ExternalStore {
+        internal: Box<FABRIC_EXTERNAL_STORE_PROVISION_APPLICATION_TYPE_DESCRIPTION>,
+        phantom: PhantomData<&'a ExternalStoreProvisionApplicationTypeDescription>,
+    },
+}
+
+impl ProvisionApplicationTypeDescriptionRaw<'_> {
+    /// Must have the same lifetime as the original struct
+    pub(crate) fn as_ffi(&self) -> FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE {
+        match self {
+            ProvisionApplicationTypeDescriptionRaw::ImageStorePath { internal, .. } => {
+                FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE {
+                    Kind: FABRIC_PROVISION_APPLICATION_TYPE_KIND_IMAGE_STORE_PATH,
+                    Value: internal.as_ref() as *const _ as *mut c_void,
+                }
+            }
+            ProvisionApplicationTypeDescriptionRaw::ExternalStore { internal, .. } => {
+                FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_BASE {
+                    Kind: FABRIC_PROVISION_APPLICATION_TYPE_KIND_EXTERNAL_STORE,
+                    Value: internal.as_ref() as *const _ as *mut c_void,
+                }
+            }
+        }
+    }
+}
+
+impl ProvisionApplicationTypeDescription {
+    pub(crate) fn build_raw(&self) -> ProvisionApplicationTypeDescriptionRaw<'_> {
+        match self {
+            ProvisionApplicationTypeDescription::ImageStorePath(desc) => {
+                let ex1 = Box::new(FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION_EX1 {
+                    ApplicationPackageCleanupPolicy: desc.application_package_cleanup_policy.into(),
+                    Reserved: std::ptr::null_mut(),
+                });
+                let internal = Box::new(FABRIC_PROVISION_APPLICATION_TYPE_DESCRIPTION {
+                    BuildPath: desc.build_path.as_pcwstr(),
+                    Async: desc.is_async,
+                    Reserved: ex1.as_ref() as *const _ as *mut c_void,
+                });
+                ProvisionApplicationTypeDescriptionRaw::ImageStorePath {
+                    internal,
+                    _internal_ex1: ex1,
+                    phantom: PhantomData,
+                }
+            }
+            ProvisionApplicationTypeDescription::ExternalStore(desc) => {
+                let internal = Box::new(
+                    FABRIC_EXTERNAL_STORE_PROVISION_APPLICATION_TYPE_DESCRIPTION {
+                        ApplicationTypeName: desc.application_type_name.as_pcwstr(),
+                        ApplicationTypeVersion: desc.application_type_version.as_pcwstr(),
+                        ApplicationPackageDownloadUri: desc
+                            .application_package_download_uri
+                            .as_pcwstr(),
+                        Async: desc.is_async,
+                        Reserved: std::ptr::null_mut(),
+                    },
+                );
+                ProvisionApplicationTypeDescriptionRaw::ExternalStore {
+                    internal,
+                    phantom: PhantomData,
+                }
+            }
+        }
+    }
+}
+
+// FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct UnprovisionApplicationTypeDescription {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub is_async: bool,
+}
+
+impl From<&UnprovisionApplicationTypeDescription>
+    for FABRIC_UNPROVISION_APPLICATION_TYPE_DESCRIPTION
+{
+    /// The lifetime of the SF raw type returned must match the
+    /// original struct.
+    fn from(value: &UnprovisionApplicationTypeDescription) -> Self {
+        Self {
+            ApplicationTypeName: value.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: value.application_type_version.as_pcwstr(),
+            Async: value.is_async,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// FABRIC_APPLICATION_UPGRADE_DESCRIPTION
+/// Only rolling upgrade is supported by SF.
+#[derive(Debug, Clone)]
+pub struct ApplicationUpgradeDescription {
+    application_name: Uri,
+    target_application_type_version: WString,
+    application_parameters: Vec<ApplicationParameter>,
+    policy: RollingUpgradePolicyDescription,
+}
+
+impl ApplicationUpgradeDescription {
+    pub fn new(
+        application_name: Uri,
+        target_application_type_version: WString,
+        policy: RollingUpgradePolicyDescription,
+    ) -> Self {
+        Self {
+            application_name,
+            target_application_type_version,
+            application_parameters: Vec::new(),
+            policy,
+        }
+    }
+
+    /// Parameters not listed are reset to the manifest default value.
+    pub fn with_application_parameter(mut self, name: WString, value: WString) -> Self {
+        self.application_parameters
+            .push(ApplicationParameter { name, value });
+        self
+    }
+
+    pub(crate) fn build_raw(&self) -> ApplicationUpgradeDescriptionRaw<'_> {
+        let params = ApplicationParameterListRaw::new(&self.application_parameters);
+        let policy = self.policy.build_raw();
+        let internal = Box::new(FABRIC_APPLICATION_UPGRADE_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            TargetApplicationTypeVersion: self.target_application_type_version.as_pcwstr(),
+            ApplicationParameters: params.as_ffi() as *const _ as *mut _,
+            UpgradeKind: FABRIC_APPLICATION_UPGRADE_KIND_ROLLING,
+            UpgradePolicyDescription: policy.as_ffi() as *const _ as *mut c_void,
+            Reserved: std::ptr::null_mut(),
+        });
+        ApplicationUpgradeDescriptionRaw {
+            internal,
+            _policy: policy,
+            _params: params,
+        }
+    }
+}
+
+pub(crate) struct ApplicationUpgradeDescriptionRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_UPGRADE_DESCRIPTION>,
+    _policy: RollingUpgradePolicyDescriptionRaw<'a>,
+    _params: ApplicationParameterListRaw<'a>,
+}
+
+impl ApplicationUpgradeDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_APPLICATION_UPGRADE_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+// FABRIC_APPLICATION_UPGRADE_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApplicationUpgradeState {
+    Invalid,
+    RollingBackInProgress,
+    RollingBackCompleted,
+    RollingForwardPending,
+    RollingForwardInProgress,
+    RollingForwardCompleted,
+    Failed,
+    RollingBackPending,
+}
+
+impl From<FABRIC_APPLICATION_UPGRADE_STATE> for ApplicationUpgradeState {
+    fn from(value: FABRIC_APPLICATION_UPGRADE_STATE) -> Self {
+        match value {
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS => {
+                Self::RollingBackInProgress
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_COMPLETED => Self::RollingBackCompleted,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_PENDING => Self::RollingForwardPending,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS => {
+                Self::RollingForwardInProgress
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED => {
+                Self::RollingForwardCompleted
+            }
+            FABRIC_APPLICATION_UPGRADE_STATE_FAILED => Self::Failed,
+            FABRIC_APPLICATION_UPGRADE_STATE_ROLLING_BACK_PENDING => Self::RollingBackPending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+/// IFabricApplicationUpgradeProgressResult2
+/// The result is also used as the input of move_next_application_upgrade_domain.
+#[derive(Debug, Clone)]
+pub struct ApplicationUpgradeProgress {
+    com: IFabricApplicationUpgradeProgressResult2,
+}
+
+impl ApplicationUpgradeProgress {
+    pub(crate) fn from_com(com: IFabricApplicationUpgradeProgressResult2) -> Self {
+        Self { com }
+    }
+
+    pub(crate) fn as_com(&self) -> &IFabricApplicationUpgradeProgressResult2 {
+        &self.com
+    }
+
+    pub fn get_application_name(&self) -> Uri {
+        let name = unsafe { self.com.get_ApplicationName() };
+        Uri::new(windows_core::PCWSTR(name.0).into())
+    }
+
+    pub fn get_application_type_name(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_ApplicationTypeName() }).into()
+    }
+
+    pub fn get_target_application_type_version(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_TargetApplicationTypeVersion() }).into()
+    }
+
+    pub fn get_upgrade_state(&self) -> ApplicationUpgradeState {
+        unsafe { self.com.get_UpgradeState() }.into()
+    }
+
+    pub fn get_rolling_upgrade_mode(&self) -> RollingUpgradeMode {
+        unsafe { self.com.get_RollingUpgradeMode() }.into()
+    }
+
+    /// Empty if there is no upgrade domain pending.
+    pub fn get_next_upgrade_domain(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_NextUpgradeDomain() }).into()
+    }
+
+    pub fn get_upgrade_domains(&self) -> crate::Result<Vec<UpgradeDomainStatus>> {
+        let mut count = 0_u32;
+        let first = unsafe { self.com.GetUpgradeDomains(std::ptr::addr_of_mut!(count)) }?;
+        let accessor = UpgradeDomainStatusListRef::<'_, Self> {
+            count,
+            first,
+            phantom: PhantomData,
+        };
+        Ok(accessor.to_vec())
+    }
+
+    /// Returns the upgrade domains whose state changed since the previous progress.
+    pub fn get_changed_upgrade_domains(
+        &self,
+        previous: &ApplicationUpgradeProgress,
+    ) -> crate::Result<Vec<UpgradeDomainStatus>> {
+        let mut count = 0_u32;
+        let first = unsafe {
+            self.com
+                .GetChangedUpgradeDomains(&*previous.com, std::ptr::addr_of_mut!(count))
+        }?;
+        let accessor = UpgradeDomainStatusListRef::<'_, Self> {
+            count,
+            first,
+            phantom: PhantomData,
+        };
+        Ok(accessor.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{WString, types::Uri};
+
+    use super::{
+        ApplicationCapacityDescription, ApplicationDescription, ApplicationMetricDescription,
+        ApplicationUpdateDescription, ApplicationUpdateDescriptionFlags,
+    };
+
+    #[test]
+    fn test_application_description_raw() {
+        let desc = ApplicationDescription::new(
+            Uri::from("fabric:/MyApp"),
+            WString::from("MyAppType"),
+            WString::from("1.0.0"),
+        )
+        .with_application_parameter(WString::from("p1"), WString::from("v1"))
+        .with_application_parameter(WString::from("p2"), WString::from("v2"))
+        .with_application_capacity(ApplicationCapacityDescription {
+            maximum_nodes: 3,
+            minimum_nodes: 1,
+            metrics: vec![ApplicationMetricDescription {
+                name: WString::from("m1"),
+                node_reservation_capacity: 1,
+                maximum_node_capacity: 2,
+                total_application_capacity: 3,
+            }],
+        });
+        let raw = desc.build_raw();
+        let ffi = raw.as_ffi();
+        assert_eq!(
+            WString::from(windows_core::PCWSTR(ffi.ApplicationName.0)),
+            WString::from("fabric:/MyApp")
+        );
+        let params = unsafe { ffi.ApplicationParameters.as_ref() }.unwrap();
+        assert_eq!(params.Count, 2);
+        let p2 = unsafe { params.Items.offset(1).as_ref() }.unwrap();
+        assert_eq!(WString::from(p2.Value), WString::from("v2"));
+        let ex1 = unsafe {
+            (ffi.Reserved as *const mssf_com::FabricTypes::FABRIC_APPLICATION_DESCRIPTION_EX1)
+                .as_ref()
+        }
+        .unwrap();
+        let capacity = unsafe { ex1.ApplicationCapacity.as_ref() }.unwrap();
+        assert_eq!(capacity.MaximumNodes, 3);
+        let metrics = unsafe { capacity.Metrics.as_ref() }.unwrap();
+        assert_eq!(metrics.Count, 1);
+        assert_eq!(unsafe { (*metrics.Capacities).TotalApplicationCapacity }, 3);
+    }
+
+    #[test]
+    fn test_application_update_description_flags() {
+        let desc = ApplicationUpdateDescription::new(Uri::from("fabric:/MyApp"))
+            .with_maximum_nodes(5)
+            .with_minimum_nodes(2);
+        let raw = desc.build_raw();
+        let ffi = raw.as_ffi();
+        assert_eq!(
+            ffi.Flags,
+            (ApplicationUpdateDescriptionFlags::FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MAXNODES
+                | ApplicationUpdateDescriptionFlags::FABRIC_APPLICATION_UPDATE_DESCRIPTION_FLAGS_MINNODES)
+                .bits()
+        );
+        assert_eq!(ffi.MaximumNodes, 5);
+        assert_eq!(ffi.MinimumNodes, 2);
+        assert_eq!(unsafe { ffi.Metrics.as_ref() }.unwrap().Count, 0);
+    }
+}
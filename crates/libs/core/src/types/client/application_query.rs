@@ -0,0 +1,438 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Query types for application types, applications and provisioned fabric versions.
+
+use crate::WString;
+use mssf_com::{
+    FabricClient::{
+        IFabricGetApplicationListResult2, IFabricGetApplicationTypePagedListResult,
+        IFabricGetProvisionedCodeVersionListResult, IFabricGetProvisionedConfigVersionListResult,
+    },
+    FabricTypes::{
+        FABRIC_APPLICATION_PARAMETER_LIST, FABRIC_APPLICATION_QUERY_RESULT_ITEM,
+        FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX1, FABRIC_APPLICATION_STATUS,
+        FABRIC_APPLICATION_STATUS_CREATING, FABRIC_APPLICATION_STATUS_DELETING,
+        FABRIC_APPLICATION_STATUS_FAILED, FABRIC_APPLICATION_STATUS_READY,
+        FABRIC_APPLICATION_STATUS_UPGRADING, FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM,
+        FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM_EX1, FABRIC_APPLICATION_TYPE_STATUS,
+        FABRIC_APPLICATION_TYPE_STATUS_AVAILABLE, FABRIC_APPLICATION_TYPE_STATUS_FAILED,
+        FABRIC_APPLICATION_TYPE_STATUS_PROVISIONING, FABRIC_APPLICATION_TYPE_STATUS_UNPROVISIONING,
+        FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION,
+        FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM,
+    },
+};
+
+use super::{PagedQueryDescription, PagingStatus, chaos::raw_slice, fault::uri_from_raw};
+use crate::{
+    iter::{FabricIter, FabricListAccessor},
+    strings::{WStringWrap, get_pcwstr_from_opt},
+    types::{ApplicationParameter, HealthState, Uri},
+};
+
+fn parameters_from_raw(
+    list: *const FABRIC_APPLICATION_PARAMETER_LIST,
+) -> Vec<ApplicationParameter> {
+    let Some(list) = (unsafe { list.as_ref() }) else {
+        return Vec::new();
+    };
+    unsafe { raw_slice(list.Items, list.Count) }
+        .iter()
+        .map(|p| ApplicationParameter {
+            name: WStringWrap::from(p.Name).into(),
+            value: WStringWrap::from(p.Value).into(),
+        })
+        .collect()
+}
+
+// PAGED_FABRIC_APPLICATION_TYPE_QUERY_DESCRIPTION
+#[derive(Debug, Default, Clone)]
+pub struct PagedApplicationTypeQueryDescription {
+    pub application_type_name_filter: Option<WString>,
+    /// Only valid together with application_type_name_filter.
+    pub application_type_version_filter: Option<WString>,
+    pub exclude_application_parameters: bool,
+    pub paged_query: PagedQueryDescription,
+}
+
+// FABRIC_APPLICATION_TYPE_STATUS
+#[derive(Debug, PartialEq, Clone)]
+pub enum ApplicationTypeStatus {
+    Invalid,
+    Provisioning,
+    Available,
+    Unprovisioning,
+    Failed,
+}
+
+impl From<FABRIC_APPLICATION_TYPE_STATUS> for ApplicationTypeStatus {
+    fn from(value: FABRIC_APPLICATION_TYPE_STATUS) -> Self {
+        match value {
+            FABRIC_APPLICATION_TYPE_STATUS_PROVISIONING => Self::Provisioning,
+            FABRIC_APPLICATION_TYPE_STATUS_AVAILABLE => Self::Available,
+            FABRIC_APPLICATION_TYPE_STATUS_UNPROVISIONING => Self::Unprovisioning,
+            FABRIC_APPLICATION_TYPE_STATUS_FAILED => Self::Failed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct ApplicationType {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub default_parameters: Vec<ApplicationParameter>,
+    pub status: ApplicationTypeStatus,
+    pub status_details: WString,
+}
+
+impl From<&FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM> for ApplicationType {
+    fn from(value: &FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        Self {
+            application_type_name: WStringWrap::from(value.ApplicationTypeName).into(),
+            application_type_version: WStringWrap::from(value.ApplicationTypeVersion).into(),
+            default_parameters: parameters_from_raw(value.DefaultParameters),
+            status: ex1.map_or(ApplicationTypeStatus::Invalid, |e| e.Status.into()),
+            status_details: ex1
+                .map(|e| WStringWrap::from(e.StatusDetails).into())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// IFabricGetApplicationTypePagedListResult
+pub struct ApplicationTypeList {
+    com: IFabricGetApplicationTypePagedListResult,
+}
+
+type ApplicationTypeListIter<'a> =
+    FabricIter<'a, FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM, ApplicationType, ApplicationTypeList>;
+
+impl FabricListAccessor<FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM> for ApplicationTypeList {
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ApplicationTypePagedList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_APPLICATION_TYPE_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ApplicationTypePagedList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ApplicationTypeList {
+    pub fn new(com: IFabricGetApplicationTypePagedListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ApplicationTypeListIter<'_> {
+        ApplicationTypeListIter::new(self, self)
+    }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
+}
+
+// FABRIC_APPLICATION_QUERY_DESCRIPTION
+#[derive(Debug, Default, Clone)]
+pub struct ApplicationQueryDescription {
+    pub application_name_filter: Option<Uri>,
+    pub application_type_name_filter: Option<WString>,
+    pub exclude_application_parameters: bool,
+    pub paged_query: PagedQueryDescription,
+}
+
+// FABRIC_APPLICATION_STATUS
+#[derive(Debug, PartialEq, Clone)]
+pub enum ApplicationStatus {
+    Invalid,
+    Ready,
+    Upgrading,
+    Creating,
+    Deleting,
+    Failed,
+}
+
+impl From<FABRIC_APPLICATION_STATUS> for ApplicationStatus {
+    fn from(value: FABRIC_APPLICATION_STATUS) -> Self {
+        match value {
+            FABRIC_APPLICATION_STATUS_READY => Self::Ready,
+            FABRIC_APPLICATION_STATUS_UPGRADING => Self::Upgrading,
+            FABRIC_APPLICATION_STATUS_CREATING => Self::Creating,
+            FABRIC_APPLICATION_STATUS_DELETING => Self::Deleting,
+            FABRIC_APPLICATION_STATUS_FAILED => Self::Failed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_APPLICATION_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct ApplicationQueryResult {
+    pub application_name: Uri,
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub status: ApplicationStatus,
+    pub health_state: HealthState,
+    pub application_parameters: Vec<ApplicationParameter>,
+    /// Target type version while the application is upgrading. Empty otherwise.
+    pub upgrade_type_version: WString,
+    pub upgrade_parameters: Vec<ApplicationParameter>,
+}
+
+impl From<&FABRIC_APPLICATION_QUERY_RESULT_ITEM> for ApplicationQueryResult {
+    fn from(value: &FABRIC_APPLICATION_QUERY_RESULT_ITEM) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX1).as_ref() };
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            application_type_name: WStringWrap::from(value.ApplicationTypeName).into(),
+            application_type_version: WStringWrap::from(value.ApplicationTypeVersion).into(),
+            status: value.Status.into(),
+            health_state: (&value.HealthState).into(),
+            application_parameters: parameters_from_raw(value.ApplicationParameters),
+            upgrade_type_version: ex1
+                .map(|e| WStringWrap::from(e.UpgradeTypeVersion).into())
+                .unwrap_or_default(),
+            upgrade_parameters: ex1
+                .map(|e| parameters_from_raw(e.UpgradeParameters))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// IFabricGetApplicationListResult2
+pub struct ApplicationList {
+    com: IFabricGetApplicationListResult2,
+}
+
+type ApplicationListIter<'a> =
+    FabricIter<'a, FABRIC_APPLICATION_QUERY_RESULT_ITEM, ApplicationQueryResult, ApplicationList>;
+
+impl FabricListAccessor<FABRIC_APPLICATION_QUERY_RESULT_ITEM> for ApplicationList {
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ApplicationList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_APPLICATION_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ApplicationList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ApplicationList {
+    pub fn new(com: IFabricGetApplicationListResult2) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ApplicationListIter<'_> {
+        ApplicationListIter::new(self, self)
+    }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
+}
+
+// FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct ProvisionedCodeVersionQueryDescription {
+    pub code_version_filter: Option<WString>,
+}
+
+impl From<&ProvisionedCodeVersionQueryDescription>
+    for FABRIC_PROVISIONED_CODE_VERSION_QUERY_DESCRIPTION
+{
+    fn from(value: &ProvisionedCodeVersionQueryDescription) -> Self {
+        Self {
+            CodeVersionFilter: get_pcwstr_from_opt(&value.code_version_filter),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionedCodeVersion {
+    pub code_version: WString,
+}
+
+impl From<&FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM> for ProvisionedCodeVersion {
+    fn from(value: &FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM) -> Self {
+        Self {
+            code_version: WStringWrap::from(value.CodeVersion).into(),
+        }
+    }
+}
+
+// IFabricGetProvisionedCodeVersionListResult
+pub struct ProvisionedCodeVersionList {
+    com: IFabricGetProvisionedCodeVersionListResult,
+}
+
+type ProvisionedCodeVersionListIter<'a> = FabricIter<
+    'a,
+    FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM,
+    ProvisionedCodeVersion,
+    ProvisionedCodeVersionList,
+>;
+
+impl FabricListAccessor<FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM>
+    for ProvisionedCodeVersionList
+{
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ProvisionedCodeVersionList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_PROVISIONED_CODE_VERSION_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ProvisionedCodeVersionList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ProvisionedCodeVersionList {
+    pub fn new(com: IFabricGetProvisionedCodeVersionListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ProvisionedCodeVersionListIter<'_> {
+        ProvisionedCodeVersionListIter::new(self, self)
+    }
+}
+
+// FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct ProvisionedConfigVersionQueryDescription {
+    pub config_version_filter: Option<WString>,
+}
+
+impl From<&ProvisionedConfigVersionQueryDescription>
+    for FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_DESCRIPTION
+{
+    fn from(value: &ProvisionedConfigVersionQueryDescription) -> Self {
+        Self {
+            ConfigVersionFilter: get_pcwstr_from_opt(&value.config_version_filter),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionedConfigVersion {
+    pub config_version: WString,
+}
+
+impl From<&FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM> for ProvisionedConfigVersion {
+    fn from(value: &FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM) -> Self {
+        Self {
+            config_version: WStringWrap::from(value.ConfigVersion).into(),
+        }
+    }
+}
+
+// IFabricGetProvisionedConfigVersionListResult
+pub struct ProvisionedConfigVersionList {
+    com: IFabricGetProvisionedConfigVersionListResult,
+}
+
+type ProvisionedConfigVersionListIter<'a> = FabricIter<
+    'a,
+    FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM,
+    ProvisionedConfigVersion,
+    ProvisionedConfigVersionList,
+>;
+
+impl FabricListAccessor<FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM>
+    for ProvisionedConfigVersionList
+{
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ProvisionedConfigVersionList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_PROVISIONED_CONFIG_VERSION_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ProvisionedConfigVersionList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ProvisionedConfigVersionList {
+    pub fn new(com: IFabricGetProvisionedConfigVersionListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ProvisionedConfigVersionListIter<'_> {
+        ProvisionedConfigVersionListIter::new(self, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_APPLICATION_PARAMETER, FABRIC_APPLICATION_PARAMETER_LIST, FABRIC_HEALTH_STATE_OK,
+        FABRIC_URI,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_application_query_result_from_raw() {
+        let name = WString::from("fabric:/app");
+        let type_name = WString::from("AppType");
+        let version = WString::from("1.0");
+        let upgrade_version = WString::from("2.0");
+        let p_name = WString::from("p");
+        let p_value = WString::from("v");
+        let mut params = [FABRIC_APPLICATION_PARAMETER {
+            Name: p_name.as_pcwstr(),
+            Value: p_value.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        }];
+        let mut param_list = FABRIC_APPLICATION_PARAMETER_LIST {
+            Count: 1,
+            Items: params.as_mut_ptr(),
+        };
+        let mut ex1 = FABRIC_APPLICATION_QUERY_RESULT_ITEM_EX1 {
+            UpgradeTypeVersion: upgrade_version.as_pcwstr(),
+            UpgradeParameters: std::ptr::null_mut(),
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_APPLICATION_QUERY_RESULT_ITEM {
+            ApplicationName: FABRIC_URI(name.as_ptr() as *mut u16),
+            ApplicationTypeName: type_name.as_pcwstr(),
+            ApplicationTypeVersion: version.as_pcwstr(),
+            Status: FABRIC_APPLICATION_STATUS_UPGRADING,
+            HealthState: FABRIC_HEALTH_STATE_OK,
+            ApplicationParameters: &mut param_list,
+            Reserved: &mut ex1 as *mut _ as *mut std::ffi::c_void,
+        };
+        let res = ApplicationQueryResult::from(&raw);
+        assert_eq!(res.application_name, Uri::from("fabric:/app"));
+        assert_eq!(res.application_type_name, type_name);
+        assert_eq!(res.status, ApplicationStatus::Upgrading);
+        assert_eq!(res.health_state, HealthState::Ok);
+        assert_eq!(
+            res.application_parameters,
+            vec![ApplicationParameter {
+                name: p_name,
+                value: p_value
+            }]
+        );
+        assert_eq!(res.upgrade_type_version, upgrade_version);
+        assert!(res.upgrade_parameters.is_empty());
+    }
+}
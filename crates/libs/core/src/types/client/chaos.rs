@@ -0,0 +1,955 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Types used by the chaos apis on the test management client.
+
+use std::{ffi::c_void, marker::PhantomData, time::SystemTime};
+
+use mssf_com::{
+    FabricClient::{IFabricChaosEventsSegmentResult, IFabricChaosReportResult},
+    FabricTypes::{
+        FABRIC_CHAOS_DESCRIPTION, FABRIC_CHAOS_EVENT, FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS,
+        FABRIC_CHAOS_EVENT_KIND_STARTED, FABRIC_CHAOS_EVENT_KIND_STOPPED,
+        FABRIC_CHAOS_EVENT_KIND_TEST_ERROR, FABRIC_CHAOS_EVENT_KIND_VALIDATION_FAILED,
+        FABRIC_CHAOS_EVENT_KIND_WAITING, FABRIC_CHAOS_EVENT_LIST,
+        FABRIC_CHAOS_EVENTS_SEGMENT_FILTER, FABRIC_CHAOS_PARAMETERS, FABRIC_CHAOS_PARAMETERS_EX1,
+        FABRIC_CHAOS_PARAMETERS_EX2, FABRIC_CHAOS_REPORT_FILTER, FABRIC_CHAOS_SCHEDULE,
+        FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP,
+        FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP_ITEM, FABRIC_CHAOS_SCHEDULE_DESCRIPTION,
+        FABRIC_CHAOS_SCHEDULE_JOB, FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS,
+        FABRIC_CHAOS_SCHEDULE_JOB_LIST, FABRIC_CHAOS_SCHEDULE_STATUS,
+        FABRIC_CHAOS_SCHEDULE_STATUS_ACTIVE, FABRIC_CHAOS_SCHEDULE_STATUS_EXPIRED,
+        FABRIC_CHAOS_SCHEDULE_STATUS_PENDING, FABRIC_CHAOS_SCHEDULE_STATUS_STOPPED,
+        FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC, FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC_LIST,
+        FABRIC_CHAOS_SCHEDULE_TIME_UTC, FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION,
+        FABRIC_CHAOS_STATUS, FABRIC_CHAOS_STATUS_RUNNING, FABRIC_CHAOS_STATUS_STOPPED,
+        FABRIC_CHAOS_TARGET_FILTER, FABRIC_EVENT_CONTEXT_MAP, FABRIC_EVENT_CONTEXT_MAP_ITEM,
+        FABRIC_EXECUTING_FAULTS_EVENT, FABRIC_STARTED_EVENT, FABRIC_STOPPED_EVENT,
+        FABRIC_TEST_ERROR_EVENT, FABRIC_VALIDATION_FAILED_EVENT, FABRIC_WAITING_EVENT,
+    },
+};
+
+use crate::{
+    PCWSTR, WString,
+    iter::{FabricIter, FabricListAccessor},
+    strings::{FabricStringListRaw, WStringWrap, fabric_string_list_to_vec},
+};
+use windows_core::Win32::Foundation::FILETIME;
+
+pub(super) const FILETIME_ZERO: FILETIME = FILETIME {
+    dwLowDateTime: 0,
+    dwHighDateTime: 0,
+};
+
+/// Views a raw Count/Items pair as a slice. Null items yields an empty slice.
+/// # Safety
+/// items must point to count valid elements that outlive 'a.
+pub(super) unsafe fn raw_slice<'a, T>(items: *const T, count: u32) -> &'a [T] {
+    if items.is_null() || count == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(items, count as usize) }
+    }
+}
+
+// FABRIC_CHAOS_STATUS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChaosStatus {
+    Invalid,
+    Running,
+    Stopped,
+}
+
+impl From<FABRIC_CHAOS_STATUS> for ChaosStatus {
+    fn from(value: FABRIC_CHAOS_STATUS) -> Self {
+        match value {
+            FABRIC_CHAOS_STATUS_RUNNING => Self::Running,
+            FABRIC_CHAOS_STATUS_STOPPED => Self::Stopped,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_STATUS
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChaosScheduleStatus {
+    Invalid,
+    Stopped,
+    Active,
+    Expired,
+    Pending,
+}
+
+impl From<FABRIC_CHAOS_SCHEDULE_STATUS> for ChaosScheduleStatus {
+    fn from(value: FABRIC_CHAOS_SCHEDULE_STATUS) -> Self {
+        match value {
+            FABRIC_CHAOS_SCHEDULE_STATUS_STOPPED => Self::Stopped,
+            FABRIC_CHAOS_SCHEDULE_STATUS_ACTIVE => Self::Active,
+            FABRIC_CHAOS_SCHEDULE_STATUS_EXPIRED => Self::Expired,
+            FABRIC_CHAOS_SCHEDULE_STATUS_PENDING => Self::Pending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+/// FABRIC_CHAOS_TARGET_FILTER
+/// Limits the faults to the listed node types and applications.
+/// Empty lists mean no restriction.
+#[derive(Debug, Clone, Default)]
+pub struct ChaosTargetFilter {
+    pub node_type_inclusion_list: Vec<WString>,
+    pub application_inclusion_list: Vec<WString>,
+}
+
+impl From<&FABRIC_CHAOS_TARGET_FILTER> for ChaosTargetFilter {
+    fn from(value: &FABRIC_CHAOS_TARGET_FILTER) -> Self {
+        Self {
+            node_type_inclusion_list: fabric_string_list_to_vec(value.NodeTypeInclusionList),
+            application_inclusion_list: fabric_string_list_to_vec(value.ApplicationInclusionList),
+        }
+    }
+}
+
+struct ChaosTargetFilterRaw<'a> {
+    internal: Box<FABRIC_CHAOS_TARGET_FILTER>,
+    _node_types: FabricStringListRaw<'a>,
+    _applications: FabricStringListRaw<'a>,
+}
+
+impl ChaosTargetFilter {
+    fn build_raw(&self) -> ChaosTargetFilterRaw<'_> {
+        let node_types = FabricStringListRaw::new(&self.node_type_inclusion_list);
+        let applications = FabricStringListRaw::new(&self.application_inclusion_list);
+        let internal = Box::new(FABRIC_CHAOS_TARGET_FILTER {
+            NodeTypeInclusionList: node_types.as_ptr_or_null(),
+            ApplicationInclusionList: applications.as_ptr_or_null(),
+            Reserved: std::ptr::null_mut(),
+        });
+        ChaosTargetFilterRaw {
+            internal,
+            _node_types: node_types,
+            _applications: applications,
+        }
+    }
+}
+
+/// FABRIC_CHAOS_PARAMETERS
+/// The cluster health policy in EX1 is not supported yet, and the cluster
+/// manifest policy is used.
+#[derive(Debug, Clone)]
+pub struct ChaosParameters {
+    pub max_cluster_stabilization_timeout_in_seconds: u32,
+    pub max_concurrent_faults: u32,
+    pub enable_move_replica_faults: bool,
+    pub time_to_run_in_seconds: u64,
+    pub wait_time_between_iterations_in_seconds: u32,
+    pub wait_time_between_faults_in_seconds: u32,
+    /// Key value pairs recorded in the started event.
+    pub context: Vec<(WString, WString)>,
+    pub chaos_target_filter: Option<ChaosTargetFilter>,
+}
+
+impl Default for ChaosParameters {
+    /// Same defaults as the SF csharp client.
+    fn default() -> Self {
+        Self {
+            max_cluster_stabilization_timeout_in_seconds: 60,
+            max_concurrent_faults: 1,
+            enable_move_replica_faults: true,
+            time_to_run_in_seconds: u32::MAX as u64,
+            wait_time_between_iterations_in_seconds: 30,
+            wait_time_between_faults_in_seconds: 20,
+            context: Vec::new(),
+            chaos_target_filter: None,
+        }
+    }
+}
+
+impl From<&FABRIC_CHAOS_PARAMETERS> for ChaosParameters {
+    fn from(value: &FABRIC_CHAOS_PARAMETERS) -> Self {
+        let context = match unsafe { value.Context.as_ref() } {
+            Some(map) => unsafe { raw_slice(map.Items, map.Count) }
+                .iter()
+                .map(|item| {
+                    (
+                        WStringWrap::from(item.Key).into(),
+                        WStringWrap::from(item.Value).into(),
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX2).as_ref()
+        });
+        let chaos_target_filter = ex2
+            .and_then(|ex2| unsafe { ex2.ChaosTargetFilter.as_ref() })
+            .map(ChaosTargetFilter::from);
+        Self {
+            max_cluster_stabilization_timeout_in_seconds: value
+                .MaxClusterStabilizationTimeoutInSeconds,
+            max_concurrent_faults: value.MaxConcurrentFaults,
+            enable_move_replica_faults: value.EnableMoveReplicaFaults,
+            time_to_run_in_seconds: value.TimeToRunInSeconds,
+            wait_time_between_iterations_in_seconds: value.WaitTimeBetweenIterationsInSeconds,
+            wait_time_between_faults_in_seconds: value.WaitTimeBetweenFaultsInSeconds,
+            context,
+            chaos_target_filter,
+        }
+    }
+}
+
+pub(crate) struct ChaosParametersRaw<'a> {
+    internal: Box<FABRIC_CHAOS_PARAMETERS>,
+    _ex1: Box<FABRIC_CHAOS_PARAMETERS_EX1>,
+    _ex2: Box<FABRIC_CHAOS_PARAMETERS_EX2>,
+    _context: Box<FABRIC_EVENT_CONTEXT_MAP>,
+    _context_items: Vec<FABRIC_EVENT_CONTEXT_MAP_ITEM>,
+    _filter: Option<ChaosTargetFilterRaw<'a>>,
+    _phantom: PhantomData<&'a ChaosParameters>,
+}
+
+impl ChaosParametersRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_CHAOS_PARAMETERS {
+        self.internal.as_ref()
+    }
+}
+
+impl ChaosParameters {
+    pub(crate) fn build_raw(&self) -> ChaosParametersRaw<'_> {
+        let mut context_items = self
+            .context
+            .iter()
+            .map(|(k, v)| FABRIC_EVENT_CONTEXT_MAP_ITEM {
+                Key: k.as_pcwstr(),
+                Value: v.as_pcwstr(),
+            })
+            .collect::<Vec<_>>();
+        let context = Box::new(FABRIC_EVENT_CONTEXT_MAP {
+            Count: context_items.len() as u32,
+            Items: context_items.as_mut_ptr(),
+        });
+        let filter = self.chaos_target_filter.as_ref().map(|f| f.build_raw());
+        let ex2 = Box::new(FABRIC_CHAOS_PARAMETERS_EX2 {
+            ChaosTargetFilter: filter
+                .as_ref()
+                .map_or(std::ptr::null(), |f| f.internal.as_ref()),
+            Reserved: std::ptr::null_mut(),
+        });
+        let ex1 = Box::new(FABRIC_CHAOS_PARAMETERS_EX1 {
+            ClusterHealthPolicy: std::ptr::null(),
+            Reserved: ex2.as_ref() as *const _ as *mut c_void,
+        });
+        let internal = Box::new(FABRIC_CHAOS_PARAMETERS {
+            MaxClusterStabilizationTimeoutInSeconds: self
+                .max_cluster_stabilization_timeout_in_seconds,
+            MaxConcurrentFaults: self.max_concurrent_faults,
+            EnableMoveReplicaFaults: self.enable_move_replica_faults,
+            TimeToRunInSeconds: self.time_to_run_in_seconds,
+            WaitTimeBetweenIterationsInSeconds: self.wait_time_between_iterations_in_seconds,
+            WaitTimeBetweenFaultsInSeconds: self.wait_time_between_faults_in_seconds,
+            Context: context.as_ref(),
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+        ChaosParametersRaw {
+            internal,
+            _ex1: ex1,
+            _ex2: ex2,
+            _context: context,
+            _context_items: context_items,
+            _filter: filter,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// FABRIC_CHAOS_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ChaosDescription {
+    /// None if chaos has never been started.
+    pub chaos_parameters: Option<ChaosParameters>,
+    pub status: ChaosStatus,
+    pub schedule_status: ChaosScheduleStatus,
+}
+
+impl From<&FABRIC_CHAOS_DESCRIPTION> for ChaosDescription {
+    fn from(value: &FABRIC_CHAOS_DESCRIPTION) -> Self {
+        Self {
+            chaos_parameters: unsafe { value.ChaosParameters.as_ref() }.map(ChaosParameters::from),
+            status: value.Status.into(),
+            schedule_status: value.ScheduleStatus.into(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_TIME_UTC
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChaosScheduleTimeUtc {
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_TIME_UTC> for ChaosScheduleTimeUtc {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_TIME_UTC) -> Self {
+        Self {
+            hour: value.Hour,
+            minute: value.Minute,
+        }
+    }
+}
+
+impl From<&ChaosScheduleTimeUtc> for FABRIC_CHAOS_SCHEDULE_TIME_UTC {
+    fn from(value: &ChaosScheduleTimeUtc) -> Self {
+        Self {
+            Hour: value.hour,
+            Minute: value.minute,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosScheduleTimeRangeUtc {
+    pub start_time: ChaosScheduleTimeUtc,
+    pub end_time: ChaosScheduleTimeUtc,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC> for ChaosScheduleTimeRangeUtc {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC) -> Self {
+        Self {
+            start_time: unsafe { value.StartTime.as_ref() }
+                .map(ChaosScheduleTimeUtc::from)
+                .unwrap_or_default(),
+            end_time: unsafe { value.EndTime.as_ref() }
+                .map(ChaosScheduleTimeUtc::from)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChaosScheduleJobActiveDays {
+    pub sunday: bool,
+    pub monday: bool,
+    pub tuesday: bool,
+    pub wednesday: bool,
+    pub thursday: bool,
+    pub friday: bool,
+    pub saturday: bool,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS> for ChaosScheduleJobActiveDays {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS) -> Self {
+        Self {
+            sunday: value.Sunday,
+            monday: value.Monday,
+            tuesday: value.Tuesday,
+            wednesday: value.Wednesday,
+            thursday: value.Thursday,
+            friday: value.Friday,
+            saturday: value.Saturday,
+        }
+    }
+}
+
+impl From<&ChaosScheduleJobActiveDays> for FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS {
+    fn from(value: &ChaosScheduleJobActiveDays) -> Self {
+        Self {
+            Sunday: value.sunday,
+            Monday: value.monday,
+            Tuesday: value.tuesday,
+            Wednesday: value.wednesday,
+            Thursday: value.thursday,
+            Friday: value.friday,
+            Saturday: value.saturday,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// FABRIC_CHAOS_SCHEDULE_JOB
+/// Runs chaos with the named parameters on the active days within the time ranges.
+#[derive(Debug, Clone)]
+pub struct ChaosScheduleJob {
+    /// Key into ChaosSchedule::chaos_parameters_map.
+    pub chaos_parameters: WString,
+    pub days: ChaosScheduleJobActiveDays,
+    pub times: Vec<ChaosScheduleTimeRangeUtc>,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_JOB> for ChaosScheduleJob {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_JOB) -> Self {
+        let times = match unsafe { value.Times.as_ref() } {
+            Some(l) => unsafe { raw_slice(l.Items, l.Count) }
+                .iter()
+                .map(ChaosScheduleTimeRangeUtc::from)
+                .collect(),
+            None => Vec::new(),
+        };
+        Self {
+            chaos_parameters: WStringWrap::from(value.ChaosParameters).into(),
+            days: unsafe { value.Days.as_ref() }
+                .map(ChaosScheduleJobActiveDays::from)
+                .unwrap_or_default(),
+            times,
+        }
+    }
+}
+
+// FABRIC_CHAOS_SCHEDULE
+#[derive(Debug, Clone)]
+pub struct ChaosSchedule {
+    pub start_date: SystemTime,
+    pub expiry_date: SystemTime,
+    pub chaos_parameters_map: Vec<(WString, ChaosParameters)>,
+    pub jobs: Vec<ChaosScheduleJob>,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE> for ChaosSchedule {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE) -> Self {
+        let chaos_parameters_map = match unsafe { value.ChaosParametersMap.as_ref() } {
+            Some(m) => unsafe { raw_slice(m.Items, m.Count) }
+                .iter()
+                .filter_map(|item| {
+                    let params = unsafe { item.Parameters.as_ref() }?;
+                    Some((
+                        WStringWrap::from(item.Name).into(),
+                        ChaosParameters::from(params),
+                    ))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let jobs = match unsafe { value.Jobs.as_ref() } {
+            Some(l) => unsafe { raw_slice(l.Items, l.Count) }
+                .iter()
+                .map(ChaosScheduleJob::from)
+                .collect(),
+            None => Vec::new(),
+        };
+        Self {
+            start_date: value.StartDate.into(),
+            expiry_date: value.ExpiryDate.into(),
+            chaos_parameters_map,
+            jobs,
+        }
+    }
+}
+
+/// FABRIC_CHAOS_SCHEDULE_DESCRIPTION
+/// The version must match the current version when setting the schedule,
+/// and SF increments it on each successful set.
+#[derive(Debug, Clone)]
+pub struct ChaosScheduleDescription {
+    pub version: u32,
+    pub schedule: ChaosSchedule,
+}
+
+impl From<&FABRIC_CHAOS_SCHEDULE_DESCRIPTION> for ChaosScheduleDescription {
+    fn from(value: &FABRIC_CHAOS_SCHEDULE_DESCRIPTION) -> Self {
+        Self {
+            version: value.Version,
+            schedule: unsafe { value.Schedule.as_ref() }
+                .map(ChaosSchedule::from)
+                .unwrap_or(ChaosSchedule {
+                    start_date: FILETIME_ZERO.into(),
+                    expiry_date: FILETIME_ZERO.into(),
+                    chaos_parameters_map: Vec::new(),
+                    jobs: Vec::new(),
+                }),
+        }
+    }
+}
+
+pub(crate) struct ChaosScheduleDescriptionRaw<'a> {
+    internal: Box<FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION>,
+    _description: Box<FABRIC_CHAOS_SCHEDULE_DESCRIPTION>,
+    _schedule: Box<FABRIC_CHAOS_SCHEDULE>,
+    _map: Box<FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP>,
+    _map_items: Vec<FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP_ITEM>,
+    _params: Vec<ChaosParametersRaw<'a>>,
+    _jobs: Box<FABRIC_CHAOS_SCHEDULE_JOB_LIST>,
+    _job_items: Vec<FABRIC_CHAOS_SCHEDULE_JOB>,
+    _days: Vec<FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS>,
+    _time_lists: Vec<FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC_LIST>,
+    _time_ranges: Vec<Vec<FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC>>,
+    _time_points: Vec<Vec<FABRIC_CHAOS_SCHEDULE_TIME_UTC>>,
+}
+
+impl ChaosScheduleDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+impl ChaosScheduleDescription {
+    pub(crate) fn build_raw(&self) -> ChaosScheduleDescriptionRaw<'_> {
+        let schedule = &self.schedule;
+        let params = schedule
+            .chaos_parameters_map
+            .iter()
+            .map(|(_, p)| p.build_raw())
+            .collect::<Vec<_>>();
+        let mut map_items = schedule
+            .chaos_parameters_map
+            .iter()
+            .zip(params.iter())
+            .map(
+                |((name, _), raw)| FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP_ITEM {
+                    Name: name.as_pcwstr(),
+                    Parameters: raw.as_ffi(),
+                },
+            )
+            .collect::<Vec<_>>();
+        let map = Box::new(FABRIC_CHAOS_SCHEDULE_CHAOS_PARAMETERS_MAP {
+            Count: map_items.len() as u32,
+            Items: map_items.as_mut_ptr(),
+        });
+
+        // Pointers into the vectors stay valid as the vectors are not resized after this.
+        // Each time range points to its start and end time, stored in pairs.
+        let time_points = schedule
+            .jobs
+            .iter()
+            .map(|j| {
+                j.times
+                    .iter()
+                    .flat_map(|t| [(&t.start_time).into(), (&t.end_time).into()])
+                    .collect::<Vec<FABRIC_CHAOS_SCHEDULE_TIME_UTC>>()
+            })
+            .collect::<Vec<_>>();
+        let mut time_ranges = time_points
+            .iter()
+            .map(|points| {
+                points
+                    .chunks(2)
+                    .map(|pair| FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC {
+                        StartTime: &pair[0],
+                        EndTime: &pair[1],
+                        Reserved: std::ptr::null_mut(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let time_lists = time_ranges
+            .iter_mut()
+            .map(|ranges| FABRIC_CHAOS_SCHEDULE_TIME_RANGE_UTC_LIST {
+                Count: ranges.len() as u32,
+                Items: ranges.as_mut_ptr(),
+            })
+            .collect::<Vec<_>>();
+        let days = schedule
+            .jobs
+            .iter()
+            .map(|j| FABRIC_CHAOS_SCHEDULE_JOB_ACTIVE_DAYS::from(&j.days))
+            .collect::<Vec<_>>();
+        let mut job_items = schedule
+            .jobs
+            .iter()
+            .zip(days.iter().zip(time_lists.iter()))
+            .map(|(job, (d, t))| FABRIC_CHAOS_SCHEDULE_JOB {
+                ChaosParameters: job.chaos_parameters.as_pcwstr(),
+                Days: d,
+                Times: t,
+                Reserved: std::ptr::null_mut(),
+            })
+            .collect::<Vec<_>>();
+        let jobs = Box::new(FABRIC_CHAOS_SCHEDULE_JOB_LIST {
+            Count: job_items.len() as u32,
+            Items: job_items.as_mut_ptr(),
+        });
+
+        let schedule_raw = Box::new(FABRIC_CHAOS_SCHEDULE {
+            StartDate: schedule.start_date.into(),
+            ExpiryDate: schedule.expiry_date.into(),
+            ChaosParametersMap: map.as_ref(),
+            Jobs: jobs.as_ref(),
+            Reserved: std::ptr::null_mut(),
+        });
+        let description = Box::new(FABRIC_CHAOS_SCHEDULE_DESCRIPTION {
+            Version: self.version,
+            Schedule: schedule_raw.as_ref(),
+            Reserved: std::ptr::null_mut(),
+        });
+        let internal = Box::new(FABRIC_CHAOS_SERVICE_SCHEDULE_DESCRIPTION {
+            ChaosScheduleDescription: description.as_ref(),
+            Reserved: std::ptr::null_mut(),
+        });
+        ChaosScheduleDescriptionRaw {
+            internal,
+            _description: description,
+            _schedule: schedule_raw,
+            _map: map,
+            _map_items: map_items,
+            _params: params,
+            _jobs: jobs,
+            _job_items: job_items,
+            _days: days,
+            _time_lists: time_lists,
+            _time_ranges: time_ranges,
+            _time_points: time_points,
+        }
+    }
+}
+
+/// FABRIC_CHAOS_EVENTS_SEGMENT_FILTER
+/// Selects the events whose timestamp falls within the range.
+#[derive(Debug, Clone, Copy)]
+pub struct ChaosEventsFilter {
+    pub start_time_utc: SystemTime,
+    pub end_time_utc: SystemTime,
+}
+
+impl From<&ChaosEventsFilter> for FABRIC_CHAOS_EVENTS_SEGMENT_FILTER {
+    fn from(value: &ChaosEventsFilter) -> Self {
+        Self {
+            StartTimeUtc: value.start_time_utc.into(),
+            EndTimeUtc: value.end_time_utc.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+impl From<&ChaosEventsFilter> for FABRIC_CHAOS_REPORT_FILTER {
+    fn from(value: &ChaosEventsFilter) -> Self {
+        Self {
+            StartTimeUtc: value.start_time_utc.into(),
+            EndTimeUtc: value.end_time_utc.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CHAOS_EVENT
+#[derive(Debug, Clone)]
+pub enum ChaosEvent {
+    Invalid,
+    Started {
+        time_stamp_utc: SystemTime,
+        chaos_parameters: Option<ChaosParameters>,
+    },
+    ExecutingFaults {
+        time_stamp_utc: SystemTime,
+        faults: Vec<WString>,
+    },
+    Waiting {
+        time_stamp_utc: SystemTime,
+        reason: WString,
+    },
+    ValidationFailed {
+        time_stamp_utc: SystemTime,
+        reason: WString,
+    },
+    TestError {
+        time_stamp_utc: SystemTime,
+        reason: WString,
+    },
+    Stopped {
+        time_stamp_utc: SystemTime,
+        reason: WString,
+    },
+}
+
+impl ChaosEvent {
+    pub fn time_stamp_utc(&self) -> Option<SystemTime> {
+        match self {
+            ChaosEvent::Invalid => None,
+            ChaosEvent::Started { time_stamp_utc, .. }
+            | ChaosEvent::ExecutingFaults { time_stamp_utc, .. }
+            | ChaosEvent::Waiting { time_stamp_utc, .. }
+            | ChaosEvent::ValidationFailed { time_stamp_utc, .. }
+            | ChaosEvent::TestError { time_stamp_utc, .. }
+            | ChaosEvent::Stopped { time_stamp_utc, .. } => Some(*time_stamp_utc),
+        }
+    }
+}
+
+impl From<&FABRIC_CHAOS_EVENT> for ChaosEvent {
+    fn from(value: &FABRIC_CHAOS_EVENT) -> Self {
+        // The reason events share the same layout but are distinct types.
+        macro_rules! reason_event {
+            ($raw:ty, $variant:ident) => {
+                match unsafe { (value.Value as *const $raw).as_ref() } {
+                    Some(e) => ChaosEvent::$variant {
+                        time_stamp_utc: e.TimeStampUtc.into(),
+                        reason: WStringWrap::from(e.Reason).into(),
+                    },
+                    None => ChaosEvent::Invalid,
+                }
+            };
+        }
+        match value.Kind {
+            FABRIC_CHAOS_EVENT_KIND_STARTED => {
+                match unsafe { (value.Value as *const FABRIC_STARTED_EVENT).as_ref() } {
+                    Some(e) => ChaosEvent::Started {
+                        time_stamp_utc: e.TimeStampUtc.into(),
+                        chaos_parameters: unsafe { e.ChaosParameters.as_ref() }
+                            .map(ChaosParameters::from),
+                    },
+                    None => ChaosEvent::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS => {
+                match unsafe { (value.Value as *const FABRIC_EXECUTING_FAULTS_EVENT).as_ref() } {
+                    Some(e) => ChaosEvent::ExecutingFaults {
+                        time_stamp_utc: e.TimeStampUtc.into(),
+                        faults: fabric_string_list_to_vec(e.Faults),
+                    },
+                    None => ChaosEvent::Invalid,
+                }
+            }
+            FABRIC_CHAOS_EVENT_KIND_WAITING => reason_event!(FABRIC_WAITING_EVENT, Waiting),
+            FABRIC_CHAOS_EVENT_KIND_VALIDATION_FAILED => {
+                reason_event!(FABRIC_VALIDATION_FAILED_EVENT, ValidationFailed)
+            }
+            FABRIC_CHAOS_EVENT_KIND_TEST_ERROR => {
+                reason_event!(FABRIC_TEST_ERROR_EVENT, TestError)
+            }
+            FABRIC_CHAOS_EVENT_KIND_STOPPED => reason_event!(FABRIC_STOPPED_EVENT, Stopped),
+            _ => ChaosEvent::Invalid,
+        }
+    }
+}
+
+type ChaosEventListIter<'a, O> = FabricIter<'a, FABRIC_CHAOS_EVENT, ChaosEvent, O>;
+
+fn event_list_accessor(list: *const FABRIC_CHAOS_EVENT_LIST) -> (u32, *const FABRIC_CHAOS_EVENT) {
+    match unsafe { list.as_ref() } {
+        Some(l) if !l.Items.is_null() => (l.Count, l.Items),
+        _ => (0, std::ptr::null()),
+    }
+}
+
+fn continuation_token_from_raw(token: PCWSTR) -> Option<WString> {
+    let token: WString = WStringWrap::from(token).into();
+    if token.is_empty() { None } else { Some(token) }
+}
+
+/// IFabricChaosEventsSegmentResult
+/// One page of chaos events.
+#[derive(Debug, Clone)]
+pub struct ChaosEventsSegment {
+    com: IFabricChaosEventsSegmentResult,
+}
+
+impl From<IFabricChaosEventsSegmentResult> for ChaosEventsSegment {
+    fn from(com: IFabricChaosEventsSegmentResult) -> Self {
+        Self { com }
+    }
+}
+
+impl FabricListAccessor<FABRIC_CHAOS_EVENT> for ChaosEventsSegment {
+    fn get_count(&self) -> u32 {
+        let segment = unsafe { self.com.get_ChaosEventsSegmentResult().as_ref().unwrap() };
+        event_list_accessor(segment.History).0
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_CHAOS_EVENT {
+        let segment = unsafe { self.com.get_ChaosEventsSegmentResult().as_ref().unwrap() };
+        event_list_accessor(segment.History).1
+    }
+}
+
+impl ChaosEventsSegment {
+    pub fn iter(&self) -> ChaosEventListIter<'_, Self> {
+        ChaosEventListIter::new(self, self)
+    }
+
+    /// None if this is the last page.
+    pub fn get_continuation_token(&self) -> Option<WString> {
+        let segment = unsafe { self.com.get_ChaosEventsSegmentResult().as_ref().unwrap() };
+        continuation_token_from_raw(segment.ContinuationToken)
+    }
+}
+
+/// IFabricChaosReportResult
+/// The chaos parameters and status, and one page of the event history.
+#[derive(Debug, Clone)]
+pub struct ChaosReport {
+    com: IFabricChaosReportResult,
+}
+
+impl From<IFabricChaosReportResult> for ChaosReport {
+    fn from(com: IFabricChaosReportResult) -> Self {
+        Self { com }
+    }
+}
+
+impl FabricListAccessor<FABRIC_CHAOS_EVENT> for ChaosReport {
+    fn get_count(&self) -> u32 {
+        let report = unsafe { self.com.get_ChaosReportResult().as_ref().unwrap() };
+        event_list_accessor(report.History).0
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_CHAOS_EVENT {
+        let report = unsafe { self.com.get_ChaosReportResult().as_ref().unwrap() };
+        event_list_accessor(report.History).1
+    }
+}
+
+impl ChaosReport {
+    pub fn iter(&self) -> ChaosEventListIter<'_, Self> {
+        ChaosEventListIter::new(self, self)
+    }
+
+    pub fn get_chaos_parameters(&self) -> Option<ChaosParameters> {
+        let report = unsafe { self.com.get_ChaosReportResult().as_ref().unwrap() };
+        unsafe { report.ChaosParameters.as_ref() }.map(ChaosParameters::from)
+    }
+
+    pub fn get_status(&self) -> ChaosStatus {
+        let report = unsafe { self.com.get_ChaosReportResult().as_ref().unwrap() };
+        report.Status.into()
+    }
+
+    /// None if this is the last page.
+    pub fn get_continuation_token(&self) -> Option<WString> {
+        let report = unsafe { self.com.get_ChaosReportResult().as_ref().unwrap() };
+        continuation_token_from_raw(report.ContinuationToken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_CHAOS_EVENT, FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS,
+        FABRIC_CHAOS_EVENT_KIND_STOPPED, FABRIC_CHAOS_PARAMETERS_EX1, FABRIC_CHAOS_PARAMETERS_EX2,
+        FABRIC_EXECUTING_FAULTS_EVENT, FABRIC_STOPPED_EVENT,
+    };
+
+    use crate::{PCWSTR, WString, strings::FabricStringListRaw};
+    use windows_core::Win32::Foundation::FILETIME;
+
+    use super::{
+        ChaosEvent, ChaosParameters, ChaosSchedule, ChaosScheduleDescription, ChaosScheduleJob,
+        ChaosScheduleJobActiveDays, ChaosScheduleTimeRangeUtc, ChaosScheduleTimeUtc,
+        ChaosTargetFilter,
+    };
+
+    #[test]
+    fn test_chaos_parameters_round_trip() {
+        let params = ChaosParameters {
+            max_concurrent_faults: 3,
+            context: vec![(WString::from("k"), WString::from("v"))],
+            chaos_target_filter: Some(ChaosTargetFilter {
+                node_type_inclusion_list: vec![WString::from("nt1"), WString::from("nt2")],
+                application_inclusion_list: vec![],
+            }),
+            ..Default::default()
+        };
+        let raw = params.build_raw();
+        let ffi = raw.as_ffi();
+        let ex1 = unsafe { (ffi.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX1).as_ref() }.unwrap();
+        assert!(ex1.ClusterHealthPolicy.is_null());
+        let ex2 = unsafe { (ex1.Reserved as *const FABRIC_CHAOS_PARAMETERS_EX2).as_ref() }.unwrap();
+        let filter = unsafe { ex2.ChaosTargetFilter.as_ref() }.unwrap();
+        assert!(filter.ApplicationInclusionList.is_null());
+
+        let back = ChaosParameters::from(ffi);
+        assert_eq!(back.max_concurrent_faults, 3);
+        assert_eq!(back.time_to_run_in_seconds, u32::MAX as u64);
+        assert_eq!(back.context, params.context);
+        let back_filter = back.chaos_target_filter.unwrap();
+        assert_eq!(
+            back_filter.node_type_inclusion_list,
+            vec![WString::from("nt1"), WString::from("nt2")]
+        );
+        assert!(back_filter.application_inclusion_list.is_empty());
+    }
+
+    #[test]
+    fn test_chaos_schedule_round_trip() {
+        let desc = ChaosScheduleDescription {
+            version: 2,
+            schedule: ChaosSchedule {
+                start_date: FILETIME {
+                    dwLowDateTime: 1,
+                    dwHighDateTime: 2,
+                }
+                .into(),
+                expiry_date: FILETIME {
+                    dwLowDateTime: 3,
+                    dwHighDateTime: 4,
+                }
+                .into(),
+                chaos_parameters_map: vec![(WString::from("p1"), ChaosParameters::default())],
+                jobs: vec![ChaosScheduleJob {
+                    chaos_parameters: WString::from("p1"),
+                    days: ChaosScheduleJobActiveDays {
+                        monday: true,
+                        ..Default::default()
+                    },
+                    times: vec![
+                        ChaosScheduleTimeRangeUtc {
+                            start_time: ChaosScheduleTimeUtc { hour: 1, minute: 2 },
+                            end_time: ChaosScheduleTimeUtc { hour: 3, minute: 4 },
+                        },
+                        ChaosScheduleTimeRangeUtc {
+                            start_time: ChaosScheduleTimeUtc { hour: 5, minute: 6 },
+                            end_time: ChaosScheduleTimeUtc { hour: 7, minute: 8 },
+                        },
+                    ],
+                }],
+            },
+        };
+        let raw = desc.build_raw();
+        let inner = unsafe { raw.as_ffi().ChaosScheduleDescription.as_ref() }.unwrap();
+        let back = ChaosScheduleDescription::from(inner);
+        assert_eq!(back.version, 2);
+        assert_eq!(back.schedule.start_date, desc.schedule.start_date);
+        assert_eq!(back.schedule.chaos_parameters_map.len(), 1);
+        assert_eq!(back.schedule.chaos_parameters_map[0].0, WString::from("p1"));
+        let job = &back.schedule.jobs[0];
+        assert_eq!(job.chaos_parameters, WString::from("p1"));
+        assert_eq!(job.days, desc.schedule.jobs[0].days);
+        assert_eq!(job.times, desc.schedule.jobs[0].times);
+    }
+
+    #[test]
+    fn test_chaos_event_conversion() {
+        let faults = [WString::from("f1"), WString::from("f2")];
+        let faults_raw = FabricStringListRaw::new(&faults);
+        let mut executing = FABRIC_EXECUTING_FAULTS_EVENT {
+            TimeStampUtc: FILETIME {
+                dwLowDateTime: 10,
+                dwHighDateTime: 0,
+            },
+            Faults: faults_raw.as_ptr_or_null(),
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_CHAOS_EVENT {
+            Kind: FABRIC_CHAOS_EVENT_KIND_EXECUTING_FAULTS,
+            Value: &mut executing as *mut _ as *mut std::ffi::c_void,
+        };
+        match ChaosEvent::from(&raw) {
+            ChaosEvent::ExecutingFaults {
+                time_stamp_utc,
+                faults: converted,
+            } => {
+                assert_eq!(FILETIME::from(time_stamp_utc).dwLowDateTime, 10);
+                assert_eq!(converted, faults.to_vec());
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+
+        let reason = WString::from("done");
+        let mut stopped = FABRIC_STOPPED_EVENT {
+            TimeStampUtc: super::FILETIME_ZERO,
+            Reason: PCWSTR(reason.as_ptr()),
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_CHAOS_EVENT {
+            Kind: FABRIC_CHAOS_EVENT_KIND_STOPPED,
+            Value: &mut stopped as *mut _ as *mut std::ffi::c_void,
+        };
+        match ChaosEvent::from(&raw) {
+            ChaosEvent::Stopped { reason: r, .. } => assert_eq!(r, reason),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use mssf_com::{
+    FabricClient::IFabricUpgradeProgressResult2,
+    FabricTypes::{
+        FABRIC_NODE_DEACTIVATION_INTENT, FABRIC_NODE_DEACTIVATION_INTENT_INVALID,
+        FABRIC_NODE_DEACTIVATION_INTENT_PAUSE, FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA,
+        FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE, FABRIC_NODE_DEACTIVATION_INTENT_RESTART,
+        FABRIC_UPGRADE_DESCRIPTION, FABRIC_UPGRADE_KIND_ROLLING, FABRIC_UPGRADE_STATE,
+        FABRIC_UPGRADE_STATE_FAILED, FABRIC_UPGRADE_STATE_ROLLING_BACK_COMPLETED,
+        FABRIC_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS, FABRIC_UPGRADE_STATE_ROLLING_BACK_PENDING,
+        FABRIC_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED,
+        FABRIC_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS,
+        FABRIC_UPGRADE_STATE_ROLLING_FORWARD_PENDING,
+    },
+};
+
+use super::upgrade::{RollingUpgradePolicyDescriptionRaw, UpgradeDomainStatusListRef};
+use crate::{
+    WString,
+    strings::{WStringWrap, get_pcwstr_from_opt},
+    types::{RollingUpgradeMode, RollingUpgradePolicyDescription, UpgradeDomainStatus},
+};
+
+// FABRIC_NODE_DEACTIVATION_INTENT
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeDeactivationIntent {
+    Invalid,
+    /// No new replicas are placed on the node. Existing replicas keep running.
+    Pause,
+    /// Replicas are moved off the node so that it can be restarted safely.
+    Restart,
+    /// The data on the node is going to be lost, i.e. reimage.
+    RemoveData,
+    /// The node is going to be removed from the cluster permanently.
+    RemoveNode,
+}
+
+impl From<FABRIC_NODE_DEACTIVATION_INTENT> for NodeDeactivationIntent {
+    fn from(value: FABRIC_NODE_DEACTIVATION_INTENT) -> Self {
+        match value {
+            FABRIC_NODE_DEACTIVATION_INTENT_PAUSE => Self::Pause,
+            FABRIC_NODE_DEACTIVATION_INTENT_RESTART => Self::Restart,
+            FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA => Self::RemoveData,
+            FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE => Self::RemoveNode,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<NodeDeactivationIntent> for FABRIC_NODE_DEACTIVATION_INTENT {
+    fn from(value: NodeDeactivationIntent) -> Self {
+        match value {
+            NodeDeactivationIntent::Invalid => FABRIC_NODE_DEACTIVATION_INTENT_INVALID,
+            NodeDeactivationIntent::Pause => FABRIC_NODE_DEACTIVATION_INTENT_PAUSE,
+            NodeDeactivationIntent::Restart => FABRIC_NODE_DEACTIVATION_INTENT_RESTART,
+            NodeDeactivationIntent::RemoveData => FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_DATA,
+            NodeDeactivationIntent::RemoveNode => FABRIC_NODE_DEACTIVATION_INTENT_REMOVE_NODE,
+        }
+    }
+}
+
+/// FABRIC_UPGRADE_DESCRIPTION
+/// At least one of code version and config version needs to be set.
+/// The versions must have been provisioned before the upgrade.
+#[derive(Debug, Clone)]
+pub struct FabricUpgradeDescription {
+    code_version: Option<WString>,
+    config_version: Option<WString>,
+    policy: RollingUpgradePolicyDescription,
+}
+
+impl FabricUpgradeDescription {
+    pub fn new(policy: RollingUpgradePolicyDescription) -> Self {
+        Self {
+            code_version: None,
+            config_version: None,
+            policy,
+        }
+    }
+
+    pub fn with_code_version(mut self, code_version: WString) -> Self {
+        self.code_version = Some(code_version);
+        self
+    }
+
+    pub fn with_config_version(mut self, config_version: WString) -> Self {
+        self.config_version = Some(config_version);
+        self
+    }
+
+    pub(crate) fn build_raw(&self) -> FabricUpgradeDescriptionRaw<'_> {
+        let policy = self.policy.build_raw();
+        let internal = Box::new(FABRIC_UPGRADE_DESCRIPTION {
+            CodeVersion: get_pcwstr_from_opt(&self.code_version),
+            ConfigVersion: get_pcwstr_from_opt(&self.config_version),
+            UpgradeKind: FABRIC_UPGRADE_KIND_ROLLING,
+            UpgradePolicyDescription: policy.as_ffi() as *const _ as *mut c_void,
+            Reserved: std::ptr::null_mut(),
+        });
+        FabricUpgradeDescriptionRaw {
+            internal,
+            _policy: policy,
+        }
+    }
+}
+
+pub(crate) struct FabricUpgradeDescriptionRaw<'a> {
+    internal: Box<FABRIC_UPGRADE_DESCRIPTION>,
+    _policy: RollingUpgradePolicyDescriptionRaw<'a>,
+}
+
+impl FabricUpgradeDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_UPGRADE_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+// FABRIC_UPGRADE_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FabricUpgradeState {
+    Invalid,
+    RollingBackInProgress,
+    RollingBackCompleted,
+    RollingForwardPending,
+    RollingForwardInProgress,
+    RollingForwardCompleted,
+    Failed,
+    RollingBackPending,
+}
+
+impl From<FABRIC_UPGRADE_STATE> for FabricUpgradeState {
+    fn from(value: FABRIC_UPGRADE_STATE) -> Self {
+        match value {
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_IN_PROGRESS => Self::RollingBackInProgress,
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_COMPLETED => Self::RollingBackCompleted,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_PENDING => Self::RollingForwardPending,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_IN_PROGRESS => Self::RollingForwardInProgress,
+            FABRIC_UPGRADE_STATE_ROLLING_FORWARD_COMPLETED => Self::RollingForwardCompleted,
+            FABRIC_UPGRADE_STATE_FAILED => Self::Failed,
+            FABRIC_UPGRADE_STATE_ROLLING_BACK_PENDING => Self::RollingBackPending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+/// IFabricUpgradeProgressResult2
+/// The result is also used as the input of move_next_fabric_upgrade_domain.
+#[derive(Debug, Clone)]
+pub struct FabricUpgradeProgress {
+    com: IFabricUpgradeProgressResult2,
+}
+
+impl FabricUpgradeProgress {
+    pub(crate) fn from_com(com: IFabricUpgradeProgressResult2) -> Self {
+        Self { com }
+    }
+
+    pub(crate) fn as_com(&self) -> &IFabricUpgradeProgressResult2 {
+        &self.com
+    }
+
+    pub fn get_target_code_version(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_TargetCodeVersion() }).into()
+    }
+
+    pub fn get_target_config_version(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_TargetConfigVersion() }).into()
+    }
+
+    pub fn get_upgrade_state(&self) -> FabricUpgradeState {
+        unsafe { self.com.get_UpgradeState() }.into()
+    }
+
+    pub fn get_rolling_upgrade_mode(&self) -> RollingUpgradeMode {
+        unsafe { self.com.get_RollingUpgradeMode() }.into()
+    }
+
+    /// Empty if there is no upgrade domain pending.
+    pub fn get_next_upgrade_domain(&self) -> WString {
+        WStringWrap::from(unsafe { self.com.get_NextUpgradeDomain() }).into()
+    }
+
+    pub fn get_upgrade_domains(&self) -> crate::Result<Vec<UpgradeDomainStatus>> {
+        let mut count = 0_u32;
+        let first = unsafe { self.com.GetUpgradeDomains(std::ptr::addr_of_mut!(count)) }?;
+        let accessor = UpgradeDomainStatusListRef::<'_, Self> {
+            count,
+            first,
+            phantom: PhantomData,
+        };
+        Ok(accessor.to_vec())
+    }
+
+    /// Returns the upgrade domains whose state changed since the previous progress.
+    pub fn get_changed_upgrade_domains(
+        &self,
+        previous: &FabricUpgradeProgress,
+    ) -> crate::Result<Vec<UpgradeDomainStatus>> {
+        let mut count = 0_u32;
+        let first = unsafe {
+            self.com
+                .GetChangedUpgradeDomains(&*previous.com, std::ptr::addr_of_mut!(count))
+        }?;
+        let accessor = UpgradeDomainStatusListRef::<'_, Self> {
+            count,
+            first,
+            phantom: PhantomData,
+        };
+        Ok(accessor.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_ROLLING_UPGRADE_MODE_MONITORED, FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION,
+        FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1,
+    };
+
+    use crate::{
+        WString,
+        types::{
+            MonitoredUpgradeFailureAction, RollingUpgradeMode, RollingUpgradeMonitoringPolicy,
+            RollingUpgradePolicyDescription,
+        },
+    };
+
+    use super::FabricUpgradeDescription;
+
+    #[test]
+    fn test_fabric_upgrade_description_raw() {
+        let desc = FabricUpgradeDescription::new(RollingUpgradePolicyDescription {
+            rolling_upgrade_mode: RollingUpgradeMode::Monitored,
+            force_restart: false,
+            upgrade_replica_set_check_timeout_in_seconds: 60,
+            monitoring_policy: Some(RollingUpgradeMonitoringPolicy {
+                failure_action: MonitoredUpgradeFailureAction::Rollback,
+                health_check_wait_duration_in_seconds: 1,
+                health_check_retry_timeout_in_seconds: 2,
+                upgrade_timeout_in_seconds: 3,
+                upgrade_domain_timeout_in_seconds: 4,
+                health_check_stable_duration_in_seconds: 5,
+            }),
+        })
+        .with_config_version(WString::from("2.0"));
+        let raw = desc.build_raw();
+        let ffi = raw.as_ffi();
+        assert!(ffi.CodeVersion.is_null());
+        assert_eq!(WString::from(ffi.ConfigVersion), WString::from("2.0"));
+        let policy = unsafe {
+            (ffi.UpgradePolicyDescription as *const FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION)
+                .as_ref()
+        }
+        .unwrap();
+        assert_eq!(
+            policy.RollingUpgradeMode,
+            FABRIC_ROLLING_UPGRADE_MODE_MONITORED
+        );
+        assert_eq!(policy.UpgradeReplicaSetCheckTimeoutInSeconds, 60);
+        let ex1 = unsafe {
+            (policy.Reserved as *const FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1).as_ref()
+        }
+        .unwrap();
+        let monitoring = unsafe { ex1.MonitoringPolicy.as_ref() }.unwrap();
+        assert_eq!(monitoring.UpgradeDomainTimeoutInSeconds, 4);
+        assert!(ex1.HealthPolicy.is_null());
+    }
+}
@@ -0,0 +1,557 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Query types for entities deployed on a node: applications, service packages,
+//! code packages and replicas.
+
+use std::time::SystemTime;
+
+use crate::{GUID, WString};
+use mssf_com::{
+    FabricClient::{
+        IFabricGetDeployedApplicationPagedListResult, IFabricGetDeployedCodePackageListResult,
+        IFabricGetDeployedReplicaListResult, IFabricGetDeployedServicePackageListResult,
+    },
+    FabricTypes::{
+        FABRIC_CODE_PACKAGE_ENTRY_POINT, FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX,
+        FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX2,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION,
+        FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+        FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX1,
+        FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM, FABRIC_DEPLOYMENT_STATUS,
+        FABRIC_DEPLOYMENT_STATUS_ACTIVATING, FABRIC_DEPLOYMENT_STATUS_ACTIVE,
+        FABRIC_DEPLOYMENT_STATUS_DEACTIVATING, FABRIC_DEPLOYMENT_STATUS_DOWNLOADING,
+        FABRIC_DEPLOYMENT_STATUS_UPGRADING, FABRIC_ENTRY_POINT_STATUS,
+        FABRIC_ENTRY_POINT_STATUS_PENDING, FABRIC_ENTRY_POINT_STATUS_STARTED,
+        FABRIC_ENTRY_POINT_STATUS_STARTING, FABRIC_ENTRY_POINT_STATUS_STOPPED,
+        FABRIC_ENTRY_POINT_STATUS_STOPPING, FABRIC_HOST_ISOLATION_MODE,
+        FABRIC_HOST_ISOLATION_MODE_HYPER_V, FABRIC_HOST_ISOLATION_MODE_PROCESS, FABRIC_HOST_TYPE,
+        FABRIC_HOST_TYPE_CONTAINER_HOST, FABRIC_HOST_TYPE_EXE_HOST, FABRIC_SERVICE_KIND_STATEFUL,
+        FABRIC_SERVICE_KIND_STATELESS,
+    },
+};
+
+use super::{
+    DeployedStatelessServiceInstanceQueryResult, PagedQueryDescription, PagingStatus,
+    QueryServiceReplicaStatus, fault::uri_from_raw,
+};
+use crate::{
+    iter::{FabricIter, FabricListAccessor},
+    strings::{WStringWrap, get_pcwstr_from_opt},
+    types::{HealthState, ReplicaRole, Uri},
+};
+
+// FABRIC_DEPLOYMENT_STATUS
+#[derive(Debug, PartialEq, Clone)]
+pub enum DeploymentStatus {
+    Invalid,
+    Downloading,
+    Activating,
+    Active,
+    Upgrading,
+    Deactivating,
+}
+
+impl From<FABRIC_DEPLOYMENT_STATUS> for DeploymentStatus {
+    fn from(value: FABRIC_DEPLOYMENT_STATUS) -> Self {
+        match value {
+            FABRIC_DEPLOYMENT_STATUS_DOWNLOADING => Self::Downloading,
+            FABRIC_DEPLOYMENT_STATUS_ACTIVATING => Self::Activating,
+            FABRIC_DEPLOYMENT_STATUS_ACTIVE => Self::Active,
+            FABRIC_DEPLOYMENT_STATUS_UPGRADING => Self::Upgrading,
+            FABRIC_DEPLOYMENT_STATUS_DEACTIVATING => Self::Deactivating,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_PAGED_DEPLOYED_APPLICATION_QUERY_DESCRIPTION
+#[derive(Debug, Default, Clone)]
+pub struct PagedDeployedApplicationQueryDescription {
+    pub node_name: WString,
+    pub application_name_filter: Option<Uri>,
+    pub include_health_state: bool,
+    pub paged_query: PagedQueryDescription,
+}
+
+// FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedApplication {
+    pub application_name: Uri,
+    pub application_type_name: WString,
+    pub deployed_application_status: DeploymentStatus,
+    pub work_directory: WString,
+    pub log_directory: WString,
+    pub temp_directory: WString,
+    /// Unknown unless the health state is requested in the query.
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM> for DeployedApplication {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM) -> Self {
+        let ex = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX).as_ref()
+        };
+        let ex2 = ex.and_then(|e| unsafe {
+            (e.Reserved as *const FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX2).as_ref()
+        });
+        let dir = |f: fn(&FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM_EX) -> crate::PCWSTR| {
+            ex.map(|e| WStringWrap::from(f(e)).into())
+                .unwrap_or_default()
+        };
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            application_type_name: WStringWrap::from(value.ApplicationTypeName).into(),
+            deployed_application_status: value.DeployedApplicationStatus.into(),
+            work_directory: dir(|e| e.WorkDirectory),
+            log_directory: dir(|e| e.LogDirectory),
+            temp_directory: dir(|e| e.TempDirectory),
+            health_state: ex2.map_or(HealthState::Unknown, |e| (&e.HealthState).into()),
+        }
+    }
+}
+
+// IFabricGetDeployedApplicationPagedListResult
+pub struct DeployedApplicationList {
+    com: IFabricGetDeployedApplicationPagedListResult,
+}
+
+type DeployedApplicationListIter<'a> = FabricIter<
+    'a,
+    FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM,
+    DeployedApplication,
+    DeployedApplicationList,
+>;
+
+impl FabricListAccessor<FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM> for DeployedApplicationList {
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_DeployedApplicationPagedList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_DEPLOYED_APPLICATION_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_DeployedApplicationPagedList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl DeployedApplicationList {
+    pub fn new(com: IFabricGetDeployedApplicationPagedListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> DeployedApplicationListIter<'_> {
+        DeployedApplicationListIter::new(self, self)
+    }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct DeployedServicePackageQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+}
+
+impl From<&DeployedServicePackageQueryDescription>
+    for FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_DESCRIPTION
+{
+    fn from(value: &DeployedServicePackageQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestNameFilter: get_pcwstr_from_opt(&value.service_manifest_name_filter),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackage {
+    pub service_manifest_name: WString,
+    pub service_manifest_version: WString,
+    pub deployed_service_package_status: DeploymentStatus,
+    pub service_package_activation_id: WString,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM> for DeployedServicePackage {
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM_EX1)
+                .as_ref()
+        };
+        Self {
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            service_manifest_version: WStringWrap::from(value.ServiceManifestVersion).into(),
+            deployed_service_package_status: value.DeployedServicePackageStatus.into(),
+            service_package_activation_id: ex1
+                .map(|e| WStringWrap::from(e.ServicePackageActivationId).into())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// IFabricGetDeployedServicePackageListResult
+pub struct DeployedServicePackageList {
+    com: IFabricGetDeployedServicePackageListResult,
+}
+
+type DeployedServicePackageListIter<'a> = FabricIter<
+    'a,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM,
+    DeployedServicePackage,
+    DeployedServicePackageList,
+>;
+
+impl FabricListAccessor<FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM>
+    for DeployedServicePackageList
+{
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_DeployedServicePackageList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_DEPLOYED_SERVICE_PACKAGE_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_DeployedServicePackageList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl DeployedServicePackageList {
+    pub fn new(com: IFabricGetDeployedServicePackageListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> DeployedServicePackageListIter<'_> {
+        DeployedServicePackageListIter::new(self, self)
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct DeployedCodePackageQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+    pub code_package_name_filter: Option<WString>,
+}
+
+impl From<&DeployedCodePackageQueryDescription> for FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_DESCRIPTION {
+    fn from(value: &DeployedCodePackageQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestNameFilter: get_pcwstr_from_opt(&value.service_manifest_name_filter),
+            CodePackageNameFilter: get_pcwstr_from_opt(&value.code_package_name_filter),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_ENTRY_POINT_STATUS
+#[derive(Debug, PartialEq, Clone)]
+pub enum EntryPointStatus {
+    Invalid,
+    Pending,
+    Starting,
+    Started,
+    Stopping,
+    Stopped,
+}
+
+impl From<FABRIC_ENTRY_POINT_STATUS> for EntryPointStatus {
+    fn from(value: FABRIC_ENTRY_POINT_STATUS) -> Self {
+        match value {
+            FABRIC_ENTRY_POINT_STATUS_PENDING => Self::Pending,
+            FABRIC_ENTRY_POINT_STATUS_STARTING => Self::Starting,
+            FABRIC_ENTRY_POINT_STATUS_STARTED => Self::Started,
+            FABRIC_ENTRY_POINT_STATUS_STOPPING => Self::Stopping,
+            FABRIC_ENTRY_POINT_STATUS_STOPPED => Self::Stopped,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_CODE_PACKAGE_ENTRY_POINT
+#[derive(Debug, Clone)]
+pub struct CodePackageEntryPoint {
+    pub entry_point_location: WString,
+    pub process_id: i64,
+    pub run_as_user_name: WString,
+    pub entry_point_status: EntryPointStatus,
+    pub next_activation_utc: SystemTime,
+}
+
+impl From<&FABRIC_CODE_PACKAGE_ENTRY_POINT> for CodePackageEntryPoint {
+    fn from(value: &FABRIC_CODE_PACKAGE_ENTRY_POINT) -> Self {
+        Self {
+            entry_point_location: WStringWrap::from(value.EntryPointLocation).into(),
+            process_id: value.ProcessId,
+            run_as_user_name: WStringWrap::from(value.RunAsUserName).into(),
+            entry_point_status: value.EntryPointStatus.into(),
+            next_activation_utc: value.NextActivationUtc.into(),
+        }
+    }
+}
+
+// FABRIC_HOST_TYPE
+#[derive(Debug, PartialEq, Clone)]
+pub enum HostType {
+    Invalid,
+    ExeHost,
+    ContainerHost,
+}
+
+impl From<FABRIC_HOST_TYPE> for HostType {
+    fn from(value: FABRIC_HOST_TYPE) -> Self {
+        match value {
+            FABRIC_HOST_TYPE_EXE_HOST => Self::ExeHost,
+            FABRIC_HOST_TYPE_CONTAINER_HOST => Self::ContainerHost,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_HOST_ISOLATION_MODE
+#[derive(Debug, PartialEq, Clone)]
+pub enum HostIsolationMode {
+    None,
+    Process,
+    HyperV,
+}
+
+impl From<FABRIC_HOST_ISOLATION_MODE> for HostIsolationMode {
+    fn from(value: FABRIC_HOST_ISOLATION_MODE) -> Self {
+        match value {
+            FABRIC_HOST_ISOLATION_MODE_PROCESS => Self::Process,
+            FABRIC_HOST_ISOLATION_MODE_HYPER_V => Self::HyperV,
+            _ => Self::None,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedCodePackage {
+    pub code_package_name: WString,
+    pub code_package_version: WString,
+    pub service_manifest_name: WString,
+    pub run_frequency_interval: u32,
+    pub deployed_code_package_status: DeploymentStatus,
+    pub setup_entry_point: Option<CodePackageEntryPoint>,
+    pub entry_point: Option<CodePackageEntryPoint>,
+    pub service_package_activation_id: WString,
+    pub host_type: HostType,
+    pub host_isolation_mode: HostIsolationMode,
+}
+
+impl From<&FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM> for DeployedCodePackage {
+    fn from(value: &FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        Self {
+            code_package_name: WStringWrap::from(value.CodePackageName).into(),
+            code_package_version: WStringWrap::from(value.CodePackageVersion).into(),
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            run_frequency_interval: value.RunFrequencyInterval,
+            deployed_code_package_status: value.DeployedCodePackageStatus.into(),
+            setup_entry_point: unsafe { value.SetupEntryPoint.as_ref() }.map(Into::into),
+            entry_point: unsafe { value.EntryPoint.as_ref() }.map(Into::into),
+            service_package_activation_id: ex1
+                .map(|e| WStringWrap::from(e.ServicePackageActivationId).into())
+                .unwrap_or_default(),
+            host_type: ex1.map_or(HostType::Invalid, |e| e.HostType.into()),
+            host_isolation_mode: ex1
+                .map_or(HostIsolationMode::None, |e| e.HostIsolationMode.into()),
+        }
+    }
+}
+
+// IFabricGetDeployedCodePackageListResult
+pub struct DeployedCodePackageList {
+    com: IFabricGetDeployedCodePackageListResult,
+}
+
+type DeployedCodePackageListIter<'a> = FabricIter<
+    'a,
+    FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM,
+    DeployedCodePackage,
+    DeployedCodePackageList,
+>;
+
+impl FabricListAccessor<FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM>
+    for DeployedCodePackageList
+{
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_DeployedCodePackageList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_DEPLOYED_CODE_PACKAGE_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_DeployedCodePackageList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl DeployedCodePackageList {
+    pub fn new(com: IFabricGetDeployedCodePackageListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> DeployedCodePackageListIter<'_> {
+        DeployedCodePackageListIter::new(self, self)
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct DeployedServiceReplicaQueryDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name_filter: Option<WString>,
+    pub partition_id_filter: Option<GUID>,
+}
+
+impl From<&DeployedServiceReplicaQueryDescription>
+    for FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_DESCRIPTION
+{
+    fn from(value: &DeployedServiceReplicaQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestNameFilter: get_pcwstr_from_opt(&value.service_manifest_name_filter),
+            PartitionIdFilter: value.partition_id_filter.unwrap_or(GUID::zeroed()),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct DeployedStatefulServiceReplicaQueryResult {
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub service_manifest_version: WString,
+    pub code_package_name: WString,
+    pub partition_id: GUID,
+    pub replica_id: i64,
+    pub replica_role: ReplicaRole,
+    pub replica_status: QueryServiceReplicaStatus,
+    pub address: WString,
+    pub service_manifest_name: WString,
+}
+
+impl From<&FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM>
+    for DeployedStatefulServiceReplicaQueryResult
+{
+    fn from(value: &FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved
+                as *const FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM_EX1)
+                .as_ref()
+        };
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            service_type_name: WStringWrap::from(value.ServiceTypeName).into(),
+            service_manifest_version: WStringWrap::from(value.ServiceManifestVersion).into(),
+            code_package_name: WStringWrap::from(value.CodePackageName).into(),
+            partition_id: value.PartitionId,
+            replica_id: value.ReplicaId,
+            replica_role: (&value.ReplicaRole).into(),
+            replica_status: (&value.ReplicaStatus).into(),
+            address: WStringWrap::from(value.Address).into(),
+            service_manifest_name: ex1
+                .map(|e| WStringWrap::from(e.ServiceManifestName).into())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub enum DeployedServiceReplicaQueryResult {
+    Invalid,
+    Stateful(DeployedStatefulServiceReplicaQueryResult),
+    Stateless(DeployedStatelessServiceInstanceQueryResult),
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM>
+    for DeployedServiceReplicaQueryResult
+{
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM) -> Self {
+        match value.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => {
+                let raw = unsafe {
+                    (value.Value
+                        as *const FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM)
+                        .as_ref()
+                        .unwrap()
+                };
+                Self::Stateful(raw.into())
+            }
+            FABRIC_SERVICE_KIND_STATELESS => {
+                let raw = unsafe {
+                    (value.Value
+                        as *const FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM)
+                        .as_ref()
+                        .unwrap()
+                };
+                Self::Stateless(DeployedStatelessServiceInstanceQueryResult::new(raw))
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// IFabricGetDeployedReplicaListResult
+pub struct DeployedServiceReplicaList {
+    com: IFabricGetDeployedReplicaListResult,
+}
+
+type DeployedServiceReplicaListIter<'a> = FabricIter<
+    'a,
+    FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM,
+    DeployedServiceReplicaQueryResult,
+    DeployedServiceReplicaList,
+>;
+
+impl FabricListAccessor<FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM>
+    for DeployedServiceReplicaList
+{
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_DeployedReplicaList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_DEPLOYED_SERVICE_REPLICA_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_DeployedReplicaList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl DeployedServiceReplicaList {
+    pub fn new(com: IFabricGetDeployedReplicaListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> DeployedServiceReplicaListIter<'_> {
+        DeployedServiceReplicaListIter::new(self, self)
+    }
+}
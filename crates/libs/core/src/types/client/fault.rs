@@ -0,0 +1,669 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Types used by the fault injection and test command apis.
+
+use std::time::Duration;
+
+use mssf_com::FabricTypes::{
+    FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION, FABRIC_DATA_LOSS_MODE, FABRIC_DATA_LOSS_MODE_FULL,
+    FABRIC_DATA_LOSS_MODE_INVALID, FABRIC_DATA_LOSS_MODE_PARTIAL,
+    FABRIC_DEPLOYED_CODE_PACKAGE_RESULT, FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME,
+    FABRIC_MOVE_PRIMARY_RESULT, FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME,
+    FABRIC_MOVE_SECONDARY_RESULT, FABRIC_NODE_RESULT, FABRIC_PARTITION_DATA_LOSS_PROGRESS,
+    FABRIC_PARTITION_DATA_LOSS_RESULT, FABRIC_PARTITION_QUORUM_LOSS_PROGRESS,
+    FABRIC_PARTITION_QUORUM_LOSS_RESULT, FABRIC_PARTITION_RESTART_PROGRESS,
+    FABRIC_PARTITION_RESTART_RESULT, FABRIC_PARTITION_SELECTOR,
+    FABRIC_PARTITION_SELECTOR_TYPE_NAMED, FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID,
+    FABRIC_PARTITION_SELECTOR_TYPE_RANDOM, FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON,
+    FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64, FABRIC_QUORUM_LOSS_MODE,
+    FABRIC_QUORUM_LOSS_MODE_ALL_REPLICAS, FABRIC_QUORUM_LOSS_MODE_INVALID,
+    FABRIC_QUORUM_LOSS_MODE_QUORUM_REPLICAS,
+    FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME,
+    FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_RESTART_PARTITION_MODE,
+    FABRIC_RESTART_PARTITION_MODE_ALL_REPLICAS_OR_INSTANCES, FABRIC_RESTART_PARTITION_MODE_INVALID,
+    FABRIC_RESTART_PARTITION_MODE_ONLY_ACTIVE_SECONDARIES, FABRIC_SELECTED_PARTITION,
+    FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION,
+    FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION, FABRIC_START_PARTITION_RESTART_DESCRIPTION,
+    FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME, FABRIC_TEST_COMMAND_PROGRESS_STATE,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_CANCELLED, FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_FAULTED, FABRIC_TEST_COMMAND_PROGRESS_STATE_FORCE_CANCELLED,
+    FABRIC_TEST_COMMAND_PROGRESS_STATE_ROLLING_BACK, FABRIC_TEST_COMMAND_PROGRESS_STATE_RUNNING,
+};
+
+use crate::{GUID, HRESULT, PCWSTR, WString, strings::WStringWrap, types::Uri};
+
+pub(super) fn uri_from_raw(uri: mssf_com::FabricTypes::FABRIC_URI) -> Uri {
+    Uri::new(PCWSTR(uri.0).into())
+}
+
+// FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct RestartNodeDescription {
+    pub node_name: WString,
+    /// The instance id of the node to restart. Use 0 to skip the instance check.
+    pub node_instance_id: u64,
+    pub should_create_fabric_dump: bool,
+}
+
+impl From<&RestartNodeDescription> for FABRIC_RESTART_NODE_DESCRIPTION_USING_NODE_NAME {
+    /// The lifetime of the SF raw type returned must match the
+    /// original struct.
+    fn from(value: &RestartNodeDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            NodeInstanceId: value.node_instance_id,
+            ShouldCreateFabricDump: value.should_create_fabric_dump,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct StartNodeDescription {
+    pub node_name: WString,
+    /// The instance id of the stopped node.
+    pub node_instance_id: u64,
+    pub ip_address_or_fqdn: WString,
+    pub cluster_connection_port: u32,
+}
+
+impl From<&StartNodeDescription> for FABRIC_START_NODE_DESCRIPTION_USING_NODE_NAME {
+    fn from(value: &StartNodeDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            NodeInstanceId: value.node_instance_id,
+            IPAddressOrFQDN: value.ip_address_or_fqdn.as_pcwstr(),
+            ClusterConnectionPort: value.cluster_connection_port,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct StopNodeDescription {
+    pub node_name: WString,
+    pub node_instance_id: u64,
+}
+
+impl From<&StopNodeDescription> for FABRIC_STOP_NODE_DESCRIPTION_USING_NODE_NAME {
+    fn from(value: &StopNodeDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            NodeInstanceId: value.node_instance_id,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_NODE_RESULT
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeResult {
+    pub node_name: WString,
+    pub node_instance: u64,
+}
+
+impl From<&FABRIC_NODE_RESULT> for NodeResult {
+    fn from(value: &FABRIC_NODE_RESULT) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            node_instance: value.NodeInstance,
+        }
+    }
+}
+
+// FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct RestartDeployedCodePackageDescription {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub code_package_name: WString,
+    /// Use 0 to skip the instance check.
+    pub code_package_instance_id: i64,
+}
+
+impl From<&RestartDeployedCodePackageDescription>
+    for FABRIC_RESTART_DEPLOYED_CODE_PACKAGE_DESCRIPTION_USING_NODE_NAME
+{
+    fn from(value: &RestartDeployedCodePackageDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ApplicationName: value.application_name.as_raw(),
+            ServiceManifestName: value.service_manifest_name.as_pcwstr(),
+            CodePackageName: value.code_package_name.as_pcwstr(),
+            CodePackageInstanceId: value.code_package_instance_id,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_CODE_PACKAGE_RESULT
+#[derive(Debug, Clone)]
+pub struct DeployedCodePackageResult {
+    pub node_name: WString,
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub code_package_name: WString,
+    pub code_package_instance_id: i64,
+}
+
+impl From<&FABRIC_DEPLOYED_CODE_PACKAGE_RESULT> for DeployedCodePackageResult {
+    fn from(value: &FABRIC_DEPLOYED_CODE_PACKAGE_RESULT) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            application_name: uri_from_raw(value.ApplicationName),
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            code_package_name: WStringWrap::from(value.CodePackageName).into(),
+            code_package_instance_id: value.CodePackageInstanceId,
+        }
+    }
+}
+
+// FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct MovePrimaryDescription {
+    /// The node to move the primary to. SF chooses a node if empty.
+    pub node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+    pub ignore_constraints: bool,
+}
+
+impl From<&MovePrimaryDescription> for FABRIC_MOVE_PRIMARY_DESCRIPTION_USING_NODE_NAME {
+    fn from(value: &MovePrimaryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            ServiceName: value.service_name.as_raw(),
+            PartitionId: value.partition_id,
+            IgnoreConstraints: value.ignore_constraints,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_MOVE_PRIMARY_RESULT
+#[derive(Debug, Clone)]
+pub struct MovePrimaryResult {
+    pub node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_MOVE_PRIMARY_RESULT> for MovePrimaryResult {
+    fn from(value: &FABRIC_MOVE_PRIMARY_RESULT) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            service_name: uri_from_raw(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+// FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME
+#[derive(Debug, Clone)]
+pub struct MoveSecondaryDescription {
+    pub current_node_name: WString,
+    /// The node to move the secondary to. SF chooses a node if empty.
+    pub new_node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+    pub ignore_constraints: bool,
+}
+
+impl From<&MoveSecondaryDescription> for FABRIC_MOVE_SECONDARY_DESCRIPTION_USING_NODE_NAME {
+    fn from(value: &MoveSecondaryDescription) -> Self {
+        Self {
+            CurrentNodeName: value.current_node_name.as_pcwstr(),
+            NewNodeName: value.new_node_name.as_pcwstr(),
+            ServiceName: value.service_name.as_raw(),
+            PartitionId: value.partition_id,
+            IgnoreConstraints: value.ignore_constraints,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_MOVE_SECONDARY_RESULT
+#[derive(Debug, Clone)]
+pub struct MoveSecondaryResult {
+    pub current_node_name: WString,
+    pub new_node_name: WString,
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_MOVE_SECONDARY_RESULT> for MoveSecondaryResult {
+    fn from(value: &FABRIC_MOVE_SECONDARY_RESULT) -> Self {
+        Self {
+            current_node_name: WStringWrap::from(value.CurrentNodeName).into(),
+            new_node_name: WStringWrap::from(value.NewNodeName).into(),
+            service_name: uri_from_raw(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+// FABRIC_PARTITION_SELECTOR
+/// Selects the partition of a service to inject the fault into.
+#[derive(Debug, Clone)]
+pub enum PartitionSelector {
+    Singleton {
+        service_name: Uri,
+    },
+    Named {
+        service_name: Uri,
+        partition_name: WString,
+    },
+    /// Selects the partition whose range contains the key.
+    UniformInt64 {
+        service_name: Uri,
+        partition_key: i64,
+    },
+    PartitionId {
+        service_name: Uri,
+        partition_id: GUID,
+    },
+    /// SF picks a random partition of the service.
+    Random {
+        service_name: Uri,
+    },
+}
+
+/// Holds the partition key string passed to SF.
+pub(crate) struct PartitionSelectorRaw<'a> {
+    internal: Box<FABRIC_PARTITION_SELECTOR>,
+    _key: Option<WString>,
+    _phantom: std::marker::PhantomData<&'a PartitionSelector>,
+}
+
+impl PartitionSelectorRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_PARTITION_SELECTOR {
+        self.internal.as_ref()
+    }
+}
+
+impl PartitionSelector {
+    pub(crate) fn build_raw(&self) -> PartitionSelectorRaw<'_> {
+        // SF parses the key from string for all selector types.
+        let (service_name, selector_type, key) = match self {
+            PartitionSelector::Singleton { service_name } => {
+                (service_name, FABRIC_PARTITION_SELECTOR_TYPE_SINGLETON, None)
+            }
+            PartitionSelector::Named {
+                service_name,
+                partition_name,
+            } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_NAMED,
+                Some(partition_name.clone()),
+            ),
+            PartitionSelector::UniformInt64 {
+                service_name,
+                partition_key,
+            } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64,
+                Some(WString::from(partition_key.to_string().as_str())),
+            ),
+            PartitionSelector::PartitionId {
+                service_name,
+                partition_id,
+            } => (
+                service_name,
+                FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID,
+                Some(WString::from(format!("{partition_id:?}").as_str())),
+            ),
+            PartitionSelector::Random { service_name } => {
+                (service_name, FABRIC_PARTITION_SELECTOR_TYPE_RANDOM, None)
+            }
+        };
+        let internal = Box::new(FABRIC_PARTITION_SELECTOR {
+            ServiceName: service_name.as_raw(),
+            PartitionSelectorType: selector_type,
+            PartitionKey: key.as_ref().map_or(PCWSTR::null(), |k| k.as_pcwstr()),
+            Reserved: std::ptr::null_mut(),
+        });
+        PartitionSelectorRaw {
+            internal,
+            _key: key,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+// FABRIC_SELECTED_PARTITION
+#[derive(Debug, Clone)]
+pub struct SelectedPartition {
+    pub service_name: Uri,
+    pub partition_id: GUID,
+}
+
+impl From<&FABRIC_SELECTED_PARTITION> for SelectedPartition {
+    fn from(value: &FABRIC_SELECTED_PARTITION) -> Self {
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            partition_id: value.PartitionId,
+        }
+    }
+}
+
+// FABRIC_DATA_LOSS_MODE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataLossMode {
+    Invalid,
+    /// Only some replicas are removed, and OnDataLoss is triggered.
+    Partial,
+    /// All replicas are removed and all data is lost.
+    Full,
+}
+
+impl From<DataLossMode> for FABRIC_DATA_LOSS_MODE {
+    fn from(value: DataLossMode) -> Self {
+        match value {
+            DataLossMode::Invalid => FABRIC_DATA_LOSS_MODE_INVALID,
+            DataLossMode::Partial => FABRIC_DATA_LOSS_MODE_PARTIAL,
+            DataLossMode::Full => FABRIC_DATA_LOSS_MODE_FULL,
+        }
+    }
+}
+
+// FABRIC_QUORUM_LOSS_MODE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuorumLossMode {
+    Invalid,
+    /// A quorum of replicas is taken down.
+    QuorumReplicas,
+    /// All replicas except the primary are taken down.
+    AllReplicas,
+}
+
+impl From<QuorumLossMode> for FABRIC_QUORUM_LOSS_MODE {
+    fn from(value: QuorumLossMode) -> Self {
+        match value {
+            QuorumLossMode::Invalid => FABRIC_QUORUM_LOSS_MODE_INVALID,
+            QuorumLossMode::QuorumReplicas => FABRIC_QUORUM_LOSS_MODE_QUORUM_REPLICAS,
+            QuorumLossMode::AllReplicas => FABRIC_QUORUM_LOSS_MODE_ALL_REPLICAS,
+        }
+    }
+}
+
+// FABRIC_RESTART_PARTITION_MODE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartPartitionMode {
+    Invalid,
+    AllReplicasOrInstances,
+    OnlyActiveSecondaries,
+}
+
+impl From<RestartPartitionMode> for FABRIC_RESTART_PARTITION_MODE {
+    fn from(value: RestartPartitionMode) -> Self {
+        match value {
+            RestartPartitionMode::Invalid => FABRIC_RESTART_PARTITION_MODE_INVALID,
+            RestartPartitionMode::AllReplicasOrInstances => {
+                FABRIC_RESTART_PARTITION_MODE_ALL_REPLICAS_OR_INSTANCES
+            }
+            RestartPartitionMode::OnlyActiveSecondaries => {
+                FABRIC_RESTART_PARTITION_MODE_ONLY_ACTIVE_SECONDARIES
+            }
+        }
+    }
+}
+
+/// FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION
+/// operation_id is chosen by the caller and is used to poll the progress.
+#[derive(Debug, Clone)]
+pub struct StartPartitionDataLossDescription {
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub data_loss_mode: DataLossMode,
+}
+
+/// FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct StartPartitionQuorumLossDescription {
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub quorum_loss_mode: QuorumLossMode,
+    /// How long the partition is kept in quorum loss.
+    pub quorum_loss_duration: Duration,
+}
+
+/// FABRIC_START_PARTITION_RESTART_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct StartPartitionRestartDescription {
+    pub operation_id: GUID,
+    pub partition_selector: PartitionSelector,
+    pub restart_partition_mode: RestartPartitionMode,
+}
+
+impl StartPartitionDataLossDescription {
+    /// selector must outlive the returned struct.
+    pub(crate) fn as_ffi(
+        &self,
+        selector: &PartitionSelectorRaw,
+    ) -> FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION {
+        FABRIC_START_PARTITION_DATA_LOSS_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: selector.as_ffi() as *const _ as *mut _,
+            DataLossMode: self.data_loss_mode.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+impl StartPartitionQuorumLossDescription {
+    /// selector must outlive the returned struct.
+    pub(crate) fn as_ffi(
+        &self,
+        selector: &PartitionSelectorRaw,
+    ) -> FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION {
+        FABRIC_START_PARTITION_QUORUM_LOSS_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: selector.as_ffi() as *const _ as *mut _,
+            QuorumLossMode: self.quorum_loss_mode.into(),
+            QuorumLossDurationInMilliSeconds: self
+                .quorum_loss_duration
+                .as_millis()
+                .try_into()
+                .unwrap_or(i32::MAX),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+impl StartPartitionRestartDescription {
+    /// selector must outlive the returned struct.
+    pub(crate) fn as_ffi(
+        &self,
+        selector: &PartitionSelectorRaw,
+    ) -> FABRIC_START_PARTITION_RESTART_DESCRIPTION {
+        FABRIC_START_PARTITION_RESTART_DESCRIPTION {
+            OperationId: self.operation_id,
+            PartitionSelector: selector.as_ffi() as *const _ as *mut _,
+            RestartPartitionMode: self.restart_partition_mode.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct CancelTestCommandDescription {
+    pub operation_id: GUID,
+    /// When false, SF rolls back the state changed by the command before cancelling.
+    pub force: bool,
+}
+
+impl From<&CancelTestCommandDescription> for FABRIC_CANCEL_TEST_COMMAND_DESCRIPTION {
+    fn from(value: &CancelTestCommandDescription) -> Self {
+        Self {
+            OperationId: value.operation_id,
+            Force: value.force,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_TEST_COMMAND_PROGRESS_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestCommandProgressState {
+    Invalid,
+    Running,
+    RollingBack,
+    Completed,
+    Faulted,
+    Cancelled,
+    ForceCancelled,
+}
+
+impl TestCommandProgressState {
+    /// The command has stopped and the state will not change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Completed | Self::Faulted | Self::Cancelled | Self::ForceCancelled
+        )
+    }
+}
+
+impl From<FABRIC_TEST_COMMAND_PROGRESS_STATE> for TestCommandProgressState {
+    fn from(value: FABRIC_TEST_COMMAND_PROGRESS_STATE) -> Self {
+        match value {
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_RUNNING => Self::Running,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_ROLLING_BACK => Self::RollingBack,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED => Self::Completed,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_FAULTED => Self::Faulted,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_CANCELLED => Self::Cancelled,
+            FABRIC_TEST_COMMAND_PROGRESS_STATE_FORCE_CANCELLED => Self::ForceCancelled,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+/// Result of a partition fault command.
+/// Shared by FABRIC_PARTITION_DATA_LOSS_RESULT, FABRIC_PARTITION_QUORUM_LOSS_RESULT
+/// and FABRIC_PARTITION_RESTART_RESULT which have the same layout.
+#[derive(Debug, Clone)]
+pub struct PartitionFaultResult {
+    pub selected_partition: Option<SelectedPartition>,
+    pub error_code: HRESULT,
+}
+
+macro_rules! impl_partition_fault_result {
+    ($raw:ty) => {
+        impl From<&$raw> for PartitionFaultResult {
+            fn from(value: &$raw) -> Self {
+                Self {
+                    selected_partition: unsafe { value.SelectedPartition.as_ref() }
+                        .map(SelectedPartition::from),
+                    error_code: value.ErrorCode,
+                }
+            }
+        }
+    };
+}
+
+impl_partition_fault_result!(FABRIC_PARTITION_DATA_LOSS_RESULT);
+impl_partition_fault_result!(FABRIC_PARTITION_QUORUM_LOSS_RESULT);
+impl_partition_fault_result!(FABRIC_PARTITION_RESTART_RESULT);
+
+/// Progress of a partition fault command.
+/// The result is only available once the state is Completed or Faulted.
+#[derive(Debug, Clone)]
+pub struct PartitionFaultProgress {
+    pub state: TestCommandProgressState,
+    pub result: Option<PartitionFaultResult>,
+}
+
+macro_rules! impl_partition_fault_progress {
+    ($raw:ty) => {
+        impl From<&$raw> for PartitionFaultProgress {
+            fn from(value: &$raw) -> Self {
+                Self {
+                    state: value.State.into(),
+                    result: unsafe { value.Result.as_ref() }.map(PartitionFaultResult::from),
+                }
+            }
+        }
+    };
+}
+
+impl_partition_fault_progress!(FABRIC_PARTITION_DATA_LOSS_PROGRESS);
+impl_partition_fault_progress!(FABRIC_PARTITION_QUORUM_LOSS_PROGRESS);
+impl_partition_fault_progress!(FABRIC_PARTITION_RESTART_PROGRESS);
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_PARTITION_DATA_LOSS_PROGRESS, FABRIC_PARTITION_DATA_LOSS_RESULT,
+        FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID, FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64,
+        FABRIC_SELECTED_PARTITION, FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED,
+    };
+
+    use crate::{GUID, HRESULT, WString, types::Uri};
+
+    use super::{PartitionFaultProgress, PartitionSelector, TestCommandProgressState};
+
+    #[test]
+    fn test_partition_selector_key() {
+        let int_selector = PartitionSelector::UniformInt64 {
+            service_name: Uri::from("fabric:/a/b"),
+            partition_key: -12,
+        };
+        let raw = int_selector.build_raw();
+        assert_eq!(
+            raw.as_ffi().PartitionSelectorType,
+            FABRIC_PARTITION_SELECTOR_TYPE_UNIFORM_INT64
+        );
+        assert_eq!(
+            WString::from(raw.as_ffi().PartitionKey),
+            WString::from("-12")
+        );
+
+        let id = GUID::from_u128(0x01234567_89ab_cdef_0123_456789abcdef);
+        let id_selector = PartitionSelector::PartitionId {
+            service_name: Uri::from("fabric:/a/b"),
+            partition_id: id,
+        };
+        let raw = id_selector.build_raw();
+        assert_eq!(
+            raw.as_ffi().PartitionSelectorType,
+            FABRIC_PARTITION_SELECTOR_TYPE_PARTITION_ID
+        );
+        assert_eq!(
+            WString::from(raw.as_ffi().PartitionKey),
+            WString::from("01234567-89AB-CDEF-0123-456789ABCDEF")
+        );
+    }
+
+    #[test]
+    fn test_partition_fault_progress() {
+        let service_name = WString::from("fabric:/a/b");
+        let mut selected = FABRIC_SELECTED_PARTITION {
+            ServiceName: mssf_com::FabricTypes::FABRIC_URI(service_name.as_ptr() as *mut u16),
+            PartitionId: GUID::from_u128(1),
+            Reserved: std::ptr::null_mut(),
+        };
+        let mut result = FABRIC_PARTITION_DATA_LOSS_RESULT {
+            SelectedPartition: &mut selected,
+            ErrorCode: HRESULT(0),
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_PARTITION_DATA_LOSS_PROGRESS {
+            State: FABRIC_TEST_COMMAND_PROGRESS_STATE_COMPLETED,
+            Result: &mut result,
+            Reserved: std::ptr::null_mut(),
+        };
+        let progress = PartitionFaultProgress::from(&raw);
+        assert_eq!(progress.state, TestCommandProgressState::Completed);
+        assert!(progress.state.is_terminal());
+        let selected = progress.result.unwrap().selected_partition.unwrap();
+        assert_eq!(selected.service_name.0, service_name);
+        assert_eq!(selected.partition_id, GUID::from_u128(1));
+    }
+}
@@ -0,0 +1,806 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Health policies, filters and query descriptions used by the health queries.
+
+use std::{any::Any, ffi::c_void, marker::PhantomData};
+
+use bitflags::bitflags;
+use mssf_com::FabricTypes::{
+    FABRIC_APPLICATION_HEALTH_POLICY, FABRIC_APPLICATION_HEALTH_POLICY_MAP,
+    FABRIC_APPLICATION_HEALTH_POLICY_MAP_ITEM, FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+    FABRIC_APPLICATION_HEALTH_STATE_FILTER, FABRIC_APPLICATION_HEALTH_STATE_FILTER_LIST,
+    FABRIC_APPLICATION_HEALTH_STATES_FILTER, FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP,
+    FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP_ITEM, FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION,
+    FABRIC_CLUSTER_HEALTH_POLICY, FABRIC_CLUSTER_HEALTH_POLICY_EX1,
+    FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION, FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION,
+    FABRIC_DEPLOYED_APPLICATION_HEALTH_STATES_FILTER,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER, FABRIC_HEALTH_EVENTS_FILTER,
+    FABRIC_HEALTH_STATE_FILTER_ALL, FABRIC_HEALTH_STATE_FILTER_DEFAULT,
+    FABRIC_HEALTH_STATE_FILTER_ERROR, FABRIC_HEALTH_STATE_FILTER_NONE,
+    FABRIC_HEALTH_STATE_FILTER_OK, FABRIC_HEALTH_STATE_FILTER_WARNING,
+    FABRIC_NODE_HEALTH_QUERY_DESCRIPTION, FABRIC_NODE_HEALTH_STATE_FILTER,
+    FABRIC_NODE_HEALTH_STATE_FILTER_LIST, FABRIC_NODE_HEALTH_STATES_FILTER,
+    FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION, FABRIC_PARTITION_HEALTH_STATES_FILTER,
+    FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION, FABRIC_REPLICA_HEALTH_STATES_FILTER,
+    FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION, FABRIC_SERVICE_HEALTH_STATES_FILTER,
+    FABRIC_SERVICE_TYPE_HEALTH_POLICY, FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP,
+    FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP_ITEM, FABRIC_URI,
+};
+
+use crate::{GUID, WString, strings::get_pcwstr_from_opt, types::Uri};
+
+bitflags! {
+    /// FABRIC_HEALTH_STATE_FILTER
+    /// Selects the events or children returned by a health query, by health state.
+    /// Default returns the entries with warning or error state.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct HealthStateFilter: u32 {
+        const Default = FABRIC_HEALTH_STATE_FILTER_DEFAULT.0 as u32;
+        const None = FABRIC_HEALTH_STATE_FILTER_NONE.0 as u32;
+        const Ok = FABRIC_HEALTH_STATE_FILTER_OK.0 as u32;
+        const Warning = FABRIC_HEALTH_STATE_FILTER_WARNING.0 as u32;
+        const Error = FABRIC_HEALTH_STATE_FILTER_ERROR.0 as u32;
+        const All = FABRIC_HEALTH_STATE_FILTER_ALL.0 as u32;
+    }
+}
+
+impl Default for HealthStateFilter {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+/// FABRIC_CLUSTER_HEALTH_POLICY
+/// Used to evaluate the cluster and node health.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterHealthPolicy {
+    pub consider_warning_as_error: bool,
+    pub max_percent_unhealthy_nodes: u8,
+    pub max_percent_unhealthy_applications: u8,
+    /// Overrides max_percent_unhealthy_applications per application type name.
+    pub application_type_health_policy_map: Vec<(WString, u8)>,
+}
+
+pub(crate) struct ClusterHealthPolicyRaw<'a> {
+    internal: Box<FABRIC_CLUSTER_HEALTH_POLICY>,
+    _ex1: Option<(
+        Box<FABRIC_CLUSTER_HEALTH_POLICY_EX1>,
+        Box<FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP>,
+        Vec<FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP_ITEM>,
+    )>,
+    _phantom: PhantomData<&'a ClusterHealthPolicy>,
+}
+
+impl ClusterHealthPolicyRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_CLUSTER_HEALTH_POLICY {
+        self.internal.as_ref()
+    }
+}
+
+impl ClusterHealthPolicy {
+    pub(crate) fn build_raw(&self) -> ClusterHealthPolicyRaw<'_> {
+        let ex1 = if self.application_type_health_policy_map.is_empty() {
+            None
+        } else {
+            let items = self
+                .application_type_health_policy_map
+                .iter()
+                .map(
+                    |(name, percent)| FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP_ITEM {
+                        ApplicationTypeName: name.as_pcwstr(),
+                        MaxPercentUnhealthyApplications: *percent,
+                    },
+                )
+                .collect::<Vec<_>>();
+            let map = Box::new(FABRIC_APPLICATION_TYPE_HEALTH_POLICY_MAP {
+                Count: items.len() as u32,
+                Items: items.as_ptr(),
+            });
+            let ex1 = Box::new(FABRIC_CLUSTER_HEALTH_POLICY_EX1 {
+                ApplicationTypeHealthPolicyMap: map.as_ref(),
+                Reserved: std::ptr::null_mut(),
+            });
+            Some((ex1, map, items))
+        };
+        let internal = Box::new(FABRIC_CLUSTER_HEALTH_POLICY {
+            ConsiderWarningAsError: self.consider_warning_as_error,
+            MaxPercentUnhealthyNodes: self.max_percent_unhealthy_nodes,
+            MaxPercentUnhealthyApplications: self.max_percent_unhealthy_applications,
+            Reserved: ex1.as_ref().map_or(std::ptr::null_mut(), |e| {
+                e.0.as_ref() as *const _ as *mut c_void
+            }),
+        });
+        ClusterHealthPolicyRaw {
+            internal,
+            _ex1: ex1,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// FABRIC_SERVICE_TYPE_HEALTH_POLICY
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ServiceTypeHealthPolicy {
+    pub max_percent_unhealthy_services: u8,
+    pub max_percent_unhealthy_partitions_per_service: u8,
+    pub max_percent_unhealthy_replicas_per_partition: u8,
+}
+
+impl From<&ServiceTypeHealthPolicy> for FABRIC_SERVICE_TYPE_HEALTH_POLICY {
+    fn from(value: &ServiceTypeHealthPolicy) -> Self {
+        Self {
+            MaxPercentUnhealthyServices: value.max_percent_unhealthy_services,
+            MaxPercentUnhealthyPartitionsPerService: value
+                .max_percent_unhealthy_partitions_per_service,
+            MaxPercentUnhealthyReplicasPerPartition: value
+                .max_percent_unhealthy_replicas_per_partition,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// FABRIC_APPLICATION_HEALTH_POLICY
+/// Used to evaluate the health of an application and its children.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApplicationHealthPolicy {
+    pub consider_warning_as_error: bool,
+    pub max_percent_unhealthy_deployed_applications: u8,
+    pub default_service_type_health_policy: Option<ServiceTypeHealthPolicy>,
+    /// Overrides the default service type policy per service type name.
+    pub service_type_health_policy_map: Vec<(WString, ServiceTypeHealthPolicy)>,
+}
+
+pub(crate) struct ApplicationHealthPolicyRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_HEALTH_POLICY>,
+    _default_service_type_policy: Option<Box<FABRIC_SERVICE_TYPE_HEALTH_POLICY>>,
+    _service_type_policy_map: Option<(
+        Box<FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP>,
+        Vec<FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP_ITEM>,
+        Vec<FABRIC_SERVICE_TYPE_HEALTH_POLICY>,
+    )>,
+    _phantom: PhantomData<&'a ApplicationHealthPolicy>,
+}
+
+impl ApplicationHealthPolicyRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_APPLICATION_HEALTH_POLICY {
+        self.internal.as_ref()
+    }
+}
+
+impl ApplicationHealthPolicy {
+    pub(crate) fn build_raw(&self) -> ApplicationHealthPolicyRaw<'_> {
+        let default_service_type_policy = self
+            .default_service_type_health_policy
+            .as_ref()
+            .map(|p| Box::new(FABRIC_SERVICE_TYPE_HEALTH_POLICY::from(p)));
+        let service_type_policy_map = if self.service_type_health_policy_map.is_empty() {
+            None
+        } else {
+            let policies = self
+                .service_type_health_policy_map
+                .iter()
+                .map(|(_, p)| FABRIC_SERVICE_TYPE_HEALTH_POLICY::from(p))
+                .collect::<Vec<_>>();
+            let mut items = self
+                .service_type_health_policy_map
+                .iter()
+                .zip(policies.iter())
+                .map(
+                    |((name, _), p)| FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP_ITEM {
+                        ServiceTypeName: name.as_pcwstr(),
+                        ServiceTypeHealthPolicy: p,
+                    },
+                )
+                .collect::<Vec<_>>();
+            let map = Box::new(FABRIC_SERVICE_TYPE_HEALTH_POLICY_MAP {
+                Count: items.len() as u32,
+                Items: items.as_mut_ptr(),
+            });
+            Some((map, items, policies))
+        };
+        let internal = Box::new(FABRIC_APPLICATION_HEALTH_POLICY {
+            ConsiderWarningAsError: self.consider_warning_as_error,
+            MaxPercentUnhealthyDeployedApplications: self
+                .max_percent_unhealthy_deployed_applications,
+            DefaultServiceTypeHealthPolicy: default_service_type_policy
+                .as_ref()
+                .map_or(std::ptr::null(), |p| p.as_ref()),
+            ServiceTypeHealthPolicyMap: service_type_policy_map
+                .as_ref()
+                .map_or(std::ptr::null(), |m| m.0.as_ref()),
+            Reserved: std::ptr::null_mut(),
+        });
+        ApplicationHealthPolicyRaw {
+            internal,
+            _default_service_type_policy: default_service_type_policy,
+            _service_type_policy_map: service_type_policy_map,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// FABRIC_APPLICATION_HEALTH_POLICY_MAP
+/// Per application health policies used when evaluating the cluster health.
+struct ApplicationHealthPolicyMapRaw<'a> {
+    internal: Box<FABRIC_APPLICATION_HEALTH_POLICY_MAP>,
+    _items: Vec<FABRIC_APPLICATION_HEALTH_POLICY_MAP_ITEM>,
+    _policies: Vec<ApplicationHealthPolicyRaw<'a>>,
+}
+
+impl<'a> ApplicationHealthPolicyMapRaw<'a> {
+    /// Returns None if the map is empty, which means no map is sent to SF.
+    fn new(map: &'a [(Uri, ApplicationHealthPolicy)]) -> Option<Self> {
+        if map.is_empty() {
+            return None;
+        }
+        let policies = map.iter().map(|(_, p)| p.build_raw()).collect::<Vec<_>>();
+        let mut items = map
+            .iter()
+            .zip(policies.iter())
+            .map(|((name, _), p)| FABRIC_APPLICATION_HEALTH_POLICY_MAP_ITEM {
+                ApplicationName: name.as_raw(),
+                HealthPolicy: p.as_ffi(),
+            })
+            .collect::<Vec<_>>();
+        let internal = Box::new(FABRIC_APPLICATION_HEALTH_POLICY_MAP {
+            Count: items.len() as u32,
+            Items: items.as_mut_ptr(),
+        });
+        Some(Self {
+            internal,
+            _items: items,
+            _policies: policies,
+        })
+    }
+}
+
+/// Keeps the memory referenced by a health query description alive
+/// while the query is submitted.
+pub(crate) struct HealthQueryDescriptionRaw<'a, T> {
+    internal: Box<T>,
+    _cluster_policy: Option<ClusterHealthPolicyRaw<'a>>,
+    _application_policy: Option<ApplicationHealthPolicyRaw<'a>>,
+    _application_policy_map: Option<ApplicationHealthPolicyMapRaw<'a>>,
+    // Filters and filter lists, which are plain ffi structs.
+    _filters: Vec<Box<dyn Any>>,
+}
+
+impl<T> HealthQueryDescriptionRaw<'_, T> {
+    pub(crate) fn as_ffi(&self) -> &T {
+        self.internal.as_ref()
+    }
+}
+
+/// Builds one of the *_HEALTH_STATES_FILTER or the events filter, which all
+/// have the same shape. The returned pointer is valid as long as the box
+/// pushed into the holder lives.
+macro_rules! states_filter {
+    ($filter:expr, $ty:ident, $holder:expr) => {
+        match $filter {
+            Some(f) => {
+                let b = Box::new($ty {
+                    HealthStateFilter: f.bits(),
+                    Reserved: std::ptr::null_mut(),
+                });
+                let ptr: *const $ty = b.as_ref();
+                $holder.push(b as Box<dyn Any>);
+                ptr
+            }
+            None => std::ptr::null(),
+        }
+    };
+}
+
+fn cluster_policy_raw(
+    policy: &Option<ClusterHealthPolicy>,
+) -> (
+    Option<ClusterHealthPolicyRaw<'_>>,
+    *const FABRIC_CLUSTER_HEALTH_POLICY,
+) {
+    let raw = policy.as_ref().map(|p| p.build_raw());
+    let ptr = raw
+        .as_ref()
+        .map_or(std::ptr::null(), |r| r.as_ffi() as *const _);
+    (raw, ptr)
+}
+
+fn application_policy_raw(
+    policy: &Option<ApplicationHealthPolicy>,
+) -> (
+    Option<ApplicationHealthPolicyRaw<'_>>,
+    *const FABRIC_APPLICATION_HEALTH_POLICY,
+) {
+    let raw = policy.as_ref().map(|p| p.build_raw());
+    let ptr = raw
+        .as_ref()
+        .map_or(std::ptr::null(), |r| r.as_ffi() as *const _);
+    (raw, ptr)
+}
+
+/// FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION
+/// The policies left as None use the ones in the cluster manifest.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterHealthQueryDescription {
+    pub health_policy: Option<ClusterHealthPolicy>,
+    pub application_health_policy_map: Vec<(Uri, ApplicationHealthPolicy)>,
+    pub events_filter: Option<HealthStateFilter>,
+    pub nodes_filter: Option<HealthStateFilter>,
+    pub applications_filter: Option<HealthStateFilter>,
+}
+
+impl ClusterHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (cluster_policy, policy_ptr) = cluster_policy_raw(&self.health_policy);
+        let policy_map = ApplicationHealthPolicyMapRaw::new(&self.application_health_policy_map);
+        let internal = Box::new(FABRIC_CLUSTER_HEALTH_QUERY_DESCRIPTION {
+            HealthPolicy: policy_ptr,
+            ApplicationHealthPolicyMap: policy_map
+                .as_ref()
+                .map_or(std::ptr::null(), |m| m.internal.as_ref()),
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            NodesFilter: states_filter!(
+                self.nodes_filter,
+                FABRIC_NODE_HEALTH_STATES_FILTER,
+                filters
+            ),
+            ApplicationsFilter: states_filter!(
+                self.applications_filter,
+                FABRIC_APPLICATION_HEALTH_STATES_FILTER,
+                filters
+            ),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: cluster_policy,
+            _application_policy: None,
+            _application_policy_map: policy_map,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_NODE_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct NodeHealthQueryDescription {
+    pub node_name: WString,
+    pub health_policy: Option<ClusterHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+}
+
+impl NodeHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_NODE_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (cluster_policy, policy_ptr) = cluster_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_NODE_HEALTH_QUERY_DESCRIPTION {
+            NodeName: self.node_name.as_pcwstr(),
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: cluster_policy,
+            _application_policy: None,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+/// FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION
+/// The policy left as None uses the one in the application manifest.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationHealthQueryDescription {
+    pub application_name: Uri,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+    pub services_filter: Option<HealthStateFilter>,
+    pub deployed_applications_filter: Option<HealthStateFilter>,
+}
+
+impl ApplicationHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_APPLICATION_HEALTH_QUERY_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            ServicesFilter: states_filter!(
+                self.services_filter,
+                FABRIC_SERVICE_HEALTH_STATES_FILTER,
+                filters
+            ),
+            DeployedApplicationsFilter: states_filter!(
+                self.deployed_applications_filter,
+                FABRIC_DEPLOYED_APPLICATION_HEALTH_STATES_FILTER,
+                filters
+            ),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ServiceHealthQueryDescription {
+    pub service_name: Uri,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+    pub partitions_filter: Option<HealthStateFilter>,
+}
+
+impl ServiceHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_SERVICE_HEALTH_QUERY_DESCRIPTION {
+            ServiceName: self.service_name.as_raw(),
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            PartitionsFilter: states_filter!(
+                self.partitions_filter,
+                FABRIC_PARTITION_HEALTH_STATES_FILTER,
+                filters
+            ),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct PartitionHealthQueryDescription {
+    pub partition_id: GUID,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+    pub replicas_filter: Option<HealthStateFilter>,
+}
+
+impl PartitionHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_PARTITION_HEALTH_QUERY_DESCRIPTION {
+            PartitionId: self.partition_id,
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            ReplicasFilter: states_filter!(
+                self.replicas_filter,
+                FABRIC_REPLICA_HEALTH_STATES_FILTER,
+                filters
+            ),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct ReplicaHealthQueryDescription {
+    pub partition_id: GUID,
+    pub replica_or_instance_id: i64,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+}
+
+impl ReplicaHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_REPLICA_HEALTH_QUERY_DESCRIPTION {
+            PartitionId: self.partition_id,
+            ReplicaOrInstanceId: self.replica_or_instance_id,
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedApplicationHealthQueryDescription {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+    pub deployed_service_packages_filter: Option<HealthStateFilter>,
+}
+
+impl DeployedApplicationHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION> {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_DEPLOYED_APPLICATION_HEALTH_QUERY_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            NodeName: self.node_name.as_pcwstr(),
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            DeployedServicePackagesFilter: states_filter!(
+                self.deployed_service_packages_filter,
+                FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATES_FILTER,
+                filters
+            ),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION
+#[derive(Debug, Clone, Default)]
+pub struct DeployedServicePackageHealthQueryDescription {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub service_manifest_name: WString,
+    pub health_policy: Option<ApplicationHealthPolicy>,
+    pub events_filter: Option<HealthStateFilter>,
+}
+
+impl DeployedServicePackageHealthQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION>
+    {
+        let mut filters = Vec::new();
+        let (application_policy, policy_ptr) = application_policy_raw(&self.health_policy);
+        let internal = Box::new(FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_QUERY_DESCRIPTION {
+            ApplicationName: self.application_name.as_raw(),
+            NodeName: self.node_name.as_pcwstr(),
+            ServiceManifestName: self.service_manifest_name.as_pcwstr(),
+            HealthPolicy: policy_ptr,
+            EventsFilter: states_filter!(self.events_filter, FABRIC_HEALTH_EVENTS_FILTER, filters),
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: None,
+            _application_policy: application_policy,
+            _application_policy_map: None,
+            _filters: filters,
+        }
+    }
+}
+
+/// FABRIC_NODE_HEALTH_STATE_FILTER
+/// Selects the nodes returned in the cluster health chunk.
+#[derive(Debug, Clone, Default)]
+pub struct NodeHealthStateFilter {
+    pub health_state_filter: HealthStateFilter,
+    /// Only returns the node with this name, if set.
+    pub node_name_filter: Option<WString>,
+}
+
+/// FABRIC_APPLICATION_HEALTH_STATE_FILTER
+/// Selects the applications returned in the cluster health chunk.
+/// The services and deployed applications of the matched applications are not
+/// returned, since the nested filters are not exposed.
+#[derive(Debug, Clone, Default)]
+pub struct ApplicationHealthStateFilter {
+    pub health_state_filter: HealthStateFilter,
+    /// Only returns the application with this name, if set.
+    pub application_name_filter: Option<Uri>,
+}
+
+/// FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION
+/// Nodes and applications are only returned if they match one of the filters.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterHealthChunkQueryDescription {
+    pub cluster_health_policy: Option<ClusterHealthPolicy>,
+    pub application_health_policy_map: Vec<(Uri, ApplicationHealthPolicy)>,
+    pub node_filters: Vec<NodeHealthStateFilter>,
+    pub application_filters: Vec<ApplicationHealthStateFilter>,
+}
+
+impl ClusterHealthChunkQueryDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> HealthQueryDescriptionRaw<'_, FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION> {
+        let mut filters: Vec<Box<dyn Any>> = Vec::new();
+        let (cluster_policy, policy_ptr) = cluster_policy_raw(&self.cluster_health_policy);
+        let policy_map = ApplicationHealthPolicyMapRaw::new(&self.application_health_policy_map);
+
+        let node_filters_ptr = if self.node_filters.is_empty() {
+            std::ptr::null()
+        } else {
+            let items = self
+                .node_filters
+                .iter()
+                .map(|f| FABRIC_NODE_HEALTH_STATE_FILTER {
+                    HealthStateFilter: f.health_state_filter.bits(),
+                    NodeNameFilter: get_pcwstr_from_opt(&f.node_name_filter),
+                    Reserved: std::ptr::null_mut(),
+                })
+                .collect::<Vec<_>>();
+            let list = Box::new(FABRIC_NODE_HEALTH_STATE_FILTER_LIST {
+                Count: items.len() as u32,
+                Items: items.as_ptr(),
+            });
+            let ptr: *const FABRIC_NODE_HEALTH_STATE_FILTER_LIST = list.as_ref();
+            filters.push(Box::new(items));
+            filters.push(list);
+            ptr
+        };
+
+        let application_filters_ptr = if self.application_filters.is_empty() {
+            std::ptr::null()
+        } else {
+            let items = self
+                .application_filters
+                .iter()
+                .map(|f| FABRIC_APPLICATION_HEALTH_STATE_FILTER {
+                    HealthStateFilter: f.health_state_filter.bits(),
+                    ApplicationNameFilter: f
+                        .application_name_filter
+                        .as_ref()
+                        .map_or(FABRIC_URI(std::ptr::null_mut()), |n| n.as_raw()),
+                    ServiceFilters: std::ptr::null(),
+                    DeployedApplicationFilters: std::ptr::null(),
+                    Reserved: std::ptr::null_mut(),
+                })
+                .collect::<Vec<_>>();
+            let list = Box::new(FABRIC_APPLICATION_HEALTH_STATE_FILTER_LIST {
+                Count: items.len() as u32,
+                Items: items.as_ptr(),
+            });
+            let ptr: *const FABRIC_APPLICATION_HEALTH_STATE_FILTER_LIST = list.as_ref();
+            filters.push(Box::new(items));
+            filters.push(list);
+            ptr
+        };
+
+        let internal = Box::new(FABRIC_CLUSTER_HEALTH_CHUNK_QUERY_DESCRIPTION {
+            ClusterHealthPolicy: policy_ptr,
+            ApplicationHealthPolicyMap: policy_map
+                .as_ref()
+                .map_or(std::ptr::null(), |m| m.internal.as_ref()),
+            ApplicationFilters: application_filters_ptr,
+            NodeFilters: node_filters_ptr,
+            Reserved: std::ptr::null_mut(),
+        });
+        HealthQueryDescriptionRaw {
+            internal,
+            _cluster_policy: cluster_policy,
+            _application_policy: None,
+            _application_policy_map: policy_map,
+            _filters: filters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::FABRIC_HEALTH_STATE_FILTER_ERROR;
+
+    use crate::{WString, types::Uri};
+
+    use super::{
+        ApplicationHealthPolicy, ClusterHealthPolicy, ClusterHealthQueryDescription,
+        HealthStateFilter, ServiceTypeHealthPolicy,
+    };
+
+    #[test]
+    fn test_cluster_health_query_description_raw() {
+        let app_policy = ApplicationHealthPolicy {
+            consider_warning_as_error: true,
+            max_percent_unhealthy_deployed_applications: 10,
+            default_service_type_health_policy: None,
+            service_type_health_policy_map: vec![(
+                WString::from("EchoType"),
+                ServiceTypeHealthPolicy {
+                    max_percent_unhealthy_services: 1,
+                    max_percent_unhealthy_partitions_per_service: 2,
+                    max_percent_unhealthy_replicas_per_partition: 3,
+                },
+            )],
+        };
+        let desc = ClusterHealthQueryDescription {
+            health_policy: Some(ClusterHealthPolicy {
+                consider_warning_as_error: false,
+                max_percent_unhealthy_nodes: 20,
+                max_percent_unhealthy_applications: 30,
+                application_type_health_policy_map: vec![(WString::from("AppType"), 40)],
+            }),
+            application_health_policy_map: vec![(Uri::from("fabric:/EchoApp"), app_policy)],
+            events_filter: Some(HealthStateFilter::Error),
+            nodes_filter: None,
+            applications_filter: Some(HealthStateFilter::All),
+        };
+        let raw = desc.build_raw();
+        let ffi = raw.as_ffi();
+
+        let policy = unsafe { ffi.HealthPolicy.as_ref() }.unwrap();
+        assert_eq!(policy.MaxPercentUnhealthyNodes, 20);
+        let ex1 = unsafe {
+            (policy.Reserved as *const mssf_com::FabricTypes::FABRIC_CLUSTER_HEALTH_POLICY_EX1)
+                .as_ref()
+        }
+        .unwrap();
+        let type_map = unsafe { ex1.ApplicationTypeHealthPolicyMap.as_ref() }.unwrap();
+        assert_eq!(type_map.Count, 1);
+        assert_eq!(
+            unsafe { type_map.Items.as_ref() }
+                .unwrap()
+                .MaxPercentUnhealthyApplications,
+            40
+        );
+
+        let app_map = unsafe { ffi.ApplicationHealthPolicyMap.as_ref() }.unwrap();
+        assert_eq!(app_map.Count, 1);
+        let item = unsafe { app_map.Items.as_ref() }.unwrap();
+        let app_policy = unsafe { item.HealthPolicy.as_ref() }.unwrap();
+        assert!(app_policy.ConsiderWarningAsError);
+        let svc_map = unsafe { app_policy.ServiceTypeHealthPolicyMap.as_ref() }.unwrap();
+        let svc_item = unsafe { svc_map.Items.as_ref() }.unwrap();
+        let svc_policy = unsafe { svc_item.ServiceTypeHealthPolicy.as_ref() }.unwrap();
+        assert_eq!(svc_policy.MaxPercentUnhealthyReplicasPerPartition, 3);
+
+        let events = unsafe { ffi.EventsFilter.as_ref() }.unwrap();
+        assert_eq!(
+            events.HealthStateFilter,
+            FABRIC_HEALTH_STATE_FILTER_ERROR.0 as u32
+        );
+        assert!(ffi.NodesFilter.is_null());
+        assert_eq!(
+            unsafe { ffi.ApplicationsFilter.as_ref() }
+                .unwrap()
+                .HealthStateFilter,
+            HealthStateFilter::All.bits()
+        );
+    }
+}
@@ -0,0 +1,1061 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Results of the health queries. The results are copied out of the
+// SF result interfaces, so they do not hold on to any com object.
+
+use std::time::SystemTime;
+
+use mssf_com::FabricTypes::{
+    FABRIC_APPLICATION_HEALTH, FABRIC_APPLICATION_HEALTH_EVALUATION, FABRIC_APPLICATION_HEALTH_EX1,
+    FABRIC_APPLICATION_HEALTH_STATE, FABRIC_APPLICATION_HEALTH_STATE_CHUNK,
+    FABRIC_APPLICATIONS_HEALTH_EVALUATION, FABRIC_CLUSTER_HEALTH, FABRIC_CLUSTER_HEALTH_CHUNK,
+    FABRIC_CLUSTER_HEALTH_EX1, FABRIC_CLUSTER_HEALTH_EX2,
+    FABRIC_DELTA_NODES_CHECK_HEALTH_EVALUATION, FABRIC_DEPLOYED_APPLICATION_HEALTH,
+    FABRIC_DEPLOYED_APPLICATION_HEALTH_EVALUATION, FABRIC_DEPLOYED_APPLICATION_HEALTH_EX1,
+    FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE, FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK,
+    FABRIC_DEPLOYED_APPLICATIONS_HEALTH_EVALUATION, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_EVALUATION, FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_EX1,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE,
+    FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK,
+    FABRIC_DEPLOYED_SERVICE_PACKAGES_HEALTH_EVALUATION, FABRIC_EVENT_HEALTH_EVALUATION,
+    FABRIC_HEALTH_EVALUATION, FABRIC_HEALTH_EVALUATION_KIND_APPLICATION,
+    FABRIC_HEALTH_EVALUATION_KIND_APPLICATIONS, FABRIC_HEALTH_EVALUATION_KIND_DELTA_NODES_CHECK,
+    FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_APPLICATION,
+    FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_APPLICATIONS,
+    FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_SERVICE_PACKAGE,
+    FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_SERVICE_PACKAGES, FABRIC_HEALTH_EVALUATION_KIND_EVENT,
+    FABRIC_HEALTH_EVALUATION_KIND_NODE, FABRIC_HEALTH_EVALUATION_KIND_NODES,
+    FABRIC_HEALTH_EVALUATION_KIND_PARTITION, FABRIC_HEALTH_EVALUATION_KIND_PARTITIONS,
+    FABRIC_HEALTH_EVALUATION_KIND_REPLICA, FABRIC_HEALTH_EVALUATION_KIND_REPLICAS,
+    FABRIC_HEALTH_EVALUATION_KIND_SERVICE, FABRIC_HEALTH_EVALUATION_KIND_SERVICES,
+    FABRIC_HEALTH_EVALUATION_KIND_SYSTEM_APPLICATION,
+    FABRIC_HEALTH_EVALUATION_KIND_UPGRADE_DOMAIN_NODES, FABRIC_HEALTH_EVENT,
+    FABRIC_HEALTH_EVENT_EX1, FABRIC_NODE_HEALTH, FABRIC_NODE_HEALTH_EVALUATION,
+    FABRIC_NODE_HEALTH_EX1, FABRIC_NODE_HEALTH_STATE, FABRIC_NODE_HEALTH_STATE_CHUNK,
+    FABRIC_NODES_HEALTH_EVALUATION, FABRIC_PARTITION_HEALTH, FABRIC_PARTITION_HEALTH_EVALUATION,
+    FABRIC_PARTITION_HEALTH_EX1, FABRIC_PARTITION_HEALTH_STATE,
+    FABRIC_PARTITION_HEALTH_STATE_CHUNK, FABRIC_PARTITIONS_HEALTH_EVALUATION,
+    FABRIC_REPLICA_HEALTH, FABRIC_REPLICA_HEALTH_EVALUATION, FABRIC_REPLICA_HEALTH_STATE,
+    FABRIC_REPLICA_HEALTH_STATE_CHUNK, FABRIC_REPLICAS_HEALTH_EVALUATION, FABRIC_SERVICE_HEALTH,
+    FABRIC_SERVICE_HEALTH_EVALUATION, FABRIC_SERVICE_HEALTH_EX1, FABRIC_SERVICE_HEALTH_STATE,
+    FABRIC_SERVICE_HEALTH_STATE_CHUNK, FABRIC_SERVICE_KIND_STATEFUL, FABRIC_SERVICE_KIND_STATELESS,
+    FABRIC_SERVICES_HEALTH_EVALUATION, FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH,
+    FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_EX1, FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_STATE,
+    FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH, FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_EX1,
+    FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_STATE, FABRIC_SYSTEM_APPLICATION_HEALTH_EVALUATION,
+    FABRIC_UPGRADE_DOMAIN_NODES_HEALTH_EVALUATION,
+};
+
+use crate::{
+    GUID, WString,
+    strings::WStringWrap,
+    types::{HealthInformation, HealthState, Uri},
+};
+
+use super::{
+    chaos::{FILETIME_ZERO, raw_slice},
+    fault::uri_from_raw,
+};
+
+/// Copies a raw list pointer with Count/Items into a Vec.
+/// Null list yields an empty Vec.
+macro_rules! vec_from_raw_list {
+    ($list:expr) => {
+        match unsafe { $list.as_ref() } {
+            Some(l) => unsafe { raw_slice(l.Items, l.Count) }
+                .iter()
+                .map(From::from)
+                .collect(),
+            None => Vec::new(),
+        }
+    };
+}
+
+/// Reads the UnhealthyEvaluations of the EX1 struct chained in Reserved.
+macro_rules! unhealthy_evaluations_ex1 {
+    ($value:expr, $ex1:ty) => {
+        match unsafe { ($value.Reserved as *const $ex1).as_ref() } {
+            Some(ex1) => vec_from_raw_list!(ex1.UnhealthyEvaluations),
+            None => Vec::new(),
+        }
+    };
+}
+
+/// FABRIC_HEALTH_EVENT
+/// A health report as seen by the health store.
+#[derive(Debug, Clone)]
+pub struct HealthEvent {
+    pub health_information: Option<HealthInformation>,
+    pub source_utc_timestamp: SystemTime,
+    pub last_modified_utc_timestamp: SystemTime,
+    pub is_expired: bool,
+    // Zero FILETIME (1601-01-01) if the event never transitioned into the state.
+    pub last_ok_transition_at: SystemTime,
+    pub last_warning_transition_at: SystemTime,
+    pub last_error_transition_at: SystemTime,
+}
+
+impl From<&FABRIC_HEALTH_EVENT> for HealthEvent {
+    fn from(value: &FABRIC_HEALTH_EVENT) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_HEALTH_EVENT_EX1).as_ref() };
+        Self {
+            health_information: unsafe { value.HealthInformation.as_ref() }
+                .map(HealthInformation::from),
+            source_utc_timestamp: value.SourceUtcTimestamp.into(),
+            last_modified_utc_timestamp: value.LastModifiedUtcTimestamp.into(),
+            is_expired: value.IsExpired,
+            last_ok_transition_at: ex1.map_or(FILETIME_ZERO, |e| e.LastOkTransitionAt).into(),
+            last_warning_transition_at: ex1
+                .map_or(FILETIME_ZERO, |e| e.LastWarningTransitionAt)
+                .into(),
+            last_error_transition_at: ex1
+                .map_or(FILETIME_ZERO, |e| e.LastErrorTransitionAt)
+                .into(),
+        }
+    }
+}
+
+/// FABRIC_HEALTH_EVALUATION
+/// Explains why an entity is evaluated as unhealthy. The evaluations form
+/// a tree, with the reports that caused the state as leaves.
+#[derive(Debug, Clone)]
+pub struct HealthEvaluation {
+    pub description: WString,
+    pub aggregated_health_state: HealthState,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+    pub kind: HealthEvaluationKind,
+}
+
+/// The data specific to each FABRIC_HEALTH_EVALUATION_KIND.
+#[derive(Debug, Clone)]
+pub enum HealthEvaluationKind {
+    Invalid,
+    Event {
+        unhealthy_event: Option<HealthEvent>,
+        consider_warning_as_error: bool,
+    },
+    Replicas {
+        total_count: u32,
+        max_percent_unhealthy_replicas_per_partition: u8,
+    },
+    Partitions {
+        total_count: u32,
+        max_percent_unhealthy_partitions_per_service: u8,
+    },
+    DeployedServicePackages {
+        total_count: u32,
+    },
+    DeployedApplications {
+        total_count: u32,
+        max_percent_unhealthy_deployed_applications: u8,
+    },
+    Services {
+        service_type_name: WString,
+        total_count: u32,
+        max_percent_unhealthy_services: u8,
+    },
+    Nodes {
+        total_count: u32,
+        max_percent_unhealthy_nodes: u8,
+    },
+    Applications {
+        total_count: u32,
+        max_percent_unhealthy_applications: u8,
+    },
+    SystemApplication,
+    UpgradeDomainNodes {
+        upgrade_domain_name: WString,
+        total_count: u32,
+        max_percent_unhealthy_nodes: u8,
+    },
+    Node {
+        node_name: WString,
+    },
+    Replica {
+        partition_id: GUID,
+        replica_or_instance_id: i64,
+    },
+    Partition {
+        partition_id: GUID,
+    },
+    Service {
+        service_name: Uri,
+    },
+    DeployedServicePackage {
+        application_name: Uri,
+        service_manifest_name: WString,
+        node_name: WString,
+    },
+    DeployedApplication {
+        application_name: Uri,
+        node_name: WString,
+    },
+    Application {
+        application_name: Uri,
+    },
+    DeltaNodesCheck {
+        baseline_error_count: u32,
+        baseline_total_count: u32,
+        total_count: u32,
+        max_percent_delta_unhealthy_nodes: u8,
+    },
+}
+
+impl From<&FABRIC_HEALTH_EVALUATION> for HealthEvaluation {
+    fn from(value: &FABRIC_HEALTH_EVALUATION) -> Self {
+        // All kinds share description, aggregated state and nested evaluations,
+        // so only the kind specific fields are spelled out per kind.
+        macro_rules! evaluation {
+            ($raw:ty, |$e:ident| $kind:expr) => {
+                match unsafe { (value.Value as *const $raw).as_ref() } {
+                    Some($e) => HealthEvaluation {
+                        description: WStringWrap::from($e.Description).into(),
+                        aggregated_health_state: (&$e.AggregatedHealthState).into(),
+                        unhealthy_evaluations: vec_from_raw_list!($e.UnhealthyEvaluations),
+                        kind: $kind,
+                    },
+                    None => HealthEvaluation::invalid(),
+                }
+            };
+        }
+        match value.Kind {
+            FABRIC_HEALTH_EVALUATION_KIND_EVENT => {
+                match unsafe { (value.Value as *const FABRIC_EVENT_HEALTH_EVALUATION).as_ref() } {
+                    Some(e) => HealthEvaluation {
+                        description: WStringWrap::from(e.Description).into(),
+                        aggregated_health_state: (&e.AggregatedHealthState).into(),
+                        unhealthy_evaluations: Vec::new(),
+                        kind: HealthEvaluationKind::Event {
+                            unhealthy_event: unsafe { e.UnhealthyEvent.as_ref() }
+                                .map(HealthEvent::from),
+                            consider_warning_as_error: e.ConsiderWarningAsError,
+                        },
+                    },
+                    None => HealthEvaluation::invalid(),
+                }
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_REPLICAS => {
+                evaluation!(FABRIC_REPLICAS_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Replicas {
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_replicas_per_partition: e
+                            .MaxPercentUnhealthyReplicasPerPartition,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_PARTITIONS => {
+                evaluation!(FABRIC_PARTITIONS_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Partitions {
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_partitions_per_service: e
+                            .MaxPercentUnhealthyPartitionsPerService,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_SERVICE_PACKAGES => {
+                evaluation!(FABRIC_DEPLOYED_SERVICE_PACKAGES_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::DeployedServicePackages {
+                        total_count: e.TotalCount,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_APPLICATIONS => {
+                evaluation!(FABRIC_DEPLOYED_APPLICATIONS_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::DeployedApplications {
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_deployed_applications: e
+                            .MaxPercentUnhealthyDeployedApplications,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_SERVICES => {
+                evaluation!(FABRIC_SERVICES_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Services {
+                        service_type_name: WStringWrap::from(e.ServiceTypeName).into(),
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_services: e.MaxPercentUnhealthyServices,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_NODES => {
+                evaluation!(FABRIC_NODES_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Nodes {
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_nodes: e.MaxPercentUnhealthyNodes,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_APPLICATIONS => {
+                evaluation!(FABRIC_APPLICATIONS_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Applications {
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_applications: e.MaxPercentUnhealthyApplications,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_SYSTEM_APPLICATION => {
+                evaluation!(FABRIC_SYSTEM_APPLICATION_HEALTH_EVALUATION, |_e| {
+                    HealthEvaluationKind::SystemApplication
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_UPGRADE_DOMAIN_NODES => {
+                evaluation!(FABRIC_UPGRADE_DOMAIN_NODES_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::UpgradeDomainNodes {
+                        upgrade_domain_name: WStringWrap::from(e.UpgradeDomainName).into(),
+                        total_count: e.TotalCount,
+                        max_percent_unhealthy_nodes: e.MaxPercentUnhealthyNodes,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_NODE => {
+                evaluation!(FABRIC_NODE_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Node {
+                        node_name: WStringWrap::from(e.NodeName).into(),
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_REPLICA => {
+                evaluation!(FABRIC_REPLICA_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Replica {
+                        partition_id: e.PartitionId,
+                        replica_or_instance_id: e.ReplicaOrInstanceId,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_PARTITION => {
+                evaluation!(FABRIC_PARTITION_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Partition {
+                        partition_id: e.PartitionId,
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_SERVICE => {
+                evaluation!(FABRIC_SERVICE_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Service {
+                        service_name: uri_from_raw(e.ServiceName),
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_SERVICE_PACKAGE => {
+                evaluation!(FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::DeployedServicePackage {
+                        application_name: uri_from_raw(e.ApplicationName),
+                        service_manifest_name: WStringWrap::from(e.ServiceManifestName).into(),
+                        node_name: WStringWrap::from(e.NodeName).into(),
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_DEPLOYED_APPLICATION => {
+                evaluation!(FABRIC_DEPLOYED_APPLICATION_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::DeployedApplication {
+                        application_name: uri_from_raw(e.ApplicationName),
+                        node_name: WStringWrap::from(e.NodeName).into(),
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_APPLICATION => {
+                evaluation!(FABRIC_APPLICATION_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::Application {
+                        application_name: uri_from_raw(e.ApplicationName),
+                    }
+                })
+            }
+            FABRIC_HEALTH_EVALUATION_KIND_DELTA_NODES_CHECK => {
+                evaluation!(FABRIC_DELTA_NODES_CHECK_HEALTH_EVALUATION, |e| {
+                    HealthEvaluationKind::DeltaNodesCheck {
+                        baseline_error_count: e.BaselineErrorCount,
+                        baseline_total_count: e.BaselineTotalCount,
+                        total_count: e.TotalCount,
+                        max_percent_delta_unhealthy_nodes: e.MaxPercentDeltaUnhealthyNodes,
+                    }
+                })
+            }
+            _ => HealthEvaluation::invalid(),
+        }
+    }
+}
+
+impl HealthEvaluation {
+    fn invalid() -> Self {
+        Self {
+            description: WString::new(),
+            aggregated_health_state: HealthState::Invalid,
+            unhealthy_evaluations: Vec::new(),
+            kind: HealthEvaluationKind::Invalid,
+        }
+    }
+}
+
+// FABRIC_NODE_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct NodeHealthState {
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_NODE_HEALTH_STATE> for NodeHealthState {
+    fn from(value: &FABRIC_NODE_HEALTH_STATE) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct ApplicationHealthState {
+    pub application_name: Uri,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_APPLICATION_HEALTH_STATE> for ApplicationHealthState {
+    fn from(value: &FABRIC_APPLICATION_HEALTH_STATE) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct ServiceHealthState {
+    pub service_name: Uri,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_SERVICE_HEALTH_STATE> for ServiceHealthState {
+    fn from(value: &FABRIC_SERVICE_HEALTH_STATE) -> Self {
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct PartitionHealthState {
+    pub partition_id: GUID,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_PARTITION_HEALTH_STATE> for PartitionHealthState {
+    fn from(value: &FABRIC_PARTITION_HEALTH_STATE) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub enum ReplicaHealthState {
+    Invalid,
+    Stateful {
+        partition_id: GUID,
+        replica_id: i64,
+        aggregated_health_state: HealthState,
+    },
+    Stateless {
+        partition_id: GUID,
+        instance_id: i64,
+        aggregated_health_state: HealthState,
+    },
+}
+
+impl From<&FABRIC_REPLICA_HEALTH_STATE> for ReplicaHealthState {
+    fn from(value: &FABRIC_REPLICA_HEALTH_STATE) -> Self {
+        match value.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => {
+                match unsafe {
+                    (value.Value as *const FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_STATE).as_ref()
+                } {
+                    Some(s) => Self::Stateful {
+                        partition_id: s.PartitionId,
+                        replica_id: s.ReplicaId,
+                        aggregated_health_state: (&s.AggregatedHealthState).into(),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_SERVICE_KIND_STATELESS => {
+                match unsafe {
+                    (value.Value as *const FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_STATE).as_ref()
+                } {
+                    Some(s) => Self::Stateless {
+                        partition_id: s.PartitionId,
+                        instance_id: s.InstanceId,
+                        aggregated_health_state: (&s.AggregatedHealthState).into(),
+                    },
+                    None => Self::Invalid,
+                }
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealthState {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE> for DeployedApplicationHealthState {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthState {
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE> for DeployedServicePackageHealthState {
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+        }
+    }
+}
+
+// FABRIC_CLUSTER_HEALTH
+#[derive(Debug, Clone)]
+pub struct ClusterHealth {
+    pub aggregated_health_state: HealthState,
+    pub node_health_states: Vec<NodeHealthState>,
+    pub application_health_states: Vec<ApplicationHealthState>,
+    pub health_events: Vec<HealthEvent>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_CLUSTER_HEALTH> for ClusterHealth {
+    fn from(value: &FABRIC_CLUSTER_HEALTH) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_CLUSTER_HEALTH_EX1).as_ref() };
+        let ex2 =
+            ex1.and_then(|e| unsafe { (e.Reserved as *const FABRIC_CLUSTER_HEALTH_EX2).as_ref() });
+        Self {
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            node_health_states: ex1.map_or(Vec::new(), |e| vec_from_raw_list!(e.NodeHealthStates)),
+            application_health_states: ex1.map_or(Vec::new(), |e| {
+                vec_from_raw_list!(e.ApplicationHealthStates)
+            }),
+            health_events: ex1.map_or(Vec::new(), |e| vec_from_raw_list!(e.HealthEvents)),
+            unhealthy_evaluations: ex2
+                .map_or(Vec::new(), |e| vec_from_raw_list!(e.UnhealthyEvaluations)),
+        }
+    }
+}
+
+// FABRIC_NODE_HEALTH
+#[derive(Debug, Clone)]
+pub struct NodeHealth {
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_NODE_HEALTH> for NodeHealth {
+    fn from(value: &FABRIC_NODE_HEALTH) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(value, FABRIC_NODE_HEALTH_EX1),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_HEALTH
+#[derive(Debug, Clone)]
+pub struct ApplicationHealth {
+    pub application_name: Uri,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub deployed_application_health_states: Vec<DeployedApplicationHealthState>,
+    pub service_health_states: Vec<ServiceHealthState>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_APPLICATION_HEALTH> for ApplicationHealth {
+    fn from(value: &FABRIC_APPLICATION_HEALTH) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            deployed_application_health_states: vec_from_raw_list!(
+                value.DeployedApplicationHealthStates
+            ),
+            service_health_states: vec_from_raw_list!(value.ServiceHealthStates),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(value, FABRIC_APPLICATION_HEALTH_EX1),
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH
+#[derive(Debug, Clone)]
+pub struct ServiceHealth {
+    pub service_name: Uri,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub partition_health_states: Vec<PartitionHealthState>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_SERVICE_HEALTH> for ServiceHealth {
+    fn from(value: &FABRIC_SERVICE_HEALTH) -> Self {
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            partition_health_states: vec_from_raw_list!(value.PartitionHealthStates),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(value, FABRIC_SERVICE_HEALTH_EX1),
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH
+#[derive(Debug, Clone)]
+pub struct PartitionHealth {
+    pub partition_id: GUID,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub replica_health_states: Vec<ReplicaHealthState>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_PARTITION_HEALTH> for PartitionHealth {
+    fn from(value: &FABRIC_PARTITION_HEALTH) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            replica_health_states: vec_from_raw_list!(value.ReplicaHealthStates),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(value, FABRIC_PARTITION_HEALTH_EX1),
+        }
+    }
+}
+
+// FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH
+#[derive(Debug, Clone)]
+pub struct StatefulServiceReplicaHealth {
+    pub partition_id: GUID,
+    pub replica_id: i64,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH> for StatefulServiceReplicaHealth {
+    fn from(value: &FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            replica_id: value.ReplicaId,
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(
+                value,
+                FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH_EX1
+            ),
+        }
+    }
+}
+
+// FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH
+#[derive(Debug, Clone)]
+pub struct StatelessServiceInstanceHealth {
+    pub partition_id: GUID,
+    pub instance_id: i64,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH> for StatelessServiceInstanceHealth {
+    fn from(value: &FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            instance_id: value.InstanceId,
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(
+                value,
+                FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH_EX1
+            ),
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH
+#[derive(Debug, Clone)]
+pub enum ReplicaHealth {
+    Invalid,
+    Stateful(StatefulServiceReplicaHealth),
+    Stateless(StatelessServiceInstanceHealth),
+}
+
+impl From<&FABRIC_REPLICA_HEALTH> for ReplicaHealth {
+    fn from(value: &FABRIC_REPLICA_HEALTH) -> Self {
+        match value.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => {
+                unsafe { (value.Value as *const FABRIC_STATEFUL_SERVICE_REPLICA_HEALTH).as_ref() }
+                    .map_or(Self::Invalid, |v| Self::Stateful(v.into()))
+            }
+            FABRIC_SERVICE_KIND_STATELESS => {
+                unsafe { (value.Value as *const FABRIC_STATELESS_SERVICE_INSTANCE_HEALTH).as_ref() }
+                    .map_or(Self::Invalid, |v| Self::Stateless(v.into()))
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealth {
+    pub application_name: Uri,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub deployed_service_package_health_states: Vec<DeployedServicePackageHealthState>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_HEALTH> for DeployedApplicationHealth {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_HEALTH) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            deployed_service_package_health_states: vec_from_raw_list!(
+                value.DeployedServicePackageHealthStates
+            ),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(
+                value,
+                FABRIC_DEPLOYED_APPLICATION_HEALTH_EX1
+            ),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealth {
+    pub application_name: Uri,
+    pub service_manifest_name: WString,
+    pub node_name: WString,
+    pub aggregated_health_state: HealthState,
+    pub health_events: Vec<HealthEvent>,
+    pub unhealthy_evaluations: Vec<HealthEvaluation>,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH> for DeployedServicePackageHealth {
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            node_name: WStringWrap::from(value.NodeName).into(),
+            aggregated_health_state: (&value.AggregatedHealthState).into(),
+            health_events: vec_from_raw_list!(value.HealthEvents),
+            unhealthy_evaluations: unhealthy_evaluations_ex1!(
+                value,
+                FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_EX1
+            ),
+        }
+    }
+}
+
+/// A page of health state chunks. total_count is the number of children
+/// that matched the filters, which can exceed the number of items returned.
+#[derive(Debug, Clone)]
+pub struct HealthStateChunkList<T> {
+    pub total_count: u32,
+    pub items: Vec<T>,
+}
+
+impl<T> Default for HealthStateChunkList<T> {
+    fn default() -> Self {
+        Self {
+            total_count: 0,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Chunk lists also carry a TotalCount, so they cannot use vec_from_raw_list.
+macro_rules! chunk_list_from_raw {
+    ($list:expr) => {
+        match unsafe { $list.as_ref() } {
+            Some(l) => HealthStateChunkList {
+                total_count: l.TotalCount,
+                items: unsafe { raw_slice(l.Items, l.Count) }
+                    .iter()
+                    .map(From::from)
+                    .collect(),
+            },
+            None => HealthStateChunkList::default(),
+        }
+    };
+}
+
+// FABRIC_NODE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct NodeHealthStateChunk {
+    pub node_name: WString,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_NODE_HEALTH_STATE_CHUNK> for NodeHealthStateChunk {
+    fn from(value: &FABRIC_NODE_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_REPLICA_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ReplicaHealthStateChunk {
+    pub replica_or_instance_id: i64,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_REPLICA_HEALTH_STATE_CHUNK> for ReplicaHealthStateChunk {
+    fn from(value: &FABRIC_REPLICA_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            replica_or_instance_id: value.ReplicaOrInstanceId,
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_PARTITION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct PartitionHealthStateChunk {
+    pub partition_id: GUID,
+    pub health_state: HealthState,
+    pub replica_health_state_chunks: HealthStateChunkList<ReplicaHealthStateChunk>,
+}
+
+impl From<&FABRIC_PARTITION_HEALTH_STATE_CHUNK> for PartitionHealthStateChunk {
+    fn from(value: &FABRIC_PARTITION_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            partition_id: value.PartitionId,
+            health_state: (&value.HealthState).into(),
+            replica_health_state_chunks: chunk_list_from_raw!(value.ReplicaHealthStateChunks),
+        }
+    }
+}
+
+// FABRIC_SERVICE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ServiceHealthStateChunk {
+    pub service_name: Uri,
+    pub health_state: HealthState,
+    pub partition_health_state_chunks: HealthStateChunkList<PartitionHealthStateChunk>,
+}
+
+impl From<&FABRIC_SERVICE_HEALTH_STATE_CHUNK> for ServiceHealthStateChunk {
+    fn from(value: &FABRIC_SERVICE_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            health_state: (&value.HealthState).into(),
+            partition_health_state_chunks: chunk_list_from_raw!(value.PartitionHealthStateChunks),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct DeployedServicePackageHealthStateChunk {
+    pub service_manifest_name: WString,
+    pub health_state: HealthState,
+}
+
+impl From<&FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK>
+    for DeployedServicePackageHealthStateChunk
+{
+    fn from(value: &FABRIC_DEPLOYED_SERVICE_PACKAGE_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            service_manifest_name: WStringWrap::from(value.ServiceManifestName).into(),
+            health_state: (&value.HealthState).into(),
+        }
+    }
+}
+
+// FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct DeployedApplicationHealthStateChunk {
+    pub node_name: WString,
+    pub health_state: HealthState,
+    pub deployed_service_package_health_state_chunks:
+        HealthStateChunkList<DeployedServicePackageHealthStateChunk>,
+}
+
+impl From<&FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK> for DeployedApplicationHealthStateChunk {
+    fn from(value: &FABRIC_DEPLOYED_APPLICATION_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            health_state: (&value.HealthState).into(),
+            deployed_service_package_health_state_chunks: chunk_list_from_raw!(
+                value.DeployedServicePackageHealthStateChunks
+            ),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_HEALTH_STATE_CHUNK
+#[derive(Debug, Clone)]
+pub struct ApplicationHealthStateChunk {
+    pub application_name: Uri,
+    pub health_state: HealthState,
+    pub service_health_state_chunks: HealthStateChunkList<ServiceHealthStateChunk>,
+    pub deployed_application_health_state_chunks:
+        HealthStateChunkList<DeployedApplicationHealthStateChunk>,
+}
+
+impl From<&FABRIC_APPLICATION_HEALTH_STATE_CHUNK> for ApplicationHealthStateChunk {
+    fn from(value: &FABRIC_APPLICATION_HEALTH_STATE_CHUNK) -> Self {
+        Self {
+            application_name: uri_from_raw(value.ApplicationName),
+            health_state: (&value.HealthState).into(),
+            service_health_state_chunks: chunk_list_from_raw!(value.ServiceHealthStateChunks),
+            deployed_application_health_state_chunks: chunk_list_from_raw!(
+                value.DeployedApplicationHealthStateChunks
+            ),
+        }
+    }
+}
+
+/// FABRIC_CLUSTER_HEALTH_CHUNK
+/// Cluster health state with the children selected by the chunk query filters.
+#[derive(Debug, Clone)]
+pub struct ClusterHealthChunk {
+    pub health_state: HealthState,
+    pub node_health_state_chunks: HealthStateChunkList<NodeHealthStateChunk>,
+    pub application_health_state_chunks: HealthStateChunkList<ApplicationHealthStateChunk>,
+}
+
+impl From<&FABRIC_CLUSTER_HEALTH_CHUNK> for ClusterHealthChunk {
+    fn from(value: &FABRIC_CLUSTER_HEALTH_CHUNK) -> Self {
+        Self {
+            health_state: (&value.HealthState).into(),
+            node_health_state_chunks: chunk_list_from_raw!(value.NodeHealthStateChunks),
+            application_health_state_chunks: chunk_list_from_raw!(
+                value.ApplicationHealthStateChunks
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_EVENT_HEALTH_EVALUATION, FABRIC_HEALTH_EVALUATION,
+        FABRIC_HEALTH_EVALUATION_KIND_EVENT, FABRIC_HEALTH_EVALUATION_KIND_NODE,
+        FABRIC_HEALTH_EVALUATION_LIST, FABRIC_HEALTH_EVENT, FABRIC_HEALTH_INFORMATION,
+        FABRIC_HEALTH_STATE_ERROR, FABRIC_NODE_HEALTH_EVALUATION,
+    };
+
+    use crate::{
+        WString,
+        types::{HealthState, client::chaos::FILETIME_ZERO},
+    };
+
+    use super::{HealthEvaluation, HealthEvaluationKind};
+
+    #[test]
+    fn test_health_evaluation_from_raw() {
+        let source = WString::from("watchdog");
+        let property = WString::from("disk");
+        let desc = WString::from("disk is full");
+        let info = FABRIC_HEALTH_INFORMATION {
+            SourceId: source.as_pcwstr(),
+            Property: property.as_pcwstr(),
+            TimeToLiveSeconds: 30,
+            State: FABRIC_HEALTH_STATE_ERROR,
+            Description: desc.as_pcwstr(),
+            SequenceNumber: 1,
+            RemoveWhenExpired: false,
+            Reserved: std::ptr::null_mut(),
+        };
+        let event = FABRIC_HEALTH_EVENT {
+            HealthInformation: &info,
+            SourceUtcTimestamp: FILETIME_ZERO,
+            LastModifiedUtcTimestamp: FILETIME_ZERO,
+            IsExpired: false,
+            Reserved: std::ptr::null_mut(),
+        };
+        let event_eval = FABRIC_EVENT_HEALTH_EVALUATION {
+            Description: desc.as_pcwstr(),
+            AggregatedHealthState: FABRIC_HEALTH_STATE_ERROR,
+            UnhealthyEvent: &event,
+            ConsiderWarningAsError: false,
+            Reserved: std::ptr::null_mut(),
+        };
+        let children = [FABRIC_HEALTH_EVALUATION {
+            Kind: FABRIC_HEALTH_EVALUATION_KIND_EVENT,
+            Value: &event_eval as *const _ as *mut _,
+        }];
+        let children_list = FABRIC_HEALTH_EVALUATION_LIST {
+            Count: children.len() as u32,
+            Items: children.as_ptr(),
+        };
+        let node_name = WString::from("_Node_0");
+        let node_eval = FABRIC_NODE_HEALTH_EVALUATION {
+            Description: desc.as_pcwstr(),
+            NodeName: node_name.as_pcwstr(),
+            AggregatedHealthState: FABRIC_HEALTH_STATE_ERROR,
+            UnhealthyEvaluations: &children_list,
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_HEALTH_EVALUATION {
+            Kind: FABRIC_HEALTH_EVALUATION_KIND_NODE,
+            Value: &node_eval as *const _ as *mut _,
+        };
+
+        let eval = HealthEvaluation::from(&raw);
+        assert_eq!(eval.aggregated_health_state, HealthState::Error);
+        assert!(matches!(
+            &eval.kind,
+            HealthEvaluationKind::Node { node_name: n } if n == &node_name
+        ));
+        assert_eq!(eval.unhealthy_evaluations.len(), 1);
+        let child = &eval.unhealthy_evaluations[0];
+        assert!(child.unhealthy_evaluations.is_empty());
+        match &child.kind {
+            HealthEvaluationKind::Event {
+                unhealthy_event: Some(e),
+                ..
+            } => {
+                let info = e.health_information.as_ref().unwrap();
+                assert_eq!(info.property, property);
+                assert_eq!(info.state, HealthState::Error);
+            }
+            other => panic!("unexpected kind {other:?}"),
+        }
+    }
+}
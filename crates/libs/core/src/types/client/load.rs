@@ -0,0 +1,505 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Load and placement query types for nodes, the cluster, replicas and applications.
+
+use std::time::SystemTime;
+
+use crate::{GUID, WString};
+use mssf_com::{
+    FabricClient::{
+        IFabricGetApplicationLoadInformationResult, IFabricGetClusterLoadInformationResult,
+        IFabricGetNodeLoadInformationResult, IFabricGetReplicaLoadInformationResult,
+        IFabricGetUnplacedReplicaInformationResult,
+    },
+    FabricTypes::{
+        FABRIC_APPLICATION_LOAD_INFORMATION, FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_APPLICATION_LOAD_METRIC_INFORMATION, FABRIC_CLUSTER_LOAD_INFORMATION,
+        FABRIC_LOAD_METRIC_INFORMATION, FABRIC_LOAD_METRIC_INFORMATION_EX1,
+        FABRIC_LOAD_METRIC_INFORMATION_EX2, FABRIC_LOAD_METRIC_REPORT,
+        FABRIC_NODE_LOAD_INFORMATION, FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_NODE_LOAD_METRIC_INFORMATION, FABRIC_NODE_LOAD_METRIC_INFORMATION_EX1,
+        FABRIC_REPLICA_LOAD_INFORMATION, FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION,
+        FABRIC_UNPLACED_REPLICA_INFORMATION, FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION,
+    },
+};
+
+use super::{LoadMetricReport, NodeId, chaos::raw_slice, fault::uri_from_raw};
+use crate::{
+    iter::{FabricIter, FabricListAccessor},
+    strings::WStringWrap,
+    types::Uri,
+};
+
+// FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct NodeLoadInformationQueryDescription {
+    pub node_name: WString,
+}
+
+impl From<&NodeLoadInformationQueryDescription> for FABRIC_NODE_LOAD_INFORMATION_QUERY_DESCRIPTION {
+    fn from(value: &NodeLoadInformationQueryDescription) -> Self {
+        Self {
+            NodeName: value.node_name.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_NODE_LOAD_METRIC_INFORMATION
+#[derive(Debug, Clone)]
+pub struct NodeLoadMetricInformation {
+    pub name: WString,
+    pub node_capacity: i64,
+    pub node_load: i64,
+    pub node_remaining_capacity: i64,
+    pub is_capacity_violation: bool,
+    pub node_buffered_capacity: i64,
+    pub node_remaining_buffered_capacity: i64,
+}
+
+impl From<&FABRIC_NODE_LOAD_METRIC_INFORMATION> for NodeLoadMetricInformation {
+    fn from(value: &FABRIC_NODE_LOAD_METRIC_INFORMATION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_NODE_LOAD_METRIC_INFORMATION_EX1).as_ref() };
+        Self {
+            name: WStringWrap::from(value.Name).into(),
+            node_capacity: value.NodeCapacity,
+            node_load: value.NodeLoad,
+            node_remaining_capacity: value.NodeRemainingCapacity,
+            is_capacity_violation: value.IsCapacityViolation,
+            node_buffered_capacity: ex1.map_or(0, |e| e.NodeBufferedCapacity),
+            node_remaining_buffered_capacity: ex1.map_or(0, |e| e.NodeRemainingBufferedCapacity),
+        }
+    }
+}
+
+// IFabricGetNodeLoadInformationResult
+pub struct NodeLoadInformation {
+    com: IFabricGetNodeLoadInformationResult,
+}
+
+type NodeLoadMetricInformationIter<'a> = FabricIter<
+    'a,
+    FABRIC_NODE_LOAD_METRIC_INFORMATION,
+    NodeLoadMetricInformation,
+    NodeLoadInformation,
+>;
+
+impl FabricListAccessor<FABRIC_NODE_LOAD_METRIC_INFORMATION> for NodeLoadInformation {
+    fn get_count(&self) -> u32 {
+        unsafe { self.raw().NodeLoadMetricInformation.as_ref() }.map_or(0, |l| l.Count)
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_NODE_LOAD_METRIC_INFORMATION {
+        unsafe { self.raw().NodeLoadMetricInformation.as_ref() }
+            .map_or(std::ptr::null(), |l| l.Items)
+    }
+}
+
+impl NodeLoadInformation {
+    pub fn new(com: IFabricGetNodeLoadInformationResult) -> Self {
+        Self { com }
+    }
+
+    fn raw(&self) -> &FABRIC_NODE_LOAD_INFORMATION {
+        unsafe { self.com.get_NodeLoadInformation().as_ref().unwrap() }
+    }
+
+    pub fn get_node_name(&self) -> WString {
+        WStringWrap::from(self.raw().NodeName).into()
+    }
+
+    /// Iterates the load of each metric on the node.
+    pub fn iter(&self) -> NodeLoadMetricInformationIter<'_> {
+        NodeLoadMetricInformationIter::new(self, self)
+    }
+}
+
+// FABRIC_LOAD_METRIC_INFORMATION
+#[derive(Debug, Clone)]
+pub struct LoadMetricInformation {
+    pub name: WString,
+    pub is_balanced_before: bool,
+    pub is_balanced_after: bool,
+    pub deviation_before: f64,
+    pub deviation_after: f64,
+    pub balancing_threshold: f64,
+    pub action: WString,
+    pub activity_threshold: u32,
+    pub cluster_capacity: i64,
+    pub cluster_load: i64,
+    pub remaining_unbuffered_capacity: i64,
+    pub node_buffer_percentage: f64,
+    pub buffered_capacity: i64,
+    pub remaining_buffered_capacity: i64,
+    pub is_cluster_capacity_violation: bool,
+    pub min_node_load_value: i64,
+    pub min_node_load_node_id: Option<NodeId>,
+    pub max_node_load_value: i64,
+    pub max_node_load_node_id: Option<NodeId>,
+}
+
+impl From<&FABRIC_LOAD_METRIC_INFORMATION> for LoadMetricInformation {
+    fn from(value: &FABRIC_LOAD_METRIC_INFORMATION) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_LOAD_METRIC_INFORMATION_EX1).as_ref() };
+        let ex2 = ex1.and_then(|e| unsafe {
+            (e.Reserved as *const FABRIC_LOAD_METRIC_INFORMATION_EX2).as_ref()
+        });
+        Self {
+            name: WStringWrap::from(value.Name).into(),
+            is_balanced_before: value.IsBalancedBefore,
+            is_balanced_after: value.IsBalancedAfter,
+            deviation_before: value.DeviationBefore,
+            deviation_after: value.DeviationAfter,
+            balancing_threshold: value.BalancingThreshold,
+            action: WStringWrap::from(value.Action).into(),
+            activity_threshold: ex1.map_or(0, |e| e.ActivityThreshold),
+            cluster_capacity: ex1.map_or(0, |e| e.ClusterCapacity),
+            cluster_load: ex1.map_or(0, |e| e.ClusterLoad),
+            remaining_unbuffered_capacity: ex2.map_or(0, |e| e.RemainingUnbufferedCapacity),
+            node_buffer_percentage: ex2.map_or(0.0, |e| e.NodeBufferPercentage),
+            buffered_capacity: ex2.map_or(0, |e| e.BufferedCapacity),
+            remaining_buffered_capacity: ex2.map_or(0, |e| e.RemainingBufferedCapacity),
+            is_cluster_capacity_violation: ex2.is_some_and(|e| e.IsClusterCapacityViolation),
+            min_node_load_value: ex2.map_or(0, |e| e.MinNodeLoadValue),
+            min_node_load_node_id: ex2.map(|e| e.MinNodeLoadNodeId.into()),
+            max_node_load_value: ex2.map_or(0, |e| e.MaxNodeLoadValue),
+            max_node_load_node_id: ex2.map(|e| e.MaxNodeLoadNodeId.into()),
+        }
+    }
+}
+
+// IFabricGetClusterLoadInformationResult
+pub struct ClusterLoadInformation {
+    com: IFabricGetClusterLoadInformationResult,
+}
+
+type LoadMetricInformationIter<'a> =
+    FabricIter<'a, FABRIC_LOAD_METRIC_INFORMATION, LoadMetricInformation, ClusterLoadInformation>;
+
+impl FabricListAccessor<FABRIC_LOAD_METRIC_INFORMATION> for ClusterLoadInformation {
+    fn get_count(&self) -> u32 {
+        unsafe { self.raw().LoadMetricInformation.as_ref() }.map_or(0, |l| l.Count)
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_LOAD_METRIC_INFORMATION {
+        unsafe { self.raw().LoadMetricInformation.as_ref() }.map_or(std::ptr::null(), |l| l.Items)
+    }
+}
+
+impl ClusterLoadInformation {
+    pub fn new(com: IFabricGetClusterLoadInformationResult) -> Self {
+        Self { com }
+    }
+
+    fn raw(&self) -> &FABRIC_CLUSTER_LOAD_INFORMATION {
+        unsafe { self.com.get_ClusterLoadInformation().as_ref().unwrap() }
+    }
+
+    pub fn get_last_balancing_start_time_utc(&self) -> SystemTime {
+        self.raw().LastBalancingStartTimeUtc.into()
+    }
+
+    pub fn get_last_balancing_end_time_utc(&self) -> SystemTime {
+        self.raw().LastBalancingEndTimeUtc.into()
+    }
+
+    /// Iterates the balancing information of each metric in the cluster.
+    pub fn iter(&self) -> LoadMetricInformationIter<'_> {
+        LoadMetricInformationIter::new(self, self)
+    }
+}
+
+// FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct ReplicaLoadInformationQueryDescription {
+    pub partition_id: GUID,
+    pub replica_or_instance_id: i64,
+}
+
+impl From<&ReplicaLoadInformationQueryDescription>
+    for FABRIC_REPLICA_LOAD_INFORMATION_QUERY_DESCRIPTION
+{
+    fn from(value: &ReplicaLoadInformationQueryDescription) -> Self {
+        Self {
+            PartitionId: value.partition_id,
+            ReplicaOrInstanceId: value.replica_or_instance_id,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// IFabricGetReplicaLoadInformationResult
+pub struct ReplicaLoadInformation {
+    com: IFabricGetReplicaLoadInformationResult,
+}
+
+type ReplicaLoadMetricReportIter<'a> =
+    FabricIter<'a, FABRIC_LOAD_METRIC_REPORT, LoadMetricReport, ReplicaLoadInformation>;
+
+impl FabricListAccessor<FABRIC_LOAD_METRIC_REPORT> for ReplicaLoadInformation {
+    fn get_count(&self) -> u32 {
+        unsafe { self.raw().LoadMetricReports.as_ref() }.map_or(0, |l| l.Count)
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_LOAD_METRIC_REPORT {
+        unsafe { self.raw().LoadMetricReports.as_ref() }.map_or(std::ptr::null(), |l| l.Items)
+    }
+}
+
+impl ReplicaLoadInformation {
+    pub fn new(com: IFabricGetReplicaLoadInformationResult) -> Self {
+        Self { com }
+    }
+
+    fn raw(&self) -> &FABRIC_REPLICA_LOAD_INFORMATION {
+        unsafe { self.com.get_ReplicaLoadInformation().as_ref().unwrap() }
+    }
+
+    pub fn get_partition_id(&self) -> GUID {
+        self.raw().PartitionId
+    }
+
+    pub fn get_replica_or_instance_id(&self) -> i64 {
+        self.raw().ReplicaOrInstanceId
+    }
+
+    /// Iterates the load reported by the replica.
+    pub fn iter(&self) -> ReplicaLoadMetricReportIter<'_> {
+        ReplicaLoadMetricReportIter::new(self, self)
+    }
+}
+
+// FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct ApplicationLoadInformationQueryDescription {
+    pub application_name: Uri,
+}
+
+impl From<&ApplicationLoadInformationQueryDescription>
+    for FABRIC_APPLICATION_LOAD_INFORMATION_QUERY_DESCRIPTION
+{
+    fn from(value: &ApplicationLoadInformationQueryDescription) -> Self {
+        Self {
+            // Unlike other queries the name is declared as a plain string.
+            ApplicationName: value.application_name.0.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_APPLICATION_LOAD_METRIC_INFORMATION
+#[derive(Debug, Clone)]
+pub struct ApplicationLoadMetricInformation {
+    pub name: WString,
+    pub reservation_capacity: i64,
+    pub application_capacity: i64,
+    pub application_load: i64,
+}
+
+impl From<&FABRIC_APPLICATION_LOAD_METRIC_INFORMATION> for ApplicationLoadMetricInformation {
+    fn from(value: &FABRIC_APPLICATION_LOAD_METRIC_INFORMATION) -> Self {
+        Self {
+            name: WStringWrap::from(value.Name).into(),
+            reservation_capacity: value.ReservationCapacity,
+            application_capacity: value.ApplicationCapacity,
+            application_load: value.ApplicationLoad,
+        }
+    }
+}
+
+// IFabricGetApplicationLoadInformationResult
+pub struct ApplicationLoadInformation {
+    com: IFabricGetApplicationLoadInformationResult,
+}
+
+type ApplicationLoadMetricInformationIter<'a> = FabricIter<
+    'a,
+    FABRIC_APPLICATION_LOAD_METRIC_INFORMATION,
+    ApplicationLoadMetricInformation,
+    ApplicationLoadInformation,
+>;
+
+impl FabricListAccessor<FABRIC_APPLICATION_LOAD_METRIC_INFORMATION> for ApplicationLoadInformation {
+    fn get_count(&self) -> u32 {
+        unsafe { self.raw().ApplicationLoadMetricInformation.as_ref() }.map_or(0, |l| l.Count)
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_APPLICATION_LOAD_METRIC_INFORMATION {
+        unsafe { self.raw().ApplicationLoadMetricInformation.as_ref() }
+            .map_or(std::ptr::null(), |l| l.LoadMetrics)
+    }
+}
+
+impl ApplicationLoadInformation {
+    pub fn new(com: IFabricGetApplicationLoadInformationResult) -> Self {
+        Self { com }
+    }
+
+    fn raw(&self) -> &FABRIC_APPLICATION_LOAD_INFORMATION {
+        unsafe { self.com.get_ApplicationLoadInformation().as_ref().unwrap() }
+    }
+
+    pub fn get_name(&self) -> WString {
+        WStringWrap::from(self.raw().Name).into()
+    }
+
+    pub fn get_minimum_nodes(&self) -> u32 {
+        self.raw().MinimumNodes
+    }
+
+    pub fn get_maximum_nodes(&self) -> u32 {
+        self.raw().MaximumNodes
+    }
+
+    pub fn get_node_count(&self) -> u32 {
+        self.raw().NodeCount
+    }
+
+    /// Iterates the load of each metric defined for the application.
+    pub fn iter(&self) -> ApplicationLoadMetricInformationIter<'_> {
+        ApplicationLoadMetricInformationIter::new(self, self)
+    }
+}
+
+// FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct UnplacedReplicaInformationQueryDescription {
+    pub service_name: Uri,
+    pub partition_id: Option<GUID>,
+    pub only_query_primaries: bool,
+}
+
+impl From<&UnplacedReplicaInformationQueryDescription>
+    for FABRIC_UNPLACED_REPLICA_INFORMATION_QUERY_DESCRIPTION
+{
+    fn from(value: &UnplacedReplicaInformationQueryDescription) -> Self {
+        Self {
+            ServiceName: value.service_name.as_raw(),
+            PartitionId: value.partition_id.unwrap_or(GUID::zeroed()),
+            OnlyQueryPrimaries: value.only_query_primaries,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_UNPLACED_REPLICA_INFORMATION
+#[derive(Debug, Clone)]
+pub struct UnplacedReplicaInformation {
+    pub service_name: Uri,
+    pub partition_id: GUID,
+    pub unplaced_replica_reasons: Vec<WString>,
+}
+
+impl From<&FABRIC_UNPLACED_REPLICA_INFORMATION> for UnplacedReplicaInformation {
+    fn from(value: &FABRIC_UNPLACED_REPLICA_INFORMATION) -> Self {
+        let reasons = unsafe { value.UnplacedReplicaReasons.as_ref() }
+            .map(|l| unsafe { raw_slice(l.Items, l.Count) })
+            .unwrap_or_default();
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            partition_id: value.PartitionId,
+            unplaced_replica_reasons: reasons
+                .iter()
+                .map(|r| WStringWrap::from(*r).into())
+                .collect(),
+        }
+    }
+}
+
+impl From<IFabricGetUnplacedReplicaInformationResult> for UnplacedReplicaInformation {
+    fn from(com: IFabricGetUnplacedReplicaInformationResult) -> Self {
+        unsafe { com.get_UnplacedReplicaInformation().as_ref().unwrap() }.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{FABRIC_NODE_ID, FABRIC_STRING_LIST};
+
+    use super::*;
+    use crate::PCWSTR;
+
+    #[test]
+    fn test_load_metric_information_from_raw() {
+        let name = WString::from("CPU");
+        let action = WString::from("NoActionNeeded");
+        let mut ex2 = FABRIC_LOAD_METRIC_INFORMATION_EX2 {
+            RemainingUnbufferedCapacity: 5,
+            NodeBufferPercentage: 0.1,
+            BufferedCapacity: 9,
+            RemainingBufferedCapacity: 4,
+            IsClusterCapacityViolation: false,
+            MinNodeLoadValue: 1,
+            MinNodeLoadNodeId: FABRIC_NODE_ID {
+                Low: 1,
+                High: 0,
+                Reserved: std::ptr::null_mut(),
+            },
+            MaxNodeLoadValue: 3,
+            MaxNodeLoadNodeId: FABRIC_NODE_ID {
+                Low: 2,
+                High: 0,
+                Reserved: std::ptr::null_mut(),
+            },
+            Reserved: std::ptr::null_mut(),
+        };
+        let mut ex1 = FABRIC_LOAD_METRIC_INFORMATION_EX1 {
+            ActivityThreshold: 7,
+            ClusterCapacity: 10,
+            ClusterLoad: 6,
+            Reserved: &mut ex2 as *mut _ as *mut std::ffi::c_void,
+        };
+        let raw = FABRIC_LOAD_METRIC_INFORMATION {
+            Name: name.as_pcwstr(),
+            IsBalancedBefore: false,
+            IsBalancedAfter: true,
+            DeviationBefore: 1.5,
+            DeviationAfter: 0.5,
+            BalancingThreshold: 2.0,
+            Action: action.as_pcwstr(),
+            Reserved: &mut ex1 as *mut _ as *mut std::ffi::c_void,
+        };
+        let info = LoadMetricInformation::from(&raw);
+        assert_eq!(info.name, name);
+        assert!(info.is_balanced_after);
+        assert_eq!(info.cluster_load, 6);
+        assert_eq!(info.max_node_load_value, 3);
+        assert_eq!(info.max_node_load_node_id.unwrap().low, 2);
+
+        // Older runtimes do not fill in the extensions.
+        let raw = FABRIC_LOAD_METRIC_INFORMATION {
+            Reserved: std::ptr::null_mut(),
+            ..raw
+        };
+        let info = LoadMetricInformation::from(&raw);
+        assert_eq!(info.cluster_capacity, 0);
+        assert!(info.min_node_load_node_id.is_none());
+    }
+
+    #[test]
+    fn test_unplaced_replica_information_from_raw() {
+        let svc = Uri::from("fabric:/app/svc");
+        let reasons = [WString::from("a"), WString::from("b")];
+        let items = reasons
+            .iter()
+            .map(|r| r.as_pcwstr())
+            .collect::<Vec<PCWSTR>>();
+        let list = FABRIC_STRING_LIST {
+            Count: items.len() as u32,
+            Items: items.as_ptr(),
+        };
+        let raw = FABRIC_UNPLACED_REPLICA_INFORMATION {
+            ServiceName: svc.as_raw(),
+            PartitionId: GUID::zeroed(),
+            UnplacedReplicaReasons: &list,
+            Reserved: std::ptr::null_mut(),
+        };
+        let info = UnplacedReplicaInformation::from(&raw);
+        assert_eq!(info.service_name, svc);
+        assert_eq!(info.unplaced_replica_reasons, reasons.to_vec());
+    }
+}
@@ -9,7 +9,8 @@ use std::time::SystemTime;
 
 use crate::WString;
 use mssf_com::{
-    FabricClient::IFabricGetPartitionLoadInformationResult, FabricTypes::FABRIC_LOAD_METRIC_REPORT,
+    FabricClient::IFabricGetPartitionLoadInformationResult,
+    FabricTypes::{FABRIC_LOAD_METRIC_REPORT, FABRIC_LOAD_METRIC_REPORT_LIST},
 };
 
 use crate::{
@@ -17,12 +18,14 @@ use crate::{
     strings::WStringWrap,
 };
 
+use super::chaos::raw_slice;
+
 /// Wrapper for FABRIC_LOAD_METRIC_REPORT
 #[derive(Debug, Clone)]
 pub struct LoadMetricReport {
     pub name: WString,
     pub value: u32,
-    pub last_reported_utc: std::time::SystemTime,
+    pub last_reported_utc: SystemTime,
 }
 
 impl From<&FABRIC_LOAD_METRIC_REPORT> for LoadMetricReport {
@@ -30,7 +33,25 @@ impl From<&FABRIC_LOAD_METRIC_REPORT> for LoadMetricReport {
         Self {
             name: WStringWrap::from(value.Name).into(),
             value: value.Value,
-            last_reported_utc: SystemTime::UNIX_EPOCH, // TODO: convert Win32 FILETIME to SystemTime in Unix or Win32 depending on the platform
+            last_reported_utc: value.LastReportedUtc.into(),
+        }
+    }
+}
+
+/// FABRIC_LOAD_METRIC_REPORT_LIST
+/// Reports copied out of a query result.
+#[derive(Debug, Clone, Default)]
+pub struct LoadMetricReportList {
+    pub items: Vec<LoadMetricReport>,
+}
+
+impl From<&FABRIC_LOAD_METRIC_REPORT_LIST> for LoadMetricReportList {
+    fn from(value: &FABRIC_LOAD_METRIC_REPORT_LIST) -> Self {
+        Self {
+            items: unsafe { raw_slice(value.Items, value.Count) }
+                .iter()
+                .map(LoadMetricReport::from)
+                .collect(),
         }
     }
 }
@@ -125,3 +146,30 @@ type PrimaryLoadMetricReportListIter<'a> =
     FabricIter<'a, FABRIC_LOAD_METRIC_REPORT, LoadMetricReport, PrimaryLoadMetricReportList>;
 type SecondaryLoadMetricReportListIter<'a> =
     FabricIter<'a, FABRIC_LOAD_METRIC_REPORT, LoadMetricReport, SecondaryLoadMetricReportList>;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use windows_core::Win32::Foundation::FILETIME;
+
+    use super::*;
+
+    #[test]
+    fn test_load_metric_report_time() {
+        let name = WString::from("CPU");
+        // 2024-01-01T00:00:00Z
+        let raw = FABRIC_LOAD_METRIC_REPORT {
+            Name: name.as_pcwstr(),
+            Value: 3,
+            LastReportedUtc: FILETIME::from_ticks(133_485_408_000_000_000),
+            Reserved: std::ptr::null_mut(),
+        };
+        let report = LoadMetricReport::from(&raw);
+        assert_eq!(report.name, name);
+        assert_eq!(
+            report.last_reported_utc,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1_704_067_200)
+        );
+    }
+}
@@ -32,19 +32,110 @@ mod metrics;
 pub use metrics::*;
 mod health;
 pub use health::*;
+mod health_policy;
+pub use health_policy::*;
+mod health_result;
+pub use health_result::*;
 mod settings;
 pub use settings::*;
+mod upgrade;
+pub use upgrade::{
+    MonitoredUpgradeFailureAction, RollingUpgradeMode, RollingUpgradeMonitoringPolicy,
+    RollingUpgradePolicyDescription, UpgradeDomainState, UpgradeDomainStatus, UpgradeFailureReason,
+};
+mod cluster;
+pub use cluster::{
+    FabricUpgradeDescription, FabricUpgradeProgress, FabricUpgradeState, NodeDeactivationIntent,
+};
+mod fault;
+pub use fault::{
+    CancelTestCommandDescription, DataLossMode, DeployedCodePackageResult, MovePrimaryDescription,
+    MovePrimaryResult, MoveSecondaryDescription, MoveSecondaryResult, NodeResult,
+    PartitionFaultProgress, PartitionFaultResult, PartitionSelector, QuorumLossMode,
+    RestartDeployedCodePackageDescription, RestartNodeDescription, RestartPartitionMode,
+    SelectedPartition, StartNodeDescription, StartPartitionDataLossDescription,
+    StartPartitionQuorumLossDescription, StartPartitionRestartDescription, StopNodeDescription,
+    TestCommandProgressState,
+};
+mod chaos;
+pub use chaos::{
+    ChaosDescription, ChaosEvent, ChaosEventsFilter, ChaosEventsSegment, ChaosParameters,
+    ChaosReport, ChaosSchedule, ChaosScheduleDescription, ChaosScheduleJob,
+    ChaosScheduleJobActiveDays, ChaosScheduleStatus, ChaosScheduleTimeRangeUtc,
+    ChaosScheduleTimeUtc, ChaosStatus, ChaosTargetFilter,
+};
+mod repair;
+pub use repair::{
+    RepairApproveDescription, RepairCancelDescription, RepairDeleteDescription,
+    RepairExecutorState, RepairImpact, RepairNodeImpact, RepairNodeImpactLevel,
+    RepairResultDescription, RepairTarget, RepairTask, RepairTaskFlags, RepairTaskHealthCheckState,
+    RepairTaskHealthPolicyUpdateDescription, RepairTaskHistory, RepairTaskList,
+    RepairTaskQueryDescription, RepairTaskResult, RepairTaskState, RepairTaskStateFilter,
+};
+mod application;
+pub use application::{
+    ApplicationCapacityDescription, ApplicationDescription, ApplicationMetricDescription,
+    ApplicationPackageCleanupPolicy, ApplicationParameter, ApplicationUpdateDescription,
+    ApplicationUpdateDescriptionFlags, ApplicationUpgradeDescription, ApplicationUpgradeProgress,
+    ApplicationUpgradeState, DeleteApplicationDescription,
+    ExternalStoreProvisionApplicationTypeDescription,
+    ImageStoreProvisionApplicationTypeDescription, ProvisionApplicationTypeDescription,
+    UnprovisionApplicationTypeDescription,
+};
 mod property;
 pub use property::{
-    EnumerationStatus, NameEnumerationResult, PropertyMetadataResult, PropertyTypeId,
-    PropertyValueResult,
+    EnumerationStatus, NameEnumerationResult, NamedPropertyMetadata, PropertyBatchOperation,
+    PropertyBatchResult, PropertyEnumerationResult, PropertyMetadataResult, PropertyTypeId,
+    PropertyValue, PropertyValueResult, PutCustomPropertyOperation,
 };
 
 mod service;
 pub use service::{
     NamedRepartitionDescription, ServiceDescription, ServiceRepartitionDescription,
     ServiceUpdateDescription, StatefulServiceDescription, StatefulServiceUpdateDescription,
-    StatelessServiceDescription, StatelessServiceUpdateDescription,
+    StatefulServiceUpdateDescriptionFlags, StatelessServiceDescription,
+    StatelessServiceUpdateDescription, StatelessServiceUpdateDescriptionFlags,
+};
+mod service_group;
+pub(crate) use service_group::ServiceGroupUpdateDescriptionRaw;
+pub use service_group::{ServiceGroupDescription, ServiceGroupMemberDescription};
+mod service_policy;
+pub use service_policy::{
+    ScalingMechanism, ScalingTrigger, ServiceCorrelationDescription, ServiceCorrelationScheme,
+    ServiceLoadMetricDescription, ServiceLoadMetricWeight, ServicePlacementPolicyDescription,
+    ServiceScalingPolicy,
+};
+
+mod application_query;
+pub use application_query::{
+    ApplicationList, ApplicationQueryDescription, ApplicationQueryResult, ApplicationStatus,
+    ApplicationType, ApplicationTypeList, ApplicationTypeStatus,
+    PagedApplicationTypeQueryDescription, ProvisionedCodeVersion, ProvisionedCodeVersionList,
+    ProvisionedCodeVersionQueryDescription, ProvisionedConfigVersion, ProvisionedConfigVersionList,
+    ProvisionedConfigVersionQueryDescription,
+};
+mod service_query;
+pub use service_query::{
+    QueryServiceStatus, ServiceList, ServiceQueryDescription, ServiceQueryResult, ServiceTypeList,
+    ServiceTypeQueryDescription, ServiceTypeQueryResult, StatefulServiceQueryResult,
+    StatelessServiceQueryResult,
+};
+mod deployed;
+pub use deployed::{
+    CodePackageEntryPoint, DeployedApplication, DeployedApplicationList, DeployedCodePackage,
+    DeployedCodePackageList, DeployedCodePackageQueryDescription, DeployedServicePackage,
+    DeployedServicePackageList, DeployedServicePackageQueryDescription, DeployedServiceReplicaList,
+    DeployedServiceReplicaQueryDescription, DeployedServiceReplicaQueryResult,
+    DeployedStatefulServiceReplicaQueryResult, DeploymentStatus, EntryPointStatus,
+    HostIsolationMode, HostType, PagedDeployedApplicationQueryDescription,
+};
+mod load;
+pub use load::{
+    ApplicationLoadInformation, ApplicationLoadInformationQueryDescription,
+    ApplicationLoadMetricInformation, ClusterLoadInformation, LoadMetricInformation,
+    NodeLoadInformation, NodeLoadInformationQueryDescription, NodeLoadMetricInformation,
+    ReplicaLoadInformation, ReplicaLoadInformationQueryDescription, UnplacedReplicaInformation,
+    UnplacedReplicaInformationQueryDescription,
 };
 
 // FABRIC_SERVICE_NOTIFICATION_FILTER_FLAGS
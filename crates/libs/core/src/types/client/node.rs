@@ -34,14 +34,14 @@ impl From<&FABRIC_PAGING_STATUS> for PagingStatus {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct PagedQueryDescription {
     pub continuation_token: Option<WString>,
     pub max_results: Option<i32>,
 }
 
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     pub struct NodeStatusFilter: u32{
         const All = FABRIC_QUERY_NODE_STATUS_FILTER_ALL.0 as u32;
         const Default = FABRIC_QUERY_NODE_STATUS_FILTER_DEFAULT.0 as u32;
@@ -61,7 +61,7 @@ impl Default for NodeStatusFilter {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct NodeQueryDescription {
     pub node_name_filter: Option<WString>,
     pub node_status_filter: NodeStatusFilter,
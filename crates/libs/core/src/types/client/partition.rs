@@ -26,14 +26,19 @@ use crate::{
     types::{HealthState, ServicePartitionInformation},
 };
 
-use super::metrics::{PrimaryLoadMetricReportList, SecondaryLoadMetricReportList};
+use super::{
+    PagingStatus,
+    metrics::{PrimaryLoadMetricReportList, SecondaryLoadMetricReportList},
+};
 
 // Partition related types
 // FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION
+#[derive(Debug, Clone)]
 pub struct ServicePartitionQueryDescription {
     pub service_name: WString,
     pub partition_id_filter: Option<GUID>,
-    // TODO: continuation token
+    /// Passed in FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION_EX1 by the query client.
+    pub continuation_token: Option<WString>,
 }
 
 impl From<&ServicePartitionQueryDescription> for FABRIC_SERVICE_PARTITION_QUERY_DESCRIPTION {
@@ -81,6 +86,11 @@ impl ServicePartitionList {
     pub fn iter(&self) -> ServicePartitionListIter<'_> {
         ServicePartitionListIter::new(self, self)
     }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
 }
 
 // FABRIC_SERVICE_PARTITION_QUERY_RESULT_ITEM
@@ -3,20 +3,33 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
+use std::{ffi::c_void, marker::PhantomData, time::SystemTime};
+
 use mssf_com::{
-    FabricClient::IFabricNameEnumerationResult,
+    FabricClient::{
+        IFabricNameEnumerationResult, IFabricPropertyBatchResult, IFabricPropertyEnumerationResult,
+    },
     FabricTypes::{
+        FABRIC_CHECK_EXISTS_PROPERTY_OPERATION, FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION,
+        FABRIC_CHECK_VALUE_PROPERTY_OPERATION, FABRIC_DELETE_PROPERTY_OPERATION,
         FABRIC_ENUMERATION_BEST_EFFORT_FINISHED, FABRIC_ENUMERATION_BEST_EFFORT_MASK,
         FABRIC_ENUMERATION_BEST_EFFORT_MORE_DATA, FABRIC_ENUMERATION_CONSISTENT_FINISHED,
         FABRIC_ENUMERATION_CONSISTENT_MASK, FABRIC_ENUMERATION_CONSISTENT_MORE_DATA,
         FABRIC_ENUMERATION_FINISHED_MASK, FABRIC_ENUMERATION_INVALID,
         FABRIC_ENUMERATION_MORE_DATA_MASK, FABRIC_ENUMERATION_STATUS,
-        FABRIC_NAMED_PROPERTY_METADATA,
+        FABRIC_GET_PROPERTY_OPERATION, FABRIC_NAMED_PROPERTY_METADATA,
+        FABRIC_NAMED_PROPERTY_METADATA_EX1, FABRIC_OPERATION_DATA_BUFFER,
+        FABRIC_PROPERTY_BATCH_OPERATION, FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_EXISTS,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_VALUE,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_DELETE, FABRIC_PROPERTY_BATCH_OPERATION_KIND_GET,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT, FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT_CUSTOM,
+        FABRIC_PUT_CUSTOM_PROPERTY_OPERATION, FABRIC_PUT_PROPERTY_OPERATION,
     },
 };
-use windows_core::WString;
+use windows_core::{PCWSTR, WString};
 
-use crate::{strings::FabricStringListAccessorIter, types::Uri};
+use crate::{error::ErrorCode, strings::FabricStringListAccessorIter, types::Uri};
 
 pub struct NameEnumerationResult {
     com: IFabricNameEnumerationResult,
@@ -63,6 +76,17 @@ pub enum EnumerationStatus {
     MoreDataMask,
 }
 
+impl EnumerationStatus {
+    /// True if there is no more data to enumerate, i.e. the continuation
+    /// result should not be passed back for the next page.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            EnumerationStatus::BestEffortFinished | EnumerationStatus::ConsistentFinished
+        )
+    }
+}
+
 impl From<FABRIC_ENUMERATION_STATUS> for EnumerationStatus {
     fn from(value: FABRIC_ENUMERATION_STATUS) -> Self {
         match value {
@@ -80,25 +104,63 @@ impl From<FABRIC_ENUMERATION_STATUS> for EnumerationStatus {
     }
 }
 
+/// A page of properties under a SF name.
+/// Pass it back to enumerate_properties to get the next page.
+pub struct PropertyEnumerationResult {
+    com: IFabricPropertyEnumerationResult,
+}
+
+impl PropertyEnumerationResult {
+    pub(crate) fn from_com(com: IFabricPropertyEnumerationResult) -> Self {
+        Self { com }
+    }
+
+    pub(crate) fn as_com(&self) -> &IFabricPropertyEnumerationResult {
+        &self.com
+    }
+
+    pub fn get_enumeration_status(&self) -> EnumerationStatus {
+        unsafe { self.com.get_EnumerationStatus().into() }
+    }
+
+    pub fn get_property_count(&self) -> u32 {
+        unsafe { self.com.get_PropertyCount() }
+    }
+
+    /// Gets the property at index in this page.
+    /// The value is empty if the enumeration did not include values.
+    pub fn get_property(&self, index: u32) -> crate::Result<PropertyValueResult> {
+        let com = unsafe { self.com.GetProperty(index) }?;
+        Ok(PropertyValueResult::from_com(com))
+    }
+}
+
 /// Metadata for a named property.
 pub struct NamedPropertyMetadata {
     pub property_name: WString,
     pub property_type_id: PropertyTypeId,
     pub value_size: i32,
     pub sequence_number: i64,
-    pub last_modified_utc: windows_core::Win32::Foundation::FILETIME,
+    pub last_modified_utc: SystemTime,
     pub name: Uri,
+    /// Custom type id set by put_custom_property, None otherwise.
+    pub custom_type_id: Option<WString>,
 }
 
 impl NamedPropertyMetadata {
     pub(crate) fn from_raw(ptr: &FABRIC_NAMED_PROPERTY_METADATA) -> Self {
+        let ex1 = unsafe { (ptr.Reserved as *const FABRIC_NAMED_PROPERTY_METADATA_EX1).as_ref() };
+        let custom_type_id = ex1
+            .map(|ex1| WString::from(ex1.CustomTypeId))
+            .filter(|id| !id.is_empty());
         Self {
             property_name: ptr.PropertyName.into(),
             property_type_id: ptr.TypeId.into(),
             value_size: ptr.ValueSize,
             sequence_number: ptr.SequenceNumber,
-            last_modified_utc: ptr.LastModifiedUtc,
+            last_modified_utc: ptr.LastModifiedUtc.into(),
             name: Uri::new(windows_core::PCWSTR(ptr.Name.0).into()),
+            custom_type_id,
         }
     }
 }
@@ -150,20 +212,347 @@ impl From<PropertyTypeId> for mssf_com::FabricTypes::FABRIC_PROPERTY_TYPE_ID {
     }
 }
 
-// TODO: batch operations is net yet supported.
-// // FABRIC_PROPERTY_BATCH_OPERATION
-// pub enum PropertyBatchOperation {
-//     Invalid,
-//     Put{name: WString, type_id: PropertyTypeId, value: WString},
-//     Get,
-//     CheckExists,
-//     CheckSequence,
-//     Delete,
-//     PutCustom,
-//     CheckValue,
-// }
+/// Typed value of a named property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyValue {
+    Binary(Vec<u8>),
+    Int64(i64),
+    Double(f64),
+    WString(WString),
+    Guid(windows_core::GUID),
+}
 
-// FABRIC_CHECK_EXISTS_PROPERTY_OPERATION
+impl PropertyValue {
+    pub fn get_type_id(&self) -> PropertyTypeId {
+        match self {
+            PropertyValue::Binary(_) => PropertyTypeId::Binary,
+            PropertyValue::Int64(_) => PropertyTypeId::Int64,
+            PropertyValue::Double(_) => PropertyTypeId::Double,
+            PropertyValue::WString(_) => PropertyTypeId::WString,
+            PropertyValue::Guid(_) => PropertyTypeId::Guid,
+        }
+    }
+
+    pub(crate) fn build_raw(&self) -> PropertyValueRaw<'_> {
+        match self {
+            PropertyValue::Binary(data) => PropertyValueRaw::Binary(
+                Box::new(FABRIC_OPERATION_DATA_BUFFER {
+                    BufferSize: data.len() as u32,
+                    Buffer: data.as_ptr() as *mut u8,
+                }),
+                PhantomData,
+            ),
+            PropertyValue::Int64(v) => PropertyValueRaw::Int64(Box::new(*v)),
+            PropertyValue::Double(v) => PropertyValueRaw::Double(Box::new(*v)),
+            PropertyValue::WString(v) => PropertyValueRaw::WString(v.as_pcwstr(), PhantomData),
+            PropertyValue::Guid(v) => PropertyValueRaw::Guid(Box::new(*v)),
+        }
+    }
+}
+
+/// Holds the memory pointed to by PropertyValue in the property operations.
+/// SF expects a FABRIC_OPERATION_DATA_BUFFER for binary, the string itself for
+/// wstring, and a pointer to the value for the rest.
+pub(crate) enum PropertyValueRaw<'a> {
+    Binary(Box<FABRIC_OPERATION_DATA_BUFFER>, PhantomData<&'a [u8]>),
+    Int64(Box<i64>),
+    Double(Box<f64>),
+    WString(PCWSTR, PhantomData<&'a WString>),
+    Guid(Box<windows_core::GUID>),
+}
+
+impl PropertyValueRaw<'_> {
+    pub(crate) fn as_ptr(&self) -> *mut c_void {
+        match self {
+            PropertyValueRaw::Binary(b, _) => b.as_ref() as *const _ as *mut c_void,
+            PropertyValueRaw::Int64(v) => v.as_ref() as *const _ as *mut c_void,
+            PropertyValueRaw::Double(v) => v.as_ref() as *const _ as *mut c_void,
+            PropertyValueRaw::WString(v, _) => v.0 as *mut c_void,
+            PropertyValueRaw::Guid(v) => v.as_ref() as *const _ as *mut c_void,
+        }
+    }
+}
+
+/// FABRIC_PROPERTY_BATCH_OPERATION
+/// The operations in a batch are executed atomically in order.
+/// If any check operation fails, none of the write operations is applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyBatchOperation {
+    Put {
+        property_name: WString,
+        value: PropertyValue,
+    },
+    /// The result can be read from PropertyBatchResult::get_property
+    /// with the index of this operation.
+    Get {
+        property_name: WString,
+        include_value: bool,
+    },
+    /// Checks that the property exists, or not exists if exists is false.
+    CheckExists {
+        property_name: WString,
+        exists: bool,
+    },
+    /// Checks that the property has the sequence number.
+    CheckSequence {
+        property_name: WString,
+        sequence_number: i64,
+    },
+    Delete {
+        property_name: WString,
+    },
+    PutCustom {
+        property_name: WString,
+        value: PropertyValue,
+        custom_type_id: WString,
+    },
+    /// Checks that the property has the value.
+    CheckValue {
+        property_name: WString,
+        value: PropertyValue,
+    },
+}
+
+// Keeps the operation payload alive while the batch is passed to SF.
+enum PropertyBatchOperationPayload {
+    Put(Box<FABRIC_PUT_PROPERTY_OPERATION>),
+    Get(Box<FABRIC_GET_PROPERTY_OPERATION>),
+    CheckExists(Box<FABRIC_CHECK_EXISTS_PROPERTY_OPERATION>),
+    CheckSequence(Box<FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION>),
+    Delete(Box<FABRIC_DELETE_PROPERTY_OPERATION>),
+    PutCustom(Box<FABRIC_PUT_CUSTOM_PROPERTY_OPERATION>),
+    CheckValue(Box<FABRIC_CHECK_VALUE_PROPERTY_OPERATION>),
+}
+
+impl PropertyBatchOperationPayload {
+    fn as_ffi(&self) -> FABRIC_PROPERTY_BATCH_OPERATION {
+        fn ptr<T>(b: &T) -> *mut c_void {
+            b as *const T as *mut c_void
+        }
+        let (kind, value) = match self {
+            Self::Put(op) => (FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT, ptr(op.as_ref())),
+            Self::Get(op) => (FABRIC_PROPERTY_BATCH_OPERATION_KIND_GET, ptr(op.as_ref())),
+            Self::CheckExists(op) => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_EXISTS,
+                ptr(op.as_ref()),
+            ),
+            Self::CheckSequence(op) => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE,
+                ptr(op.as_ref()),
+            ),
+            Self::Delete(op) => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_DELETE,
+                ptr(op.as_ref()),
+            ),
+            Self::PutCustom(op) => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT_CUSTOM,
+                ptr(op.as_ref()),
+            ),
+            Self::CheckValue(op) => (
+                FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_VALUE,
+                ptr(op.as_ref()),
+            ),
+        };
+        FABRIC_PROPERTY_BATCH_OPERATION {
+            Kind: kind,
+            Value: value,
+        }
+    }
+}
+
+pub(crate) struct PropertyBatchRaw<'a> {
+    operations: Vec<FABRIC_PROPERTY_BATCH_OPERATION>,
+    _payloads: Vec<PropertyBatchOperationPayload>,
+    _values: Vec<PropertyValueRaw<'a>>,
+}
+
+impl PropertyBatchRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &[FABRIC_PROPERTY_BATCH_OPERATION] {
+        &self.operations
+    }
+}
+
+impl PropertyBatchOperation {
+    pub(crate) fn build_batch_raw(batch: &[PropertyBatchOperation]) -> PropertyBatchRaw<'_> {
+        let mut values = Vec::new();
+        let mut payloads = Vec::with_capacity(batch.len());
+        for op in batch {
+            let payload = match op {
+                PropertyBatchOperation::Put {
+                    property_name,
+                    value,
+                } => {
+                    let value_raw = value.build_raw();
+                    let payload = PropertyBatchOperationPayload::Put(Box::new(
+                        FABRIC_PUT_PROPERTY_OPERATION {
+                            PropertyName: property_name.as_pcwstr(),
+                            PropertyTypeId: value.get_type_id().into(),
+                            PropertyValue: value_raw.as_ptr(),
+                            Reserved: std::ptr::null_mut(),
+                        },
+                    ));
+                    values.push(value_raw);
+                    payload
+                }
+                PropertyBatchOperation::Get {
+                    property_name,
+                    include_value,
+                } => PropertyBatchOperationPayload::Get(Box::new(FABRIC_GET_PROPERTY_OPERATION {
+                    PropertyName: property_name.as_pcwstr(),
+                    IncludeValue: *include_value,
+                    Reserved: std::ptr::null_mut(),
+                })),
+                PropertyBatchOperation::CheckExists {
+                    property_name,
+                    exists,
+                } => PropertyBatchOperationPayload::CheckExists(Box::new(
+                    FABRIC_CHECK_EXISTS_PROPERTY_OPERATION {
+                        PropertyName: property_name.as_pcwstr(),
+                        ExistenceCheck: *exists,
+                        Reserved: std::ptr::null_mut(),
+                    },
+                )),
+                PropertyBatchOperation::CheckSequence {
+                    property_name,
+                    sequence_number,
+                } => PropertyBatchOperationPayload::CheckSequence(Box::new(
+                    FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION {
+                        PropertyName: property_name.as_pcwstr(),
+                        SequenceNumber: *sequence_number,
+                        Reserved: std::ptr::null_mut(),
+                    },
+                )),
+                PropertyBatchOperation::Delete { property_name } => {
+                    PropertyBatchOperationPayload::Delete(Box::new(
+                        FABRIC_DELETE_PROPERTY_OPERATION {
+                            PropertyName: property_name.as_pcwstr(),
+                            Reserved: std::ptr::null_mut(),
+                        },
+                    ))
+                }
+                PropertyBatchOperation::PutCustom {
+                    property_name,
+                    value,
+                    custom_type_id,
+                } => {
+                    let value_raw = value.build_raw();
+                    let payload = PropertyBatchOperationPayload::PutCustom(Box::new(
+                        FABRIC_PUT_CUSTOM_PROPERTY_OPERATION {
+                            PropertyName: property_name.as_pcwstr(),
+                            PropertyTypeId: value.get_type_id().into(),
+                            PropertyValue: value_raw.as_ptr(),
+                            PropertyCustomTypeId: custom_type_id.as_pcwstr(),
+                            Reserved: std::ptr::null_mut(),
+                        },
+                    ));
+                    values.push(value_raw);
+                    payload
+                }
+                PropertyBatchOperation::CheckValue {
+                    property_name,
+                    value,
+                } => {
+                    let value_raw = value.build_raw();
+                    let payload = PropertyBatchOperationPayload::CheckValue(Box::new(
+                        FABRIC_CHECK_VALUE_PROPERTY_OPERATION {
+                            PropertyName: property_name.as_pcwstr(),
+                            PropertyTypeId: value.get_type_id().into(),
+                            PropertyValue: value_raw.as_ptr(),
+                            Reserved: std::ptr::null_mut(),
+                        },
+                    ));
+                    values.push(value_raw);
+                    payload
+                }
+            };
+            payloads.push(payload);
+        }
+        let operations = payloads.iter().map(|p| p.as_ffi()).collect();
+        PropertyBatchRaw {
+            operations,
+            _payloads: payloads,
+            _values: values,
+        }
+    }
+}
+
+/// FABRIC_PUT_CUSTOM_PROPERTY_OPERATION
+/// The property type id is taken from the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PutCustomPropertyOperation {
+    pub property_name: WString,
+    pub value: PropertyValue,
+    /// User defined type tag, returned in NamedPropertyMetadata::custom_type_id.
+    pub custom_type_id: WString,
+    /// Expected sequence number of the existing property, for optimistic concurrency.
+    /// SF has no sequence check on this operation, so it is sent as a batch
+    /// together with a CheckSequence operation.
+    pub sequence_number: Option<i64>,
+}
+
+pub(crate) struct PutCustomPropertyOperationRaw<'a> {
+    internal: Box<FABRIC_PUT_CUSTOM_PROPERTY_OPERATION>,
+    _value: PropertyValueRaw<'a>,
+}
+
+impl PutCustomPropertyOperationRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_PUT_CUSTOM_PROPERTY_OPERATION {
+        self.internal.as_ref()
+    }
+}
+
+impl PutCustomPropertyOperation {
+    pub(crate) fn build_raw(&self) -> PutCustomPropertyOperationRaw<'_> {
+        let value = self.value.build_raw();
+        let internal = Box::new(FABRIC_PUT_CUSTOM_PROPERTY_OPERATION {
+            PropertyName: self.property_name.as_pcwstr(),
+            PropertyTypeId: self.value.get_type_id().into(),
+            PropertyValue: value.as_ptr(),
+            PropertyCustomTypeId: self.custom_type_id.as_pcwstr(),
+            Reserved: std::ptr::null_mut(),
+        });
+        PutCustomPropertyOperationRaw {
+            internal,
+            _value: value,
+        }
+    }
+}
+
+/// Result of a submitted property batch.
+pub struct PropertyBatchResult {
+    com: IFabricPropertyBatchResult,
+    failed_operation_index: u32,
+}
+
+impl PropertyBatchResult {
+    pub(crate) fn from_com(com: IFabricPropertyBatchResult, failed_operation_index: u32) -> Self {
+        Self {
+            com,
+            failed_operation_index,
+        }
+    }
+
+    pub fn get_com(&self) -> IFabricPropertyBatchResult {
+        self.com.clone()
+    }
+
+    /// Index of the operation that failed, in which case no operation in the
+    /// batch is applied. None if the whole batch succeeded.
+    pub fn get_failed_operation_index(&self) -> Option<u32> {
+        // SF reports -1 when all operations succeed.
+        if self.failed_operation_index == u32::MAX {
+            None
+        } else {
+            Some(self.failed_operation_index)
+        }
+    }
+
+    /// Gets the result of the Get operation at the index in the batch.
+    pub fn get_property(&self, operation_index: u32) -> crate::Result<PropertyValueResult> {
+        let com = unsafe { self.com.GetProperty(operation_index) }?;
+        Ok(PropertyValueResult::from_com(com))
+    }
+}
 
 /// Represents the result of a property value operation.
 pub struct PropertyValueResult {
@@ -191,6 +580,20 @@ impl PropertyValueResult {
         (meta, data)
     }
 
+    /// Get the property value typed according to its metadata.
+    pub fn get_value(&self) -> crate::Result<PropertyValue> {
+        let ptr = unsafe { self.com.get_Property().as_ref() }.unwrap();
+        let type_id = unsafe { ptr.Metadata.as_ref() }.unwrap().TypeId.into();
+        match type_id {
+            PropertyTypeId::Binary => self.get_value_as_binary().map(PropertyValue::Binary),
+            PropertyTypeId::Int64 => self.get_value_as_int64().map(PropertyValue::Int64),
+            PropertyTypeId::Double => self.get_value_as_double().map(PropertyValue::Double),
+            PropertyTypeId::WString => self.get_value_as_wstring().map(PropertyValue::WString),
+            PropertyTypeId::Guid => self.get_value_as_guid().map(PropertyValue::Guid),
+            PropertyTypeId::Invalid => Err(ErrorCode::E_INVALIDARG.into()),
+        }
+    }
+
     pub fn get_value_as_binary(&self) -> crate::Result<Vec<u8>> {
         let mut bytecount = 0_u32;
         let data = unsafe { self.com.GetValueAsBinary(&mut bytecount as *mut u32) }?;
@@ -215,3 +618,93 @@ impl PropertyValueResult {
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION, FABRIC_OPERATION_DATA_BUFFER,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE,
+        FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT, FABRIC_PROPERTY_TYPE_BINARY,
+        FABRIC_PROPERTY_TYPE_INT64, FABRIC_PROPERTY_TYPE_WSTRING, FABRIC_PUT_PROPERTY_OPERATION,
+    };
+
+    use crate::{PCWSTR, WString};
+
+    use super::{
+        EnumerationStatus, PropertyBatchOperation, PropertyValue, PutCustomPropertyOperation,
+    };
+
+    #[test]
+    fn test_put_custom_property_raw() {
+        let op = PutCustomPropertyOperation {
+            property_name: WString::from("config"),
+            value: PropertyValue::WString(WString::from("blob")),
+            custom_type_id: WString::from("schema_v2"),
+            sequence_number: None,
+        };
+        let raw = op.build_raw();
+        let ffi = raw.as_ffi();
+        assert_eq!(ffi.PropertyTypeId, FABRIC_PROPERTY_TYPE_WSTRING);
+        assert_eq!(
+            WString::from(ffi.PropertyCustomTypeId),
+            WString::from("schema_v2")
+        );
+        // wstring values are passed as the string pointer itself.
+        assert_eq!(
+            WString::from(PCWSTR(ffi.PropertyValue as *const u16)),
+            WString::from("blob")
+        );
+    }
+
+    #[test]
+    fn test_enumeration_status_finished() {
+        assert!(EnumerationStatus::BestEffortFinished.is_finished());
+        assert!(EnumerationStatus::ConsistentFinished.is_finished());
+        assert!(!EnumerationStatus::BestEffortMoreData.is_finished());
+        assert!(!EnumerationStatus::ConsistentMoreData.is_finished());
+    }
+
+    #[test]
+    fn test_property_batch_raw() {
+        let batch = vec![
+            PropertyBatchOperation::CheckSequence {
+                property_name: WString::from("leader"),
+                sequence_number: 7,
+            },
+            PropertyBatchOperation::Put {
+                property_name: WString::from("leader"),
+                value: PropertyValue::Int64(42),
+            },
+            PropertyBatchOperation::Put {
+                property_name: WString::from("data"),
+                value: PropertyValue::Binary(vec![1, 2, 3]),
+            },
+        ];
+        let raw = PropertyBatchOperation::build_batch_raw(&batch);
+        let ops = raw.as_ffi();
+        assert_eq!(ops.len(), 3);
+
+        assert_eq!(
+            ops[0].Kind,
+            FABRIC_PROPERTY_BATCH_OPERATION_KIND_CHECK_SEQUENCE
+        );
+        let check =
+            unsafe { (ops[0].Value as *const FABRIC_CHECK_SEQUENCE_PROPERTY_OPERATION).as_ref() }
+                .unwrap();
+        assert_eq!(check.SequenceNumber, 7);
+
+        assert_eq!(ops[1].Kind, FABRIC_PROPERTY_BATCH_OPERATION_KIND_PUT);
+        let put =
+            unsafe { (ops[1].Value as *const FABRIC_PUT_PROPERTY_OPERATION).as_ref() }.unwrap();
+        assert_eq!(put.PropertyTypeId, FABRIC_PROPERTY_TYPE_INT64);
+        assert_eq!(unsafe { *(put.PropertyValue as *const i64) }, 42);
+
+        let put =
+            unsafe { (ops[2].Value as *const FABRIC_PUT_PROPERTY_OPERATION).as_ref() }.unwrap();
+        assert_eq!(put.PropertyTypeId, FABRIC_PROPERTY_TYPE_BINARY);
+        let buf =
+            unsafe { (put.PropertyValue as *const FABRIC_OPERATION_DATA_BUFFER).as_ref() }.unwrap();
+        let data = unsafe { std::slice::from_raw_parts(buf.Buffer, buf.BufferSize as usize) };
+        assert_eq!(data, &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,839 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Types used by the repair manager apis.
+
+use std::{ffi::c_void, time::SystemTime};
+
+use bitflags::bitflags;
+use mssf_com::{
+    FabricClient::IFabricGetRepairTaskListResult,
+    FabricTypes::{
+        FABRIC_REPAIR_APPROVE_DESCRIPTION, FABRIC_REPAIR_CANCEL_DESCRIPTION,
+        FABRIC_REPAIR_DELETE_DESCRIPTION, FABRIC_REPAIR_EXECUTOR_STATE,
+        FABRIC_REPAIR_IMPACT_DESCRIPTION, FABRIC_REPAIR_IMPACT_KIND_NODE,
+        FABRIC_REPAIR_NODE_IMPACT, FABRIC_REPAIR_NODE_IMPACT_LEVEL,
+        FABRIC_REPAIR_NODE_IMPACT_LEVEL_INVALID, FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE,
+        FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA, FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE,
+        FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART, FABRIC_REPAIR_NODE_IMPACT_LIST,
+        FABRIC_REPAIR_RESULT_DESCRIPTION, FABRIC_REPAIR_SCOPE_IDENTIFIER,
+        FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER, FABRIC_REPAIR_TARGET_DESCRIPTION,
+        FABRIC_REPAIR_TARGET_KIND_NODE, FABRIC_REPAIR_TASK, FABRIC_REPAIR_TASK_EX1,
+        FABRIC_REPAIR_TASK_FLAGS_ABORT_REQUESTED, FABRIC_REPAIR_TASK_FLAGS_CANCEL_REQUESTED,
+        FABRIC_REPAIR_TASK_FLAGS_FORCED_APPROVAL, FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE,
+        FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_IN_PROGRESS,
+        FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SKIPPED,
+        FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SUCCEEDED,
+        FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_TIMEDOUT,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_PREPARING_HEALTH_CHECK,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK,
+        FABRIC_REPAIR_TASK_HISTORY, FABRIC_REPAIR_TASK_HISTORY_EX1,
+        FABRIC_REPAIR_TASK_QUERY_DESCRIPTION, FABRIC_REPAIR_TASK_RESULT,
+        FABRIC_REPAIR_TASK_RESULT_CANCELLED, FABRIC_REPAIR_TASK_RESULT_FAILED,
+        FABRIC_REPAIR_TASK_RESULT_INTERRUPTED, FABRIC_REPAIR_TASK_RESULT_INVALID,
+        FABRIC_REPAIR_TASK_RESULT_PENDING, FABRIC_REPAIR_TASK_RESULT_SUCCEEDED,
+        FABRIC_REPAIR_TASK_STATE, FABRIC_REPAIR_TASK_STATE_APPROVED,
+        FABRIC_REPAIR_TASK_STATE_CLAIMED, FABRIC_REPAIR_TASK_STATE_COMPLETED,
+        FABRIC_REPAIR_TASK_STATE_CREATED, FABRIC_REPAIR_TASK_STATE_EXECUTING,
+        FABRIC_REPAIR_TASK_STATE_FILTER_ACTIVE, FABRIC_REPAIR_TASK_STATE_FILTER_ALL,
+        FABRIC_REPAIR_TASK_STATE_FILTER_APPROVED, FABRIC_REPAIR_TASK_STATE_FILTER_CLAIMED,
+        FABRIC_REPAIR_TASK_STATE_FILTER_COMPLETED, FABRIC_REPAIR_TASK_STATE_FILTER_CREATED,
+        FABRIC_REPAIR_TASK_STATE_FILTER_DEFAULT, FABRIC_REPAIR_TASK_STATE_FILTER_EXECUTING,
+        FABRIC_REPAIR_TASK_STATE_FILTER_PREPARING,
+        FABRIC_REPAIR_TASK_STATE_FILTER_READY_TO_EXECUTE,
+        FABRIC_REPAIR_TASK_STATE_FILTER_RESTORING, FABRIC_REPAIR_TASK_STATE_INVALID,
+        FABRIC_REPAIR_TASK_STATE_PREPARING, FABRIC_REPAIR_TASK_STATE_RESTORING, FABRIC_STRING_LIST,
+    },
+};
+use windows_core::Win32::Foundation::FILETIME;
+
+use crate::{
+    HRESULT, WString,
+    iter::{FabricIter, FabricListAccessor},
+    strings::{FabricStringListRaw, WStringWrap, fabric_string_list_to_vec, get_pcwstr_from_opt},
+};
+
+// FABRIC_REPAIR_TASK_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairTaskState {
+    Invalid,
+    /// Created by the repair requester.
+    Created,
+    /// Claimed by a repair executor.
+    Claimed,
+    /// The repair manager is preparing the cluster, i.e. deactivating nodes.
+    Preparing,
+    /// The repair is approved and the executor can start.
+    Approved,
+    Executing,
+    /// The repair manager is restoring the cluster, i.e. activating nodes.
+    Restoring,
+    Completed,
+}
+
+impl From<FABRIC_REPAIR_TASK_STATE> for RepairTaskState {
+    fn from(value: FABRIC_REPAIR_TASK_STATE) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_STATE_CREATED => Self::Created,
+            FABRIC_REPAIR_TASK_STATE_CLAIMED => Self::Claimed,
+            FABRIC_REPAIR_TASK_STATE_PREPARING => Self::Preparing,
+            FABRIC_REPAIR_TASK_STATE_APPROVED => Self::Approved,
+            FABRIC_REPAIR_TASK_STATE_EXECUTING => Self::Executing,
+            FABRIC_REPAIR_TASK_STATE_RESTORING => Self::Restoring,
+            FABRIC_REPAIR_TASK_STATE_COMPLETED => Self::Completed,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairTaskState> for FABRIC_REPAIR_TASK_STATE {
+    fn from(value: RepairTaskState) -> Self {
+        match value {
+            RepairTaskState::Invalid => FABRIC_REPAIR_TASK_STATE_INVALID,
+            RepairTaskState::Created => FABRIC_REPAIR_TASK_STATE_CREATED,
+            RepairTaskState::Claimed => FABRIC_REPAIR_TASK_STATE_CLAIMED,
+            RepairTaskState::Preparing => FABRIC_REPAIR_TASK_STATE_PREPARING,
+            RepairTaskState::Approved => FABRIC_REPAIR_TASK_STATE_APPROVED,
+            RepairTaskState::Executing => FABRIC_REPAIR_TASK_STATE_EXECUTING,
+            RepairTaskState::Restoring => FABRIC_REPAIR_TASK_STATE_RESTORING,
+            RepairTaskState::Completed => FABRIC_REPAIR_TASK_STATE_COMPLETED,
+        }
+    }
+}
+
+bitflags! {
+    /// FABRIC_REPAIR_TASK_STATE_FILTER
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct RepairTaskStateFilter: u32 {
+        const Default = FABRIC_REPAIR_TASK_STATE_FILTER_DEFAULT.0 as u32;
+        const Created = FABRIC_REPAIR_TASK_STATE_FILTER_CREATED.0 as u32;
+        const Claimed = FABRIC_REPAIR_TASK_STATE_FILTER_CLAIMED.0 as u32;
+        const Preparing = FABRIC_REPAIR_TASK_STATE_FILTER_PREPARING.0 as u32;
+        const Approved = FABRIC_REPAIR_TASK_STATE_FILTER_APPROVED.0 as u32;
+        const Executing = FABRIC_REPAIR_TASK_STATE_FILTER_EXECUTING.0 as u32;
+        const ReadyToExecute = FABRIC_REPAIR_TASK_STATE_FILTER_READY_TO_EXECUTE.0 as u32;
+        const Restoring = FABRIC_REPAIR_TASK_STATE_FILTER_RESTORING.0 as u32;
+        const Active = FABRIC_REPAIR_TASK_STATE_FILTER_ACTIVE.0 as u32;
+        const Completed = FABRIC_REPAIR_TASK_STATE_FILTER_COMPLETED.0 as u32;
+        const All = FABRIC_REPAIR_TASK_STATE_FILTER_ALL.0 as u32;
+    }
+}
+
+impl Default for RepairTaskStateFilter {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+bitflags! {
+    /// FABRIC_REPAIR_TASK_FLAGS
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct RepairTaskFlags: u32 {
+        const CancelRequested = FABRIC_REPAIR_TASK_FLAGS_CANCEL_REQUESTED.0 as u32;
+        const AbortRequested = FABRIC_REPAIR_TASK_FLAGS_ABORT_REQUESTED.0 as u32;
+        const ForcedApproval = FABRIC_REPAIR_TASK_FLAGS_FORCED_APPROVAL.0 as u32;
+    }
+}
+
+// FABRIC_REPAIR_TASK_RESULT
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairTaskResult {
+    Invalid,
+    Succeeded,
+    Cancelled,
+    Interrupted,
+    Failed,
+    Pending,
+}
+
+impl From<FABRIC_REPAIR_TASK_RESULT> for RepairTaskResult {
+    fn from(value: FABRIC_REPAIR_TASK_RESULT) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_RESULT_SUCCEEDED => Self::Succeeded,
+            FABRIC_REPAIR_TASK_RESULT_CANCELLED => Self::Cancelled,
+            FABRIC_REPAIR_TASK_RESULT_INTERRUPTED => Self::Interrupted,
+            FABRIC_REPAIR_TASK_RESULT_FAILED => Self::Failed,
+            FABRIC_REPAIR_TASK_RESULT_PENDING => Self::Pending,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairTaskResult> for FABRIC_REPAIR_TASK_RESULT {
+    fn from(value: RepairTaskResult) -> Self {
+        match value {
+            RepairTaskResult::Invalid => FABRIC_REPAIR_TASK_RESULT_INVALID,
+            RepairTaskResult::Succeeded => FABRIC_REPAIR_TASK_RESULT_SUCCEEDED,
+            RepairTaskResult::Cancelled => FABRIC_REPAIR_TASK_RESULT_CANCELLED,
+            RepairTaskResult::Interrupted => FABRIC_REPAIR_TASK_RESULT_INTERRUPTED,
+            RepairTaskResult::Failed => FABRIC_REPAIR_TASK_RESULT_FAILED,
+            RepairTaskResult::Pending => FABRIC_REPAIR_TASK_RESULT_PENDING,
+        }
+    }
+}
+
+// FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairTaskHealthCheckState {
+    NotStarted,
+    InProgress,
+    Succeeded,
+    Skipped,
+    TimedOut,
+}
+
+impl From<FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE> for RepairTaskHealthCheckState {
+    fn from(value: FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE) -> Self {
+        match value {
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_IN_PROGRESS => Self::InProgress,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SUCCEEDED => Self::Succeeded,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_SKIPPED => Self::Skipped,
+            FABRIC_REPAIR_TASK_HEALTH_CHECK_STATE_TIMEDOUT => Self::TimedOut,
+            _ => Self::NotStarted,
+        }
+    }
+}
+
+// FABRIC_REPAIR_NODE_IMPACT_LEVEL
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairNodeImpactLevel {
+    Invalid,
+    None,
+    Restart,
+    RemoveData,
+    RemoveNode,
+}
+
+impl From<FABRIC_REPAIR_NODE_IMPACT_LEVEL> for RepairNodeImpactLevel {
+    fn from(value: FABRIC_REPAIR_NODE_IMPACT_LEVEL) -> Self {
+        match value {
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE => Self::None,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART => Self::Restart,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA => Self::RemoveData,
+            FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE => Self::RemoveNode,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RepairNodeImpactLevel> for FABRIC_REPAIR_NODE_IMPACT_LEVEL {
+    fn from(value: RepairNodeImpactLevel) -> Self {
+        match value {
+            RepairNodeImpactLevel::Invalid => FABRIC_REPAIR_NODE_IMPACT_LEVEL_INVALID,
+            RepairNodeImpactLevel::None => FABRIC_REPAIR_NODE_IMPACT_LEVEL_NONE,
+            RepairNodeImpactLevel::Restart => FABRIC_REPAIR_NODE_IMPACT_LEVEL_RESTART,
+            RepairNodeImpactLevel::RemoveData => FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_DATA,
+            RepairNodeImpactLevel::RemoveNode => FABRIC_REPAIR_NODE_IMPACT_LEVEL_REMOVE_NODE,
+        }
+    }
+}
+
+// FABRIC_REPAIR_NODE_IMPACT
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairNodeImpact {
+    pub node_name: WString,
+    pub impact_level: RepairNodeImpactLevel,
+}
+
+impl From<&FABRIC_REPAIR_NODE_IMPACT> for RepairNodeImpact {
+    fn from(value: &FABRIC_REPAIR_NODE_IMPACT) -> Self {
+        Self {
+            node_name: WStringWrap::from(value.NodeName).into(),
+            impact_level: value.ImpactLevel.into(),
+        }
+    }
+}
+
+/// FABRIC_REPAIR_TARGET_DESCRIPTION
+/// The entities the repair is performed on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairTarget {
+    Invalid,
+    Nodes(Vec<WString>),
+}
+
+impl From<&FABRIC_REPAIR_TARGET_DESCRIPTION> for RepairTarget {
+    fn from(value: &FABRIC_REPAIR_TARGET_DESCRIPTION) -> Self {
+        match value.Kind {
+            FABRIC_REPAIR_TARGET_KIND_NODE => {
+                Self::Nodes(fabric_string_list_to_vec(value.Value as *const _))
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+/// FABRIC_REPAIR_IMPACT_DESCRIPTION
+/// The expected impact of the repair, which the repair manager prepares for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepairImpact {
+    Invalid,
+    Nodes(Vec<RepairNodeImpact>),
+}
+
+impl From<&FABRIC_REPAIR_IMPACT_DESCRIPTION> for RepairImpact {
+    fn from(value: &FABRIC_REPAIR_IMPACT_DESCRIPTION) -> Self {
+        match value.Kind {
+            FABRIC_REPAIR_IMPACT_KIND_NODE => {
+                let list =
+                    unsafe { (value.Value as *const FABRIC_REPAIR_NODE_IMPACT_LIST).as_ref() };
+                let impacts = match list {
+                    Some(l) if !l.Items.is_null() => {
+                        unsafe { std::slice::from_raw_parts(l.Items, l.Count as usize) }
+                            .iter()
+                            .map(RepairNodeImpact::from)
+                            .collect()
+                    }
+                    _ => Vec::new(),
+                };
+                Self::Nodes(impacts)
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_REPAIR_EXECUTOR_STATE
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairExecutorState {
+    pub executor: WString,
+    /// Opaque data owned by the executor.
+    pub executor_data: WString,
+}
+
+impl From<&FABRIC_REPAIR_EXECUTOR_STATE> for RepairExecutorState {
+    fn from(value: &FABRIC_REPAIR_EXECUTOR_STATE) -> Self {
+        Self {
+            executor: WStringWrap::from(value.Executor).into(),
+            executor_data: WStringWrap::from(value.ExecutorData).into(),
+        }
+    }
+}
+
+// FABRIC_REPAIR_RESULT_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairResultDescription {
+    pub result_status: RepairTaskResult,
+    pub result_code: HRESULT,
+    pub result_details: WString,
+}
+
+impl From<&FABRIC_REPAIR_RESULT_DESCRIPTION> for RepairResultDescription {
+    fn from(value: &FABRIC_REPAIR_RESULT_DESCRIPTION) -> Self {
+        Self {
+            result_status: value.ResultStatus.into(),
+            result_code: value.ResultCode,
+            result_details: WStringWrap::from(value.ResultDetails).into(),
+        }
+    }
+}
+
+/// FABRIC_REPAIR_TASK_HISTORY
+/// Timestamps of the state transitions. Zero FILETIME (1601-01-01) if the state is not reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepairTaskHistory {
+    pub created_utc_timestamp: SystemTime,
+    pub claimed_utc_timestamp: SystemTime,
+    pub preparing_utc_timestamp: SystemTime,
+    pub approved_utc_timestamp: SystemTime,
+    pub executing_utc_timestamp: SystemTime,
+    pub restoring_utc_timestamp: SystemTime,
+    pub completed_utc_timestamp: SystemTime,
+    pub preparing_health_check_start_utc_timestamp: SystemTime,
+    pub preparing_health_check_end_utc_timestamp: SystemTime,
+    pub restoring_health_check_start_utc_timestamp: SystemTime,
+    pub restoring_health_check_end_utc_timestamp: SystemTime,
+}
+
+impl From<&FABRIC_REPAIR_TASK_HISTORY> for RepairTaskHistory {
+    fn from(value: &FABRIC_REPAIR_TASK_HISTORY) -> Self {
+        let zero = FILETIME {
+            dwLowDateTime: 0,
+            dwHighDateTime: 0,
+        };
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_REPAIR_TASK_HISTORY_EX1).as_ref() };
+        Self {
+            created_utc_timestamp: value.CreatedUtcTimestamp.into(),
+            claimed_utc_timestamp: value.ClaimedUtcTimestamp.into(),
+            preparing_utc_timestamp: value.PreparingUtcTimestamp.into(),
+            approved_utc_timestamp: value.ApprovedUtcTimestamp.into(),
+            executing_utc_timestamp: value.ExecutingUtcTimestamp.into(),
+            restoring_utc_timestamp: value.RestoringUtcTimestamp.into(),
+            completed_utc_timestamp: value.CompletedUtcTimestamp.into(),
+            preparing_health_check_start_utc_timestamp: ex1
+                .map_or(zero, |e| e.PreparingHealthCheckStartUtcTimestamp)
+                .into(),
+            preparing_health_check_end_utc_timestamp: ex1
+                .map_or(zero, |e| e.PreparingHealthCheckEndUtcTimestamp)
+                .into(),
+            restoring_health_check_start_utc_timestamp: ex1
+                .map_or(zero, |e| e.RestoringHealthCheckStartUtcTimestamp)
+                .into(),
+            restoring_health_check_end_utc_timestamp: ex1
+                .map_or(zero, |e| e.RestoringHealthCheckEndUtcTimestamp)
+                .into(),
+        }
+    }
+}
+
+/// FABRIC_REPAIR_TASK
+/// Used both as the result of the list query and as the input for
+/// create_repair_task and update_repair_execution_state.
+/// The read only fields like history and health check states are ignored by SF as input.
+#[derive(Debug, Clone)]
+pub struct RepairTask {
+    pub task_id: WString,
+    /// Must match the current version on updates. 0 skips the check.
+    pub version: i64,
+    pub description: WString,
+    pub state: RepairTaskState,
+    pub flags: RepairTaskFlags,
+    pub action: WString,
+    pub target: Option<RepairTarget>,
+    pub executor_state: Option<RepairExecutorState>,
+    pub impact: Option<RepairImpact>,
+    pub result: Option<RepairResultDescription>,
+    pub history: Option<RepairTaskHistory>,
+    pub perform_preparing_health_check: bool,
+    pub perform_restoring_health_check: bool,
+    pub preparing_health_check_state: RepairTaskHealthCheckState,
+    pub restoring_health_check_state: RepairTaskHealthCheckState,
+}
+
+impl RepairTask {
+    /// A new task in Created state, ready to be passed to create_repair_task.
+    pub fn new(task_id: WString, action: WString) -> Self {
+        Self {
+            task_id,
+            version: 0,
+            description: WString::new(),
+            state: RepairTaskState::Created,
+            flags: RepairTaskFlags::empty(),
+            action,
+            target: None,
+            executor_state: None,
+            impact: None,
+            result: None,
+            history: None,
+            perform_preparing_health_check: false,
+            perform_restoring_health_check: false,
+            preparing_health_check_state: RepairTaskHealthCheckState::NotStarted,
+            restoring_health_check_state: RepairTaskHealthCheckState::NotStarted,
+        }
+    }
+}
+
+impl From<&FABRIC_REPAIR_TASK> for RepairTask {
+    fn from(value: &FABRIC_REPAIR_TASK) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_REPAIR_TASK_EX1).as_ref() };
+        Self {
+            task_id: WStringWrap::from(value.TaskId).into(),
+            version: value.Version,
+            description: WStringWrap::from(value.Description).into(),
+            state: value.State.into(),
+            flags: RepairTaskFlags::from_bits_truncate(value.Flags),
+            action: WStringWrap::from(value.Action).into(),
+            target: unsafe { value.Target.as_ref() }.map(RepairTarget::from),
+            executor_state: unsafe { value.ExecutorState.as_ref() }.map(RepairExecutorState::from),
+            impact: unsafe { value.Impact.as_ref() }.map(RepairImpact::from),
+            result: unsafe { value.Result.as_ref() }.map(RepairResultDescription::from),
+            history: unsafe { value.History.as_ref() }.map(RepairTaskHistory::from),
+            perform_preparing_health_check: ex1.is_some_and(|e| e.PerformPreparingHealthCheck),
+            perform_restoring_health_check: ex1.is_some_and(|e| e.PerformRestoringHealthCheck),
+            preparing_health_check_state: ex1.map_or(RepairTaskHealthCheckState::NotStarted, |e| {
+                e.PreparingHealthCheckState.into()
+            }),
+            restoring_health_check_state: ex1.map_or(RepairTaskHealthCheckState::NotStarted, |e| {
+                e.RestoringHealthCheckState.into()
+            }),
+        }
+    }
+}
+
+/// Only the cluster scope is defined by SF.
+fn cluster_scope() -> Box<FABRIC_REPAIR_SCOPE_IDENTIFIER> {
+    Box::new(FABRIC_REPAIR_SCOPE_IDENTIFIER {
+        Kind: FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER,
+        Value: std::ptr::null_mut(),
+    })
+}
+
+pub(crate) struct RepairTaskRaw<'a> {
+    internal: Box<FABRIC_REPAIR_TASK>,
+    _scope: Box<FABRIC_REPAIR_SCOPE_IDENTIFIER>,
+    _ex1: Box<FABRIC_REPAIR_TASK_EX1>,
+    _target: Option<(
+        Box<FABRIC_REPAIR_TARGET_DESCRIPTION>,
+        FabricStringListRaw<'a>,
+    )>,
+    _executor_state: Option<Box<FABRIC_REPAIR_EXECUTOR_STATE>>,
+    _impact: Option<(
+        Box<FABRIC_REPAIR_IMPACT_DESCRIPTION>,
+        Box<FABRIC_REPAIR_NODE_IMPACT_LIST>,
+        Vec<FABRIC_REPAIR_NODE_IMPACT>,
+    )>,
+    _result: Option<Box<FABRIC_REPAIR_RESULT_DESCRIPTION>>,
+}
+
+impl RepairTaskRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_REPAIR_TASK {
+        self.internal.as_ref()
+    }
+}
+
+impl RepairTask {
+    pub(crate) fn build_raw(&self) -> RepairTaskRaw<'_> {
+        let scope = cluster_scope();
+        let target = match &self.target {
+            Some(RepairTarget::Nodes(nodes)) => {
+                let list = FabricStringListRaw::new(nodes);
+                let desc = Box::new(FABRIC_REPAIR_TARGET_DESCRIPTION {
+                    Kind: FABRIC_REPAIR_TARGET_KIND_NODE,
+                    Value: list.as_ffi() as *const FABRIC_STRING_LIST as *mut c_void,
+                });
+                Some((desc, list))
+            }
+            _ => None,
+        };
+        let executor_state = self.executor_state.as_ref().map(|e| {
+            Box::new(FABRIC_REPAIR_EXECUTOR_STATE {
+                Executor: e.executor.as_pcwstr(),
+                ExecutorData: e.executor_data.as_pcwstr(),
+                Reserved: std::ptr::null_mut(),
+            })
+        });
+        let impact = match &self.impact {
+            Some(RepairImpact::Nodes(nodes)) => {
+                let items = nodes
+                    .iter()
+                    .map(|n| FABRIC_REPAIR_NODE_IMPACT {
+                        NodeName: n.node_name.as_pcwstr(),
+                        ImpactLevel: n.impact_level.into(),
+                        Reserved: std::ptr::null_mut(),
+                    })
+                    .collect::<Vec<_>>();
+                let list = Box::new(FABRIC_REPAIR_NODE_IMPACT_LIST {
+                    Count: items.len() as u32,
+                    Items: items.as_ptr(),
+                });
+                let desc = Box::new(FABRIC_REPAIR_IMPACT_DESCRIPTION {
+                    Kind: FABRIC_REPAIR_IMPACT_KIND_NODE,
+                    Value: list.as_ref() as *const _ as *mut c_void,
+                });
+                Some((desc, list, items))
+            }
+            _ => None,
+        };
+        let result = self.result.as_ref().map(|r| {
+            Box::new(FABRIC_REPAIR_RESULT_DESCRIPTION {
+                ResultStatus: r.result_status.into(),
+                ResultCode: r.result_code,
+                ResultDetails: r.result_details.as_pcwstr(),
+                Reserved: std::ptr::null_mut(),
+            })
+        });
+        let ex1 = Box::new(FABRIC_REPAIR_TASK_EX1 {
+            PerformPreparingHealthCheck: self.perform_preparing_health_check,
+            PerformRestoringHealthCheck: self.perform_restoring_health_check,
+            PreparingHealthCheckState: Default::default(),
+            RestoringHealthCheckState: Default::default(),
+            Reserved: std::ptr::null_mut(),
+        });
+        let internal = Box::new(FABRIC_REPAIR_TASK {
+            Scope: scope.as_ref(),
+            TaskId: self.task_id.as_pcwstr(),
+            Version: self.version,
+            Description: self.description.as_pcwstr(),
+            State: self.state.into(),
+            Flags: self.flags.bits(),
+            Action: self.action.as_pcwstr(),
+            Target: target.as_ref().map_or(std::ptr::null(), |t| t.0.as_ref()),
+            ExecutorState: executor_state
+                .as_ref()
+                .map_or(std::ptr::null_mut(), |e| e.as_ref() as *const _ as *mut _),
+            Impact: impact
+                .as_ref()
+                .map_or(std::ptr::null_mut(), |i| i.0.as_ref() as *const _ as *mut _),
+            Result: result
+                .as_ref()
+                .map_or(std::ptr::null_mut(), |r| r.as_ref() as *const _ as *mut _),
+            // History is maintained by SF.
+            History: std::ptr::null(),
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+        RepairTaskRaw {
+            internal,
+            _scope: scope,
+            _ex1: ex1,
+            _target: target,
+            _executor_state: executor_state,
+            _impact: impact,
+            _result: result,
+        }
+    }
+}
+
+/// Raw repair request that points to the cluster scope.
+pub(crate) struct RepairRequestRaw<T> {
+    internal: Box<T>,
+    _scope: Box<FABRIC_REPAIR_SCOPE_IDENTIFIER>,
+}
+
+impl<T> RepairRequestRaw<T> {
+    pub(crate) fn as_ffi(&self) -> &T {
+        self.internal.as_ref()
+    }
+}
+
+// FABRIC_REPAIR_CANCEL_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct RepairCancelDescription {
+    pub repair_task_id: WString,
+    /// 0 skips the version check.
+    pub version: i64,
+    /// Abort the repair even if it is already executing.
+    pub request_abort: bool,
+}
+
+impl RepairCancelDescription {
+    pub(crate) fn build_raw(&self) -> RepairRequestRaw<FABRIC_REPAIR_CANCEL_DESCRIPTION> {
+        let scope = cluster_scope();
+        let internal = Box::new(FABRIC_REPAIR_CANCEL_DESCRIPTION {
+            Scope: scope.as_ref() as *const _ as *mut _,
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            RequestAbort: self.request_abort,
+            Reserved: std::ptr::null_mut(),
+        });
+        RepairRequestRaw {
+            internal,
+            _scope: scope,
+        }
+    }
+}
+
+// FABRIC_REPAIR_APPROVE_DESCRIPTION
+#[derive(Debug, Clone)]
+pub struct RepairApproveDescription {
+    pub repair_task_id: WString,
+    pub version: i64,
+}
+
+impl RepairApproveDescription {
+    pub(crate) fn build_raw(&self) -> RepairRequestRaw<FABRIC_REPAIR_APPROVE_DESCRIPTION> {
+        let scope = cluster_scope();
+        let internal = Box::new(FABRIC_REPAIR_APPROVE_DESCRIPTION {
+            Scope: scope.as_ref() as *const _ as *mut _,
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Reserved: std::ptr::null_mut(),
+        });
+        RepairRequestRaw {
+            internal,
+            _scope: scope,
+        }
+    }
+}
+
+/// FABRIC_REPAIR_DELETE_DESCRIPTION
+/// Only completed tasks can be deleted.
+#[derive(Debug, Clone)]
+pub struct RepairDeleteDescription {
+    pub repair_task_id: WString,
+    pub version: i64,
+}
+
+impl RepairDeleteDescription {
+    pub(crate) fn build_raw(&self) -> RepairRequestRaw<FABRIC_REPAIR_DELETE_DESCRIPTION> {
+        let scope = cluster_scope();
+        let internal = Box::new(FABRIC_REPAIR_DELETE_DESCRIPTION {
+            Scope: scope.as_ref() as *const _ as *mut _,
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Reserved: std::ptr::null_mut(),
+        });
+        RepairRequestRaw {
+            internal,
+            _scope: scope,
+        }
+    }
+}
+
+/// FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION
+/// The health checks left as None are not changed.
+#[derive(Debug, Clone)]
+pub struct RepairTaskHealthPolicyUpdateDescription {
+    pub repair_task_id: WString,
+    pub version: i64,
+    pub perform_preparing_health_check: Option<bool>,
+    pub perform_restoring_health_check: Option<bool>,
+}
+
+impl RepairTaskHealthPolicyUpdateDescription {
+    pub(crate) fn build_raw(
+        &self,
+    ) -> RepairRequestRaw<FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION> {
+        let mut flags = 0;
+        if self.perform_preparing_health_check.is_some() {
+            flags |=
+                FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_PREPARING_HEALTH_CHECK
+                    .0 as u32;
+        }
+        if self.perform_restoring_health_check.is_some() {
+            flags |=
+                FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK
+                    .0 as u32;
+        }
+        let scope = cluster_scope();
+        let internal = Box::new(FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_DESCRIPTION {
+            Scope: scope.as_ref() as *const _ as *mut _,
+            RepairTaskId: self.repair_task_id.as_pcwstr(),
+            Version: self.version,
+            Flags: flags,
+            PerformPreparingHealthCheck: self.perform_preparing_health_check.unwrap_or_default(),
+            PerformRestoringHealthCheck: self.perform_restoring_health_check.unwrap_or_default(),
+            Reserved: std::ptr::null_mut(),
+        });
+        RepairRequestRaw {
+            internal,
+            _scope: scope,
+        }
+    }
+}
+
+/// FABRIC_REPAIR_TASK_QUERY_DESCRIPTION
+/// The task id filter matches task ids by prefix.
+#[derive(Debug, Clone, Default)]
+pub struct RepairTaskQueryDescription {
+    pub task_id_filter: Option<WString>,
+    pub state_filter: RepairTaskStateFilter,
+    pub executor_filter: Option<WString>,
+}
+
+impl RepairTaskQueryDescription {
+    pub(crate) fn build_raw(&self) -> RepairRequestRaw<FABRIC_REPAIR_TASK_QUERY_DESCRIPTION> {
+        let scope = cluster_scope();
+        let internal = Box::new(FABRIC_REPAIR_TASK_QUERY_DESCRIPTION {
+            Scope: scope.as_ref() as *const _ as *mut _,
+            TaskIdFilter: get_pcwstr_from_opt(&self.task_id_filter),
+            StateFilter: self.state_filter.bits(),
+            ExecutorFilter: get_pcwstr_from_opt(&self.executor_filter),
+            Reserved: std::ptr::null_mut(),
+        });
+        RepairRequestRaw {
+            internal,
+            _scope: scope,
+        }
+    }
+}
+
+// IFabricGetRepairTaskListResult
+#[derive(Debug, Clone)]
+pub struct RepairTaskList {
+    com: IFabricGetRepairTaskListResult,
+}
+
+impl From<IFabricGetRepairTaskListResult> for RepairTaskList {
+    fn from(com: IFabricGetRepairTaskListResult) -> Self {
+        Self { com }
+    }
+}
+
+impl FabricListAccessor<FABRIC_REPAIR_TASK> for RepairTaskList {
+    fn get_count(&self) -> u32 {
+        let list = unsafe { self.com.get_Tasks().as_ref().unwrap() };
+        list.Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_REPAIR_TASK {
+        let list = unsafe { self.com.get_Tasks().as_ref().unwrap() };
+        list.Items
+    }
+}
+
+type RepairTaskListIter<'a> = FabricIter<'a, FABRIC_REPAIR_TASK, RepairTask, RepairTaskList>;
+
+impl RepairTaskList {
+    pub fn iter(&self) -> RepairTaskListIter<'_> {
+        RepairTaskListIter::new(self, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER,
+        FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK,
+        FABRIC_REPAIR_TASK_STATE_CLAIMED,
+    };
+
+    use crate::WString;
+
+    use super::{
+        RepairExecutorState, RepairImpact, RepairNodeImpact, RepairNodeImpactLevel, RepairTarget,
+        RepairTask, RepairTaskFlags, RepairTaskHealthPolicyUpdateDescription, RepairTaskState,
+    };
+
+    #[test]
+    fn test_repair_task_round_trip() {
+        let mut task = RepairTask::new(WString::from("task1"), WString::from("System.Reboot"));
+        task.state = RepairTaskState::Claimed;
+        task.flags = RepairTaskFlags::CancelRequested;
+        task.target = Some(RepairTarget::Nodes(vec![
+            WString::from("n1"),
+            WString::from("n2"),
+        ]));
+        task.impact = Some(RepairImpact::Nodes(vec![RepairNodeImpact {
+            node_name: WString::from("n1"),
+            impact_level: RepairNodeImpactLevel::Restart,
+        }]));
+        task.executor_state = Some(RepairExecutorState {
+            executor: WString::from("exec"),
+            executor_data: WString::from("data"),
+        });
+        task.perform_preparing_health_check = true;
+
+        let raw = task.build_raw();
+        let ffi = raw.as_ffi();
+        assert_eq!(ffi.State, FABRIC_REPAIR_TASK_STATE_CLAIMED);
+        assert_eq!(
+            unsafe { ffi.Scope.as_ref() }.unwrap().Kind,
+            FABRIC_REPAIR_SCOPE_IDENTIFIER_KIND_CLUSTER
+        );
+
+        let back = RepairTask::from(ffi);
+        assert_eq!(back.task_id, task.task_id);
+        assert_eq!(back.action, task.action);
+        assert_eq!(back.state, RepairTaskState::Claimed);
+        assert_eq!(back.flags, RepairTaskFlags::CancelRequested);
+        assert_eq!(back.target, task.target);
+        assert_eq!(back.impact, task.impact);
+        assert_eq!(back.executor_state, task.executor_state);
+        assert!(back.result.is_none());
+        assert!(back.history.is_none());
+        assert!(back.perform_preparing_health_check);
+        assert!(!back.perform_restoring_health_check);
+    }
+
+    #[test]
+    fn test_health_policy_update_flags() {
+        let desc = RepairTaskHealthPolicyUpdateDescription {
+            repair_task_id: WString::from("task1"),
+            version: 3,
+            perform_preparing_health_check: None,
+            perform_restoring_health_check: Some(true),
+        };
+        let raw = desc.build_raw();
+        let ffi = raw.as_ffi();
+        assert_eq!(
+            ffi.Flags,
+            FABRIC_REPAIR_TASK_HEALTH_POLICY_UPDATE_SETTINGS_HONOR_PERFORM_RESTORING_HEALTH_CHECK.0
+                as u32
+        );
+        assert!(ffi.PerformRestoringHealthCheck);
+        assert!(!ffi.PerformPreparingHealthCheck);
+    }
+}
@@ -12,11 +12,15 @@ use mssf_com::{
         FABRIC_DEPLOYED_SERVICE_REPLICA_DETAIL_QUERY_DESCRIPTION,
         FABRIC_DEPLOYED_STATEFUL_SERVICE_REPLICA_DETAIL_QUERY_RESULT_ITEM,
         FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM,
-        FABRIC_QUERY_SERVICE_REPLICA_STATUS, FABRIC_QUERY_SERVICE_REPLICA_STATUS_DOWN,
-        FABRIC_QUERY_SERVICE_REPLICA_STATUS_DROPPED, FABRIC_QUERY_SERVICE_REPLICA_STATUS_INBUILD,
-        FABRIC_QUERY_SERVICE_REPLICA_STATUS_INVALID, FABRIC_QUERY_SERVICE_REPLICA_STATUS_READY,
-        FABRIC_QUERY_SERVICE_REPLICA_STATUS_STANDBY, FABRIC_REMOVE_REPLICA_DESCRIPTION,
-        FABRIC_RESTART_REPLICA_DESCRIPTION, FABRIC_SERVICE_KIND_STATEFUL,
+        FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT, FABRIC_QUERY_SERVICE_REPLICA_STATUS,
+        FABRIC_QUERY_SERVICE_REPLICA_STATUS_DOWN, FABRIC_QUERY_SERVICE_REPLICA_STATUS_DROPPED,
+        FABRIC_QUERY_SERVICE_REPLICA_STATUS_INBUILD, FABRIC_QUERY_SERVICE_REPLICA_STATUS_INVALID,
+        FABRIC_QUERY_SERVICE_REPLICA_STATUS_READY, FABRIC_QUERY_SERVICE_REPLICA_STATUS_STANDBY,
+        FABRIC_REMOTE_REPLICATOR_STATUS, FABRIC_REMOVE_REPLICA_DESCRIPTION,
+        FABRIC_REPLICA_ROLE_ACTIVE_SECONDARY, FABRIC_REPLICA_ROLE_IDLE_SECONDARY,
+        FABRIC_REPLICA_ROLE_PRIMARY, FABRIC_REPLICATOR_QUEUE_STATUS,
+        FABRIC_REPLICATOR_STATUS_QUERY_RESULT, FABRIC_RESTART_REPLICA_DESCRIPTION,
+        FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT, FABRIC_SERVICE_KIND_STATEFUL,
         FABRIC_SERVICE_KIND_STATELESS, FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION,
         FABRIC_SERVICE_REPLICA_QUERY_RESULT_ITEM,
         FABRIC_STATEFUL_SERVICE_REPLICA_QUERY_RESULT_ITEM,
@@ -30,12 +34,18 @@ use crate::{
     types::{HealthState, ReplicaRole},
 };
 
-use super::{QueryReplicatorOperationName, QueryServiceOperationName};
+use super::{
+    LoadMetricReportList, PagingStatus, QueryReplicatorOperationName, QueryServiceOperationName,
+    chaos::raw_slice,
+};
 
 // FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION
+#[derive(Debug, Clone)]
 pub struct ServiceReplicaQueryDescription {
     pub partition_id: GUID,
-    pub replica_id_or_instance_id_filter: Option<i64>, // TODO: reserved fields
+    pub replica_id_or_instance_id_filter: Option<i64>,
+    /// Passed in FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION_EX2 by the query client.
+    pub continuation_token: Option<WString>,
 }
 
 impl From<&ServiceReplicaQueryDescription> for FABRIC_SERVICE_REPLICA_QUERY_DESCRIPTION {
@@ -62,6 +72,11 @@ impl ServiceReplicaList {
     pub fn iter(&self) -> ServiceReplicaListIter<'_> {
         ServiceReplicaListIter::new(self, self)
     }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
 }
 
 type ServiceReplicaListIter<'a> = FabricIter<
@@ -244,8 +259,8 @@ pub struct DeployedStatefulServiceReplicaDetailQueryResult {
     pub current_replicator_operation: QueryReplicatorOperationName,
     pub read_status: ServicePartitionAccessStatus,
     pub write_status: ServicePartitionAccessStatus,
-    // pub reported_load: LoadMetricReportList, // TODO: convert value.ReportedLoad to LoadMetricReportList
-    // pub replicator_status: ReplicatorStatus, // TODO: convert value.ReplicatorStatus to ReplicatorStatus
+    pub reported_load: LoadMetricReportList,
+    pub replicator_status: ReplicatorStatus,
     // pub Reserved: *mut core::ffi::c_void,
 }
 
@@ -256,14 +271,158 @@ impl DeployedStatefulServiceReplicaDetailQueryResult {
             partition_id: value.PartitionId,
             replica_id: value.ReplicaId,
             current_service_operation: (value.CurrentServiceOperation).into(),
-            current_service_operation_start_time_utc: SystemTime::UNIX_EPOCH, // TODO: convert Win32 FILETIME to SystemTime in Unix or Win32 depending on the platform
+            current_service_operation_start_time_utc: value
+                .CurrentServiceOperationStartTimeUtc
+                .into(),
             current_replicator_operation: (value.CurrentReplicatorOperation).into(),
             read_status: (value.ReadStatus).into(),
             write_status: (value.WriteStatus).into(),
+            reported_load: unsafe { value.ReportedLoad.as_ref() }
+                .map(LoadMetricReportList::from)
+                .unwrap_or_default(),
+            replicator_status: unsafe { value.ReplicatorStatus.as_ref() }
+                .map(ReplicatorStatus::from)
+                .unwrap_or(ReplicatorStatus::Invalid),
+        }
+    }
+}
+
+// FABRIC_REPLICATOR_QUEUE_STATUS
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicatorQueueStatus {
+    pub queue_utilization_percentage: u32,
+    pub queue_memory_size: i64,
+    pub first_sequence_number: i64,
+    pub completed_sequence_number: i64,
+    pub committed_sequence_number: i64,
+    pub last_sequence_number: i64,
+}
+
+impl From<&FABRIC_REPLICATOR_QUEUE_STATUS> for ReplicatorQueueStatus {
+    fn from(value: &FABRIC_REPLICATOR_QUEUE_STATUS) -> Self {
+        Self {
+            queue_utilization_percentage: value.QueueUtilizationPercentage,
+            queue_memory_size: value.QueueMemorySize,
+            first_sequence_number: value.FirstSequenceNumber,
+            completed_sequence_number: value.CompletedSequenceNumber,
+            committed_sequence_number: value.CommittedSequenceNumber,
+            last_sequence_number: value.LastSequenceNumber,
+        }
+    }
+}
+
+// FABRIC_REMOTE_REPLICATOR_STATUS
+/// Progress of a secondary as seen by the primary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteReplicatorStatus {
+    pub replica_id: i64,
+    pub last_acknowledgement_processed_time_utc: SystemTime,
+    pub last_received_replication_sequence_number: i64,
+    pub last_applied_replication_sequence_number: i64,
+    pub is_in_build: bool,
+    pub last_received_copy_sequence_number: i64,
+    pub last_applied_copy_sequence_number: i64,
+}
+
+impl From<&FABRIC_REMOTE_REPLICATOR_STATUS> for RemoteReplicatorStatus {
+    fn from(value: &FABRIC_REMOTE_REPLICATOR_STATUS) -> Self {
+        Self {
+            replica_id: value.ReplicaId,
+            last_acknowledgement_processed_time_utc: value
+                .LastAcknowledgementProcessedTimeUtc
+                .into(),
+            last_received_replication_sequence_number: value.LastReceivedReplicationSequenceNumber,
+            last_applied_replication_sequence_number: value.LastAppliedReplicationSequenceNumber,
+            is_in_build: value.IsInBuild,
+            last_received_copy_sequence_number: value.LastReceivedCopySequenceNumber,
+            last_applied_copy_sequence_number: value.LastAppliedCopySequenceNumber,
+        }
+    }
+}
+
+// FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimaryReplicatorStatus {
+    pub replication_queue_status: Option<ReplicatorQueueStatus>,
+    pub remote_replicators: Vec<RemoteReplicatorStatus>,
+}
+
+impl From<&FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT> for PrimaryReplicatorStatus {
+    fn from(value: &FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT) -> Self {
+        let remote_replicators = match unsafe { value.RemoteReplicators.as_ref() } {
+            Some(l) => unsafe { raw_slice(l.Items, l.Count) }
+                .iter()
+                .map(RemoteReplicatorStatus::from)
+                .collect(),
+            None => Vec::new(),
+        };
+        Self {
+            replication_queue_status: unsafe { value.ReplicationQueueStatus.as_ref() }
+                .map(ReplicatorQueueStatus::from),
+            remote_replicators,
+        }
+    }
+}
+
+// FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecondaryReplicatorStatus {
+    pub replication_queue_status: Option<ReplicatorQueueStatus>,
+    pub last_replication_operation_received_time_utc: SystemTime,
+    pub is_in_build: bool,
+    pub copy_queue_status: Option<ReplicatorQueueStatus>,
+    pub last_copy_operation_received_time_utc: SystemTime,
+    pub last_acknowledgement_sent_time_utc: SystemTime,
+}
+
+impl From<&FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT> for SecondaryReplicatorStatus {
+    fn from(value: &FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT) -> Self {
+        Self {
+            replication_queue_status: unsafe { value.ReplicationQueueStatus.as_ref() }
+                .map(ReplicatorQueueStatus::from),
+            last_replication_operation_received_time_utc: value
+                .LastReplicationOperationReceivedTimeUtc
+                .into(),
+            is_in_build: value.IsInBuild,
+            copy_queue_status: unsafe { value.CopyQueueStatus.as_ref() }
+                .map(ReplicatorQueueStatus::from),
+            last_copy_operation_received_time_utc: value.LastCopyOperationReceivedTimeUtc.into(),
+            last_acknowledgement_sent_time_utc: value.LastAcknowledgementSentTimeUtc.into(),
         }
     }
 }
 
+// FABRIC_REPLICATOR_STATUS_QUERY_RESULT
+/// The layout of the status depends on the role of the replica.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplicatorStatus {
+    Invalid,
+    Primary(PrimaryReplicatorStatus),
+    IdleSecondary(SecondaryReplicatorStatus),
+    ActiveSecondary(SecondaryReplicatorStatus),
+}
+
+impl From<&FABRIC_REPLICATOR_STATUS_QUERY_RESULT> for ReplicatorStatus {
+    fn from(value: &FABRIC_REPLICATOR_STATUS_QUERY_RESULT) -> Self {
+        let secondary = || {
+            unsafe {
+                (value.Value as *const FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT).as_ref()
+            }
+            .map(SecondaryReplicatorStatus::from)
+        };
+        let status = match value.Role {
+            FABRIC_REPLICA_ROLE_PRIMARY => unsafe {
+                (value.Value as *const FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT).as_ref()
+            }
+            .map(|p| Self::Primary(p.into())),
+            FABRIC_REPLICA_ROLE_IDLE_SECONDARY => secondary().map(Self::IdleSecondary),
+            FABRIC_REPLICA_ROLE_ACTIVE_SECONDARY => secondary().map(Self::ActiveSecondary),
+            _ => None,
+        };
+        status.unwrap_or(Self::Invalid)
+    }
+}
+
 // FABRIC_DEPLOYED_STATELESS_SERVICE_INSTANCE_QUERY_RESULT_ITEM
 #[derive(Debug, Clone)]
 pub struct DeployedStatelessServiceInstanceQueryResult {
@@ -357,3 +516,114 @@ impl DeployedServiceReplicaDetailQueryResult {
         Self { value }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::FabricTypes::{
+        FABRIC_LOAD_METRIC_REPORT, FABRIC_LOAD_METRIC_REPORT_LIST,
+        FABRIC_REMOTE_REPLICATOR_STATUS_LIST, FABRIC_REPLICA_ROLE_NONE,
+    };
+    use windows_core::Win32::Foundation::FILETIME;
+
+    use super::*;
+
+    #[test]
+    fn test_replicator_status_from_raw() {
+        let mut queue = FABRIC_REPLICATOR_QUEUE_STATUS {
+            QueueUtilizationPercentage: 40,
+            QueueMemorySize: 1024,
+            FirstSequenceNumber: 1,
+            CompletedSequenceNumber: 5,
+            CommittedSequenceNumber: 6,
+            LastSequenceNumber: 9,
+            Reserved: std::ptr::null_mut(),
+        };
+        let mut remotes = [FABRIC_REMOTE_REPLICATOR_STATUS {
+            ReplicaId: 7,
+            LastAcknowledgementProcessedTimeUtc: FILETIME::from_ticks(116_444_736_000_000_000),
+            LastReceivedReplicationSequenceNumber: 9,
+            LastAppliedReplicationSequenceNumber: 8,
+            IsInBuild: false,
+            LastReceivedCopySequenceNumber: 0,
+            LastAppliedCopySequenceNumber: 0,
+            Reserved: std::ptr::null_mut(),
+        }];
+        let mut remote_list = FABRIC_REMOTE_REPLICATOR_STATUS_LIST {
+            Count: remotes.len() as u32,
+            Items: remotes.as_mut_ptr(),
+        };
+        let mut primary = FABRIC_PRIMARY_REPLICATOR_STATUS_QUERY_RESULT {
+            ReplicationQueueStatus: &mut queue,
+            RemoteReplicators: &mut remote_list,
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_REPLICATOR_STATUS_QUERY_RESULT {
+            Role: FABRIC_REPLICA_ROLE_PRIMARY,
+            Value: &mut primary as *mut _ as *mut std::ffi::c_void,
+        };
+        match ReplicatorStatus::from(&raw) {
+            ReplicatorStatus::Primary(p) => {
+                assert_eq!(
+                    p.replication_queue_status
+                        .unwrap()
+                        .queue_utilization_percentage,
+                    40
+                );
+                assert_eq!(p.remote_replicators.len(), 1);
+                assert_eq!(p.remote_replicators[0].replica_id, 7);
+                assert_eq!(
+                    p.remote_replicators[0].last_acknowledgement_processed_time_utc,
+                    SystemTime::UNIX_EPOCH
+                );
+            }
+            other => panic!("unexpected status {other:?}"),
+        }
+
+        let mut secondary = FABRIC_SECONDARY_REPLICATOR_STATUS_QUERY_RESULT {
+            ReplicationQueueStatus: &mut queue,
+            LastReplicationOperationReceivedTimeUtc: FILETIME::from_ticks(0),
+            IsInBuild: true,
+            CopyQueueStatus: std::ptr::null_mut(),
+            LastCopyOperationReceivedTimeUtc: FILETIME::from_ticks(0),
+            LastAcknowledgementSentTimeUtc: FILETIME::from_ticks(0),
+            Reserved: std::ptr::null_mut(),
+        };
+        let raw = FABRIC_REPLICATOR_STATUS_QUERY_RESULT {
+            Role: FABRIC_REPLICA_ROLE_IDLE_SECONDARY,
+            Value: &mut secondary as *mut _ as *mut std::ffi::c_void,
+        };
+        match ReplicatorStatus::from(&raw) {
+            ReplicatorStatus::IdleSecondary(s) => {
+                assert!(s.is_in_build);
+                assert_eq!(s.replication_queue_status.unwrap().last_sequence_number, 9);
+                assert!(s.copy_queue_status.is_none());
+            }
+            other => panic!("unexpected status {other:?}"),
+        }
+
+        let raw = FABRIC_REPLICATOR_STATUS_QUERY_RESULT {
+            Role: FABRIC_REPLICA_ROLE_NONE,
+            Value: std::ptr::null_mut(),
+        };
+        assert_eq!(ReplicatorStatus::from(&raw), ReplicatorStatus::Invalid);
+    }
+
+    #[test]
+    fn test_load_metric_report_list_from_raw() {
+        let name = WString::from("MemoryMB");
+        let mut reports = [FABRIC_LOAD_METRIC_REPORT {
+            Name: name.as_pcwstr(),
+            Value: 12,
+            LastReportedUtc: FILETIME::from_ticks(0),
+            Reserved: std::ptr::null_mut(),
+        }];
+        let raw = FABRIC_LOAD_METRIC_REPORT_LIST {
+            Count: reports.len() as u32,
+            Items: reports.as_mut_ptr(),
+        };
+        let list = LoadMetricReportList::from(&raw);
+        assert_eq!(list.items.len(), 1);
+        assert_eq!(list.items[0].name, name);
+        assert_eq!(list.items[0].value, 12);
+    }
+}
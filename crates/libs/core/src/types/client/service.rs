@@ -5,6 +5,8 @@
 
 use std::{ffi::c_void, marker::PhantomData};
 
+use mssf_com::FabricClient::IFabricServiceDescriptionResult;
+
 use mssf_com::FabricTypes::{
     FABRIC_NAMED_REPARTITION_DESCRIPTION, FABRIC_SERVICE_DESCRIPTION,
     FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL, FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS,
@@ -24,15 +26,26 @@ use mssf_com::FabricTypes::{
 };
 use windows_core::{PCWSTR, WString};
 
-use crate::types::{MoveCost, PartitionSchemeDescription, ServicePackageActivationMode, Uri};
+use crate::{
+    ErrorCode,
+    strings::get_pcwstr_from_opt,
+    types::{
+        MoveCost, PartitionSchemeDescription, ServiceCorrelationDescription,
+        ServiceLoadMetricDescription, ServicePackageActivationMode,
+        ServicePlacementPolicyDescription, ServiceScalingPolicy, Uri,
+    },
+};
+
+use super::service_policy::{ServicePolicies, ServicePoliciesRaw};
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum ServiceDescription {
     // Invalid,
     Stateful(StatefulServiceDescription), // FABRIC_STATEFUL_SERVICE_DESCRIPTION
     Stateless(StatelessServiceDescription), // FABRIC_STATELESS_SERVICE_DESCRIPTION
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct StatefulServiceDescription {
     // common
     // Note: if application_name is not set, SF com api will succeed but the service does not show up in the SF explorer.
@@ -47,19 +60,19 @@ pub struct StatefulServiceDescription {
     target_replica_set_size: i32,
     // common
     placement_contraints: WString,
-    _correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-    _metrics: Vec<WString>,      // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+    correlations: Vec<ServiceCorrelationDescription>,
+    metrics: Vec<ServiceLoadMetricDescription>,
     has_persistent_state: bool,
     // ex1
-    _policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
+    policy_list: Vec<ServicePlacementPolicyDescription>,
     _failover_settings: WString, // TODO: FABRIC_SERVICE_PARTITION_KIND
     // ex2
-    default_move_cost: Option<MoveCost>, // TODO: FABRIC_MOVE_COST
+    default_move_cost: Option<MoveCost>,
     // ex3
     service_package_activation_mode: ServicePackageActivationMode,
-    _service_dns_name: Option<WString>, // TODO: FABRIC_SERVICE_DNS_NAME
+    service_dns_name: Option<WString>,
     // ex4
-    _service_scaling_policys: Vec<WString>, // TODO: FABRIC_SERVICE_SCALING_POLICY
+    service_scaling_policies: Vec<ServiceScalingPolicy>,
 }
 
 impl StatefulServiceDescription {
@@ -80,20 +93,18 @@ impl StatefulServiceDescription {
             min_replica_set_size: 1,
             target_replica_set_size: 1,
             placement_contraints: WString::default(),
-            _correlations: Vec::new(),
-            _metrics: Vec::new(),
+            correlations: Vec::new(),
+            metrics: Vec::new(),
             has_persistent_state: false,
-            _policy_list: Vec::new(),
+            policy_list: Vec::new(),
             _failover_settings: WString::default(),
             default_move_cost: None,
             service_package_activation_mode: ServicePackageActivationMode::default(),
-            _service_dns_name: None,
-            _service_scaling_policys: Vec::new(),
+            service_dns_name: None,
+            service_scaling_policies: Vec::new(),
         }
     }
 
-    // TODO: add more setters for the fields.
-
     pub fn with_initialization_data(mut self, initialization_data: Vec<u8>) -> Self {
         self.initialization_data = Some(initialization_data);
         self
@@ -111,6 +122,34 @@ impl StatefulServiceDescription {
         self.has_persistent_state = has_persistent_state;
         self
     }
+    pub fn with_placement_constraints(mut self, placement_contraints: WString) -> Self {
+        self.placement_contraints = placement_contraints;
+        self
+    }
+    pub fn with_correlations(mut self, correlations: Vec<ServiceCorrelationDescription>) -> Self {
+        self.correlations = correlations;
+        self
+    }
+    pub fn with_load_metrics(mut self, metrics: Vec<ServiceLoadMetricDescription>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    pub fn with_placement_policies(
+        mut self,
+        policy_list: Vec<ServicePlacementPolicyDescription>,
+    ) -> Self {
+        self.policy_list = policy_list;
+        self
+    }
+    /// The DNS name requires the DNS system service to be enabled in the cluster.
+    pub fn with_service_dns_name(mut self, service_dns_name: WString) -> Self {
+        self.service_dns_name = Some(service_dns_name);
+        self
+    }
+    pub fn with_scaling_policies(mut self, scaling_policies: Vec<ServiceScalingPolicy>) -> Self {
+        self.service_scaling_policies = scaling_policies;
+        self
+    }
 
     pub fn with_default_move_cost(mut self, default_move_cost: MoveCost) -> Self {
         self.default_move_cost = Some(default_move_cost);
@@ -125,12 +164,65 @@ impl StatefulServiceDescription {
     }
 }
 
+// getters for the fields
+impl StatefulServiceDescription {
+    pub fn application_name(&self) -> &Uri {
+        &self.application_name
+    }
+    pub fn service_name(&self) -> &Uri {
+        &self.service_name
+    }
+    pub fn service_type_name(&self) -> &WString {
+        &self.service_type_name
+    }
+    pub fn initialization_data(&self) -> Option<&[u8]> {
+        self.initialization_data.as_deref()
+    }
+    pub fn placement_constraints(&self) -> &WString {
+        &self.placement_contraints
+    }
+    pub fn correlations(&self) -> &[ServiceCorrelationDescription] {
+        &self.correlations
+    }
+    pub fn load_metrics(&self) -> &[ServiceLoadMetricDescription] {
+        &self.metrics
+    }
+    pub fn placement_policies(&self) -> &[ServicePlacementPolicyDescription] {
+        &self.policy_list
+    }
+    pub fn default_move_cost(&self) -> Option<&MoveCost> {
+        self.default_move_cost.as_ref()
+    }
+    pub fn service_activation_mode(&self) -> &ServicePackageActivationMode {
+        &self.service_package_activation_mode
+    }
+    pub fn service_dns_name(&self) -> Option<&WString> {
+        self.service_dns_name.as_ref()
+    }
+    pub fn scaling_policies(&self) -> &[ServiceScalingPolicy] {
+        &self.service_scaling_policies
+    }
+    pub fn partition_scheme(&self) -> &PartitionSchemeDescription {
+        &self.partition_scheme
+    }
+    pub fn min_replica_set_size(&self) -> i32 {
+        self.min_replica_set_size
+    }
+    pub fn target_replica_set_size(&self) -> i32 {
+        self.target_replica_set_size
+    }
+    pub fn has_persistent_state(&self) -> bool {
+        self.has_persistent_state
+    }
+}
+
 pub(crate) struct StatefulServiceDescriptionRaw<'a> {
     internal: Box<FABRIC_STATEFUL_SERVICE_DESCRIPTION>,
     _internal_ex1: Box<FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1>,
     _internal_ex2: Box<FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX2>,
     _internal_ex3: Box<FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3>,
     _internal_ex4: Box<FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX4>,
+    _policies: ServicePoliciesRaw<'a>,
     phantom: PhantomData<&'a StatefulServiceDescription>,
 }
 
@@ -143,13 +235,20 @@ impl StatefulServiceDescriptionRaw<'_> {
 impl StatefulServiceDescription {
     // Initializes the internal struct
     fn build_raw(&self) -> StatefulServiceDescriptionRaw<'_> {
+        let policies = ServicePoliciesRaw::new(
+            &self.metrics,
+            &self.correlations,
+            &self.policy_list,
+            &self.service_scaling_policies,
+        );
+        let (scaling_policy_count, scaling_policies) = policies.scaling_policies();
         let ex4 = Box::new(FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX4 {
-            ServiceScalingPolicies: std::ptr::null_mut(), // TODO: support scaling policies
-            ScalingPolicyCount: 0,
+            ServiceScalingPolicies: scaling_policies,
+            ScalingPolicyCount: scaling_policy_count,
             Reserved: std::ptr::null_mut(),
         });
         let ex3 = Box::new(FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3 {
-            ServiceDnsName: windows_core::PCWSTR::null(), // TODO: FABRIC_SERVICE_DNS_NAME
+            ServiceDnsName: get_pcwstr_from_opt(&self.service_dns_name),
             ServicePackageActivationMode: self.service_package_activation_mode.clone().into(),
             Reserved: ex4.as_ref() as *const _ as *mut c_void,
         });
@@ -163,7 +262,7 @@ impl StatefulServiceDescription {
             Reserved: ex3.as_ref() as *const _ as *mut c_void,
         });
         let ex1 = Box::new(FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1 {
-            PolicyList: std::ptr::null_mut(),       // TODO:
+            PolicyList: policies.policy_list(),
             FailoverSettings: std::ptr::null_mut(), // TODO:
             Reserved: ex2.as_ref() as *const _ as *mut c_void,
         });
@@ -173,6 +272,8 @@ impl StatefulServiceDescription {
             .as_ref()
             .map(|v| (v.as_ptr() as *mut u8, v.len() as u32))
             .unwrap_or((std::ptr::null_mut(), 0));
+        let (correlation_count, correlations) = policies.correlations();
+        let (metric_count, metrics) = policies.metrics();
 
         let internal = Box::new(FABRIC_STATEFUL_SERVICE_DESCRIPTION {
             ApplicationName: self.application_name.as_raw(),
@@ -184,11 +285,11 @@ impl StatefulServiceDescription {
             PartitionSchemeDescription: self.partition_scheme.as_raw().1,
             TargetReplicaSetSize: self.target_replica_set_size,
             MinReplicaSetSize: self.min_replica_set_size,
-            PlacementConstraints: self.placement_contraints.as_pcwstr(), // TODO:
-            CorrelationCount: 0,
-            Correlations: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-            Metrics: std::ptr::null_mut(),      // TODO:
-            MetricCount: 0,
+            PlacementConstraints: self.placement_contraints.as_pcwstr(),
+            CorrelationCount: correlation_count,
+            Correlations: correlations,
+            Metrics: metrics,
+            MetricCount: metric_count,
             HasPersistedState: self.has_persistent_state,
             Reserved: ex1.as_ref() as *const _ as *mut c_void,
         });
@@ -199,11 +300,13 @@ impl StatefulServiceDescription {
             _internal_ex2: ex2,
             _internal_ex3: ex3,
             _internal_ex4: ex4,
+            _policies: policies,
             phantom: PhantomData,
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct StatelessServiceDescription {
     // common
     application_name: WString,
@@ -215,17 +318,17 @@ pub struct StatelessServiceDescription {
     instance_count: i32,
     // common
     placement_contraints: WString,
-    _correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-    _metrics: Vec<WString>,      // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+    correlations: Vec<ServiceCorrelationDescription>,
+    metrics: Vec<ServiceLoadMetricDescription>,
     // ex1
-    _policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
+    policy_list: Vec<ServicePlacementPolicyDescription>,
     // ex2
-    default_move_cost: Option<MoveCost>, // TODO: FABRIC_MOVE_COST
+    default_move_cost: Option<MoveCost>,
     // ex3
-    service_package_activation_mode: ServicePackageActivationMode, // TODO: FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE
-    _service_dns_name: WString,                                    // TODO: FABRIC_SERVICE_DNS_NAME
+    service_package_activation_mode: ServicePackageActivationMode,
+    service_dns_name: Option<WString>,
     // ex4
-    _service_scaling_policys: Vec<WString>, // TODO: FABRIC_SERVICE_SCALING_POLICY
+    service_scaling_policies: Vec<ServiceScalingPolicy>,
 }
 impl StatelessServiceDescription {
     pub fn new(
@@ -242,13 +345,13 @@ impl StatelessServiceDescription {
             partition_scheme_description,
             instance_count: 1,
             placement_contraints: WString::default(),
-            _correlations: Vec::new(),
-            _metrics: Vec::new(),
-            _policy_list: Vec::new(),
+            correlations: Vec::new(),
+            metrics: Vec::new(),
+            policy_list: Vec::new(),
             default_move_cost: None,
             service_package_activation_mode: ServicePackageActivationMode::default(),
-            _service_dns_name: WString::default(),
-            _service_scaling_policys: Vec::new(),
+            service_dns_name: None,
+            service_scaling_policies: Vec::new(),
         }
     }
 
@@ -264,6 +367,30 @@ impl StatelessServiceDescription {
         self.placement_contraints = placement_contraints;
         self
     }
+    pub fn with_correlations(mut self, correlations: Vec<ServiceCorrelationDescription>) -> Self {
+        self.correlations = correlations;
+        self
+    }
+    pub fn with_load_metrics(mut self, metrics: Vec<ServiceLoadMetricDescription>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+    pub fn with_placement_policies(
+        mut self,
+        policy_list: Vec<ServicePlacementPolicyDescription>,
+    ) -> Self {
+        self.policy_list = policy_list;
+        self
+    }
+    /// The DNS name requires the DNS system service to be enabled in the cluster.
+    pub fn with_service_dns_name(mut self, service_dns_name: WString) -> Self {
+        self.service_dns_name = Some(service_dns_name);
+        self
+    }
+    pub fn with_scaling_policies(mut self, scaling_policies: Vec<ServiceScalingPolicy>) -> Self {
+        self.service_scaling_policies = scaling_policies;
+        self
+    }
     pub fn with_default_move_cost(mut self, default_move_cost: MoveCost) -> Self {
         self.default_move_cost = Some(default_move_cost);
         self
@@ -276,12 +403,59 @@ impl StatelessServiceDescription {
         self
     }
 }
+// getters for the fields
+impl StatelessServiceDescription {
+    pub fn application_name(&self) -> &WString {
+        &self.application_name
+    }
+    pub fn service_name(&self) -> &WString {
+        &self.service_name
+    }
+    pub fn service_type_name(&self) -> &WString {
+        &self.service_type_name
+    }
+    pub fn initialization_data(&self) -> Option<&[u8]> {
+        self.initialization_data.as_deref()
+    }
+    pub fn placement_constraints(&self) -> &WString {
+        &self.placement_contraints
+    }
+    pub fn correlations(&self) -> &[ServiceCorrelationDescription] {
+        &self.correlations
+    }
+    pub fn load_metrics(&self) -> &[ServiceLoadMetricDescription] {
+        &self.metrics
+    }
+    pub fn placement_policies(&self) -> &[ServicePlacementPolicyDescription] {
+        &self.policy_list
+    }
+    pub fn default_move_cost(&self) -> Option<&MoveCost> {
+        self.default_move_cost.as_ref()
+    }
+    pub fn service_activation_mode(&self) -> &ServicePackageActivationMode {
+        &self.service_package_activation_mode
+    }
+    pub fn service_dns_name(&self) -> Option<&WString> {
+        self.service_dns_name.as_ref()
+    }
+    pub fn scaling_policies(&self) -> &[ServiceScalingPolicy] {
+        &self.service_scaling_policies
+    }
+    pub fn partition_scheme(&self) -> &PartitionSchemeDescription {
+        &self.partition_scheme_description
+    }
+    pub fn instance_count(&self) -> i32 {
+        self.instance_count
+    }
+}
+
 pub(crate) struct StatelessServiceDescriptionRaw<'a> {
     internal: Box<FABRIC_STATELESS_SERVICE_DESCRIPTION>,
     _internal_ex1: Box<FABRIC_STATELESS_SERVICE_DESCRIPTION_EX1>,
     _internal_ex2: Box<FABRIC_STATELESS_SERVICE_DESCRIPTION_EX2>,
     _internal_ex3: Box<FABRIC_STATELESS_SERVICE_DESCRIPTION_EX3>,
     _internal_ex4: Box<FABRIC_STATELESS_SERVICE_DESCRIPTION_EX4>,
+    _policies: ServicePoliciesRaw<'a>,
     phantom: PhantomData<&'a StatelessServiceDescription>,
 }
 impl StatelessServiceDescriptionRaw<'_> {
@@ -292,13 +466,20 @@ impl StatelessServiceDescriptionRaw<'_> {
 
 impl StatelessServiceDescription {
     fn build_raw(&self) -> StatelessServiceDescriptionRaw<'_> {
+        let policies = ServicePoliciesRaw::new(
+            &self.metrics,
+            &self.correlations,
+            &self.policy_list,
+            &self.service_scaling_policies,
+        );
+        let (scaling_policy_count, scaling_policies) = policies.scaling_policies();
         let ex4 = Box::new(FABRIC_STATELESS_SERVICE_DESCRIPTION_EX4 {
-            ServiceScalingPolicies: std::ptr::null_mut(), // TODO: support scaling policies
-            ScalingPolicyCount: 0,
+            ServiceScalingPolicies: scaling_policies,
+            ScalingPolicyCount: scaling_policy_count,
             Reserved: std::ptr::null_mut(),
         });
         let ex3 = Box::new(FABRIC_STATELESS_SERVICE_DESCRIPTION_EX3 {
-            ServiceDnsName: windows_core::PCWSTR::null(), // TODO: FABRIC_SERVICE_DNS_NAME
+            ServiceDnsName: get_pcwstr_from_opt(&self.service_dns_name),
             ServicePackageActivationMode: self.service_package_activation_mode.clone().into(),
             Reserved: ex4.as_ref() as *const _ as *mut c_void,
         });
@@ -312,7 +493,7 @@ impl StatelessServiceDescription {
             Reserved: ex3.as_ref() as *const _ as *mut c_void,
         });
         let ex1 = Box::new(FABRIC_STATELESS_SERVICE_DESCRIPTION_EX1 {
-            PolicyList: std::ptr::null_mut(), // TODO:
+            PolicyList: policies.policy_list(),
             Reserved: ex2.as_ref() as *const _ as *mut c_void,
         });
 
@@ -321,6 +502,8 @@ impl StatelessServiceDescription {
             .as_ref()
             .map(|v| (v.as_ptr() as *mut u8, v.len() as u32))
             .unwrap_or((std::ptr::null_mut(), 0));
+        let (correlation_count, correlations) = policies.correlations();
+        let (metric_count, metrics) = policies.metrics();
 
         let internal = Box::new(FABRIC_STATELESS_SERVICE_DESCRIPTION {
             ApplicationName: FABRIC_URI(self.application_name.as_ptr() as *mut u16),
@@ -331,11 +514,11 @@ impl StatelessServiceDescription {
             PartitionScheme: self.partition_scheme_description.as_raw().0,
             PartitionSchemeDescription: self.partition_scheme_description.as_raw().1,
             InstanceCount: self.instance_count,
-            PlacementConstraints: self.placement_contraints.as_pcwstr(), // TODO:
-            CorrelationCount: 0,
-            Correlations: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-            Metrics: std::ptr::null_mut(),      // TODO:
-            MetricCount: 0,
+            PlacementConstraints: self.placement_contraints.as_pcwstr(),
+            CorrelationCount: correlation_count,
+            Correlations: correlations,
+            Metrics: metrics,
+            MetricCount: metric_count,
             Reserved: ex1.as_ref() as *const _ as *mut c_void,
         });
         StatelessServiceDescriptionRaw {
@@ -344,6 +527,7 @@ impl StatelessServiceDescription {
             _internal_ex2: ex2,
             _internal_ex3: ex3,
             _internal_ex4: ex4,
+            _policies: policies,
             phantom: PhantomData,
         }
     }
@@ -382,6 +566,163 @@ impl ServiceDescription {
     }
 }
 
+// Conversions from the description returned by SF
+// ===================================================
+
+fn init_data_from_raw(data: *const u8, len: u32) -> Option<Vec<u8>> {
+    if data.is_null() || len == 0 {
+        None
+    } else {
+        Some(unsafe { std::slice::from_raw_parts(data, len as usize) }.to_vec())
+    }
+}
+
+fn dns_name_from_raw(name: PCWSTR) -> Option<WString> {
+    Some(WString::from(name)).filter(|n| !n.is_empty())
+}
+
+impl From<&FABRIC_STATEFUL_SERVICE_DESCRIPTION> for StatefulServiceDescription {
+    fn from(value: &FABRIC_STATEFUL_SERVICE_DESCRIPTION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX2).as_ref()
+        });
+        let ex3 = ex2.and_then(|ex2| unsafe {
+            (ex2.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX3).as_ref()
+        });
+        let ex4 = ex3.and_then(|ex3| unsafe {
+            (ex3.Reserved as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION_EX4).as_ref()
+        });
+        let policies = unsafe {
+            ServicePolicies::from_raw(
+                (value.MetricCount, value.Metrics),
+                (value.CorrelationCount, value.Correlations),
+                ex1.map_or(std::ptr::null(), |ex1| ex1.PolicyList),
+                ex4.map_or((0, std::ptr::null()), |ex4| {
+                    (ex4.ScalingPolicyCount, ex4.ServiceScalingPolicies)
+                }),
+            )
+        };
+        Self {
+            application_name: Uri::new(WString::from(PCWSTR(value.ApplicationName.0))),
+            service_name: Uri::new(WString::from(PCWSTR(value.ServiceName.0))),
+            service_type_name: WString::from(value.ServiceTypeName),
+            initialization_data: init_data_from_raw(
+                value.InitializationData,
+                value.InitializationDataSize,
+            ),
+            partition_scheme: unsafe {
+                PartitionSchemeDescription::from_raw(
+                    value.PartitionScheme,
+                    value.PartitionSchemeDescription,
+                )
+            },
+            min_replica_set_size: value.MinReplicaSetSize,
+            target_replica_set_size: value.TargetReplicaSetSize,
+            placement_contraints: WString::from(value.PlacementConstraints),
+            correlations: policies.correlations,
+            metrics: policies.metrics,
+            has_persistent_state: value.HasPersistedState,
+            policy_list: policies.policy_list,
+            _failover_settings: WString::default(),
+            default_move_cost: ex2
+                .filter(|ex2| ex2.IsDefaultMoveCostSpecified)
+                .map(|ex2| ex2.DefaultMoveCost.into()),
+            service_package_activation_mode: ex3
+                .map(|ex3| ex3.ServicePackageActivationMode.into())
+                .unwrap_or_default(),
+            service_dns_name: ex3.and_then(|ex3| dns_name_from_raw(ex3.ServiceDnsName)),
+            service_scaling_policies: policies.scaling_policies,
+        }
+    }
+}
+
+impl From<&FABRIC_STATELESS_SERVICE_DESCRIPTION> for StatelessServiceDescription {
+    fn from(value: &FABRIC_STATELESS_SERVICE_DESCRIPTION) -> Self {
+        let ex1 =
+            unsafe { (value.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX1).as_ref() };
+        let ex2 = ex1.and_then(|ex1| unsafe {
+            (ex1.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX2).as_ref()
+        });
+        let ex3 = ex2.and_then(|ex2| unsafe {
+            (ex2.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX3).as_ref()
+        });
+        let ex4 = ex3.and_then(|ex3| unsafe {
+            (ex3.Reserved as *const FABRIC_STATELESS_SERVICE_DESCRIPTION_EX4).as_ref()
+        });
+        let policies = unsafe {
+            ServicePolicies::from_raw(
+                (value.MetricCount, value.Metrics),
+                (value.CorrelationCount, value.Correlations),
+                ex1.map_or(std::ptr::null(), |ex1| ex1.PolicyList),
+                ex4.map_or((0, std::ptr::null()), |ex4| {
+                    (ex4.ScalingPolicyCount, ex4.ServiceScalingPolicies)
+                }),
+            )
+        };
+        Self {
+            application_name: WString::from(PCWSTR(value.ApplicationName.0)),
+            service_name: WString::from(PCWSTR(value.ServiceName.0)),
+            service_type_name: WString::from(value.ServiceTypeName),
+            initialization_data: init_data_from_raw(
+                value.InitializationData,
+                value.InitializationDataSize,
+            ),
+            partition_scheme_description: unsafe {
+                PartitionSchemeDescription::from_raw(
+                    value.PartitionScheme,
+                    value.PartitionSchemeDescription,
+                )
+            },
+            instance_count: value.InstanceCount,
+            placement_contraints: WString::from(value.PlacementConstraints),
+            correlations: policies.correlations,
+            metrics: policies.metrics,
+            policy_list: policies.policy_list,
+            default_move_cost: ex2
+                .filter(|ex2| ex2.IsDefaultMoveCostSpecified)
+                .map(|ex2| ex2.DefaultMoveCost.into()),
+            service_package_activation_mode: ex3
+                .map(|ex3| ex3.ServicePackageActivationMode.into())
+                .unwrap_or_default(),
+            service_dns_name: ex3.and_then(|ex3| dns_name_from_raw(ex3.ServiceDnsName)),
+            service_scaling_policies: policies.scaling_policies,
+        }
+    }
+}
+
+impl TryFrom<&FABRIC_SERVICE_DESCRIPTION> for ServiceDescription {
+    type Error = crate::Error;
+
+    fn try_from(value: &FABRIC_SERVICE_DESCRIPTION) -> Result<Self, Self::Error> {
+        match value.Kind {
+            FABRIC_SERVICE_DESCRIPTION_KIND_STATEFUL => {
+                unsafe { (value.Value as *const FABRIC_STATEFUL_SERVICE_DESCRIPTION).as_ref() }
+                    .map(|desc| Self::Stateful(desc.into()))
+                    .ok_or(ErrorCode::E_POINTER.into())
+            }
+            FABRIC_SERVICE_DESCRIPTION_KIND_STATELESS => {
+                unsafe { (value.Value as *const FABRIC_STATELESS_SERVICE_DESCRIPTION).as_ref() }
+                    .map(|desc| Self::Stateless(desc.into()))
+                    .ok_or(ErrorCode::E_POINTER.into())
+            }
+            _ => Err(ErrorCode::E_UNEXPECTED.into()),
+        }
+    }
+}
+
+impl TryFrom<&IFabricServiceDescriptionResult> for ServiceDescription {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricServiceDescriptionResult) -> Result<Self, Self::Error> {
+        match unsafe { value.get_Description().as_ref() } {
+            Some(desc) => desc.try_into(),
+            None => Err(ErrorCode::E_POINTER.into()),
+        }
+    }
+}
+
 // Update API payloads
 // ===================================================
 
@@ -479,7 +820,7 @@ bitflags::bitflags! {
     pub struct StatefulServiceUpdateDescriptionFlags: u32 {
         const FABRIC_STATEFUL_SERVICE_NONE = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_NONE.0 as u32;
         const FABRIC_STATEFUL_SERVICE_TARGET_REPLICA_SET_SIZE = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_TARGET_REPLICA_SET_SIZE.0 as u32;
-        const FABRIC_STATEFUL_SERVICE_REPLICA_RESTART_WAIT_DURATION = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_CORRELATIONS.0 as u32;
+        const FABRIC_STATEFUL_SERVICE_REPLICA_RESTART_WAIT_DURATION = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_REPLICA_RESTART_WAIT_DURATION.0 as u32;
         const FABRIC_STATEFUL_SERVICE_QUORUM_LOSS_WAIT_DURATION = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_QUORUM_LOSS_WAIT_DURATION.0 as u32;
         const FABRIC_STATEFUL_SERVICE_STANDBY_REPLICA_KEEP_DURATION = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_STANDBY_REPLICA_KEEP_DURATION.0 as u32;
         const FABRIC_STATEFUL_SERVICE_MIN_REPLICA_SET_SIZE = mssf_com::FabricTypes::FABRIC_STATEFUL_SERVICE_MIN_REPLICA_SET_SIZE.0 as u32;
@@ -497,7 +838,23 @@ impl Default for StatefulServiceUpdateDescriptionFlags {
     }
 }
 
-/// FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION
+bitflags::bitflags! {
+    /// FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_FLAGS
+    /// Indicates what fields are set in the description.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct StatelessServiceUpdateDescriptionFlags: u32 {
+        const FABRIC_STATELESS_SERVICE_NONE = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_NONE.0 as u32;
+        const FABRIC_STATELESS_SERVICE_INSTANCE_COUNT = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT.0 as u32;
+        const FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_POLICY_LIST = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_POLICY_LIST.0 as u32;
+        const FABRIC_STATELESS_SERVICE_CORRELATIONS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_CORRELATIONS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_METRICS = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_METRICS.0 as u32;
+        const FABRIC_STATELESS_SERVICE_MOVE_COST = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_MOVE_COST.0 as u32;
+        const FABRIC_STATELESS_SERVICE_SCALING_POLICY = mssf_com::FabricTypes::FABRIC_STATELESS_SERVICE_SCALING_POLICY.0 as u32;
+    }
+}
+
+/// FABRIC_SERVICE_UPDATE_DESCRIPTION
 pub enum ServiceUpdateDescription {
     Stateful(StatefulServiceUpdateDescription), // FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION
     Stateless(StatelessServiceUpdateDescription), // FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION
@@ -531,14 +888,14 @@ pub struct StatefulServiceUpdateDescription {
     min_replica_set_size: i32,
     // ex3
     placement_contraints: WString,
-    _policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
-    _correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-    _metrics: Vec<WString>,     // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+    policy_list: Vec<ServicePlacementPolicyDescription>,
+    correlations: Vec<ServiceCorrelationDescription>,
+    metrics: Vec<ServiceLoadMetricDescription>,
     // ex4
-    default_move_cost: MoveCost, // TODO: FABRIC_MOVE_COST
+    default_move_cost: MoveCost,
     // ex5
     repartition_description: ServiceRepartitionDescription,
-    _scaling_policys: Vec<WString>, // TODO: FABRIC_SERVICE_SCALING_POLICY
+    scaling_policies: Vec<ServiceScalingPolicy>,
 }
 
 // setters for the fields
@@ -591,6 +948,42 @@ impl StatefulServiceUpdateDescription {
         self
     }
 
+    pub fn with_placement_constraints(mut self, placement_contraints: WString) -> Self {
+        self.flags |=
+            StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_PLACEMENT_CONSTRAINTS;
+        self.placement_contraints = placement_contraints;
+        self
+    }
+
+    /// Replaces all placement policies of the service.
+    pub fn with_placement_policies(
+        mut self,
+        policy_list: Vec<ServicePlacementPolicyDescription>,
+    ) -> Self {
+        self.flags |= StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_POLICY_LIST;
+        self.policy_list = policy_list;
+        self
+    }
+
+    pub fn with_correlations(mut self, correlations: Vec<ServiceCorrelationDescription>) -> Self {
+        self.flags |= StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_CORRELATIONS;
+        self.correlations = correlations;
+        self
+    }
+
+    pub fn with_load_metrics(mut self, metrics: Vec<ServiceLoadMetricDescription>) -> Self {
+        self.flags |= StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_METRICS;
+        self.metrics = metrics;
+        self
+    }
+
+    /// An empty list removes the scaling policies of the service.
+    pub fn with_scaling_policies(mut self, scaling_policies: Vec<ServiceScalingPolicy>) -> Self {
+        self.flags |= StatefulServiceUpdateDescriptionFlags::FABRIC_STATEFUL_SERVICE_SCALING_POLICY;
+        self.scaling_policies = scaling_policies;
+        self
+    }
+
     pub fn with_repartition_description(
         mut self,
         repartition_description: ServiceRepartitionDescription,
@@ -602,31 +995,92 @@ impl StatefulServiceUpdateDescription {
 }
 
 /// FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION
+#[derive(Debug, Default)]
 pub struct StatelessServiceUpdateDescription {
-    pub flags: StatefulServiceUpdateDescriptionFlags,
-    pub instance_count: i32,
+    flags: StatelessServiceUpdateDescriptionFlags,
+    instance_count: i32,
     // ex1
-    pub placement_contraints: WString,
-    pub policy_list: Vec<WString>, // TODO: FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
-    pub correlations: Vec<WString>, // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-    pub metrics: Vec<WString>,     // TODO: FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+    placement_contraints: WString,
+    policy_list: Vec<ServicePlacementPolicyDescription>,
+    correlations: Vec<ServiceCorrelationDescription>,
+    metrics: Vec<ServiceLoadMetricDescription>,
     // ex2
-    pub default_move_cost: Option<MoveCost>, // TODO: FABRIC_MOVE_COST
-    // ex5
-    pub repartition_description: PartitionSchemeDescription, // TODO
-    pub scaling_policys: Vec<WString>,                       // TODO: FABRIC_SERVICE_SCALING_POLICY
+    default_move_cost: MoveCost,
+    // ex3
+    repartition_description: ServiceRepartitionDescription,
+    scaling_policies: Vec<ServiceScalingPolicy>,
 }
 
+// setters for the fields
 impl StatelessServiceUpdateDescription {
-    fn build_raw(&self) -> StatelessServiceUpdateDescriptionRaw {
-        unimplemented!()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_instance_count(mut self, instance_count: i32) -> Self {
+        self.flags |=
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_INSTANCE_COUNT;
+        self.instance_count = instance_count;
+        self
+    }
+
+    pub fn with_move_cost(mut self, default_move_cost: MoveCost) -> Self {
+        self.flags |= StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_MOVE_COST;
+        self.default_move_cost = default_move_cost;
+        self
+    }
+
+    pub fn with_placement_constraints(mut self, placement_contraints: WString) -> Self {
+        self.flags |=
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_PLACEMENT_CONSTRAINTS;
+        self.placement_contraints = placement_contraints;
+        self
+    }
+
+    /// Replaces all placement policies of the service.
+    pub fn with_placement_policies(
+        mut self,
+        policy_list: Vec<ServicePlacementPolicyDescription>,
+    ) -> Self {
+        self.flags |= StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_POLICY_LIST;
+        self.policy_list = policy_list;
+        self
+    }
+
+    pub fn with_correlations(mut self, correlations: Vec<ServiceCorrelationDescription>) -> Self {
+        self.flags |= StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_CORRELATIONS;
+        self.correlations = correlations;
+        self
+    }
+
+    pub fn with_load_metrics(mut self, metrics: Vec<ServiceLoadMetricDescription>) -> Self {
+        self.flags |= StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_METRICS;
+        self.metrics = metrics;
+        self
+    }
+
+    /// An empty list removes the scaling policies of the service.
+    pub fn with_scaling_policies(mut self, scaling_policies: Vec<ServiceScalingPolicy>) -> Self {
+        self.flags |=
+            StatelessServiceUpdateDescriptionFlags::FABRIC_STATELESS_SERVICE_SCALING_POLICY;
+        self.scaling_policies = scaling_policies;
+        self
+    }
+
+    pub fn with_repartition_description(
+        mut self,
+        repartition_description: ServiceRepartitionDescription,
+    ) -> Self {
+        // the ffi field is a ptr so no need to set the flag
+        self.repartition_description = repartition_description;
+        self
     }
 }
 
 /// Temp enum to hold the raw data
 pub(crate) enum ServiceUpdateDescriptionRaw<'a> {
     Stateful(StatefulServiceUpdateDescriptionRaw<'a>),
-    Stateless(StatelessServiceUpdateDescriptionRaw),
+    Stateless(StatelessServiceUpdateDescriptionRaw<'a>),
 }
 
 /// Raw type
@@ -638,6 +1092,7 @@ pub(crate) struct StatefulServiceUpdateDescriptionRaw<'a> {
     _internal_ex4: Box<FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX4>,
     _internal_ex5: Box<FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX5>,
     _repartition_owner: ServiceRepartitionDescriptionRaw<'a>,
+    _policies: ServicePoliciesRaw<'a>,
 }
 
 impl StatefulServiceUpdateDescriptionRaw<'_> {
@@ -646,18 +1101,18 @@ impl StatefulServiceUpdateDescriptionRaw<'_> {
     }
 }
 
-pub(crate) struct StatelessServiceUpdateDescriptionRaw {
-    _internal: Box<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION>,
+pub(crate) struct StatelessServiceUpdateDescriptionRaw<'a> {
+    internal: Box<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION>,
     _internal_ex1: Box<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX1>,
     _internal_ex2: Box<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX2>,
     _internal_ex3: Box<FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX3>,
+    _repartition_owner: ServiceRepartitionDescriptionRaw<'a>,
+    _policies: ServicePoliciesRaw<'a>,
 }
 
-impl StatelessServiceUpdateDescriptionRaw {
-    // TODO:
+impl StatelessServiceUpdateDescriptionRaw<'_> {
     pub(crate) fn as_ffi(&self) -> &FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION {
-        unimplemented!()
-        //self.internal.as_ref()
+        self.internal.as_ref()
     }
 }
 
@@ -680,11 +1135,20 @@ impl ServiceUpdateDescriptionRaw<'_> {
 impl StatefulServiceUpdateDescription {
     pub(crate) fn build_raw(&self) -> StatefulServiceUpdateDescriptionRaw<'_> {
         let repartition_raw = self.repartition_description.as_raw();
+        let policies = ServicePoliciesRaw::new(
+            &self.metrics,
+            &self.correlations,
+            &self.policy_list,
+            &self.scaling_policies,
+        );
+        let (scaling_policy_count, scaling_policies) = policies.scaling_policies();
+        let (correlation_count, correlations) = policies.correlations();
+        let (metric_count, metrics) = policies.metrics();
         let ex5 = Box::new(FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX5 {
             RepartitionDescription: repartition_raw.as_ffi().1 as *const _ as *mut _,
             RepartitionKind: repartition_raw.as_ffi().0,
-            ScalingPolicyCount: 0,
-            ServiceScalingPolicies: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_SCALING_POLICY
+            ScalingPolicyCount: scaling_policy_count,
+            ServiceScalingPolicies: scaling_policies,
             Reserved: std::ptr::null_mut(),
         });
         let ex4 = Box::new(FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX4 {
@@ -692,12 +1156,12 @@ impl StatefulServiceUpdateDescription {
             Reserved: ex5.as_ref() as *const _ as *mut c_void,
         });
         let ex3 = Box::new(FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX3 {
-            PlacementConstraints: self.placement_contraints.as_pcwstr(), // TODO:
-            PolicyList: std::ptr::null_mut(),                            // TODO:
-            CorrelationCount: 0,
-            Correlations: std::ptr::null_mut(), // TODO: FABRIC_SERVICE_CORRELATION_DESCRIPTION
-            Metrics: std::ptr::null_mut(),      // TODO:
-            MetricCount: 0,
+            PlacementConstraints: self.placement_contraints.as_pcwstr(),
+            PolicyList: policies.policy_list(),
+            CorrelationCount: correlation_count,
+            Correlations: correlations,
+            Metrics: metrics,
+            MetricCount: metric_count,
             Reserved: ex4.as_ref() as *const _ as *mut c_void,
         });
         let ex2 = Box::new(FABRIC_STATEFUL_SERVICE_UPDATE_DESCRIPTION_EX2 {
@@ -725,6 +1189,116 @@ impl StatefulServiceUpdateDescription {
             _internal_ex4: ex4,
             _internal_ex5: ex5,
             _repartition_owner: repartition_raw,
+            _policies: policies,
+        }
+    }
+}
+
+impl StatelessServiceUpdateDescription {
+    pub(crate) fn build_raw(&self) -> StatelessServiceUpdateDescriptionRaw<'_> {
+        let repartition_raw = self.repartition_description.as_raw();
+        let policies = ServicePoliciesRaw::new(
+            &self.metrics,
+            &self.correlations,
+            &self.policy_list,
+            &self.scaling_policies,
+        );
+        let (scaling_policy_count, scaling_policies) = policies.scaling_policies();
+        let (correlation_count, correlations) = policies.correlations();
+        let (metric_count, metrics) = policies.metrics();
+        let ex3 = Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX3 {
+            RepartitionKind: repartition_raw.as_ffi().0,
+            RepartitionDescription: repartition_raw.as_ffi().1 as *const _ as *mut _,
+            ScalingPolicyCount: scaling_policy_count,
+            ServiceScalingPolicies: scaling_policies,
+            Reserved: std::ptr::null_mut(),
+        });
+        let ex2 = Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX2 {
+            DefaultMoveCost: self.default_move_cost.clone().into(),
+            Reserved: ex3.as_ref() as *const _ as *mut c_void,
+        });
+        let ex1 = Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION_EX1 {
+            PlacementConstraints: self.placement_contraints.as_pcwstr(),
+            PolicyList: policies.policy_list(),
+            CorrelationCount: correlation_count,
+            Correlations: correlations,
+            MetricCount: metric_count,
+            Metrics: metrics,
+            Reserved: ex2.as_ref() as *const _ as *mut c_void,
+        });
+
+        let internal = Box::new(FABRIC_STATELESS_SERVICE_UPDATE_DESCRIPTION {
+            Flags: self.flags.bits(),
+            InstanceCount: self.instance_count,
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+
+        StatelessServiceUpdateDescriptionRaw {
+            internal,
+            _internal_ex1: ex1,
+            _internal_ex2: ex2,
+            _internal_ex3: ex3,
+            _repartition_owner: repartition_raw,
+            _policies: policies,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        NamedPartitionSchemeDescription, ServiceLoadMetricWeight,
+        UniformIn64PartitionSchemeDescription,
+    };
+
+    #[test]
+    fn test_service_description_round_trip() {
+        let desc = ServiceDescription::Stateful(
+            StatefulServiceDescription::new(
+                Uri::from("fabric:/app"),
+                Uri::from("fabric:/app/svc"),
+                WString::from("SvcType"),
+                PartitionSchemeDescription::Int64Range(UniformIn64PartitionSchemeDescription::new(
+                    3, 0, 99,
+                )),
+            )
+            .with_initialization_data(vec![1, 2, 3])
+            .with_min_replica_set_size(2)
+            .with_target_replica_set_size(3)
+            .with_has_persistent_state(true)
+            .with_placement_constraints(WString::from("NodeType == fe"))
+            .with_load_metrics(vec![
+                ServiceLoadMetricDescription::new(
+                    WString::from("MemoryMB"),
+                    ServiceLoadMetricWeight::High,
+                )
+                .with_default_load(100, 50),
+            ])
+            .with_default_move_cost(MoveCost::Low)
+            .with_service_dns_name(WString::from("svc.app")),
+        );
+        let raw = desc.build_raw();
+        let back = ServiceDescription::try_from(&raw.as_ffi()).unwrap();
+        assert_eq!(back, desc);
+
+        let desc = ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                WString::from("fabric:/app"),
+                WString::from("fabric:/app/svc2"),
+                WString::from("SvcType2"),
+                PartitionSchemeDescription::Named(NamedPartitionSchemeDescription::new(vec![
+                    WString::from("a"),
+                    WString::from("b"),
+                ])),
+            )
+            .with_instance_count(-1)
+            .with_placement_policies(vec![
+                ServicePlacementPolicyDescription::InvalidDomain(WString::from("fd:/dc2")),
+            ]),
+        );
+        let raw = desc.build_raw();
+        let back = ServiceDescription::try_from(&raw.as_ffi()).unwrap();
+        assert_eq!(back, desc);
+    }
+}
@@ -0,0 +1,252 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::marker::PhantomData;
+
+use mssf_com::{
+    FabricClient::IFabricServiceGroupDescriptionResult,
+    FabricTypes::{
+        FABRIC_SERVICE_DESCRIPTION, FABRIC_SERVICE_GROUP_DESCRIPTION,
+        FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION, FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION,
+        FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION, FABRIC_SERVICE_UPDATE_DESCRIPTION,
+    },
+};
+
+use super::{
+    chaos::raw_slice,
+    service::{ServiceDescriptionRaw, ServiceUpdateDescriptionRaw},
+};
+use crate::{
+    ErrorCode, PCWSTR, WString,
+    types::{ServiceDescription, ServiceLoadMetricDescription, ServiceUpdateDescription, Uri},
+};
+
+/// FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION
+/// A member service of the group. The service_name must be prefixed by
+/// the group service name, i.e. "fabric:/app/group#member".
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceGroupMemberDescription {
+    pub service_type_name: WString,
+    pub service_name: Uri,
+    pub initialization_data: Option<Vec<u8>>,
+    pub load_metrics: Vec<ServiceLoadMetricDescription>,
+}
+
+impl ServiceGroupMemberDescription {
+    pub fn new(service_type_name: WString, service_name: Uri) -> Self {
+        Self {
+            service_type_name,
+            service_name,
+            initialization_data: None,
+            load_metrics: Vec::new(),
+        }
+    }
+}
+
+impl From<&FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION> for ServiceGroupMemberDescription {
+    fn from(value: &FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION) -> Self {
+        let init_data =
+            unsafe { raw_slice(value.InitializationData, value.InitializationDataSize) };
+        Self {
+            service_type_name: WString::from(value.ServiceType),
+            service_name: Uri::new(WString::from(PCWSTR(value.ServiceName.0))),
+            initialization_data: (!init_data.is_empty()).then(|| init_data.to_vec()),
+            load_metrics: unsafe { raw_slice(value.Metrics, value.MetricCount) }
+                .iter()
+                .map(ServiceLoadMetricDescription::from)
+                .collect(),
+        }
+    }
+}
+
+/// FABRIC_SERVICE_GROUP_DESCRIPTION
+/// The description of the group service itself, i.e. partitioning and replica counts,
+/// plus the members hosted in each replica of the group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceGroupDescription {
+    pub description: ServiceDescription,
+    pub members: Vec<ServiceGroupMemberDescription>,
+}
+
+impl ServiceGroupDescription {
+    pub fn new(description: ServiceDescription) -> Self {
+        Self {
+            description,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn with_member(mut self, member: ServiceGroupMemberDescription) -> Self {
+        self.members.push(member);
+        self
+    }
+}
+
+/// Holder for memories passed to the FFI
+pub(crate) struct ServiceGroupDescriptionRaw<'a> {
+    internal: Box<FABRIC_SERVICE_GROUP_DESCRIPTION>,
+    _description: Box<FABRIC_SERVICE_DESCRIPTION>,
+    _description_owner: ServiceDescriptionRaw<'a>,
+    _members: Vec<FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION>,
+    _metrics: Vec<Vec<FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION>>,
+    phantom: PhantomData<&'a ServiceGroupDescription>,
+}
+
+impl ServiceGroupDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_SERVICE_GROUP_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+impl ServiceGroupDescription {
+    pub(crate) fn build_raw(&self) -> ServiceGroupDescriptionRaw<'_> {
+        let description_owner = self.description.build_raw();
+        let description = Box::new(description_owner.as_ffi());
+        let metrics = self
+            .members
+            .iter()
+            .map(|m| {
+                m.load_metrics
+                    .iter()
+                    .map(FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION::from)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let members = self
+            .members
+            .iter()
+            .zip(metrics.iter())
+            .map(|(m, metrics)| {
+                let (init_data, init_data_len) = m
+                    .initialization_data
+                    .as_ref()
+                    .map(|v| (v.as_ptr(), v.len() as u32))
+                    .unwrap_or((std::ptr::null(), 0));
+                FABRIC_SERVICE_GROUP_MEMBER_DESCRIPTION {
+                    ServiceType: m.service_type_name.as_pcwstr(),
+                    ServiceName: m.service_name.as_raw(),
+                    InitializationDataSize: init_data_len,
+                    InitializationData: init_data,
+                    MetricCount: metrics.len() as u32,
+                    Metrics: metrics.as_ptr() as *mut _,
+                    Reserved: std::ptr::null_mut(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let internal = Box::new(FABRIC_SERVICE_GROUP_DESCRIPTION {
+            Description: description.as_ref() as *const _ as *mut _,
+            MemberCount: members.len() as u32,
+            MemberDescriptions: members.as_ptr() as *mut _,
+            Reserved: std::ptr::null_mut(),
+        });
+        ServiceGroupDescriptionRaw {
+            internal,
+            _description: description,
+            _description_owner: description_owner,
+            _members: members,
+            _metrics: metrics,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl TryFrom<&FABRIC_SERVICE_GROUP_DESCRIPTION> for ServiceGroupDescription {
+    type Error = crate::Error;
+
+    fn try_from(value: &FABRIC_SERVICE_GROUP_DESCRIPTION) -> Result<Self, Self::Error> {
+        let description = unsafe { value.Description.as_ref() }
+            .ok_or(crate::Error::from(ErrorCode::E_POINTER))?
+            .try_into()?;
+        Ok(Self {
+            description,
+            members: unsafe { raw_slice(value.MemberDescriptions, value.MemberCount) }
+                .iter()
+                .map(ServiceGroupMemberDescription::from)
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<&IFabricServiceGroupDescriptionResult> for ServiceGroupDescription {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricServiceGroupDescriptionResult) -> Result<Self, Self::Error> {
+        match unsafe { value.get_Description().as_ref() } {
+            Some(desc) => desc.try_into(),
+            None => Err(ErrorCode::E_POINTER.into()),
+        }
+    }
+}
+
+/// Holder for memories of FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION passed to the FFI
+pub(crate) struct ServiceGroupUpdateDescriptionRaw<'a> {
+    internal: Box<FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION>,
+    _description: Box<FABRIC_SERVICE_UPDATE_DESCRIPTION>,
+    _description_owner: ServiceUpdateDescriptionRaw<'a>,
+}
+
+impl ServiceGroupUpdateDescriptionRaw<'_> {
+    /// Updates of a group apply to the group service, members cannot be changed.
+    pub(crate) fn new(desc: &ServiceUpdateDescription) -> ServiceGroupUpdateDescriptionRaw<'_> {
+        let description_owner = desc.build_raw();
+        let description = Box::new(description_owner.as_ffi());
+        let internal = Box::new(FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION {
+            Description: description.as_ref() as *const _ as *mut _,
+            Reserved: std::ptr::null_mut(),
+        });
+        ServiceGroupUpdateDescriptionRaw {
+            internal,
+            _description: description,
+            _description_owner: description_owner,
+        }
+    }
+
+    pub(crate) fn as_ffi(&self) -> &FABRIC_SERVICE_GROUP_UPDATE_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        PartitionSchemeDescription, ServiceLoadMetricWeight, StatelessServiceDescription,
+    };
+
+    #[test]
+    fn test_service_group_description_round_trip() {
+        let mut member = ServiceGroupMemberDescription::new(
+            WString::from("MemberAType"),
+            Uri::from("fabric:/app/group#a"),
+        );
+        member.initialization_data = Some(vec![7]);
+        member.load_metrics = vec![
+            ServiceLoadMetricDescription::new(
+                WString::from("Requests"),
+                ServiceLoadMetricWeight::Medium,
+            )
+            .with_default_load(5, 0),
+        ];
+        let desc = ServiceGroupDescription::new(ServiceDescription::Stateless(
+            StatelessServiceDescription::new(
+                WString::from("fabric:/app"),
+                WString::from("fabric:/app/group"),
+                WString::from("GroupType"),
+                PartitionSchemeDescription::Singleton,
+            )
+            .with_instance_count(2),
+        ))
+        .with_member(member)
+        .with_member(ServiceGroupMemberDescription::new(
+            WString::from("MemberBType"),
+            Uri::from("fabric:/app/group#b"),
+        ));
+
+        let raw = desc.build_raw();
+        assert_eq!(raw.as_ffi().MemberCount, 2);
+        let back = ServiceGroupDescription::try_from(raw.as_ffi()).unwrap();
+        assert_eq!(back, desc);
+    }
+}
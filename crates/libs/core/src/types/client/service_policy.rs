@@ -0,0 +1,720 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+// Load metrics, correlations, placement and scaling policies shared by the
+// stateful and stateless service (update) descriptions.
+
+use std::{any::Any, ffi::c_void, marker::PhantomData};
+
+use mssf_com::FabricTypes::{
+    FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN, FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN_DESCRIPTION,
+    FABRIC_PLACEMENT_POLICY_NONPARTIALLY_PLACE_SERVICE,
+    FABRIC_PLACEMENT_POLICY_NONPARTIALLY_PLACE_SERVICE_DESCRIPTION,
+    FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN,
+    FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN_DESCRIPTION,
+    FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN, FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DESCRIPTION,
+    FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DISTRIBUTION,
+    FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DISTRIBUTION_DESCRIPTION, FABRIC_SCALING_MECHANISM,
+    FABRIC_SCALING_MECHANISM_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION,
+    FABRIC_SCALING_MECHANISM_KIND_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION,
+    FABRIC_SCALING_MECHANISM_KIND_SCALE_PARTITION_INSTANCE_COUNT,
+    FABRIC_SCALING_MECHANISM_PARTITION_INSTANCE_COUNT, FABRIC_SCALING_TRIGGER,
+    FABRIC_SCALING_TRIGGER_AVERAGE_PARTITION_LOAD, FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD,
+    FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD_EX1,
+    FABRIC_SCALING_TRIGGER_KIND_AVERAGE_PARTITION_LOAD,
+    FABRIC_SCALING_TRIGGER_KIND_AVERAGE_SERVICE_LOAD, FABRIC_SERVICE_CORRELATION_DESCRIPTION,
+    FABRIC_SERVICE_CORRELATION_SCHEME, FABRIC_SERVICE_CORRELATION_SCHEME_AFFINITY,
+    FABRIC_SERVICE_CORRELATION_SCHEME_ALIGNED_AFFINITY,
+    FABRIC_SERVICE_CORRELATION_SCHEME_NONALIGNED_AFFINITY, FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION,
+    FABRIC_SERVICE_LOAD_METRIC_WEIGHT, FABRIC_SERVICE_LOAD_METRIC_WEIGHT_HIGH,
+    FABRIC_SERVICE_LOAD_METRIC_WEIGHT_LOW, FABRIC_SERVICE_LOAD_METRIC_WEIGHT_MEDIUM,
+    FABRIC_SERVICE_LOAD_METRIC_WEIGHT_ZERO, FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION,
+    FABRIC_SERVICE_PLACEMENT_POLICY_LIST, FABRIC_SERVICE_SCALING_POLICY,
+};
+
+use super::chaos::raw_slice;
+use crate::{PCWSTR, WString, types::Uri};
+
+// FABRIC_SERVICE_LOAD_METRIC_WEIGHT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ServiceLoadMetricWeight {
+    Zero,
+    #[default]
+    Low,
+    Medium,
+    High,
+}
+
+impl From<FABRIC_SERVICE_LOAD_METRIC_WEIGHT> for ServiceLoadMetricWeight {
+    fn from(value: FABRIC_SERVICE_LOAD_METRIC_WEIGHT) -> Self {
+        match value {
+            FABRIC_SERVICE_LOAD_METRIC_WEIGHT_ZERO => Self::Zero,
+            FABRIC_SERVICE_LOAD_METRIC_WEIGHT_MEDIUM => Self::Medium,
+            FABRIC_SERVICE_LOAD_METRIC_WEIGHT_HIGH => Self::High,
+            _ => Self::Low,
+        }
+    }
+}
+
+impl From<ServiceLoadMetricWeight> for FABRIC_SERVICE_LOAD_METRIC_WEIGHT {
+    fn from(value: ServiceLoadMetricWeight) -> Self {
+        match value {
+            ServiceLoadMetricWeight::Zero => FABRIC_SERVICE_LOAD_METRIC_WEIGHT_ZERO,
+            ServiceLoadMetricWeight::Low => FABRIC_SERVICE_LOAD_METRIC_WEIGHT_LOW,
+            ServiceLoadMetricWeight::Medium => FABRIC_SERVICE_LOAD_METRIC_WEIGHT_MEDIUM,
+            ServiceLoadMetricWeight::High => FABRIC_SERVICE_LOAD_METRIC_WEIGHT_HIGH,
+        }
+    }
+}
+
+/// FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION
+/// For stateless services primary_default_load is the default load of an instance,
+/// and secondary_default_load is ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceLoadMetricDescription {
+    pub name: WString,
+    pub weight: ServiceLoadMetricWeight,
+    pub primary_default_load: u32,
+    pub secondary_default_load: u32,
+}
+
+impl ServiceLoadMetricDescription {
+    pub fn new(name: WString, weight: ServiceLoadMetricWeight) -> Self {
+        Self {
+            name,
+            weight,
+            primary_default_load: 0,
+            secondary_default_load: 0,
+        }
+    }
+
+    pub fn with_default_load(mut self, primary: u32, secondary: u32) -> Self {
+        self.primary_default_load = primary;
+        self.secondary_default_load = secondary;
+        self
+    }
+}
+
+impl From<&FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION> for ServiceLoadMetricDescription {
+    fn from(value: &FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION) -> Self {
+        Self {
+            name: WString::from(value.Name),
+            weight: value.Weight.into(),
+            primary_default_load: value.PrimaryDefaultLoad,
+            secondary_default_load: value.SecondaryDefaultLoad,
+        }
+    }
+}
+
+impl From<&ServiceLoadMetricDescription> for FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION {
+    fn from(value: &ServiceLoadMetricDescription) -> Self {
+        Self {
+            Name: value.name.as_pcwstr(),
+            Weight: value.weight.into(),
+            PrimaryDefaultLoad: value.primary_default_load,
+            SecondaryDefaultLoad: value.secondary_default_load,
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_CORRELATION_SCHEME
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceCorrelationScheme {
+    Invalid,
+    Affinity,
+    AlignedAffinity,
+    NonAlignedAffinity,
+}
+
+impl From<FABRIC_SERVICE_CORRELATION_SCHEME> for ServiceCorrelationScheme {
+    fn from(value: FABRIC_SERVICE_CORRELATION_SCHEME) -> Self {
+        match value {
+            FABRIC_SERVICE_CORRELATION_SCHEME_AFFINITY => Self::Affinity,
+            FABRIC_SERVICE_CORRELATION_SCHEME_ALIGNED_AFFINITY => Self::AlignedAffinity,
+            FABRIC_SERVICE_CORRELATION_SCHEME_NONALIGNED_AFFINITY => Self::NonAlignedAffinity,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<ServiceCorrelationScheme> for FABRIC_SERVICE_CORRELATION_SCHEME {
+    fn from(value: ServiceCorrelationScheme) -> Self {
+        match value {
+            ServiceCorrelationScheme::Invalid => FABRIC_SERVICE_CORRELATION_SCHEME(0),
+            ServiceCorrelationScheme::Affinity => FABRIC_SERVICE_CORRELATION_SCHEME_AFFINITY,
+            ServiceCorrelationScheme::AlignedAffinity => {
+                FABRIC_SERVICE_CORRELATION_SCHEME_ALIGNED_AFFINITY
+            }
+            ServiceCorrelationScheme::NonAlignedAffinity => {
+                FABRIC_SERVICE_CORRELATION_SCHEME_NONALIGNED_AFFINITY
+            }
+        }
+    }
+}
+
+/// FABRIC_SERVICE_CORRELATION_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceCorrelationDescription {
+    pub service_name: Uri,
+    pub scheme: ServiceCorrelationScheme,
+}
+
+impl From<&FABRIC_SERVICE_CORRELATION_DESCRIPTION> for ServiceCorrelationDescription {
+    fn from(value: &FABRIC_SERVICE_CORRELATION_DESCRIPTION) -> Self {
+        Self {
+            service_name: Uri::new(WString::from(PCWSTR(value.ServiceName.0))),
+            scheme: value.Scheme.into(),
+        }
+    }
+}
+
+impl From<&ServiceCorrelationDescription> for FABRIC_SERVICE_CORRELATION_DESCRIPTION {
+    fn from(value: &ServiceCorrelationDescription) -> Self {
+        Self {
+            ServiceName: value.service_name.as_raw(),
+            Scheme: value.scheme.into(),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION
+/// The domain names are fault domains, for example "fd:/dc1".
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServicePlacementPolicyDescription {
+    InvalidDomain(WString),
+    RequiredDomain(WString),
+    PreferredPrimaryDomain(WString),
+    RequiredDomainDistribution,
+    NonPartiallyPlaceService,
+}
+
+/// FABRIC_SCALING_TRIGGER
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalingTrigger {
+    AveragePartitionLoad {
+        metric_name: WString,
+        lower_load_threshold: f64,
+        upper_load_threshold: f64,
+        scale_interval_in_seconds: u32,
+    },
+    AverageServiceLoad {
+        metric_name: WString,
+        lower_load_threshold: f64,
+        upper_load_threshold: f64,
+        scale_interval_in_seconds: u32,
+        use_only_primary_load: bool,
+    },
+}
+
+/// FABRIC_SCALING_MECHANISM
+/// PartitionInstanceCount is only valid for stateless services.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalingMechanism {
+    PartitionInstanceCount {
+        minimum_instance_count: i32,
+        maximum_instance_count: i32,
+        scale_increment: i32,
+    },
+    AddRemoveIncrementalNamedPartition {
+        minimum_partition_count: i32,
+        maximum_partition_count: i32,
+        scale_increment: i32,
+    },
+}
+
+/// FABRIC_SERVICE_SCALING_POLICY
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceScalingPolicy {
+    pub trigger: ScalingTrigger,
+    pub mechanism: ScalingMechanism,
+}
+
+impl ServicePlacementPolicyDescription {
+    /// Returns None for policy types unknown to mssf.
+    /// # Safety
+    /// Value must point to the description struct matching the Type.
+    unsafe fn from_raw(value: &FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION) -> Option<Self> {
+        match value.Type {
+            FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN => unsafe {
+                (value.Value as *const FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN_DESCRIPTION).as_ref()
+            }
+            .map(|d| Self::InvalidDomain(WString::from(d.InvalidFaultDomain))),
+            FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN => unsafe {
+                (value.Value as *const FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DESCRIPTION).as_ref()
+            }
+            .map(|d| Self::RequiredDomain(WString::from(d.RequiredFaultDomain))),
+            FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN => unsafe {
+                (value.Value as *const FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN_DESCRIPTION)
+                    .as_ref()
+            }
+            .map(|d| Self::PreferredPrimaryDomain(WString::from(d.PreferredPrimaryFaultDomain))),
+            FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DISTRIBUTION => {
+                Some(Self::RequiredDomainDistribution)
+            }
+            FABRIC_PLACEMENT_POLICY_NONPARTIALLY_PLACE_SERVICE => {
+                Some(Self::NonPartiallyPlaceService)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ScalingTrigger {
+    /// # Safety
+    /// ScalingTriggerDescription must point to the struct matching the kind.
+    unsafe fn from_raw(value: &FABRIC_SCALING_TRIGGER) -> Option<Self> {
+        match value.ScalingTriggerKind {
+            FABRIC_SCALING_TRIGGER_KIND_AVERAGE_PARTITION_LOAD => unsafe {
+                (value.ScalingTriggerDescription
+                    as *const FABRIC_SCALING_TRIGGER_AVERAGE_PARTITION_LOAD)
+                    .as_ref()
+            }
+            .map(|d| Self::AveragePartitionLoad {
+                metric_name: WString::from(d.MetricName),
+                lower_load_threshold: d.LowerLoadThreshold,
+                upper_load_threshold: d.UpperLoadThreshold,
+                scale_interval_in_seconds: d.ScaleIntervalInSeconds,
+            }),
+            FABRIC_SCALING_TRIGGER_KIND_AVERAGE_SERVICE_LOAD => unsafe {
+                (value.ScalingTriggerDescription
+                    as *const FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD)
+                    .as_ref()
+            }
+            .map(|d| Self::AverageServiceLoad {
+                metric_name: WString::from(d.MetricName),
+                lower_load_threshold: d.LowerLoadThreshold,
+                upper_load_threshold: d.UpperLoadThreshold,
+                scale_interval_in_seconds: d.ScaleIntervalInSeconds,
+                use_only_primary_load: unsafe {
+                    (d.Reserved as *const FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD_EX1).as_ref()
+                }
+                .is_some_and(|ex1| ex1.UseOnlyPrimaryLoad),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl ScalingMechanism {
+    /// # Safety
+    /// ScalingMechanismDescription must point to the struct matching the kind.
+    unsafe fn from_raw(value: &FABRIC_SCALING_MECHANISM) -> Option<Self> {
+        match value.ScalingMechanismKind {
+            FABRIC_SCALING_MECHANISM_KIND_SCALE_PARTITION_INSTANCE_COUNT => unsafe {
+                (value.ScalingMechanismDescription
+                    as *const FABRIC_SCALING_MECHANISM_PARTITION_INSTANCE_COUNT)
+                    .as_ref()
+            }
+            .map(|d| Self::PartitionInstanceCount {
+                minimum_instance_count: d.MinimumInstanceCount,
+                maximum_instance_count: d.MaximumInstanceCount,
+                scale_increment: d.ScaleIncrement,
+            }),
+            FABRIC_SCALING_MECHANISM_KIND_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION => unsafe {
+                (value.ScalingMechanismDescription
+                    as *const FABRIC_SCALING_MECHANISM_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION)
+                    .as_ref()
+            }
+            .map(|d| Self::AddRemoveIncrementalNamedPartition {
+                minimum_partition_count: d.MinimumPartitionCount,
+                maximum_partition_count: d.MaximumPartitionCount,
+                scale_increment: d.ScaleIncrement,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Policies of a service description returned by SF.
+/// Entries of kinds unknown to mssf are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct ServicePolicies {
+    pub metrics: Vec<ServiceLoadMetricDescription>,
+    pub correlations: Vec<ServiceCorrelationDescription>,
+    pub policy_list: Vec<ServicePlacementPolicyDescription>,
+    pub scaling_policies: Vec<ServiceScalingPolicy>,
+}
+
+impl ServicePolicies {
+    /// # Safety
+    /// The pointers must be valid for the counts, as returned by SF.
+    pub(crate) unsafe fn from_raw(
+        metrics: (u32, *const FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION),
+        correlations: (u32, *const FABRIC_SERVICE_CORRELATION_DESCRIPTION),
+        policy_list: *const FABRIC_SERVICE_PLACEMENT_POLICY_LIST,
+        scaling_policies: (u32, *const FABRIC_SERVICE_SCALING_POLICY),
+    ) -> Self {
+        let policies = match unsafe { policy_list.as_ref() } {
+            Some(l) => unsafe { raw_slice(l.Policies, l.PolicyCount) },
+            None => &[],
+        };
+        Self {
+            metrics: unsafe { raw_slice(metrics.1, metrics.0) }
+                .iter()
+                .map(From::from)
+                .collect(),
+            correlations: unsafe { raw_slice(correlations.1, correlations.0) }
+                .iter()
+                .map(From::from)
+                .collect(),
+            policy_list: policies
+                .iter()
+                .filter_map(|p| unsafe { ServicePlacementPolicyDescription::from_raw(p) })
+                .collect(),
+            scaling_policies: unsafe { raw_slice(scaling_policies.1, scaling_policies.0) }
+                .iter()
+                .filter_map(|p| {
+                    Some(ServiceScalingPolicy {
+                        trigger: unsafe {
+                            ScalingTrigger::from_raw(&p.ServiceScalingPolicyTrigger)
+                        }?,
+                        mechanism: unsafe {
+                            ScalingMechanism::from_raw(&p.ServiceScalingPolicyMechanism)
+                        }?,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Holds the FFI buffers of the policies of a service description.
+/// Pointers in the FFI structs point into the boxes owned here and into
+/// the strings of the rust descriptions, hence the lifetime.
+pub(crate) struct ServicePoliciesRaw<'a> {
+    metrics: Vec<FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION>,
+    correlations: Vec<FABRIC_SERVICE_CORRELATION_DESCRIPTION>,
+    policy_list: Option<Box<FABRIC_SERVICE_PLACEMENT_POLICY_LIST>>,
+    _policies: Vec<FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION>,
+    scaling_policies: Vec<FABRIC_SERVICE_SCALING_POLICY>,
+    // Keeps the structs pointed to by the policy Value pointers alive.
+    _values: Vec<Box<dyn Any>>,
+    phantom: PhantomData<&'a ()>,
+}
+
+/// Boxes the value and returns a pointer to it for the FFI struct.
+fn keep<T: 'static>(values: &mut Vec<Box<dyn Any>>, value: T) -> *mut c_void {
+    let b = Box::new(value);
+    let ptr = b.as_ref() as *const T as *mut c_void;
+    values.push(b);
+    ptr
+}
+
+impl<'a> ServicePoliciesRaw<'a> {
+    pub(crate) fn new(
+        metrics: &'a [ServiceLoadMetricDescription],
+        correlations: &'a [ServiceCorrelationDescription],
+        policies: &'a [ServicePlacementPolicyDescription],
+        scaling_policies: &'a [ServiceScalingPolicy],
+    ) -> Self {
+        let mut values = Vec::new();
+        let policies = policies
+            .iter()
+            .map(|p| match p {
+                ServicePlacementPolicyDescription::InvalidDomain(d) => {
+                    FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION {
+                        Type: FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN,
+                        Value: keep(
+                            &mut values,
+                            FABRIC_PLACEMENT_POLICY_INVALID_DOMAIN_DESCRIPTION {
+                                InvalidFaultDomain: d.as_pcwstr(),
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    }
+                }
+                ServicePlacementPolicyDescription::RequiredDomain(d) => {
+                    FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION {
+                        Type: FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN,
+                        Value: keep(
+                            &mut values,
+                            FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DESCRIPTION {
+                                RequiredFaultDomain: d.as_pcwstr(),
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    }
+                }
+                ServicePlacementPolicyDescription::PreferredPrimaryDomain(d) => {
+                    FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION {
+                        Type: FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN,
+                        Value: keep(
+                            &mut values,
+                            FABRIC_PLACEMENT_POLICY_PREFERRED_PRIMARY_DOMAIN_DESCRIPTION {
+                                PreferredPrimaryFaultDomain: d.as_pcwstr(),
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    }
+                }
+                ServicePlacementPolicyDescription::RequiredDomainDistribution => {
+                    FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION {
+                        Type: FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DISTRIBUTION,
+                        Value: keep(
+                            &mut values,
+                            FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DISTRIBUTION_DESCRIPTION {
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    }
+                }
+                ServicePlacementPolicyDescription::NonPartiallyPlaceService => {
+                    FABRIC_SERVICE_PLACEMENT_POLICY_DESCRIPTION {
+                        Type: FABRIC_PLACEMENT_POLICY_NONPARTIALLY_PLACE_SERVICE,
+                        Value: keep(
+                            &mut values,
+                            FABRIC_PLACEMENT_POLICY_NONPARTIALLY_PLACE_SERVICE_DESCRIPTION {
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let policy_list = (!policies.is_empty()).then(|| {
+            Box::new(FABRIC_SERVICE_PLACEMENT_POLICY_LIST {
+                PolicyCount: policies.len() as u32,
+                Policies: policies.as_ptr() as *mut _,
+            })
+        });
+
+        let scaling_policies = scaling_policies
+            .iter()
+            .map(|p| {
+                let trigger = match &p.trigger {
+                    ScalingTrigger::AveragePartitionLoad {
+                        metric_name,
+                        lower_load_threshold,
+                        upper_load_threshold,
+                        scale_interval_in_seconds,
+                    } => FABRIC_SCALING_TRIGGER {
+                        ScalingTriggerKind: FABRIC_SCALING_TRIGGER_KIND_AVERAGE_PARTITION_LOAD,
+                        ScalingTriggerDescription: keep(
+                            &mut values,
+                            FABRIC_SCALING_TRIGGER_AVERAGE_PARTITION_LOAD {
+                                MetricName: metric_name.as_pcwstr(),
+                                LowerLoadThreshold: *lower_load_threshold,
+                                UpperLoadThreshold: *upper_load_threshold,
+                                ScaleIntervalInSeconds: *scale_interval_in_seconds,
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    },
+                    ScalingTrigger::AverageServiceLoad {
+                        metric_name,
+                        lower_load_threshold,
+                        upper_load_threshold,
+                        scale_interval_in_seconds,
+                        use_only_primary_load,
+                    } => {
+                        let ex1 = keep(
+                            &mut values,
+                            FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD_EX1 {
+                                UseOnlyPrimaryLoad: *use_only_primary_load,
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        );
+                        FABRIC_SCALING_TRIGGER {
+                            ScalingTriggerKind: FABRIC_SCALING_TRIGGER_KIND_AVERAGE_SERVICE_LOAD,
+                            ScalingTriggerDescription: keep(
+                                &mut values,
+                                FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD {
+                                    MetricName: metric_name.as_pcwstr(),
+                                    LowerLoadThreshold: *lower_load_threshold,
+                                    UpperLoadThreshold: *upper_load_threshold,
+                                    ScaleIntervalInSeconds: *scale_interval_in_seconds,
+                                    Reserved: ex1,
+                                },
+                            ),
+                        }
+                    }
+                };
+                let mechanism = match &p.mechanism {
+                    ScalingMechanism::PartitionInstanceCount {
+                        minimum_instance_count,
+                        maximum_instance_count,
+                        scale_increment,
+                    } => FABRIC_SCALING_MECHANISM {
+                        ScalingMechanismKind:
+                            FABRIC_SCALING_MECHANISM_KIND_SCALE_PARTITION_INSTANCE_COUNT,
+                        ScalingMechanismDescription: keep(
+                            &mut values,
+                            FABRIC_SCALING_MECHANISM_PARTITION_INSTANCE_COUNT {
+                                MaximumInstanceCount: *maximum_instance_count,
+                                MinimumInstanceCount: *minimum_instance_count,
+                                ScaleIncrement: *scale_increment,
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    },
+                    ScalingMechanism::AddRemoveIncrementalNamedPartition {
+                        minimum_partition_count,
+                        maximum_partition_count,
+                        scale_increment,
+                    } => FABRIC_SCALING_MECHANISM {
+                        ScalingMechanismKind:
+                            FABRIC_SCALING_MECHANISM_KIND_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION,
+                        ScalingMechanismDescription: keep(
+                            &mut values,
+                            FABRIC_SCALING_MECHANISM_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION {
+                                MaximumPartitionCount: *maximum_partition_count,
+                                MinimumPartitionCount: *minimum_partition_count,
+                                ScaleIncrement: *scale_increment,
+                                Reserved: std::ptr::null_mut(),
+                            },
+                        ),
+                    },
+                };
+                FABRIC_SERVICE_SCALING_POLICY {
+                    ServiceScalingPolicyTrigger: trigger,
+                    ServiceScalingPolicyMechanism: mechanism,
+                    Reserved: std::ptr::null_mut(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            metrics: metrics.iter().map(From::from).collect(),
+            correlations: correlations.iter().map(From::from).collect(),
+            policy_list,
+            _policies: policies,
+            scaling_policies,
+            _values: values,
+            phantom: PhantomData,
+        }
+    }
+
+    /// (count, ptr) of the metrics. Null when empty.
+    pub(crate) fn metrics(&self) -> (u32, *mut FABRIC_SERVICE_LOAD_METRIC_DESCRIPTION) {
+        raw_parts(&self.metrics)
+    }
+
+    pub(crate) fn correlations(&self) -> (u32, *mut FABRIC_SERVICE_CORRELATION_DESCRIPTION) {
+        raw_parts(&self.correlations)
+    }
+
+    pub(crate) fn policy_list(&self) -> *mut FABRIC_SERVICE_PLACEMENT_POLICY_LIST {
+        self.policy_list
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |l| l.as_ref() as *const _ as *mut _)
+    }
+
+    pub(crate) fn scaling_policies(&self) -> (u32, *mut FABRIC_SERVICE_SCALING_POLICY) {
+        raw_parts(&self.scaling_policies)
+    }
+}
+
+fn raw_parts<T>(v: &[T]) -> (u32, *mut T) {
+    if v.is_empty() {
+        (0, std::ptr::null_mut())
+    } else {
+        (v.len() as u32, v.as_ptr() as *mut T)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_policies_raw() {
+        let metrics = vec![
+            ServiceLoadMetricDescription::new(WString::from("CPU"), ServiceLoadMetricWeight::High)
+                .with_default_load(10, 2),
+        ];
+        let correlations = vec![ServiceCorrelationDescription {
+            service_name: Uri::from("fabric:/app/other"),
+            scheme: ServiceCorrelationScheme::Affinity,
+        }];
+        let policies = vec![
+            ServicePlacementPolicyDescription::RequiredDomain(WString::from("fd:/dc1")),
+            ServicePlacementPolicyDescription::RequiredDomainDistribution,
+        ];
+        let scaling = vec![ServiceScalingPolicy {
+            trigger: ScalingTrigger::AverageServiceLoad {
+                metric_name: WString::from("CPU"),
+                lower_load_threshold: 1.0,
+                upper_load_threshold: 5.0,
+                scale_interval_in_seconds: 60,
+                use_only_primary_load: true,
+            },
+            mechanism: ScalingMechanism::AddRemoveIncrementalNamedPartition {
+                minimum_partition_count: 1,
+                maximum_partition_count: 5,
+                scale_increment: 1,
+            },
+        }];
+        let raw = ServicePoliciesRaw::new(&metrics, &correlations, &policies, &scaling);
+
+        let (count, ptr) = raw.metrics();
+        assert_eq!(count, 1);
+        let m = unsafe { ptr.as_ref() }.unwrap();
+        assert_eq!(WString::from(m.Name), metrics[0].name);
+        assert_eq!(m.Weight, FABRIC_SERVICE_LOAD_METRIC_WEIGHT_HIGH);
+        assert_eq!((m.PrimaryDefaultLoad, m.SecondaryDefaultLoad), (10, 2));
+
+        let (count, ptr) = raw.correlations();
+        assert_eq!(count, 1);
+        let c = unsafe { ptr.as_ref() }.unwrap();
+        assert_eq!(c.Scheme, FABRIC_SERVICE_CORRELATION_SCHEME_AFFINITY);
+
+        let list = unsafe { raw.policy_list().as_ref() }.unwrap();
+        assert_eq!(list.PolicyCount, 2);
+        let p = unsafe { list.Policies.as_ref() }.unwrap();
+        assert_eq!(p.Type, FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN);
+        let d = unsafe {
+            (p.Value as *const FABRIC_PLACEMENT_POLICY_REQUIRED_DOMAIN_DESCRIPTION).as_ref()
+        }
+        .unwrap();
+        assert_eq!(
+            WString::from(d.RequiredFaultDomain),
+            WString::from("fd:/dc1")
+        );
+
+        let (count, ptr) = raw.scaling_policies();
+        assert_eq!(count, 1);
+        let s = unsafe { ptr.as_ref() }.unwrap();
+        let t = unsafe {
+            (s.ServiceScalingPolicyTrigger.ScalingTriggerDescription
+                as *const FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD)
+                .as_ref()
+        }
+        .unwrap();
+        assert_eq!(t.ScaleIntervalInSeconds, 60);
+        let ex1 = unsafe {
+            (t.Reserved as *const FABRIC_SCALING_TRIGGER_AVERAGE_SERVICE_LOAD_EX1).as_ref()
+        }
+        .unwrap();
+        assert!(ex1.UseOnlyPrimaryLoad);
+        assert_eq!(
+            s.ServiceScalingPolicyMechanism.ScalingMechanismKind,
+            FABRIC_SCALING_MECHANISM_KIND_ADD_REMOVE_INCREMENTAL_NAMED_PARTITION
+        );
+
+        // Reading the raw policies back gives the same descriptions.
+        let (metric_count, metric_ptr) = raw.metrics();
+        let (correlation_count, correlation_ptr) = raw.correlations();
+        let (scaling_count, scaling_ptr) = raw.scaling_policies();
+        let back = unsafe {
+            ServicePolicies::from_raw(
+                (metric_count, metric_ptr),
+                (correlation_count, correlation_ptr),
+                raw.policy_list(),
+                (scaling_count, scaling_ptr),
+            )
+        };
+        assert_eq!(back.metrics, metrics);
+        assert_eq!(back.correlations, correlations);
+        assert_eq!(back.policy_list, policies);
+        assert_eq!(back.scaling_policies, scaling);
+
+        // Empty policies are passed as null.
+        let raw = ServicePoliciesRaw::new(&[], &[], &[], &[]);
+        assert!(raw.metrics().1.is_null());
+        assert!(raw.policy_list().is_null());
+    }
+}
@@ -0,0 +1,302 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Query types for services and service types.
+
+use crate::WString;
+use mssf_com::{
+    FabricClient::{IFabricGetServiceListResult2, IFabricGetServiceTypeListResult},
+    FabricTypes::{
+        FABRIC_QUERY_SERVICE_STATUS, FABRIC_QUERY_SERVICE_STATUS_ACTIVE,
+        FABRIC_QUERY_SERVICE_STATUS_CREATING, FABRIC_QUERY_SERVICE_STATUS_DELETING,
+        FABRIC_QUERY_SERVICE_STATUS_FAILED, FABRIC_QUERY_SERVICE_STATUS_UPGRADING,
+        FABRIC_SERVICE_KIND_STATEFUL, FABRIC_SERVICE_KIND_STATELESS,
+        FABRIC_SERVICE_QUERY_RESULT_ITEM, FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION,
+        FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM, FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM, FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM_EX2, FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION,
+        FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM, FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM_EX1,
+        FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM_EX2, FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION,
+    },
+};
+
+use super::{PagedQueryDescription, PagingStatus, fault::uri_from_raw};
+use crate::{
+    iter::{FabricIter, FabricListAccessor},
+    strings::{WStringWrap, get_pcwstr_from_opt},
+    types::{HealthState, Uri},
+};
+
+// FABRIC_SERVICE_QUERY_DESCRIPTION
+#[derive(Debug, Default, Clone)]
+pub struct ServiceQueryDescription {
+    pub application_name: Uri,
+    pub service_name_filter: Option<Uri>,
+    pub service_type_name_filter: Option<WString>,
+    pub paged_query: PagedQueryDescription,
+}
+
+// FABRIC_QUERY_SERVICE_STATUS
+#[derive(Debug, PartialEq, Clone)]
+pub enum QueryServiceStatus {
+    Unknown,
+    Active,
+    Upgrading,
+    Deleting,
+    Creating,
+    Failed,
+}
+
+impl From<FABRIC_QUERY_SERVICE_STATUS> for QueryServiceStatus {
+    fn from(value: FABRIC_QUERY_SERVICE_STATUS) -> Self {
+        match value {
+            FABRIC_QUERY_SERVICE_STATUS_ACTIVE => Self::Active,
+            FABRIC_QUERY_SERVICE_STATUS_UPGRADING => Self::Upgrading,
+            FABRIC_QUERY_SERVICE_STATUS_DELETING => Self::Deleting,
+            FABRIC_QUERY_SERVICE_STATUS_CREATING => Self::Creating,
+            FABRIC_QUERY_SERVICE_STATUS_FAILED => Self::Failed,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+// FABRIC_SERVICE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub enum ServiceQueryResult {
+    Invalid,
+    Stateful(StatefulServiceQueryResult),
+    Stateless(StatelessServiceQueryResult),
+}
+
+impl From<&FABRIC_SERVICE_QUERY_RESULT_ITEM> for ServiceQueryResult {
+    fn from(value: &FABRIC_SERVICE_QUERY_RESULT_ITEM) -> Self {
+        match value.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => {
+                let raw = unsafe {
+                    (value.Value as *const FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM)
+                        .as_ref()
+                        .unwrap()
+                };
+                Self::Stateful(raw.into())
+            }
+            FABRIC_SERVICE_KIND_STATELESS => {
+                let raw = unsafe {
+                    (value.Value as *const FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM)
+                        .as_ref()
+                        .unwrap()
+                };
+                Self::Stateless(raw.into())
+            }
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct StatefulServiceQueryResult {
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub service_manifest_version: WString,
+    pub has_persisted_state: bool,
+    pub health_state: HealthState,
+    pub service_status: QueryServiceStatus,
+    pub is_service_group: bool,
+}
+
+impl From<&FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM> for StatefulServiceQueryResult {
+    fn from(value: &FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        let ex2 = ex1.and_then(|e| unsafe {
+            (e.Reserved as *const FABRIC_STATEFUL_SERVICE_QUERY_RESULT_ITEM_EX2).as_ref()
+        });
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            service_type_name: WStringWrap::from(value.ServiceTypeName).into(),
+            service_manifest_version: WStringWrap::from(value.ServiceManifestVersion).into(),
+            has_persisted_state: value.HasPersistedState,
+            health_state: (&value.HealthState).into(),
+            service_status: ex1.map_or(QueryServiceStatus::Unknown, |e| e.ServiceStatus.into()),
+            is_service_group: ex2.is_some_and(|e| e.IsServiceGroup),
+        }
+    }
+}
+
+// FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM
+#[derive(Debug, Clone)]
+pub struct StatelessServiceQueryResult {
+    pub service_name: Uri,
+    pub service_type_name: WString,
+    pub service_manifest_version: WString,
+    pub health_state: HealthState,
+    pub service_status: QueryServiceStatus,
+    pub is_service_group: bool,
+}
+
+impl From<&FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM> for StatelessServiceQueryResult {
+    fn from(value: &FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        let ex2 = ex1.and_then(|e| unsafe {
+            (e.Reserved as *const FABRIC_STATELESS_SERVICE_QUERY_RESULT_ITEM_EX2).as_ref()
+        });
+        Self {
+            service_name: uri_from_raw(value.ServiceName),
+            service_type_name: WStringWrap::from(value.ServiceTypeName).into(),
+            service_manifest_version: WStringWrap::from(value.ServiceManifestVersion).into(),
+            health_state: (&value.HealthState).into(),
+            service_status: ex1.map_or(QueryServiceStatus::Unknown, |e| e.ServiceStatus.into()),
+            is_service_group: ex2.is_some_and(|e| e.IsServiceGroup),
+        }
+    }
+}
+
+// IFabricGetServiceListResult2
+pub struct ServiceList {
+    com: IFabricGetServiceListResult2,
+}
+
+type ServiceListIter<'a> =
+    FabricIter<'a, FABRIC_SERVICE_QUERY_RESULT_ITEM, ServiceQueryResult, ServiceList>;
+
+impl FabricListAccessor<FABRIC_SERVICE_QUERY_RESULT_ITEM> for ServiceList {
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ServiceList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_SERVICE_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ServiceList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ServiceList {
+    pub fn new(com: IFabricGetServiceListResult2) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ServiceListIter<'_> {
+        ServiceListIter::new(self, self)
+    }
+
+    pub fn get_paging_status(&self) -> Option<PagingStatus> {
+        let raw = unsafe { self.com.get_PagingStatus().as_ref() }?;
+        Some(raw.into())
+    }
+}
+
+// FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION
+#[derive(Debug, Default)]
+pub struct ServiceTypeQueryDescription {
+    pub application_type_name: WString,
+    pub application_type_version: WString,
+    pub service_type_name_filter: Option<WString>,
+}
+
+impl From<&ServiceTypeQueryDescription> for FABRIC_SERVICE_TYPE_QUERY_DESCRIPTION {
+    fn from(value: &ServiceTypeQueryDescription) -> Self {
+        Self {
+            ApplicationTypeName: value.application_type_name.as_pcwstr(),
+            ApplicationTypeVersion: value.application_type_version.as_pcwstr(),
+            ServiceTypeNameFilter: get_pcwstr_from_opt(&value.service_type_name_filter),
+            Reserved: std::ptr::null_mut(),
+        }
+    }
+}
+
+// FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM
+// Only the common parts of the service type description are surfaced.
+#[derive(Debug, Clone)]
+pub struct ServiceTypeQueryResult {
+    pub service_type_name: WString,
+    /// True for stateful service types.
+    pub is_stateful: bool,
+    pub placement_constraints: WString,
+    /// Stateful only.
+    pub has_persisted_state: bool,
+    /// Stateless only.
+    pub use_implicit_host: bool,
+    pub service_manifest_version: WString,
+    pub service_manifest_name: WString,
+}
+
+impl From<&FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM> for ServiceTypeQueryResult {
+    fn from(value: &FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM) -> Self {
+        let ex1 = unsafe {
+            (value.Reserved as *const FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM_EX1).as_ref()
+        };
+        let mut res = Self {
+            service_type_name: WString::new(),
+            is_stateful: false,
+            placement_constraints: WString::new(),
+            has_persisted_state: false,
+            use_implicit_host: false,
+            service_manifest_version: WStringWrap::from(value.ServiceManifestVersion).into(),
+            service_manifest_name: ex1
+                .map(|e| WStringWrap::from(e.ServiceManifestName).into())
+                .unwrap_or_default(),
+        };
+        let Some(desc) = (unsafe { value.ServiceTypeDescription.as_ref() }) else {
+            return res;
+        };
+        match desc.Kind {
+            FABRIC_SERVICE_KIND_STATEFUL => {
+                if let Some(raw) = unsafe {
+                    (desc.Value as *const FABRIC_STATEFUL_SERVICE_TYPE_DESCRIPTION).as_ref()
+                } {
+                    res.is_stateful = true;
+                    res.service_type_name = WStringWrap::from(raw.ServiceTypeName).into();
+                    res.placement_constraints = WStringWrap::from(raw.PlacementConstraints).into();
+                    res.has_persisted_state = raw.HasPersistedState;
+                }
+            }
+            FABRIC_SERVICE_KIND_STATELESS => {
+                if let Some(raw) = unsafe {
+                    (desc.Value as *const FABRIC_STATELESS_SERVICE_TYPE_DESCRIPTION).as_ref()
+                } {
+                    res.service_type_name = WStringWrap::from(raw.ServiceTypeName).into();
+                    res.placement_constraints = WStringWrap::from(raw.PlacementConstraints).into();
+                    res.use_implicit_host = raw.UseImplicitHost;
+                }
+            }
+            _ => {}
+        }
+        res
+    }
+}
+
+// IFabricGetServiceTypeListResult
+pub struct ServiceTypeList {
+    com: IFabricGetServiceTypeListResult,
+}
+
+type ServiceTypeListIter<'a> =
+    FabricIter<'a, FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM, ServiceTypeQueryResult, ServiceTypeList>;
+
+impl FabricListAccessor<FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM> for ServiceTypeList {
+    fn get_count(&self) -> u32 {
+        let raw = unsafe { self.com.get_ServiceTypeList().as_ref() };
+        raw.unwrap().Count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_SERVICE_TYPE_QUERY_RESULT_ITEM {
+        let raw = unsafe { self.com.get_ServiceTypeList().as_ref() };
+        raw.unwrap().Items
+    }
+}
+
+impl ServiceTypeList {
+    pub fn new(com: IFabricGetServiceTypeListResult) -> Self {
+        Self { com }
+    }
+
+    pub fn iter(&self) -> ServiceTypeListIter<'_> {
+        ServiceTypeListIter::new(self, self)
+    }
+}
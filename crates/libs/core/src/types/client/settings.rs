@@ -3,7 +3,17 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use mssf_com::FabricClient::IFabricClientSettings2;
+use std::{ffi::c_void, num::NonZeroU32};
+
+use mssf_com::{
+    FabricClient::IFabricClientSettings2,
+    FabricTypes::{
+        FABRIC_CLIENT_SETTINGS, FABRIC_CLIENT_SETTINGS_EX1, FABRIC_CLIENT_SETTINGS_EX2,
+        FABRIC_CLIENT_SETTINGS_EX3, FABRIC_CLIENT_SETTINGS_EX4,
+    },
+};
+
+use crate::{WString, strings::WStringWrap};
 
 /// A idiomatic Rust version of FABRIC_CLIENT_SETTINGS
 ///
@@ -13,19 +23,324 @@ use mssf_com::FabricClient::IFabricClientSettings2;
 /// # use std::num::NonZeroU32;
 /// # use mssf_core::types::FabricClientSettings;
 /// let my_settings = FabricClientSettings {
-///  // TODO: uncomment in next PR
-///  // PartitionLocationCacheLimit: Some(NonZeroU32::new(1).expect("Non-zero value")),
+///  PartitionLocationCacheLimit: Some(NonZeroU32::new(1).expect("Non-zero value")),
 ///  // Any other hypothetical settings you're interested in here,
 ///  ..Default::default()
 /// };
 /// ```
-#[derive(Default)]
-pub struct FabricClientSettings {}
+/// Fields left as None keep the value the client currently has.
+/// Settings where zero has a meaning (e.g. disables the feature) are plain u32,
+/// the rest reject zero at the type level.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[allow(non_snake_case, reason = "Consistency with underlying API")]
+pub struct FabricClientSettings {
+    /// Max number of partitions cached by the client for service resolution.
+    pub PartitionLocationCacheLimit: Option<NonZeroU32>,
+    pub ServiceChangePollIntervalInSeconds: Option<NonZeroU32>,
+    pub ConnectionInitializationTimeoutInSeconds: Option<NonZeroU32>,
+    /// Zero disables the keep alive.
+    pub KeepAliveIntervalInSeconds: Option<u32>,
+    pub HealthOperationTimeoutInSeconds: Option<NonZeroU32>,
+    /// Zero sends the health reports immediately without batching.
+    pub HealthReportSendIntervalInSeconds: Option<u32>,
+    // FABRIC_CLIENT_SETTINGS_EX1
+    pub ClientFriendlyName: Option<WString>,
+    pub PartitionLocationCacheBucketCount: Option<NonZeroU32>,
+    pub HealthReportRetrySendIntervalInSeconds: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX2
+    pub NotificationGatewayConnectionTimeoutInSeconds: Option<NonZeroU32>,
+    pub NotificationCacheUpdateTimeoutInSeconds: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX3
+    pub AuthTokenBufferSize: Option<NonZeroU32>,
+    // FABRIC_CLIENT_SETTINGS_EX4
+    /// Zero disables the idle timeout.
+    pub ConnectionIdleTimeoutInSeconds: Option<u32>,
+}
+
+/// Reads the settings and the extensions chained in Reserved.
+/// Zero values of the NonZeroU32 fields are read as None.
+impl From<&FABRIC_CLIENT_SETTINGS> for FabricClientSettings {
+    fn from(value: &FABRIC_CLIENT_SETTINGS) -> Self {
+        let ex1 = unsafe { (value.Reserved as *const FABRIC_CLIENT_SETTINGS_EX1).as_ref() };
+        let ex2 =
+            ex1.and_then(|e| unsafe { (e.Reserved as *const FABRIC_CLIENT_SETTINGS_EX2).as_ref() });
+        let ex3 =
+            ex2.and_then(|e| unsafe { (e.Reserved as *const FABRIC_CLIENT_SETTINGS_EX3).as_ref() });
+        let ex4 =
+            ex3.and_then(|e| unsafe { (e.Reserved as *const FABRIC_CLIENT_SETTINGS_EX4).as_ref() });
+        Self {
+            PartitionLocationCacheLimit: NonZeroU32::new(value.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: NonZeroU32::new(
+                value.ServiceChangePollIntervalInSeconds,
+            ),
+            ConnectionInitializationTimeoutInSeconds: NonZeroU32::new(
+                value.ConnectionInitializationTimeoutInSeconds,
+            ),
+            KeepAliveIntervalInSeconds: Some(value.KeepAliveIntervalInSeconds),
+            HealthOperationTimeoutInSeconds: NonZeroU32::new(value.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: Some(value.HealthReportSendIntervalInSeconds),
+            ClientFriendlyName: ex1
+                .filter(|e| !e.ClientFriendlyName.is_null())
+                .map(|e| WStringWrap::from(e.ClientFriendlyName).into()),
+            PartitionLocationCacheBucketCount: ex1
+                .and_then(|e| NonZeroU32::new(e.PartitionLocationCacheBucketCount)),
+            HealthReportRetrySendIntervalInSeconds: ex1
+                .and_then(|e| NonZeroU32::new(e.HealthReportRetrySendIntervalInSeconds)),
+            NotificationGatewayConnectionTimeoutInSeconds: ex2
+                .and_then(|e| NonZeroU32::new(e.NotificationGatewayConnectionTimeoutInSeconds)),
+            NotificationCacheUpdateTimeoutInSeconds: ex2
+                .and_then(|e| NonZeroU32::new(e.NotificationCacheUpdateTimeoutInSeconds)),
+            AuthTokenBufferSize: ex3.and_then(|e| NonZeroU32::new(e.AuthTokenBufferSize)),
+            ConnectionIdleTimeoutInSeconds: ex4.map(|e| e.ConnectionIdleTimeoutInSeconds),
+        }
+    }
+}
+
+/// Owns the FABRIC_CLIENT_SETTINGS and its full extension chain.
+pub(crate) struct FabricClientSettingsRaw {
+    internal: Box<FABRIC_CLIENT_SETTINGS>,
+    _ex1: Box<FABRIC_CLIENT_SETTINGS_EX1>,
+    _ex2: Box<FABRIC_CLIENT_SETTINGS_EX2>,
+    _ex3: Box<FABRIC_CLIENT_SETTINGS_EX3>,
+    _ex4: Box<FABRIC_CLIENT_SETTINGS_EX4>,
+    // Referenced by ex1. Moving the WString does not move its buffer.
+    _client_friendly_name: Option<WString>,
+}
+
+impl FabricClientSettingsRaw {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_CLIENT_SETTINGS {
+        self.internal.as_ref()
+    }
+}
 
 impl FabricClientSettings {
+    /// Fields set in self override the ones in base.
+    fn merge(&self, base: FabricClientSettings) -> FabricClientSettings {
+        FabricClientSettings {
+            PartitionLocationCacheLimit: self
+                .PartitionLocationCacheLimit
+                .or(base.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: self
+                .ServiceChangePollIntervalInSeconds
+                .or(base.ServiceChangePollIntervalInSeconds),
+            ConnectionInitializationTimeoutInSeconds: self
+                .ConnectionInitializationTimeoutInSeconds
+                .or(base.ConnectionInitializationTimeoutInSeconds),
+            KeepAliveIntervalInSeconds: self
+                .KeepAliveIntervalInSeconds
+                .or(base.KeepAliveIntervalInSeconds),
+            HealthOperationTimeoutInSeconds: self
+                .HealthOperationTimeoutInSeconds
+                .or(base.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: self
+                .HealthReportSendIntervalInSeconds
+                .or(base.HealthReportSendIntervalInSeconds),
+            ClientFriendlyName: self.ClientFriendlyName.clone().or(base.ClientFriendlyName),
+            PartitionLocationCacheBucketCount: self
+                .PartitionLocationCacheBucketCount
+                .or(base.PartitionLocationCacheBucketCount),
+            HealthReportRetrySendIntervalInSeconds: self
+                .HealthReportRetrySendIntervalInSeconds
+                .or(base.HealthReportRetrySendIntervalInSeconds),
+            NotificationGatewayConnectionTimeoutInSeconds: self
+                .NotificationGatewayConnectionTimeoutInSeconds
+                .or(base.NotificationGatewayConnectionTimeoutInSeconds),
+            NotificationCacheUpdateTimeoutInSeconds: self
+                .NotificationCacheUpdateTimeoutInSeconds
+                .or(base.NotificationCacheUpdateTimeoutInSeconds),
+            AuthTokenBufferSize: self.AuthTokenBufferSize.or(base.AuthTokenBufferSize),
+            ConnectionIdleTimeoutInSeconds: self
+                .ConnectionIdleTimeoutInSeconds
+                .or(base.ConnectionIdleTimeoutInSeconds),
+        }
+    }
+
+    /// Fields left as None are passed as zero.
+    pub(crate) fn build_raw(&self) -> FabricClientSettingsRaw {
+        fn val(v: Option<NonZeroU32>) -> u32 {
+            v.map_or(0, NonZeroU32::get)
+        }
+        let client_friendly_name = self.ClientFriendlyName.clone();
+        let ex4 = Box::new(FABRIC_CLIENT_SETTINGS_EX4 {
+            ConnectionIdleTimeoutInSeconds: self.ConnectionIdleTimeoutInSeconds.unwrap_or(0),
+            Reserved: std::ptr::null_mut(),
+        });
+        let ex3 = Box::new(FABRIC_CLIENT_SETTINGS_EX3 {
+            AuthTokenBufferSize: val(self.AuthTokenBufferSize),
+            Reserved: ex4.as_ref() as *const _ as *mut c_void,
+        });
+        let ex2 = Box::new(FABRIC_CLIENT_SETTINGS_EX2 {
+            NotificationGatewayConnectionTimeoutInSeconds: val(
+                self.NotificationGatewayConnectionTimeoutInSeconds
+            ),
+            NotificationCacheUpdateTimeoutInSeconds: val(
+                self.NotificationCacheUpdateTimeoutInSeconds
+            ),
+            Reserved: ex3.as_ref() as *const _ as *mut c_void,
+        });
+        let ex1 = Box::new(FABRIC_CLIENT_SETTINGS_EX1 {
+            ClientFriendlyName: client_friendly_name
+                .as_ref()
+                .map_or(crate::PCWSTR::null(), |n| n.as_pcwstr()),
+            PartitionLocationCacheBucketCount: val(self.PartitionLocationCacheBucketCount),
+            HealthReportRetrySendIntervalInSeconds: val(self.HealthReportRetrySendIntervalInSeconds),
+            Reserved: ex2.as_ref() as *const _ as *mut c_void,
+        });
+        let internal = Box::new(FABRIC_CLIENT_SETTINGS {
+            PartitionLocationCacheLimit: val(self.PartitionLocationCacheLimit),
+            ServiceChangePollIntervalInSeconds: val(self.ServiceChangePollIntervalInSeconds),
+            ConnectionInitializationTimeoutInSeconds: val(
+                self.ConnectionInitializationTimeoutInSeconds
+            ),
+            KeepAliveIntervalInSeconds: self.KeepAliveIntervalInSeconds.unwrap_or(0),
+            HealthOperationTimeoutInSeconds: val(self.HealthOperationTimeoutInSeconds),
+            HealthReportSendIntervalInSeconds: self.HealthReportSendIntervalInSeconds.unwrap_or(0),
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+        FabricClientSettingsRaw {
+            internal,
+            _ex1: ex1,
+            _ex2: ex2,
+            _ex3: ex3,
+            _ex4: ex4,
+            _client_friendly_name: client_friendly_name,
+        }
+    }
+
+    /// Reads the settings currently used by the client.
+    pub(crate) fn get(settings_interface: &IFabricClientSettings2) -> crate::Result<Self> {
+        let result = unsafe { settings_interface.GetSettings() }?;
+        let raw = unsafe { result.get_Settings().as_ref() }
+            .ok_or_else(|| crate::Error::from(crate::ErrorCode::E_POINTER))?;
+        Ok(FabricClientSettings::from(raw))
+    }
+
     /// Note: only overrides non-default settings; leaves any settings set previously that don't explicitly have new values alone
-    pub(crate) fn apply(&self, _settings_interface: &IFabricClientSettings2) -> crate::Result<()> {
-        // Placeholder
-        Ok(())
+    pub(crate) fn apply(&self, settings_interface: &IFabricClientSettings2) -> crate::Result<()> {
+        let merged = self.merge(Self::get(settings_interface)?);
+        let raw = merged.build_raw();
+        unsafe { settings_interface.SetSettings(raw.as_ffi()) }.map_err(crate::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        num::NonZeroU32,
+        sync::{Arc, Mutex},
+    };
+
+    use mssf_com::{
+        FabricClient::IFabricClientSettings2,
+        FabricTypes::{
+            FABRIC_CLIENT_SETTINGS, FABRIC_CLIENT_SETTINGS_EX1, FABRIC_E_INVALID_CONFIGURATION,
+        },
+    };
+
+    use crate::{
+        WString,
+        types::mockifabricclientsettings::{
+            MockIFabricClientSettings, MockIFabricClientSettingsResult,
+        },
+    };
+
+    use super::FabricClientSettings;
+
+    fn make_current_settings() -> FabricClientSettings {
+        FabricClientSettings {
+            PartitionLocationCacheLimit: NonZeroU32::new(100000),
+            ServiceChangePollIntervalInSeconds: NonZeroU32::new(120),
+            ConnectionInitializationTimeoutInSeconds: NonZeroU32::new(2),
+            KeepAliveIntervalInSeconds: Some(0),
+            HealthOperationTimeoutInSeconds: NonZeroU32::new(120),
+            HealthReportSendIntervalInSeconds: Some(30),
+            ClientFriendlyName: Some(WString::from("current")),
+            PartitionLocationCacheBucketCount: NonZeroU32::new(1024),
+            HealthReportRetrySendIntervalInSeconds: NonZeroU32::new(30),
+            NotificationGatewayConnectionTimeoutInSeconds: NonZeroU32::new(30),
+            NotificationCacheUpdateTimeoutInSeconds: NonZeroU32::new(30),
+            AuthTokenBufferSize: NonZeroU32::new(4096),
+            ConnectionIdleTimeoutInSeconds: Some(0),
+        }
+    }
+
+    #[test]
+    fn settings_raw_round_trip() {
+        let settings = make_current_settings();
+        let raw = settings.build_raw();
+        assert_eq!(FabricClientSettings::from(raw.as_ffi()), settings);
+    }
+
+    #[test]
+    fn settings_apply_get_failure() {
+        let mock = IFabricClientSettings2::from(MockIFabricClientSettings::new_all_methods_fail());
+        let result = FabricClientSettings::default().apply(&mock);
+        assert_eq!(
+            result,
+            Err(crate::Error::from(FABRIC_E_INVALID_CONFIGURATION))
+        );
+    }
+
+    #[test]
+    fn settings_apply_merges_current() {
+        let set_calls = Arc::new(Mutex::new(Vec::<FabricClientSettings>::new()));
+        let set_calls_copy = Arc::clone(&set_calls);
+        let mock =
+            IFabricClientSettings2::from(MockIFabricClientSettings::new_with_settings_mocks(
+                Box::new(|| {
+                    Ok(MockIFabricClientSettingsResult::new(&make_current_settings()).into())
+                }),
+                Box::new(move |settings: *const FABRIC_CLIENT_SETTINGS| {
+                    assert!(!settings.is_null() && settings.is_aligned());
+                    // SAFETY: test code. non-null and alignment is checked above
+                    let settings_ref = unsafe { settings.as_ref() }.unwrap();
+                    let ex1 = settings_ref.Reserved as *const FABRIC_CLIENT_SETTINGS_EX1;
+                    assert!(!ex1.is_null() && ex1.is_aligned());
+                    set_calls_copy
+                        .lock()
+                        .expect("Not poisoned")
+                        .push(FabricClientSettings::from(settings_ref));
+                    Ok(())
+                }),
+            ));
+
+        let desired = FabricClientSettings {
+            PartitionLocationCacheLimit: NonZeroU32::new(1),
+            KeepAliveIntervalInSeconds: Some(20),
+            ClientFriendlyName: Some(WString::from("desired")),
+            ConnectionIdleTimeoutInSeconds: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(desired.apply(&mock), Ok(()));
+
+        let calls = set_calls.lock().expect("Not poisoned");
+        assert_eq!(calls.len(), 1);
+        let expected = FabricClientSettings {
+            PartitionLocationCacheLimit: NonZeroU32::new(1),
+            KeepAliveIntervalInSeconds: Some(20),
+            ClientFriendlyName: Some(WString::from("desired")),
+            ConnectionIdleTimeoutInSeconds: Some(60),
+            ..make_current_settings()
+        };
+        assert_eq!(calls[0], expected);
+    }
+
+    #[test]
+    fn settings_get_without_extensions() {
+        let raw = FABRIC_CLIENT_SETTINGS {
+            PartitionLocationCacheLimit: 10,
+            ServiceChangePollIntervalInSeconds: 0,
+            ConnectionInitializationTimeoutInSeconds: 2,
+            KeepAliveIntervalInSeconds: 0,
+            HealthOperationTimeoutInSeconds: 120,
+            HealthReportSendIntervalInSeconds: 30,
+            Reserved: std::ptr::null_mut(),
+        };
+        let settings = FabricClientSettings::from(&raw);
+        assert_eq!(settings.PartitionLocationCacheLimit, NonZeroU32::new(10));
+        assert_eq!(settings.ServiceChangePollIntervalInSeconds, None);
+        assert_eq!(settings.KeepAliveIntervalInSeconds, Some(0));
+        assert_eq!(settings.ClientFriendlyName, None);
+        assert_eq!(settings.ConnectionIdleTimeoutInSeconds, None);
     }
 }
@@ -0,0 +1,290 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Rolling upgrade types shared by application and cluster upgrades.
+
+use std::{ffi::c_void, marker::PhantomData};
+
+use mssf_com::FabricTypes::{
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION, FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_INVALID,
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL,
+    FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK, FABRIC_ROLLING_UPGRADE_MODE,
+    FABRIC_ROLLING_UPGRADE_MODE_INVALID, FABRIC_ROLLING_UPGRADE_MODE_MONITORED,
+    FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO, FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL,
+    FABRIC_ROLLING_UPGRADE_MONITORING_POLICY, FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1,
+    FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION, FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1,
+    FABRIC_UPGRADE_DOMAIN_STATE, FABRIC_UPGRADE_DOMAIN_STATE_COMPLETED,
+    FABRIC_UPGRADE_DOMAIN_STATE_IN_PROGRESS, FABRIC_UPGRADE_DOMAIN_STATE_INVALID,
+    FABRIC_UPGRADE_DOMAIN_STATE_PENDING, FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION,
+    FABRIC_UPGRADE_FAILURE_REASON, FABRIC_UPGRADE_FAILURE_REASON_HEALTH_CHECK,
+    FABRIC_UPGRADE_FAILURE_REASON_INTERRUPTED, FABRIC_UPGRADE_FAILURE_REASON_NONE,
+    FABRIC_UPGRADE_FAILURE_REASON_OVERALL_UPGRADE_TIMEOUT,
+    FABRIC_UPGRADE_FAILURE_REASON_PROCESSING_FAILURE,
+    FABRIC_UPGRADE_FAILURE_REASON_UPGRADE_DOMAIN_TIMEOUT,
+};
+
+use crate::{
+    WString,
+    iter::{FabricIter, FabricListAccessor},
+    strings::WStringWrap,
+};
+
+// FABRIC_ROLLING_UPGRADE_MODE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollingUpgradeMode {
+    Invalid,
+    /// The upgrade proceeds automatically without health monitoring.
+    UnmonitoredAuto,
+    /// The upgrade stops after each upgrade domain and waits for
+    /// move_next_*_upgrade_domain to be called.
+    UnmonitoredManual,
+    /// The upgrade stops after each upgrade domain and automatically
+    /// proceeds if health checks pass.
+    Monitored,
+}
+
+impl From<FABRIC_ROLLING_UPGRADE_MODE> for RollingUpgradeMode {
+    fn from(value: FABRIC_ROLLING_UPGRADE_MODE) -> Self {
+        match value {
+            FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO => Self::UnmonitoredAuto,
+            FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL => Self::UnmonitoredManual,
+            FABRIC_ROLLING_UPGRADE_MODE_MONITORED => Self::Monitored,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<RollingUpgradeMode> for FABRIC_ROLLING_UPGRADE_MODE {
+    fn from(value: RollingUpgradeMode) -> Self {
+        match value {
+            RollingUpgradeMode::Invalid => FABRIC_ROLLING_UPGRADE_MODE_INVALID,
+            RollingUpgradeMode::UnmonitoredAuto => FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_AUTO,
+            RollingUpgradeMode::UnmonitoredManual => FABRIC_ROLLING_UPGRADE_MODE_UNMONITORED_MANUAL,
+            RollingUpgradeMode::Monitored => FABRIC_ROLLING_UPGRADE_MODE_MONITORED,
+        }
+    }
+}
+
+// FABRIC_MONITORED_UPGRADE_FAILURE_ACTION
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MonitoredUpgradeFailureAction {
+    Invalid,
+    /// The upgrade starts rolling back automatically.
+    Rollback,
+    /// The upgrade switches to UnmonitoredManual mode.
+    Manual,
+}
+
+impl From<FABRIC_MONITORED_UPGRADE_FAILURE_ACTION> for MonitoredUpgradeFailureAction {
+    fn from(value: FABRIC_MONITORED_UPGRADE_FAILURE_ACTION) -> Self {
+        match value {
+            FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK => Self::Rollback,
+            FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL => Self::Manual,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<MonitoredUpgradeFailureAction> for FABRIC_MONITORED_UPGRADE_FAILURE_ACTION {
+    fn from(value: MonitoredUpgradeFailureAction) -> Self {
+        match value {
+            MonitoredUpgradeFailureAction::Invalid => {
+                FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_INVALID
+            }
+            MonitoredUpgradeFailureAction::Rollback => {
+                FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_ROLLBACK
+            }
+            MonitoredUpgradeFailureAction::Manual => FABRIC_MONITORED_UPGRADE_FAILURE_ACTION_MANUAL,
+        }
+    }
+}
+
+/// FABRIC_ROLLING_UPGRADE_MONITORING_POLICY
+/// Only used when the upgrade mode is Monitored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingUpgradeMonitoringPolicy {
+    pub failure_action: MonitoredUpgradeFailureAction,
+    pub health_check_wait_duration_in_seconds: u32,
+    pub health_check_retry_timeout_in_seconds: u32,
+    pub upgrade_timeout_in_seconds: u32,
+    pub upgrade_domain_timeout_in_seconds: u32,
+    // ex1
+    pub health_check_stable_duration_in_seconds: u32,
+}
+
+/// Holder for memories passed to the FFI
+pub(crate) struct RollingUpgradeMonitoringPolicyRaw<'a> {
+    internal: Box<FABRIC_ROLLING_UPGRADE_MONITORING_POLICY>,
+    _internal_ex1: Box<FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1>,
+    phantom: PhantomData<&'a RollingUpgradeMonitoringPolicy>,
+}
+
+impl RollingUpgradeMonitoringPolicyRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_ROLLING_UPGRADE_MONITORING_POLICY {
+        self.internal.as_ref()
+    }
+}
+
+impl RollingUpgradeMonitoringPolicy {
+    pub(crate) fn build_raw(&self) -> RollingUpgradeMonitoringPolicyRaw<'_> {
+        let ex1 = Box::new(FABRIC_ROLLING_UPGRADE_MONITORING_POLICY_EX1 {
+            HealthCheckStableDurationInSeconds: self.health_check_stable_duration_in_seconds,
+            Reserved: std::ptr::null_mut(),
+        });
+        let internal = Box::new(FABRIC_ROLLING_UPGRADE_MONITORING_POLICY {
+            FailureAction: self.failure_action.into(),
+            HealthCheckWaitDurationInSeconds: self.health_check_wait_duration_in_seconds,
+            HealthCheckRetryTimeoutInSeconds: self.health_check_retry_timeout_in_seconds,
+            UpgradeTimeoutInSeconds: self.upgrade_timeout_in_seconds,
+            UpgradeDomainTimeoutInSeconds: self.upgrade_domain_timeout_in_seconds,
+            Reserved: ex1.as_ref() as *const _ as *mut _,
+        });
+        RollingUpgradeMonitoringPolicyRaw {
+            internal,
+            _internal_ex1: ex1,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingUpgradePolicyDescription {
+    pub rolling_upgrade_mode: RollingUpgradeMode,
+    /// Restarts the code packages even if only config or data changed.
+    pub force_restart: bool,
+    pub upgrade_replica_set_check_timeout_in_seconds: u32,
+    // ex1
+    pub monitoring_policy: Option<RollingUpgradeMonitoringPolicy>,
+}
+
+pub(crate) struct RollingUpgradePolicyDescriptionRaw<'a> {
+    internal: Box<FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION>,
+    _internal_ex1: Box<FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1>,
+    _monitoring: Option<RollingUpgradeMonitoringPolicyRaw<'a>>,
+}
+
+impl RollingUpgradePolicyDescriptionRaw<'_> {
+    pub(crate) fn as_ffi(&self) -> &FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+        self.internal.as_ref()
+    }
+}
+
+impl RollingUpgradePolicyDescription {
+    pub(crate) fn build_raw(&self) -> RollingUpgradePolicyDescriptionRaw<'_> {
+        let monitoring = self.monitoring_policy.as_ref().map(|m| m.build_raw());
+        let ex1 = Box::new(FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION_EX1 {
+            MonitoringPolicy: monitoring
+                .as_ref()
+                .map_or(std::ptr::null(), |m| m.as_ffi() as *const _),
+            // The health policy in the manifest is used when null.
+            HealthPolicy: std::ptr::null_mut(),
+            Reserved: std::ptr::null_mut(),
+        });
+        let internal = Box::new(FABRIC_ROLLING_UPGRADE_POLICY_DESCRIPTION {
+            RollingUpgradeMode: self.rolling_upgrade_mode.into(),
+            ForceRestart: self.force_restart,
+            UpgradeReplicaSetCheckTimeoutInSeconds: self
+                .upgrade_replica_set_check_timeout_in_seconds,
+            Reserved: ex1.as_ref() as *const _ as *mut c_void,
+        });
+        RollingUpgradePolicyDescriptionRaw {
+            internal,
+            _internal_ex1: ex1,
+            _monitoring: monitoring,
+        }
+    }
+}
+
+// FABRIC_UPGRADE_DOMAIN_STATE
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpgradeDomainState {
+    Invalid,
+    Pending,
+    InProgress,
+    Completed,
+}
+
+impl From<FABRIC_UPGRADE_DOMAIN_STATE> for UpgradeDomainState {
+    fn from(value: FABRIC_UPGRADE_DOMAIN_STATE) -> Self {
+        match value {
+            FABRIC_UPGRADE_DOMAIN_STATE_PENDING => Self::Pending,
+            FABRIC_UPGRADE_DOMAIN_STATE_IN_PROGRESS => Self::InProgress,
+            FABRIC_UPGRADE_DOMAIN_STATE_COMPLETED => Self::Completed,
+            FABRIC_UPGRADE_DOMAIN_STATE_INVALID => Self::Invalid,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+// FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeDomainStatus {
+    pub name: WString,
+    pub state: UpgradeDomainState,
+}
+
+impl From<&FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION> for UpgradeDomainStatus {
+    fn from(value: &FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION) -> Self {
+        Self {
+            name: WStringWrap::from(value.Name).into(),
+            state: value.State.into(),
+        }
+    }
+}
+
+/// Accessor for the upgrade domain arrays returned by the upgrade progress results.
+/// O is the com object owning the memory.
+pub(crate) struct UpgradeDomainStatusListRef<'a, O> {
+    pub(crate) count: u32,
+    pub(crate) first: *const FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION,
+    pub(crate) phantom: PhantomData<&'a O>,
+}
+
+impl<O> FabricListAccessor<FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION>
+    for UpgradeDomainStatusListRef<'_, O>
+{
+    fn get_count(&self) -> u32 {
+        self.count
+    }
+
+    fn get_first_item(&self) -> *const FABRIC_UPGRADE_DOMAIN_STATUS_DESCRIPTION {
+        self.first
+    }
+}
+
+impl<O> UpgradeDomainStatusListRef<'_, O> {
+    pub(crate) fn to_vec(&self) -> Vec<UpgradeDomainStatus> {
+        if self.first.is_null() {
+            return Vec::new();
+        }
+        FabricIter::<_, UpgradeDomainStatus, _>::new(self, self).collect()
+    }
+}
+
+// FABRIC_UPGRADE_FAILURE_REASON
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpgradeFailureReason {
+    None,
+    Interrupted,
+    HealthCheck,
+    UpgradeDomainTimeout,
+    OverallUpgradeTimeout,
+    ProcessingFailure,
+}
+
+impl From<FABRIC_UPGRADE_FAILURE_REASON> for UpgradeFailureReason {
+    fn from(value: FABRIC_UPGRADE_FAILURE_REASON) -> Self {
+        match value {
+            FABRIC_UPGRADE_FAILURE_REASON_INTERRUPTED => Self::Interrupted,
+            FABRIC_UPGRADE_FAILURE_REASON_HEALTH_CHECK => Self::HealthCheck,
+            FABRIC_UPGRADE_FAILURE_REASON_UPGRADE_DOMAIN_TIMEOUT => Self::UpgradeDomainTimeout,
+            FABRIC_UPGRADE_FAILURE_REASON_OVERALL_UPGRADE_TIMEOUT => Self::OverallUpgradeTimeout,
+            FABRIC_UPGRADE_FAILURE_REASON_PROCESSING_FAILURE => Self::ProcessingFailure,
+            FABRIC_UPGRADE_FAILURE_REASON_NONE => Self::None,
+            _ => Self::None,
+        }
+    }
+}
@@ -199,6 +199,14 @@ impl UniformIn64PartitionSchemeDescription {
     }
 }
 
+impl PartialEq for UniformIn64PartitionSchemeDescription {
+    fn eq(&self, other: &Self) -> bool {
+        self.internal.PartitionCount == other.internal.PartitionCount
+            && self.internal.LowKey == other.internal.LowKey
+            && self.internal.HighKey == other.internal.HighKey
+    }
+}
+
 impl Clone for UniformIn64PartitionSchemeDescription {
     fn clone(&self) -> Self {
         Self::new(
@@ -248,6 +256,12 @@ impl NamedPartitionSchemeDescription {
     }
 }
 
+impl PartialEq for NamedPartitionSchemeDescription {
+    fn eq(&self, other: &Self) -> bool {
+        self._names == other._names
+    }
+}
+
 impl Clone for NamedPartitionSchemeDescription {
     fn clone(&self) -> Self {
         Self::new(self._names.clone())
@@ -262,6 +276,15 @@ pub enum ServicePackageActivationMode {
     ExclusiveProcess,
 }
 
+impl From<FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE> for ServicePackageActivationMode {
+    fn from(mode: FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE) -> Self {
+        match mode {
+            FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE_EXCLUSIVE_PROCESS => Self::ExclusiveProcess,
+            _ => Self::SharedProcess,
+        }
+    }
+}
+
 impl From<ServicePackageActivationMode> for FABRIC_SERVICE_PACKAGE_ACTIVATION_MODE {
     fn from(mode: ServicePackageActivationMode) -> Self {
         match mode {
@@ -275,7 +298,7 @@ impl From<ServicePackageActivationMode> for FABRIC_SERVICE_PACKAGE_ACTIVATION_MO
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub enum PartitionSchemeDescription {
     #[default]
     Invalid,
@@ -285,6 +308,47 @@ pub enum PartitionSchemeDescription {
 }
 
 impl PartitionSchemeDescription {
+    /// Copies the scheme description returned by SF.
+    /// # Safety
+    /// desc must point to the description struct matching the scheme.
+    pub(crate) unsafe fn from_raw(scheme: FABRIC_PARTITION_SCHEME, desc: *const c_void) -> Self {
+        match scheme {
+            FABRIC_PARTITION_SCHEME_SINGLETON => Self::Singleton,
+            FABRIC_PARTITION_SCHEME_UNIFORM_INT64_RANGE => {
+                match unsafe {
+                    (desc as *const FABRIC_UNIFORM_INT64_RANGE_PARTITION_SCHEME_DESCRIPTION)
+                        .as_ref()
+                } {
+                    Some(d) => Self::Int64Range(UniformIn64PartitionSchemeDescription::new(
+                        d.PartitionCount,
+                        d.LowKey,
+                        d.HighKey,
+                    )),
+                    None => Self::Invalid,
+                }
+            }
+            FABRIC_PARTITION_SCHEME_NAMED => {
+                match unsafe { (desc as *const FABRIC_NAMED_PARTITION_SCHEME_DESCRIPTION).as_ref() }
+                {
+                    Some(d) => {
+                        let names = if d.Names.is_null() || d.PartitionCount <= 0 {
+                            &[]
+                        } else {
+                            unsafe {
+                                std::slice::from_raw_parts(d.Names, d.PartitionCount as usize)
+                            }
+                        };
+                        Self::Named(NamedPartitionSchemeDescription::new(
+                            names.iter().map(WString::from).collect(),
+                        ))
+                    }
+                    None => Self::Invalid,
+                }
+            }
+            _ => Self::Invalid,
+        }
+    }
+
     /// Needs to have lifetime as self. Can be moved.
     pub(crate) fn as_raw(&self) -> (FABRIC_PARTITION_SCHEME, *mut c_void) {
         match self {
@@ -8,13 +8,16 @@
 use mssf_com::{
     FabricClient::{
         IFabricClientSettings_Impl, IFabricClientSettings2, IFabricClientSettings2_Impl,
-        IFabricClientSettingsResult,
+        IFabricClientSettingsResult, IFabricClientSettingsResult_Impl,
     },
     FabricTypes::{
         FABRIC_CLIENT_SETTINGS, FABRIC_E_INVALID_CONFIGURATION, FABRIC_E_INVALID_CREDENTIALS,
         FABRIC_SECURITY_CREDENTIALS,
     },
 };
+
+use crate::types::{FabricClientSettings, client::FabricClientSettingsRaw};
+
 pub(crate) mod test_constants {
     pub const TEST_SERVER_NAME_1: &str = "test.contoso.com";
     pub const TEST_SERVER_NAME_2: &str = "test2.contoso.com";
@@ -112,6 +115,40 @@ impl MockIFabricClientSettings {
             set_settings_mock: Box::new(|_| panic!("Unexpected call to SetSettings")),
         }
     }
+
+    pub fn new_with_settings_mocks(
+        get_mock: Box<dyn Fn() -> windows_core::Result<IFabricClientSettingsResult>>,
+        set_mock: Box<dyn Fn(*const FABRIC_CLIENT_SETTINGS) -> windows_core::Result<()>>,
+    ) -> Self {
+        Self {
+            set_security_credentials_mock: Box::new(|_| {
+                panic!("Unexpected call to SetSecurityCredentials")
+            }),
+            set_keepalive_mock: Box::new(|_| panic!("Unexpected call to SetKeepAlive")),
+            get_settings_mock: get_mock,
+            set_settings_mock: set_mock,
+        }
+    }
+}
+
+/// Returns the given settings from GetSettings, like SF does for the current client settings.
+#[windows_core::implement(IFabricClientSettingsResult)]
+pub(crate) struct MockIFabricClientSettingsResult {
+    settings: FabricClientSettingsRaw,
+}
+
+impl MockIFabricClientSettingsResult {
+    pub fn new(settings: &FabricClientSettings) -> Self {
+        Self {
+            settings: settings.build_raw(),
+        }
+    }
+}
+
+impl IFabricClientSettingsResult_Impl for MockIFabricClientSettingsResult_Impl {
+    fn get_Settings(&self) -> *mut FABRIC_CLIENT_SETTINGS {
+        self.settings.as_ffi() as *const _ as *mut _
+    }
 }
 
 impl IFabricClientSettings_Impl for MockIFabricClientSettings_Impl {
@@ -8,10 +8,15 @@
 use std::{ffi::c_void, marker::PhantomData};
 
 use crate::{PCWSTR, WString};
+use mssf_com::FabricRuntime::IFabricOperationData;
 use mssf_com::FabricTypes::{
-    FABRIC_EPOCH, FABRIC_REPLICA_INFORMATION, FABRIC_REPLICA_INFORMATION_EX1,
-    FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_OPEN_MODE_EXISTING, FABRIC_REPLICA_OPEN_MODE_INVALID,
-    FABRIC_REPLICA_OPEN_MODE_NEW, FABRIC_REPLICA_SET_CONFIGURATION, FABRIC_REPLICA_SET_QUORUM_ALL,
+    FABRIC_EPOCH, FABRIC_OPERATION_TYPE, FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION,
+    FABRIC_OPERATION_TYPE_COMMIT_ATOMIC_GROUP, FABRIC_OPERATION_TYPE_CREATE_ATOMIC_GROUP,
+    FABRIC_OPERATION_TYPE_END_OF_STREAM, FABRIC_OPERATION_TYPE_NORMAL,
+    FABRIC_OPERATION_TYPE_ROLLBACK_ATOMIC_GROUP, FABRIC_REPLICA_INFORMATION,
+    FABRIC_REPLICA_INFORMATION_EX1, FABRIC_REPLICA_OPEN_MODE, FABRIC_REPLICA_OPEN_MODE_EXISTING,
+    FABRIC_REPLICA_OPEN_MODE_INVALID, FABRIC_REPLICA_OPEN_MODE_NEW,
+    FABRIC_REPLICA_SET_CONFIGURATION, FABRIC_REPLICA_SET_QUORUM_ALL,
     FABRIC_REPLICA_SET_QUORUM_INVALID, FABRIC_REPLICA_SET_QUORUM_MODE,
     FABRIC_REPLICA_SET_WRITE_QUORUM, FABRIC_REPLICA_STATUS, FABRIC_REPLICA_STATUS_DOWN,
     FABRIC_REPLICA_STATUS_INVALID, FABRIC_REPLICA_STATUS_UP,
@@ -287,6 +292,100 @@ impl From<ReplicaSetQuorumMode> for FABRIC_REPLICA_SET_QUORUM_MODE {
     }
 }
 
+/// Data of a replication or copy operation.
+/// The buffers are handed to SF as they are, and the receiving side
+/// gets the same list of buffers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationData {
+    pub buffers: Vec<Vec<u8>>,
+}
+
+impl From<Vec<u8>> for OperationData {
+    fn from(value: Vec<u8>) -> Self {
+        Self {
+            buffers: vec![value],
+        }
+    }
+}
+
+impl From<Vec<Vec<u8>>> for OperationData {
+    fn from(buffers: Vec<Vec<u8>>) -> Self {
+        Self { buffers }
+    }
+}
+
+/// Copies the buffers out of the SF operation data.
+impl TryFrom<&IFabricOperationData> for OperationData {
+    type Error = crate::Error;
+
+    fn try_from(value: &IFabricOperationData) -> Result<Self, Self::Error> {
+        let mut count = 0;
+        let raw = unsafe { value.GetData(&mut count) }?;
+        let buffers = if raw.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(raw, count as usize) }
+                .iter()
+                .map(|b| {
+                    if b.Buffer.is_null() {
+                        Vec::new()
+                    } else {
+                        unsafe { std::slice::from_raw_parts(b.Buffer, b.BufferSize as usize) }
+                            .to_vec()
+                    }
+                })
+                .collect()
+        };
+        Ok(Self { buffers })
+    }
+}
+
+/// Type of an operation received from an OperationStream.
+/// SF reports both copy and replication operations as normal operations,
+/// which are told apart by the stream they are received from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    Invalid,
+    /// Operation of the copy stream.
+    Copy,
+    /// Operation of the replication stream.
+    Replication,
+    /// Last operation of the stream, received only if the replicator is created with
+    /// FABRIC_REPLICATOR_SETTINGS_STREAM_FAULTS_AND_END_OF_STREAM_OPERATION_ACK flag.
+    /// It carries no data but must be acknowledged.
+    EndOfStream,
+    CreateAtomicGroup,
+    AtomicGroupOperation,
+    CommitAtomicGroup,
+    RollbackAtomicGroup,
+}
+
+impl OperationType {
+    pub(crate) fn from_raw(value: FABRIC_OPERATION_TYPE, is_copy: bool) -> Self {
+        match value {
+            FABRIC_OPERATION_TYPE_NORMAL if is_copy => Self::Copy,
+            FABRIC_OPERATION_TYPE_NORMAL => Self::Replication,
+            FABRIC_OPERATION_TYPE_END_OF_STREAM => Self::EndOfStream,
+            FABRIC_OPERATION_TYPE_CREATE_ATOMIC_GROUP => Self::CreateAtomicGroup,
+            FABRIC_OPERATION_TYPE_ATOMIC_GROUP_OPERATION => Self::AtomicGroupOperation,
+            FABRIC_OPERATION_TYPE_COMMIT_ATOMIC_GROUP => Self::CommitAtomicGroup,
+            FABRIC_OPERATION_TYPE_ROLLBACK_ATOMIC_GROUP => Self::RollbackAtomicGroup,
+            _ => Self::Invalid,
+        }
+    }
+
+    /// True if the operation belongs to an atomic group.
+    pub fn is_atomic_group(&self) -> bool {
+        matches!(
+            self,
+            Self::CreateAtomicGroup
+                | Self::AtomicGroupOperation
+                | Self::CommitAtomicGroup
+                | Self::RollbackAtomicGroup
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::ffi::c_void;
@@ -3,10 +3,13 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
-use crate::PCWSTR;
+use crate::{PCWSTR, WString, strings::WStringWrap};
 use mssf_com::FabricTypes::{
-    FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_LOCAL_STORE_KIND, FABRIC_LOCAL_STORE_KIND_ESE,
-    FABRIC_LOCAL_STORE_KIND_INVALID, FABRIC_REPLICATOR_SETTINGS,
+    FABRIC_ESE_LOCAL_STORE_SETTINGS, FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS,
+    FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1, FABRIC_LOCAL_STORE_KIND,
+    FABRIC_LOCAL_STORE_KIND_ESE, FABRIC_LOCAL_STORE_KIND_INVALID, FABRIC_REPLICATOR_SETTINGS,
+    FABRIC_STORE_BACKUP_INFO, FABRIC_STORE_BACKUP_OPTION, FABRIC_STORE_BACKUP_OPTION_FULL,
+    FABRIC_STORE_BACKUP_OPTION_INCREMENTAL, FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY,
     FABRIC_TRANSACTION_ISOLATION_LEVEL, FABRIC_TRANSACTION_ISOLATION_LEVEL_DEFAULT,
     FABRIC_TRANSACTION_ISOLATION_LEVEL_READ_COMMITTED,
     FABRIC_TRANSACTION_ISOLATION_LEVEL_READ_UNCOMMITTED,
@@ -119,3 +122,82 @@ impl From<FABRIC_TRANSACTION_ISOLATION_LEVEL> for TransactionIsolationLevel {
         }
     }
 }
+
+// FABRIC_STORE_BACKUP_OPTION
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreBackupOption {
+    Invalid,
+    /// Backs up the whole store.
+    Full,
+    /// Backs up the logs since the last full or incremental backup.
+    Incremental,
+    /// Truncates the logs without a backup, e.g. when backups are no longer needed.
+    TruncateLogsOnly,
+}
+
+impl From<FABRIC_STORE_BACKUP_OPTION> for StoreBackupOption {
+    fn from(value: FABRIC_STORE_BACKUP_OPTION) -> Self {
+        match value {
+            FABRIC_STORE_BACKUP_OPTION_FULL => Self::Full,
+            FABRIC_STORE_BACKUP_OPTION_INCREMENTAL => Self::Incremental,
+            FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY => Self::TruncateLogsOnly,
+            _ => Self::Invalid,
+        }
+    }
+}
+
+impl From<StoreBackupOption> for FABRIC_STORE_BACKUP_OPTION {
+    fn from(value: StoreBackupOption) -> Self {
+        match value {
+            StoreBackupOption::Full => FABRIC_STORE_BACKUP_OPTION_FULL,
+            StoreBackupOption::Incremental => FABRIC_STORE_BACKUP_OPTION_INCREMENTAL,
+            StoreBackupOption::TruncateLogsOnly => FABRIC_STORE_BACKUP_OPTION_TRUNCATE_LOGS_ONLY,
+            StoreBackupOption::Invalid => FABRIC_STORE_BACKUP_OPTION(0),
+        }
+    }
+}
+
+// FABRIC_STORE_BACKUP_INFO
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreBackupInfo {
+    pub backup_folder: WString,
+    pub backup_option: StoreBackupOption,
+}
+
+impl From<&FABRIC_STORE_BACKUP_INFO> for StoreBackupInfo {
+    fn from(value: &FABRIC_STORE_BACKUP_INFO) -> Self {
+        Self {
+            backup_folder: WStringWrap::from(value.BackupFolder).into(),
+            backup_option: value.BackupOption.into(),
+        }
+    }
+}
+
+// FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS
+#[derive(Debug, Clone, Default)]
+pub struct KeyValueStoreRestoreSettings {
+    /// Restores without closing and reopening the replica.
+    pub inline_reopen: bool,
+    /// Fails the restore if the backup is older than the current state.
+    pub enable_lsn_check: bool,
+}
+
+impl KeyValueStoreRestoreSettings {
+    // caller needs to stitch the reserved ptr.
+    pub fn get_raw_parts(
+        &self,
+    ) -> (
+        FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS,
+        FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1,
+    ) {
+        let settings = FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS {
+            InlineReopen: self.inline_reopen,
+            Reserved: std::ptr::null_mut(),
+        };
+        let ex1 = FABRIC_KEY_VALUE_STORE_RESTORE_SETTINGS_EX1 {
+            EnableLsnCheck: self.enable_lsn_check,
+            Reserved: std::ptr::null_mut(),
+        };
+        (settings, ex1)
+    }
+}
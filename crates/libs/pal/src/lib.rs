@@ -26,6 +26,8 @@ pub use windows_core::{
 // This is used on windows as well.
 mod strings;
 pub use strings::*;
+// FILETIME <-> SystemTime conversions.
+mod time;
 
 // pal definition for windows types
 #[allow(
@@ -0,0 +1,111 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Conversion between FILETIME and std SystemTime.
+//! SF reports time as FILETIME on both Windows and Linux: 100ns ticks since 1601-01-01 UTC.
+//! SystemTime is based on a platform specific epoch, so the conversion is done
+//! by offsetting from UNIX_EPOCH instead of calling Win32 apis.
+
+use std::time::{Duration, SystemTime};
+
+use crate::Win32::Foundation::FILETIME;
+
+const TICKS_PER_SECOND: u64 = 10_000_000;
+const NANOS_PER_TICK: u64 = 100;
+/// Ticks between 1601-01-01 and 1970-01-01.
+const UNIX_EPOCH_TICKS: u64 = 11_644_473_600 * TICKS_PER_SECOND;
+/// Largest FILETIME accepted by Win32 (FileTimeToSystemTime) and by SystemTime on Windows.
+const MAX_TICKS: u64 = i64::MAX as u64;
+
+fn ticks_to_duration(ticks: u64) -> Duration {
+    Duration::new(
+        ticks / TICKS_PER_SECOND,
+        ((ticks % TICKS_PER_SECOND) * NANOS_PER_TICK) as u32,
+    )
+}
+
+fn duration_to_ticks(d: Duration) -> u64 {
+    let ticks = d.as_nanos() / NANOS_PER_TICK as u128;
+    ticks.min(MAX_TICKS as u128) as u64
+}
+
+impl FILETIME {
+    pub fn from_ticks(ticks: u64) -> Self {
+        Self {
+            dwLowDateTime: ticks as u32,
+            dwHighDateTime: (ticks >> 32) as u32,
+        }
+    }
+
+    /// 100ns intervals since 1601-01-01 UTC.
+    pub fn ticks(&self) -> u64 {
+        ((self.dwHighDateTime as u64) << 32) | self.dwLowDateTime as u64
+    }
+}
+
+/// A zero FILETIME, which SF uses for unset timestamps, converts to 1601-01-01.
+/// Values past the Win32 range are clamped.
+impl From<FILETIME> for SystemTime {
+    fn from(value: FILETIME) -> Self {
+        let ticks = value.ticks().min(MAX_TICKS);
+        if ticks >= UNIX_EPOCH_TICKS {
+            SystemTime::UNIX_EPOCH + ticks_to_duration(ticks - UNIX_EPOCH_TICKS)
+        } else {
+            SystemTime::UNIX_EPOCH - ticks_to_duration(UNIX_EPOCH_TICKS - ticks)
+        }
+    }
+}
+
+/// Time before 1601-01-01 is clamped to zero, and sub tick precision is truncated.
+impl From<SystemTime> for FILETIME {
+    fn from(value: SystemTime) -> Self {
+        let ticks = match value.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(after) => UNIX_EPOCH_TICKS.saturating_add(duration_to_ticks(after)),
+            Err(e) => UNIX_EPOCH_TICKS.saturating_sub(duration_to_ticks(e.duration())),
+        };
+        Self::from_ticks(ticks.min(MAX_TICKS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use super::{FILETIME, UNIX_EPOCH_TICKS};
+
+    #[test]
+    fn filetime_system_time_test() {
+        // Unix epoch
+        let ft = FILETIME::from_ticks(UNIX_EPOCH_TICKS);
+        assert_eq!(ft.dwHighDateTime, 0x019D_B1DE);
+        assert_eq!(ft.dwLowDateTime, 0xD53E_8000);
+        assert_eq!(SystemTime::from(ft), SystemTime::UNIX_EPOCH);
+        assert_eq!(FILETIME::from(SystemTime::UNIX_EPOCH), ft);
+
+        // 2024-01-01T00:00:00.1234567Z
+        let t = SystemTime::UNIX_EPOCH + Duration::new(1_704_067_200, 123_456_700);
+        let ft = FILETIME::from(t);
+        assert_eq!(ft.ticks(), 133_485_408_001_234_567);
+        assert_eq!(SystemTime::from(ft), t);
+
+        // sub tick precision is dropped.
+        let ft = FILETIME::from(t + Duration::from_nanos(99));
+        assert_eq!(SystemTime::from(ft), t);
+
+        // Unset timestamps are before unix epoch.
+        let zero = FILETIME::from_ticks(0);
+        let t = SystemTime::from(zero);
+        assert_eq!(
+            SystemTime::UNIX_EPOCH.duration_since(t).unwrap(),
+            Duration::from_secs(11_644_473_600)
+        );
+        assert_eq!(FILETIME::from(t), zero);
+        assert_eq!(
+            FILETIME::from(t - Duration::from_secs(1)),
+            zero,
+            "clamped to zero"
+        );
+    }
+}
@@ -0,0 +1,332 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+//! Parsing of the addresses published by service replicas, and selection
+//! of the endpoint to talk to from a resolved partition.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    hash::{BuildHasher, RandomState},
+    str::FromStr,
+};
+
+use mssf_core::{
+    ErrorCode,
+    client::svc_mgmt_client::{ResolvedServiceEndpoint, ServiceEndpointRole},
+};
+
+/// Address of a listener, e.g. "http://localhost:8080/api" or "localhost:8080".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointUri {
+    pub scheme: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+    /// The remainder after the authority, including the leading '/', '?' or '#', or empty.
+    pub path: String,
+}
+
+impl FromStr for EndpointUri {
+    type Err = mssf_core::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || mssf_core::Error::from(ErrorCode::FABRIC_E_INVALID_ADDRESS);
+        let (scheme, rest) = match s.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme.to_string()), rest),
+            None => (None, s),
+        };
+        let (authority, path) = match rest.find(['/', '?', '#']) {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        // ipv6 hosts are enclosed in brackets, e.g. [::1]:8080
+        let (host, port) = if let Some(v6) = authority.strip_prefix('[') {
+            let (host, port) = v6.split_once(']').ok_or_else(invalid)?;
+            match port {
+                "" => (host, None),
+                _ => (host, Some(port.strip_prefix(':').ok_or_else(invalid)?)),
+            }
+        } else {
+            match authority.rsplit_once(':') {
+                // An unbracketed ipv6 host is ambiguous with the port.
+                Some((host, _)) if host.contains(':') => return Err(invalid()),
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            }
+        };
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        let port = port
+            .map(|p| p.parse::<u16>().map_err(|_| invalid()))
+            .transpose()?;
+        Ok(Self {
+            scheme,
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+impl Display for EndpointUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        if self.host.contains(':') {
+            write!(f, "[{}]", self.host)?;
+        } else {
+            write!(f, "{}", self.host)?;
+        }
+        if let Some(port) = self.port {
+            write!(f, ":{port}")?;
+        }
+        write!(f, "{}", self.path)
+    }
+}
+
+/// The listeners published by a replica.
+/// Replicas following the dotnet conventions publish json like
+/// `{"Endpoints":{"Listener1":"http://host:80"}}`.
+/// Any other address is a single listener with the default (empty) name.
+/// Listeners in the json whose address is not a uri, e.g. a custom token,
+/// are skipped so the other listeners remain selectable.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EndpointAddressSet {
+    listeners: BTreeMap<String, EndpointUri>,
+}
+
+impl EndpointAddressSet {
+    pub fn parse(address: &str) -> mssf_core::Result<Self> {
+        let address = address.trim();
+        let mut listeners = BTreeMap::new();
+        if address.starts_with('{') {
+            let json: serde_json::Value = serde_json::from_str(address)
+                .map_err(|_| mssf_core::Error::from(ErrorCode::FABRIC_E_INVALID_ADDRESS))?;
+            let endpoints = json
+                .get("Endpoints")
+                .and_then(|e| e.as_object())
+                .ok_or(ErrorCode::FABRIC_E_INVALID_ADDRESS)?;
+            for (name, addr) in endpoints {
+                let addr = addr.as_str().ok_or(ErrorCode::FABRIC_E_INVALID_ADDRESS)?;
+                match addr.parse() {
+                    Ok(uri) => {
+                        listeners.insert(name.clone(), uri);
+                    }
+                    Err(_e) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("Skipping listener {name} with address {addr}: {_e}");
+                    }
+                }
+            }
+        } else if !address.is_empty() {
+            listeners.insert(String::new(), address.parse()?);
+        }
+        Ok(Self { listeners })
+    }
+
+    /// Gets the listener by name.
+    pub fn listener(&self, name: &str) -> Option<&EndpointUri> {
+        self.listeners.get(name)
+    }
+
+    /// Gets the default (unnamed) listener, or the first listener by name if
+    /// there is no default one.
+    pub fn default_listener(&self) -> Option<&EndpointUri> {
+        self.listeners
+            .get("")
+            .or_else(|| self.listeners.values().next())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EndpointUri)> {
+        self.listeners.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+}
+
+impl TryFrom<&ResolvedServiceEndpoint> for EndpointAddressSet {
+    type Error = mssf_core::Error;
+
+    fn try_from(value: &ResolvedServiceEndpoint) -> Result<Self, Self::Error> {
+        Self::parse(&value.address.to_string_lossy())
+    }
+}
+
+/// Which replica of the resolved partition to pick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaSelector {
+    /// The primary of a stateful partition.
+    Primary,
+    /// Any replica or instance, picked at random.
+    Any,
+    /// A secondary of a stateful partition, picked at random.
+    RandomSecondary,
+}
+
+/// Picks the endpoint from the endpoints of a resolved partition,
+/// i.e. `ResolvedServicePartition::get_endpoint_list().iter()`.
+/// Returns None if no endpoint of the required role exists.
+pub fn select_endpoint(
+    endpoints: impl IntoIterator<Item = ResolvedServiceEndpoint>,
+    selector: ReplicaSelector,
+) -> Option<ResolvedServiceEndpoint> {
+    let mut candidates = endpoints
+        .into_iter()
+        .filter(|e| match selector {
+            ReplicaSelector::Primary => e.role == ServiceEndpointRole::StatefulPrimary,
+            ReplicaSelector::Any => true,
+            ReplicaSelector::RandomSecondary => e.role == ServiceEndpointRole::StatefulSecondary,
+        })
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return None;
+    }
+    let i = random_index(candidates.len());
+    Some(candidates.swap_remove(i))
+}
+
+/// Picks the endpoint and returns the address of the listener, or the default listener
+/// if no name is given.
+/// Returns None if no endpoint of the required role or no such listener exists.
+pub fn select_address(
+    endpoints: impl IntoIterator<Item = ResolvedServiceEndpoint>,
+    selector: ReplicaSelector,
+    listener_name: Option<&str>,
+) -> mssf_core::Result<Option<EndpointUri>> {
+    let Some(endpoint) = select_endpoint(endpoints, selector) else {
+        return Ok(None);
+    };
+    let set = EndpointAddressSet::try_from(&endpoint)?;
+    let uri = match listener_name {
+        Some(name) => set.listener(name),
+        None => set.default_listener(),
+    };
+    Ok(uri.cloned())
+}
+
+// std has no rng, but the hasher is randomly seeded per instance.
+fn random_index(len: usize) -> usize {
+    (RandomState::new().hash_one(0u8) % len as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_core::{
+        WString,
+        client::svc_mgmt_client::{ResolvedServiceEndpoint, ServiceEndpointRole},
+    };
+
+    use super::{EndpointAddressSet, EndpointUri, ReplicaSelector, select_address};
+
+    #[test]
+    fn test_parse_uri() {
+        let uri: EndpointUri = "http://localhost:8080/api?x=1".parse().unwrap();
+        assert_eq!(uri.scheme.as_deref(), Some("http"));
+        assert_eq!(uri.host, "localhost");
+        assert_eq!(uri.port, Some(8080));
+        assert_eq!(uri.path, "/api?x=1");
+        assert_eq!(uri.to_string(), "http://localhost:8080/api?x=1");
+
+        let uri: EndpointUri = "10.0.0.1:123".parse().unwrap();
+        assert_eq!(uri.scheme, None);
+        assert_eq!(uri.host, "10.0.0.1");
+        assert_eq!(uri.port, Some(123));
+
+        let uri: EndpointUri = "net.tcp://[::1]:80".parse().unwrap();
+        assert_eq!(uri.host, "::1");
+        assert_eq!(uri.port, Some(80));
+        assert_eq!(uri.to_string(), "net.tcp://[::1]:80");
+
+        let uri: EndpointUri = "http://host:80?x=1".parse().unwrap();
+        assert_eq!(uri.host, "host");
+        assert_eq!(uri.port, Some(80));
+        assert_eq!(uri.path, "?x=1");
+        assert_eq!(uri.to_string(), "http://host:80?x=1");
+
+        let uri: EndpointUri = "http://host:80#f".parse().unwrap();
+        assert_eq!(uri.port, Some(80));
+        assert_eq!(uri.path, "#f");
+
+        assert!("http://:80".parse::<EndpointUri>().is_err());
+        assert!("localhost:http".parse::<EndpointUri>().is_err());
+        assert!("fe80::1".parse::<EndpointUri>().is_err());
+        assert!("::1".parse::<EndpointUri>().is_err());
+        assert!("http://fe80::1:80/x".parse::<EndpointUri>().is_err());
+    }
+
+    #[test]
+    fn test_parse_address_set() {
+        let set = EndpointAddressSet::parse(
+            r#"{"Endpoints":{"Listener1":"http://a:1/x","Listener2":"https://b:2"}}"#,
+        )
+        .unwrap();
+        assert_eq!(set.listener("Listener2").unwrap().host, "b");
+        assert_eq!(set.default_listener().unwrap().path, "/x");
+        assert!(set.listener("Listener3").is_none());
+
+        let set = EndpointAddressSet::parse("localhost:99").unwrap();
+        assert_eq!(set.listener("").unwrap().port, Some(99));
+
+        assert!(EndpointAddressSet::parse("").unwrap().is_empty());
+        assert!(EndpointAddressSet::parse(r#"{"Endpoints":[]}"#).is_err());
+        assert!(EndpointAddressSet::parse(r#"{"Endpoints":{"L":1}}"#).is_err());
+
+        // A malformed listener does not hide the others.
+        let set = EndpointAddressSet::parse(
+            r#"{"Endpoints":{"Http":"http://h:80","Custom":"opaque-token:x"}}"#,
+        )
+        .unwrap();
+        assert_eq!(set.listener("Http").unwrap().port, Some(80));
+        assert!(set.listener("Custom").is_none());
+        assert_eq!(set.default_listener().unwrap().host, "h");
+    }
+
+    #[test]
+    fn test_select_address() {
+        let endpoints = vec![
+            ResolvedServiceEndpoint {
+                address: WString::from("p:1"),
+                role: ServiceEndpointRole::StatefulPrimary,
+            },
+            ResolvedServiceEndpoint {
+                address: WString::from(r#"{"Endpoints":{"L":"s:2"}}"#),
+                role: ServiceEndpointRole::StatefulSecondary,
+            },
+        ];
+        let p = select_address(endpoints.clone(), ReplicaSelector::Primary, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(p.host, "p");
+        let s = select_address(
+            endpoints.clone(),
+            ReplicaSelector::RandomSecondary,
+            Some("L"),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(s.host, "s");
+        assert!(
+            select_address(endpoints.clone(), ReplicaSelector::Primary, Some("L"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            select_address(Vec::new(), ReplicaSelector::Any, None)
+                .unwrap()
+                .is_none()
+        );
+        for _ in 0..10 {
+            assert!(
+                select_address(endpoints.clone(), ReplicaSelector::Any, None)
+                    .unwrap()
+                    .is_some()
+            );
+        }
+    }
+}
@@ -11,3 +11,7 @@ pub mod tokio;
 // Requires tokio_util
 #[cfg(feature = "tokio")]
 pub mod resolve;
+
+// Requires serde_json
+#[cfg(feature = "json")]
+pub mod endpoint;
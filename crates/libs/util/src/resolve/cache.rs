@@ -0,0 +1,530 @@
+// ------------------------------------------------------------
+// Copyright (c) Microsoft Corporation.  All rights reserved.
+// Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
+// ------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+};
+
+use mssf_core::{
+    WString,
+    client::{
+        ServiceNotification,
+        svc_mgmt_client::{PartitionKeyType, ResolvedServicePartition},
+    },
+    types::ServicePartitionInformation,
+};
+
+/// Hit and miss counts of the resolver cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolverCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Cache of resolved partitions for ServicePartitionResolver,
+/// keyed by service name and partition key.
+///
+/// Entries are invalidated by service notifications, so the cache must be hooked
+/// into the FabricClient used by the resolver:
+/// ```ignore
+/// let cache = ResolverCache::new(1024);
+/// let cache_cp = cache.clone();
+/// let fc = FabricClient::builder()
+///     .with_on_service_notification(move |n| {
+///         cache_cp.on_service_notification(n);
+///         Ok(())
+///     })
+///     .build()?;
+/// let resolver = ServicePartitionResolver::builder(fc).with_cache(cache).build();
+/// ```
+/// The resolver registers a notification filter for each service on its first resolve,
+/// and caches the partitions of a service only after its filter is registered.
+/// Notifications only mark entries stale: a ResolvedServicePartition is owned by SF and
+/// cannot be built from the notification endpoints. Stale entries are resolved again,
+/// and the newer version of concurrent resolves is kept.
+/// The least recently used entry is evicted when the capacity is reached.
+/// Filters stay registered for the lifetime of the FabricClient, so at most capacity
+/// services are tracked. Services beyond that are resolved without the cache.
+#[derive(Clone)]
+pub struct ResolverCache {
+    inner: Arc<Mutex<CacheInner>>,
+}
+
+struct CacheInner {
+    entries: LruMap<(WString, PartitionKeyType), CacheEntry>,
+    filters: HashMap<WString, FilterState>,
+    filter_capacity: usize,
+    metrics: ResolverCacheMetrics,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterState {
+    // Registration is in progress, and may still fail.
+    Pending,
+    Registered,
+}
+
+struct CacheEntry {
+    rsp: ResolvedServicePartition,
+    // Set by notifications. Stale entries are resolved again from SF.
+    stale: bool,
+}
+
+impl ResolverCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CacheInner {
+                entries: LruMap::new(capacity),
+                filters: HashMap::new(),
+                filter_capacity: capacity.max(1),
+                metrics: ResolverCacheMetrics::default(),
+            })),
+        }
+    }
+
+    pub fn metrics(&self) -> ResolverCacheMetrics {
+        self.inner.lock().unwrap().metrics
+    }
+
+    /// Marks the cached partitions of the notification as stale.
+    /// To be called from FabricClientBuilder::with_on_service_notification.
+    pub fn on_service_notification(&self, notification: &ServiceNotification) {
+        let mut inner = self.inner.lock().unwrap();
+        for ((name, key), entry) in inner.entries.iter_mut() {
+            if *name != notification.service_name {
+                continue;
+            }
+            // Without partition info it is unknown which partition changed.
+            let matched = match &notification.partition_info {
+                Some(info) => key_in_partition(key, info),
+                None => true,
+            };
+            if matched {
+                entry.stale = true;
+            }
+        }
+    }
+
+    /// Returns the fresh cached entry and counts the hit or miss.
+    pub(super) fn get(
+        &self,
+        name: &WString,
+        key_type: &PartitionKeyType,
+    ) -> Option<ResolvedServicePartition> {
+        let mut inner = self.inner.lock().unwrap();
+        let rsp = inner
+            .entries
+            .get(&(name.clone(), key_type.clone()))
+            .filter(|e| !e.stale)
+            .map(|e| e.rsp.clone());
+        match rsp {
+            Some(_) => inner.metrics.hits += 1,
+            None => inner.metrics.misses += 1,
+        }
+        rsp
+    }
+
+    /// Stores the resolved partition unless the cached one is newer.
+    /// Nothing is stored until the notification filter of the service is registered,
+    /// since the entry would not be invalidated.
+    pub(super) fn insert(
+        &self,
+        name: &WString,
+        key_type: &PartitionKeyType,
+        rsp: ResolvedServicePartition,
+    ) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.filters.get(name) != Some(&FilterState::Registered) {
+            return;
+        }
+        let key = (name.clone(), key_type.clone());
+        if let Some(entry) = inner.entries.get(&key) {
+            // Versions of different partitions cannot be compared, e.g. after repartition.
+            // The new result wins then.
+            if let Ok(i) = entry.rsp.compare_version(&rsp)
+                && i > 0
+            {
+                return;
+            }
+        }
+        inner.entries.insert(key, CacheEntry { rsp, stale: false });
+    }
+
+    /// Returns a claim if the caller should register the notification filter of the service.
+    /// None if the filter is registered or being registered, or too many services are tracked.
+    pub(super) fn claim_filter(&self, name: &WString) -> Option<FilterClaim> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.filters.contains_key(name) || inner.filters.len() >= inner.filter_capacity {
+            return None;
+        }
+        inner.filters.insert(name.clone(), FilterState::Pending);
+        Some(FilterClaim {
+            cache: self.clone(),
+            name: name.clone(),
+            armed: true,
+        })
+    }
+}
+
+/// Pending registration of a notification filter.
+/// Dropping the claim before registered is called, i.e. on failure or when the
+/// resolve future is dropped, releases it so that the next resolve registers again.
+pub(super) struct FilterClaim {
+    cache: ResolverCache,
+    name: WString,
+    armed: bool,
+}
+
+impl FilterClaim {
+    pub(super) fn registered(mut self) {
+        self.armed = false;
+        self.cache
+            .inner
+            .lock()
+            .unwrap()
+            .filters
+            .insert(self.name.clone(), FilterState::Registered);
+    }
+}
+
+impl Drop for FilterClaim {
+    fn drop(&mut self) {
+        if self.armed {
+            self.cache.inner.lock().unwrap().filters.remove(&self.name);
+        }
+    }
+}
+
+/// Checks if the key belongs to the partition.
+fn key_in_partition(key: &PartitionKeyType, info: &ServicePartitionInformation) -> bool {
+    match (key, info) {
+        (PartitionKeyType::None, ServicePartitionInformation::Singleton(_)) => true,
+        (PartitionKeyType::Int64(k), ServicePartitionInformation::Int64Range(r)) => {
+            r.low_key <= *k && *k <= r.high_key
+        }
+        (PartitionKeyType::String(k), ServicePartitionInformation::Named(n)) => *k == n.name,
+        // The partition kind of the service changed, i.e. the service was recreated.
+        _ => true,
+    }
+}
+
+/// Map that evicts the least recently used entry when full.
+/// Eviction scans all entries, which is fine for the size of a resolve cache.
+struct LruMap<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+}
+
+impl<K: Eq + Hash + Clone, V> LruMap<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        self.entries.get_mut(key).map(|(v, t)| {
+            *t = tick;
+            &*v
+        })
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.entries.len() >= self.capacity
+            && !self.entries.contains_key(&key)
+            && let Some(lru) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, t))| *t)
+                .map(|(k, _)| k.clone())
+        {
+            self.entries.remove(&lru);
+        }
+        let tick = self.next_tick();
+        self.entries.insert(key, (value, tick));
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.entries.iter_mut().map(|(k, (v, _))| (k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mssf_com::{
+        FabricClient::{
+            IFabricResolvedServicePartitionResult, IFabricResolvedServicePartitionResult_Impl,
+            IFabricServiceEndpointsVersion, IFabricServiceNotification,
+            IFabricServiceNotification_Impl,
+        },
+        FabricTypes::{
+            FABRIC_INT64_RANGE_PARTITION_INFORMATION, FABRIC_RESOLVED_SERVICE_ENDPOINT,
+            FABRIC_RESOLVED_SERVICE_PARTITION, FABRIC_SERVICE_NOTIFICATION,
+            FABRIC_SERVICE_PARTITION_INFORMATION, FABRIC_SERVICE_PARTITION_KIND_INT64_RANGE,
+            FABRIC_URI,
+        },
+    };
+    use mssf_core::{
+        GUID, WString,
+        client::{
+            ServiceNotification,
+            svc_mgmt_client::{PartitionKeyType, ResolvedServicePartition},
+        },
+        types::{
+            Int64PartitionInfomation, NamedPartitionInfomation, ServicePartitionInformation,
+            SingletonPartitionInfomation,
+        },
+    };
+    use std::task::{Context, Waker};
+    use windows_core::{AsImpl, Ref};
+
+    use super::{LruMap, ResolverCache, ResolverCacheMetrics, key_in_partition};
+
+    /// Resolved partition that only supports version compare.
+    #[windows_core::implement(IFabricResolvedServicePartitionResult)]
+    struct TestPartition {
+        version: i64,
+    }
+
+    impl IFabricResolvedServicePartitionResult_Impl for TestPartition_Impl {
+        fn get_Partition(&self) -> *mut FABRIC_RESOLVED_SERVICE_PARTITION {
+            std::ptr::null_mut()
+        }
+
+        fn GetEndpoint(&self) -> windows_core::Result<*mut FABRIC_RESOLVED_SERVICE_ENDPOINT> {
+            Err(windows_core::Error::empty())
+        }
+
+        fn CompareVersion(
+            &self,
+            other: Ref<'_, IFabricResolvedServicePartitionResult>,
+        ) -> windows_core::Result<i32> {
+            let other: &TestPartition = unsafe { other.ok()?.as_impl() };
+            Ok(self.version.cmp(&other.version) as i32)
+        }
+    }
+
+    fn partition(version: i64) -> ResolvedServicePartition {
+        IFabricResolvedServicePartitionResult::from(TestPartition { version }).into()
+    }
+
+    /// Notification of an int64 range partition, or of the whole service.
+    #[windows_core::implement(IFabricServiceNotification)]
+    struct TestNotification {
+        raw: Box<FABRIC_SERVICE_NOTIFICATION>,
+        _name: WString,
+        _info: Option<Box<FABRIC_SERVICE_PARTITION_INFORMATION>>,
+        _range: Box<FABRIC_INT64_RANGE_PARTITION_INFORMATION>,
+    }
+
+    impl IFabricServiceNotification_Impl for TestNotification_Impl {
+        fn get_Notification(&self) -> *mut FABRIC_SERVICE_NOTIFICATION {
+            self.raw.as_ref() as *const _ as *mut _
+        }
+
+        fn GetVersion(&self) -> windows_core::Result<IFabricServiceEndpointsVersion> {
+            Err(windows_core::Error::empty())
+        }
+    }
+
+    fn notification(name: &str, range: Option<(i64, i64)>) -> ServiceNotification {
+        let name = WString::from(name);
+        let (low_key, high_key) = range.unwrap_or_default();
+        let mut range_raw = Box::new(FABRIC_INT64_RANGE_PARTITION_INFORMATION {
+            LowKey: low_key,
+            HighKey: high_key,
+            ..Default::default()
+        });
+        let mut info = range.map(|_| {
+            Box::new(FABRIC_SERVICE_PARTITION_INFORMATION {
+                Kind: FABRIC_SERVICE_PARTITION_KIND_INT64_RANGE,
+                Value: range_raw.as_mut() as *mut _ as *mut _,
+            })
+        });
+        let raw = Box::new(FABRIC_SERVICE_NOTIFICATION {
+            ServiceName: FABRIC_URI(name.as_ptr() as *mut u16),
+            PartitionInfo: info
+                .as_mut()
+                .map_or(std::ptr::null_mut(), |i| i.as_mut() as *mut _),
+            ..Default::default()
+        });
+        let com: IFabricServiceNotification = TestNotification {
+            raw,
+            _name: name,
+            _info: info,
+            _range: range_raw,
+        }
+        .into();
+        ServiceNotification::from(com)
+    }
+
+    fn registered_cache(name: &WString) -> ResolverCache {
+        let cache = ResolverCache::new(8);
+        cache.claim_filter(name).unwrap().registered();
+        cache
+    }
+
+    #[test]
+    fn test_lru_map() {
+        let mut map = LruMap::new(2);
+        map.insert(1, "a");
+        map.insert(2, "b");
+        // 1 becomes the most recently used.
+        assert_eq!(map.get(&1), Some(&"a"));
+        map.insert(3, "c");
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.get(&1), Some(&"a"));
+        assert_eq!(map.get(&3), Some(&"c"));
+        // Replacing does not evict.
+        map.insert(3, "d");
+        assert_eq!(map.get(&1), Some(&"a"));
+        assert_eq!(map.get(&3), Some(&"d"));
+    }
+
+    #[test]
+    fn test_key_in_partition() {
+        let range = ServicePartitionInformation::Int64Range(Int64PartitionInfomation {
+            id: GUID::zeroed(),
+            low_key: 0,
+            high_key: 9,
+        });
+        assert!(key_in_partition(&PartitionKeyType::Int64(9), &range));
+        assert!(!key_in_partition(&PartitionKeyType::Int64(10), &range));
+
+        let named = ServicePartitionInformation::Named(NamedPartitionInfomation {
+            id: GUID::zeroed(),
+            name: WString::from("a"),
+        });
+        assert!(key_in_partition(
+            &PartitionKeyType::String(WString::from("a")),
+            &named
+        ));
+        assert!(!key_in_partition(
+            &PartitionKeyType::String(WString::from("b")),
+            &named
+        ));
+
+        let singleton = ServicePartitionInformation::Singleton(SingletonPartitionInfomation {
+            id: GUID::zeroed(),
+        });
+        assert!(key_in_partition(&PartitionKeyType::None, &singleton));
+    }
+
+    #[test]
+    fn test_cache_metrics() {
+        let name = WString::from("fabric:/a");
+        let cache = registered_cache(&name);
+        let key = PartitionKeyType::Int64(1);
+        assert!(cache.get(&name, &key).is_none());
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key) == Some(partition(1)));
+        assert!(cache.get(&name, &PartitionKeyType::Int64(2)).is_none());
+        assert_eq!(cache.metrics(), ResolverCacheMetrics { hits: 1, misses: 2 });
+    }
+
+    #[test]
+    fn test_cache_notification() {
+        let name = WString::from("fabric:/a");
+        let cache = registered_cache(&name);
+        let key1 = PartitionKeyType::Int64(1);
+        let key2 = PartitionKeyType::Int64(20);
+        cache.insert(&name, &key1, partition(1));
+        cache.insert(&name, &key2, partition(1));
+
+        // Other services and partitions are not affected.
+        cache.on_service_notification(&notification("fabric:/b", None));
+        cache.on_service_notification(&notification("fabric:/a", Some((10, 30))));
+        assert!(cache.get(&name, &key1).is_some());
+        assert!(cache.get(&name, &key2).is_none());
+
+        // Resolving again refreshes the entry.
+        cache.insert(&name, &key2, partition(2));
+        assert!(cache.get(&name, &key2) == Some(partition(2)));
+
+        // Without partition info all partitions of the service are stale.
+        cache.on_service_notification(&notification("fabric:/a", None));
+        assert!(cache.get(&name, &key1).is_none());
+        assert!(cache.get(&name, &key2).is_none());
+    }
+
+    #[test]
+    fn test_cache_insert_version() {
+        let name = WString::from("fabric:/a");
+        let cache = registered_cache(&name);
+        let key = PartitionKeyType::None;
+        cache.insert(&name, &key, partition(2));
+        // An older result of a concurrent resolve is dropped.
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key) == Some(partition(2)));
+        cache.insert(&name, &key, partition(3));
+        assert!(cache.get(&name, &key) == Some(partition(3)));
+    }
+
+    #[test]
+    fn test_cache_filter() {
+        let name = WString::from("fabric:/a");
+        let key = PartitionKeyType::None;
+        let cache = ResolverCache::new(8);
+        let claim = cache.claim_filter(&name).unwrap();
+        // A concurrent resolve does not cache while the registration is pending.
+        assert!(cache.claim_filter(&name).is_none());
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key).is_none());
+
+        // The next resolve retries the failed registration.
+        drop(claim);
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key).is_none());
+        cache.claim_filter(&name).unwrap().registered();
+        assert!(cache.claim_filter(&name).is_none());
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key) == Some(partition(1)));
+    }
+
+    #[test]
+    fn test_cache_filter_dropped() {
+        let name = WString::from("fabric:/a");
+        let cache = ResolverCache::new(8);
+        // The resolve future is dropped while the registration is in flight,
+        // e.g. by a caller timeout.
+        let cache_cp = cache.clone();
+        let name_cp = name.clone();
+        let mut fut = Box::pin(async move {
+            let claim = cache_cp.claim_filter(&name_cp).unwrap();
+            std::future::pending::<()>().await;
+            claim.registered();
+        });
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(fut.as_mut().poll(&mut cx).is_pending());
+        assert!(cache.claim_filter(&name).is_none());
+        drop(fut);
+        assert!(cache.claim_filter(&name).is_some());
+    }
+
+    #[test]
+    fn test_cache_filter_capacity() {
+        let cache = ResolverCache::new(1);
+        cache
+            .claim_filter(&WString::from("fabric:/a"))
+            .unwrap()
+            .registered();
+        let name = WString::from("fabric:/b");
+        let key = PartitionKeyType::None;
+        assert!(cache.claim_filter(&name).is_none());
+        cache.insert(&name, &key, partition(1));
+        assert!(cache.get(&name, &key).is_none());
+    }
+}
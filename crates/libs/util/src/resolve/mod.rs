@@ -6,6 +6,7 @@
 use std::{pin::Pin, time::Duration};
 
 use mssf_core::runtime::executor::{BoxedCancelToken, Timer};
+use mssf_core::types::{ServiceNotificationFilterDescription, ServiceNotificationFilterFlags};
 use mssf_core::{ErrorCode, WString};
 
 use mssf_core::client::{
@@ -13,12 +14,19 @@ use mssf_core::client::{
     svc_mgmt_client::{PartitionKeyType, ResolvedServicePartition, ServiceManagementClient},
 };
 
+mod cache;
+pub use cache::{ResolverCache, ResolverCacheMetrics};
+
 /// The same as dotnet sdk:
 /// https://github.com/microsoft/service-fabric-services-and-actors-dotnet/blob/develop/src/Microsoft.ServiceFabric.Services/Client/ServicePartitionResolver.cs
-/// But this does not register notification on resolve success.
+/// Without a cache this does not register notification on resolve success.
 /// User needs to register notification manually on the FabricClient before creating this resolver.
+/// With a cache (see ResolverCache) the notification filters are registered on the first resolve
+/// of each service, and the resolved partitions are served from the cache until a notification
+/// invalidates them.
 pub struct ServicePartitionResolver {
     sm: ServiceManagementClient,
+    cache: Option<ResolverCache>,
     timer: Box<dyn Timer>,
     default_timeout: Duration,
     max_retry_interval: Duration,
@@ -65,6 +73,7 @@ pub struct ServicePartitionResolverBuilder {
     timer: Option<Box<dyn Timer>>,
     default_timeout: Option<Duration>,
     default_max_retry_interval: Option<Duration>,
+    cache: Option<ResolverCache>,
 }
 
 impl ServicePartitionResolverBuilder {
@@ -74,6 +83,7 @@ impl ServicePartitionResolverBuilder {
             timer: None,
             default_timeout: None,
             default_max_retry_interval: None,
+            cache: None,
         }
     }
 
@@ -83,9 +93,17 @@ impl ServicePartitionResolverBuilder {
         self
     }
 
+    /// With a cache of the resolved partitions.
+    /// The cache must receive the service notifications of the FabricClient.
+    pub fn with_cache(mut self, cache: ResolverCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     pub fn build(self) -> ServicePartitionResolver {
         ServicePartitionResolver {
             sm: self.fc.get_service_manager().clone(),
+            cache: self.cache,
             timer: self.timer.unwrap_or(Box::new(crate::tokio::TokioTimer)),
             default_timeout: self.default_timeout.unwrap_or(Duration::from_secs(30)),
             max_retry_interval: self
@@ -100,8 +118,15 @@ impl ServicePartitionResolver {
         ServicePartitionResolverBuilder::new(fc)
     }
 
+    /// Returns the cache metrics if the resolver has a cache.
+    pub fn cache_metrics(&self) -> Option<ResolverCacheMetrics> {
+        self.cache.as_ref().map(ResolverCache::metrics)
+    }
+
     /// Resolve the service partition by name and key type.
     /// It retries all transient errors and timeouts.
+    /// With a cache, the cached partition is returned if it is not invalidated by a
+    /// notification, unless prev is given, i.e. the caller found prev to be outdated.
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(skip_all, fields(uri = %name, timeout = ?timeout.unwrap_or(self.default_timeout)), err)
@@ -113,6 +138,55 @@ impl ServicePartitionResolver {
         prev: Option<&ResolvedServicePartition>,
         timeout: Option<Duration>, // Total timeout for the operation
         token: Option<BoxedCancelToken>,
+    ) -> mssf_core::Result<ResolvedServicePartition> {
+        let Some(cache) = &self.cache else {
+            return self
+                .resolve_no_cache(name, key_type, prev, timeout, token)
+                .await;
+        };
+        if prev.is_none()
+            && let Some(rsp) = cache.get(name, key_type)
+        {
+            return Ok(rsp);
+        }
+        // Registration and resolve share the total timeout.
+        let counter = TimeCounter::new(timeout.unwrap_or(self.default_timeout));
+        // Entries are only valid as long as notifications for the service arrive,
+        // so the cache only stores them once the filter is registered.
+        let remaining = counter.remaining()?;
+        if let Some(claim) = cache.claim_filter(name) {
+            let desc = ServiceNotificationFilterDescription {
+                name: name.clone(),
+                flags: ServiceNotificationFilterFlags::None,
+            };
+            match self
+                .sm
+                .register_service_notification_filter(&desc, remaining, token.clone())
+                .await
+            {
+                Ok(_) => claim.registered(),
+                // Resolve still works. The claim is released on drop,
+                // so registration is retried on the next resolve.
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("Failed to register service notification filter: {_e}");
+                }
+            }
+        }
+        let rsp = self
+            .resolve_no_cache(name, key_type, prev, Some(counter.remaining()?), token)
+            .await?;
+        cache.insert(name, key_type, rsp.clone());
+        Ok(rsp)
+    }
+
+    async fn resolve_no_cache(
+        &self,
+        name: &WString,
+        key_type: &PartitionKeyType,
+        prev: Option<&ResolvedServicePartition>,
+        timeout: Option<Duration>,
+        token: Option<BoxedCancelToken>,
     ) -> mssf_core::Result<ResolvedServicePartition> {
         let timeout = timeout.unwrap_or(self.default_timeout);
         let timer = TimeCounter::new(timeout);
@@ -457,3 +457,330 @@ mod proxy_test {
         }
     }
 }
+
+mod operation_test {
+    use std::sync::Mutex;
+
+    use mssf_com::FabricRuntime::{
+        IFabricAtomicGroupStateProvider, IFabricOperationDataStream, IFabricStateProvider,
+    };
+    use mssf_core::{
+        ErrorCode, Interface,
+        runtime::{
+            executor::BoxedCancelToken,
+            operation_proxy::OperationDataStreamProxy,
+            stateful::{AtomicGroupStateProvider, OperationDataStream, StateProvider},
+            stateful_bridge::{
+                IFabricAtomicGroupStateProviderBridge, IFabricOperationDataStreamBridge,
+                IFabricStateProviderBridge,
+            },
+        },
+        sync::fabric_begin_end_proxy,
+        types::{Epoch, OperationData},
+    };
+    use tokio::runtime::Handle;
+
+    use crate::tokio::{TokioCancelToken, TokioExecutor};
+
+    struct VecStream {
+        data: Mutex<Vec<OperationData>>,
+    }
+
+    impl VecStream {
+        fn new(mut data: Vec<OperationData>) -> Self {
+            data.reverse();
+            Self {
+                data: Mutex::new(data),
+            }
+        }
+    }
+
+    impl OperationDataStream for VecStream {
+        async fn get_next(&self, _: BoxedCancelToken) -> mssf_core::Result<Option<OperationData>> {
+            Ok(self.data.lock().unwrap().pop())
+        }
+    }
+
+    fn test_data() -> Vec<OperationData> {
+        vec![
+            OperationData::from(b"hello".to_vec()),
+            OperationData::default(),
+            OperationData::from(vec![b"a".to_vec(), Vec::new(), b"bc".to_vec()]),
+        ]
+    }
+
+    async fn collect(stream: &impl OperationDataStream) -> Vec<OperationData> {
+        let mut res = Vec::new();
+        while let Some(data) = stream
+            .get_next(TokioCancelToken::new_boxed())
+            .await
+            .unwrap()
+        {
+            res.push(data);
+        }
+        res
+    }
+
+    #[tokio::test]
+    async fn test_operation_data_stream() {
+        let rt = TokioExecutor::new(Handle::current());
+        let com: IFabricOperationDataStream =
+            IFabricOperationDataStreamBridge::create(VecStream::new(test_data()), rt).into();
+        let proxy = OperationDataStreamProxy::from(com);
+        assert_eq!(collect(&proxy).await, test_data());
+        // The end of the stream is sticky.
+        assert_eq!(
+            proxy.get_next(TokioCancelToken::new_boxed()).await.unwrap(),
+            None
+        );
+    }
+
+    struct Provider;
+
+    impl StateProvider for Provider {
+        async fn update_epoch(
+            &self,
+            _: &Epoch,
+            _: i64,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<()> {
+            Ok(())
+        }
+
+        fn get_last_committed_sequence_number(&self) -> mssf_core::Result<i64> {
+            Ok(42)
+        }
+
+        async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<bool> {
+            Ok(false)
+        }
+
+        fn get_copy_context(&self) -> mssf_core::Result<Option<impl OperationDataStream>> {
+            Ok(None::<VecStream>)
+        }
+
+        fn get_copy_state(
+            &self,
+            up_to_sequence_number: i64,
+            copy_context: Option<OperationDataStreamProxy>,
+        ) -> mssf_core::Result<impl OperationDataStream> {
+            assert!(copy_context.is_some());
+            Ok(VecStream::new(vec![OperationData::from(
+                up_to_sequence_number.to_le_bytes().to_vec(),
+            )]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_state_provider_bridge() {
+        let rt = TokioExecutor::new(Handle::current());
+        let provider: IFabricStateProvider =
+            IFabricStateProviderBridge::create(Provider, rt.clone()).into();
+        assert_eq!(
+            unsafe { provider.GetLastCommittedSequenceNumber() }.unwrap(),
+            42
+        );
+        // No copy context is a null stream.
+        let err = unsafe { provider.GetCopyContext() }.unwrap_err();
+        assert_eq!(err.code(), mssf_core::HRESULT(0));
+
+        let context: IFabricOperationDataStream =
+            IFabricOperationDataStreamBridge::create(VecStream::new(test_data()), rt).into();
+        let state = unsafe { provider.GetCopyState(7, &context) }.unwrap();
+        assert_eq!(
+            collect(&OperationDataStreamProxy::from(state)).await,
+            vec![OperationData::from(7i64.to_le_bytes().to_vec())]
+        );
+    }
+
+    /// Records the atomic group callbacks.
+    struct AtomicProvider {
+        calls: Mutex<Vec<(&'static str, i64, i64)>>,
+    }
+
+    impl StateProvider for AtomicProvider {
+        async fn update_epoch(
+            &self,
+            _: &Epoch,
+            _: i64,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<()> {
+            Ok(())
+        }
+
+        fn get_last_committed_sequence_number(&self) -> mssf_core::Result<i64> {
+            Ok(5)
+        }
+
+        async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<bool> {
+            Ok(true)
+        }
+
+        fn get_copy_context(&self) -> mssf_core::Result<Option<impl OperationDataStream>> {
+            Ok(None::<VecStream>)
+        }
+
+        fn get_copy_state(
+            &self,
+            _: i64,
+            _: Option<OperationDataStreamProxy>,
+        ) -> mssf_core::Result<impl OperationDataStream> {
+            Ok(VecStream::new(Vec::new()))
+        }
+    }
+
+    impl AtomicGroupStateProvider for AtomicProvider {
+        async fn atomic_group_commit(
+            &self,
+            atomic_group_id: i64,
+            commit_sequence_number: i64,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<()> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(("commit", atomic_group_id, commit_sequence_number));
+            Ok(())
+        }
+
+        async fn atomic_group_rollback(
+            &self,
+            atomic_group_id: i64,
+            rollback_sequence_number: i64,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<()> {
+            self.calls.lock().unwrap().push((
+                "rollback",
+                atomic_group_id,
+                rollback_sequence_number,
+            ));
+            Ok(())
+        }
+
+        async fn atomic_group_undo_progress(
+            &self,
+            _: i64,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<()> {
+            Err(ErrorCode::E_NOTIMPL.into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_atomic_group_state_provider_bridge() {
+        let rt = TokioExecutor::new(Handle::current());
+        let provider: IFabricAtomicGroupStateProvider =
+            IFabricAtomicGroupStateProviderBridge::create(
+                AtomicProvider {
+                    calls: Mutex::new(Vec::new()),
+                },
+                rt,
+            )
+            .into();
+        // The state provider part is reachable from the same object.
+        let state_provider = provider.cast::<IFabricStateProvider>().unwrap();
+        assert_eq!(
+            unsafe { state_provider.GetLastCommittedSequenceNumber() }.unwrap(),
+            5
+        );
+        let com1 = state_provider.clone();
+        let data_loss = fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginOnDataLoss(callback) },
+            move |ctx| unsafe { state_provider.EndOnDataLoss(ctx) },
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(data_loss, 1);
+
+        let (com1, com2) = (provider.clone(), provider.clone());
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginAtomicGroupCommit(1, 10, callback) },
+            move |ctx| unsafe { com2.EndAtomicGroupCommit(ctx) },
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let (com1, com2) = (provider.clone(), provider.clone());
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginAtomicGroupRollback(2, 11, callback) },
+            move |ctx| unsafe { com2.EndAtomicGroupRollback(ctx) },
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let (com1, com2) = (provider.clone(), provider.clone());
+        let err = fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginUndoProgress(12, callback) },
+            move |ctx| unsafe { com2.EndUndoProgress(ctx) },
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap_err();
+        assert_eq!(err, ErrorCode::E_NOTIMPL.into());
+    }
+}
+
+mod store_test {
+    use mssf_com::{
+        FabricRuntime::IFabricStorePostBackupHandler, FabricTypes::FABRIC_STORE_BACKUP_INFO,
+    };
+    use mssf_core::{
+        PCWSTR, WString,
+        runtime::{
+            executor::BoxedCancelToken,
+            store::{IFabricStorePostBackupHandlerBridge, StorePostBackupHandler},
+        },
+        sync::fabric_begin_end_proxy,
+        types::{StoreBackupInfo, StoreBackupOption},
+    };
+    use tokio::runtime::Handle;
+
+    use crate::tokio::TokioExecutor;
+
+    /// Keeps the chain of incremental backups only.
+    struct Handler;
+
+    impl StorePostBackupHandler for Handler {
+        async fn post_backup(
+            &self,
+            info: StoreBackupInfo,
+            _: BoxedCancelToken,
+        ) -> mssf_core::Result<bool> {
+            assert_eq!(info.backup_folder, WString::from("C:\\backup"));
+            Ok(info.backup_option == StoreBackupOption::Incremental)
+        }
+    }
+
+    async fn post_backup(com: &IFabricStorePostBackupHandler, option: StoreBackupOption) -> u8 {
+        let folder = WString::from("C:\\backup");
+        let info = FABRIC_STORE_BACKUP_INFO {
+            BackupFolder: PCWSTR::from_raw(folder.as_ptr()),
+            BackupOption: option.into(),
+            Reserved: std::ptr::null_mut(),
+        };
+        let com1 = com.clone();
+        let com2 = com.clone();
+        fabric_begin_end_proxy(
+            move |callback| unsafe { com1.BeginPostBackup(&info, callback) },
+            move |ctx| unsafe { com2.EndPostBackup(ctx) },
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_post_backup_handler_bridge() {
+        let rt = TokioExecutor::new(Handle::current());
+        let com: IFabricStorePostBackupHandler =
+            IFabricStorePostBackupHandlerBridge::create(Handler, rt).into();
+        assert_eq!(post_backup(&com, StoreBackupOption::Incremental).await, 1);
+        assert_eq!(post_backup(&com, StoreBackupOption::Full).await, 0);
+    }
+}
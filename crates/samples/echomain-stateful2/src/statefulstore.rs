@@ -3,24 +3,33 @@
 // Licensed under the MIT License (MIT). See License.txt in the repo root for license information.
 // ------------------------------------------------------------
 
+use mssf_com::FabricTypes::FABRIC_REPLICATOR_ADDRESS;
 use mssf_core::runtime::executor::BoxedCancelToken;
 use mssf_core::{Error, WString};
 use mssf_core::{
     runtime::{
-        stateful::{PrimaryReplicator, Replicator, StatefulServiceFactory, StatefulServiceReplica},
-        stateful_proxy::StatefulServicePartition,
+        operation_proxy::{OperationDataStreamProxy, OperationStream},
+        stateful::{
+            OperationDataStream, PrimaryReplicator, Replicator, StateProvider,
+            StatefulServiceFactory, StatefulServiceReplica,
+        },
+        stateful_proxy::{PrimaryReplicatorProxy, StateReplicator, StatefulServicePartition},
     },
     types::{
-        Epoch, OpenMode, ReplicaInformation, ReplicaRole, ReplicaSetConfig, ReplicaSetQuorumMode,
+        Epoch, OpenMode, OperationData, ReplicaInformation, ReplicaRole, ReplicaSetConfig,
+        ReplicaSetQuorumMode, ReplicatorSettings,
     },
 };
 use mssf_util::tokio::TokioExecutor;
 use std::{
     cell::Cell,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicI64, Ordering},
+    },
 };
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{error, info};
 
 use crate::echo;
 
@@ -48,61 +57,54 @@ fn get_addr(port: u32, hostname: WString) -> String {
     addr
 }
 
+/// Traces the calls to the SF system replicator created from the CounterState.
 pub struct AppFabricReplicator {
-    port_: u32,
-    hostname_: WString,
+    inner: PrimaryReplicatorProxy,
     ctx: ReplicaCtx,
 }
 
 impl AppFabricReplicator {
-    pub fn new(port: u32, hostname: WString, ctx: ReplicaCtx) -> AppFabricReplicator {
-        AppFabricReplicator {
-            port_: port,
-            hostname_: hostname,
-            ctx,
-        }
+    pub fn new(inner: PrimaryReplicatorProxy, ctx: ReplicaCtx) -> AppFabricReplicator {
+        AppFabricReplicator { inner, ctx }
     }
 }
 
-// This is basic implementation of Replicator
 impl Replicator for AppFabricReplicator {
-    async fn open(&self, _: BoxedCancelToken) -> mssf_core::Result<WString> {
+    async fn open(&self, token: BoxedCancelToken) -> mssf_core::Result<WString> {
         info!(
             "AppFabricReplicator2::Replicator::Open: {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        let addr = get_addr(self.port_, self.hostname_.clone());
-        let str_res = WString::from(addr);
-        Ok(str_res)
+        self.inner.open(token).await
     }
 
-    async fn close(&self, _: BoxedCancelToken) -> mssf_core::Result<()> {
+    async fn close(&self, token: BoxedCancelToken) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::Replicator::close {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner.close(token).await
     }
 
     async fn change_role(
         &self,
         epoch: &Epoch,
         role: &ReplicaRole,
-        _: BoxedCancelToken,
+        token: BoxedCancelToken,
     ) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::Replicator::change_role epoch:{epoch:?}, role:{role:?}, {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner.change_role(epoch, role, token).await
     }
 
-    async fn update_epoch(&self, epoch: &Epoch, _: BoxedCancelToken) -> mssf_core::Result<()> {
+    async fn update_epoch(&self, epoch: &Epoch, token: BoxedCancelToken) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::Replicator::update_epoch: {epoch:?}, {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner.update_epoch(epoch, token).await
     }
 
     fn get_current_progress(&self) -> mssf_core::Result<i64> {
@@ -110,7 +112,7 @@ impl Replicator for AppFabricReplicator {
             "AppFabricReplicator2::Replicator::get_current_progress {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(0)
+        self.inner.get_current_progress()
     }
 
     fn get_catch_up_capability(&self) -> mssf_core::Result<i64> {
@@ -118,7 +120,7 @@ impl Replicator for AppFabricReplicator {
             "AppFabricReplicator2::Replicator::get_catch_up_capability {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(0)
+        self.inner.get_catch_up_capability()
     }
 
     fn abort(&self) {
@@ -126,17 +128,17 @@ impl Replicator for AppFabricReplicator {
             "AppFabricReplicator2::Replicator::abort {:?}",
             self.ctx.get_trace_read_write_status()
         );
+        self.inner.abort()
     }
 }
 
-// This is basic implementation of PrimaryReplicator
 impl PrimaryReplicator for AppFabricReplicator {
-    async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<u8> {
+    async fn on_data_loss(&self, token: BoxedCancelToken) -> mssf_core::Result<u8> {
         info!(
             "AppFabricReplicator2::PrimaryReplicator::on_data_loss {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(0)
+        self.inner.on_data_loss(token).await
     }
 
     fn update_catch_up_replica_set_configuration(
@@ -148,13 +150,14 @@ impl PrimaryReplicator for AppFabricReplicator {
             "AppFabricReplicator2::PrimaryReplicator::update_catch_up_replica_set_configuration: curr: {currentconfiguration:?}, prev: {previousconfiguration:?},{:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner
+            .update_catch_up_replica_set_configuration(currentconfiguration, previousconfiguration)
     }
 
     async fn wait_for_catch_up_quorum(
         &self,
         catchupmode: ReplicaSetQuorumMode,
-        _: BoxedCancelToken,
+        token: BoxedCancelToken,
     ) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::PrimaryReplicator::wait_for_catch_up_quorum mode:{catchupmode:?} {:?}",
@@ -180,7 +183,9 @@ impl PrimaryReplicator for AppFabricReplicator {
         // * update_catch_up_replica_set_configuration (R:P, W:P)
         // * wait_for_catch_up_quorum (R:P, W:P)
         // * update_current_replica_set_configuration (R:G, W:G)
-        Ok(())
+        self.inner
+            .wait_for_catch_up_quorum(catchupmode, token)
+            .await
     }
 
     fn update_current_replica_set_configuration(
@@ -191,28 +196,114 @@ impl PrimaryReplicator for AppFabricReplicator {
             "AppFabricReplicator2::PrimaryReplicator::update_current_replica_set_configuration {currentconfiguration:?} {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner
+            .update_current_replica_set_configuration(currentconfiguration)
     }
 
     async fn build_replica(
         &self,
         replica: &ReplicaInformation,
-        _: BoxedCancelToken,
+        token: BoxedCancelToken,
     ) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::PrimaryReplicator::build_replica: info: {replica:?} {:?}",
             self.ctx.get_trace_read_write_status()
         );
-        Ok(())
+        self.inner.build_replica(replica, token).await
     }
 
-    fn remove_replica(&self, _replicaid: i64) -> mssf_core::Result<()> {
+    fn remove_replica(&self, replicaid: i64) -> mssf_core::Result<()> {
         info!(
             "AppFabricReplicator2::PrimaryReplicator::remove_replica {:?}",
             self.ctx.get_trace_read_write_status()
         );
+        self.inner.remove_replica(replicaid)
+    }
+}
+
+/// Replicated state of the sample: the number of operations applied,
+/// which is also the LSN of the last one.
+#[derive(Clone, Default)]
+pub struct CounterState {
+    last_lsn: Arc<AtomicI64>,
+}
+
+impl CounterState {
+    fn apply(&self, lsn: i64) {
+        self.last_lsn.fetch_max(lsn, Ordering::Relaxed);
+    }
+}
+
+impl StateProvider for CounterState {
+    async fn update_epoch(
+        &self,
+        epoch: &Epoch,
+        previous_epoch_last_sequence_number: i64,
+        _: BoxedCancelToken,
+    ) -> mssf_core::Result<()> {
+        info!(
+            "CounterState::update_epoch {epoch:?}, prev lsn {previous_epoch_last_sequence_number}"
+        );
         Ok(())
     }
+
+    fn get_last_committed_sequence_number(&self) -> mssf_core::Result<i64> {
+        Ok(self.last_lsn.load(Ordering::Relaxed))
+    }
+
+    async fn on_data_loss(&self, _: BoxedCancelToken) -> mssf_core::Result<bool> {
+        Ok(false)
+    }
+
+    fn get_copy_context(&self) -> mssf_core::Result<Option<impl OperationDataStream>> {
+        Ok(None::<CopyStream>)
+    }
+
+    fn get_copy_state(
+        &self,
+        up_to_sequence_number: i64,
+        _: Option<OperationDataStreamProxy>,
+    ) -> mssf_core::Result<impl OperationDataStream> {
+        // The whole state is the lsn.
+        let data = OperationData::from(vec![up_to_sequence_number.to_le_bytes().to_vec()]);
+        Ok(CopyStream(Mutex::new(Some(data))))
+    }
+}
+
+/// Copy stream with a single operation.
+struct CopyStream(Mutex<Option<OperationData>>);
+
+impl OperationDataStream for CopyStream {
+    async fn get_next(&self, _: BoxedCancelToken) -> mssf_core::Result<Option<OperationData>> {
+        Ok(self.0.lock().unwrap().take())
+    }
+}
+
+/// Applies the copy stream, whose only operation carries the lsn of the state.
+async fn pump_copy_stream(stream: OperationStream, state: CounterState) -> mssf_core::Result<()> {
+    while let Some(op) = stream.next(None).await? {
+        let lsn = op
+            .data()
+            .first()
+            .and_then(|b| <[u8; 8]>::try_from(*b).ok())
+            .map(i64::from_le_bytes)
+            .ok_or(mssf_core::ErrorCode::E_UNEXPECTED)?;
+        info!("pump_copy_stream: copied lsn {lsn}");
+        state.apply(lsn);
+    }
+    Ok(())
+}
+
+/// Applies the operations of the replication stream until the stream ends.
+async fn pump_replication_stream(
+    stream: OperationStream,
+    state: CounterState,
+) -> mssf_core::Result<()> {
+    while let Some(op) = stream.next(None).await? {
+        info!("pump_replication_stream: lsn {}", op.sequence_number());
+        state.apply(op.sequence_number());
+    }
+    Ok(())
 }
 
 impl StatefulServiceFactory for Factory {
@@ -238,7 +329,12 @@ impl StatefulServiceFactory for Factory {
             self.replication_port,
             self.hostname.clone(),
         );
-        let replica = Replica::new(self.replication_port, self.hostname.clone(), svc);
+        let replica = Replica::new(
+            self.replication_port,
+            self.hostname.clone(),
+            svc,
+            self.rt.clone(),
+        );
         Ok(replica)
     }
 }
@@ -248,15 +344,70 @@ pub struct Replica {
     hostname_: WString,
     svc: Service,
     ctx: ReplicaCtx,
+    rt: TokioExecutor,
+    state: CounterState,
+    state_replicator: Mutex<Option<StateReplicator>>,
+    role: Mutex<ReplicaRole>,
 }
 
 impl Replica {
-    pub fn new(port: u32, hostname: WString, svc: Service) -> Replica {
+    pub fn new(port: u32, hostname: WString, svc: Service, rt: TokioExecutor) -> Replica {
         Replica {
             port_: port,
             hostname_: hostname,
             svc,
             ctx: ReplicaCtx::empty(),
+            rt,
+            state: CounterState::default(),
+            state_replicator: Mutex::new(None),
+            role: Mutex::new(ReplicaRole::None),
+        }
+    }
+
+    // The primary replicates an operation,
+    // and secondaries apply the copy and replication streams.
+    fn start_replication(&self, prev_role: &ReplicaRole, new_role: &ReplicaRole) {
+        let Some(sr) = self.state_replicator.lock().unwrap().clone() else {
+            return;
+        };
+        let state = self.state.clone();
+        match new_role {
+            ReplicaRole::Primary => {
+                self.rt.get_ref().spawn(async move {
+                    let data = OperationData::from(vec![b"hello".to_vec()]);
+                    match sr.replicate(data, None).await {
+                        Ok(lsn) => {
+                            info!("replicated lsn {lsn}");
+                            state.apply(lsn);
+                        }
+                        Err(e) => error!("replicate failed {e}"),
+                    }
+                });
+            }
+            // A new secondary is built from the copy stream first.
+            ReplicaRole::IdleSecondary => {
+                self.rt.get_ref().spawn(async move {
+                    let res = async {
+                        pump_copy_stream(sr.get_copy_stream()?, state.clone()).await?;
+                        pump_replication_stream(sr.get_replication_stream()?, state).await
+                    };
+                    if let Err(e) = res.await {
+                        error!("pump streams failed {e}");
+                    }
+                });
+            }
+            // A demoted primary starts to receive the replication stream.
+            ReplicaRole::ActiveSecondary if *prev_role == ReplicaRole::Primary => {
+                self.rt.get_ref().spawn(async move {
+                    let res = async {
+                        pump_replication_stream(sr.get_replication_stream()?, state).await
+                    };
+                    if let Err(e) = res.await {
+                        error!("pump replication stream failed {e}");
+                    }
+                });
+            }
+            _ => {}
         }
     }
 }
@@ -314,11 +465,18 @@ impl StatefulServiceReplica for Replica {
             self.ctx.get_trace_read_write_status()
         );
         self.svc.start_loop_in_background(partition);
-        Ok(AppFabricReplicator::new(
-            self.port_,
-            self.hostname_.clone(),
-            self.ctx.clone(),
-        ))
+        let settings = ReplicatorSettings {
+            flags: FABRIC_REPLICATOR_ADDRESS.0 as u32,
+            replicator_address: WString::from(get_addr(self.port_, self.hostname_.clone())),
+            ..Default::default()
+        };
+        let (state_replicator, primary) =
+            partition.create_replicator(self.state.clone(), Some(&settings), self.rt.clone())?;
+        self.state_replicator
+            .lock()
+            .unwrap()
+            .replace(state_replicator);
+        Ok(AppFabricReplicator::new(primary, self.ctx.clone()))
     }
     async fn change_role(
         &self,
@@ -332,6 +490,8 @@ impl StatefulServiceReplica for Replica {
         if newrole == ReplicaRole::Primary {
             info!("primary {:?}", self.svc.tcp_port);
         }
+        let prev_role = std::mem::replace(&mut *self.role.lock().unwrap(), newrole.clone());
+        self.start_replication(&prev_role, &newrole);
         // return the address
         let addr = get_addr(self.port_, self.hostname_.clone());
         let str_res = WString::from(addr);
@@ -19,8 +19,10 @@ use mssf_core::{
         DeployedServiceReplicaDetailQueryResultValue, NamedPartitionSchemeDescription,
         NamedRepartitionDescription, PartitionLoadInformation,
         PartitionLoadInformationQueryDescription, QueryServiceReplicaStatus, ReplicaRole,
-        RestartReplicaDescription, ServiceDescription, ServiceNotificationFilterDescription,
-        ServiceNotificationFilterFlags, ServicePartitionAccessStatus, ServicePartitionInformation,
+        ReplicatorStatus, RestartReplicaDescription, ServiceDescription,
+        ServiceLoadMetricDescription, ServiceLoadMetricWeight,
+        ServiceNotificationFilterDescription, ServiceNotificationFilterFlags,
+        ServicePartitionAccessStatus, ServicePartitionInformation,
         ServicePartitionQueryDescription, ServicePartitionQueryResult, ServicePartitionStatus,
         ServiceReplicaQueryDescription, ServiceReplicaQueryResult, ServiceUpdateDescription,
         SingletonPartitionInfomation, StatefulServiceDescription,
@@ -57,6 +59,7 @@ impl TestClient {
         let desc = ServicePartitionQueryDescription {
             service_name: self.service_uri.clone(),
             partition_id_filter: None,
+            continuation_token: None,
         };
         let list = qc
             .get_partition_list(&desc, self.timeout, None)
@@ -103,6 +106,7 @@ impl TestClient {
         let desc = ServiceReplicaQueryDescription {
             partition_id,
             replica_id_or_instance_id_filter: None,
+            continuation_token: None,
         };
         let replicas = qc
             .get_replica_list(&desc, self.timeout, None)
@@ -417,6 +421,19 @@ async fn test_partition_info() {
     // check write ad read status of primary is GRANTED
     assert_eq!(result.write_status, ServicePartitionAccessStatus::Granted);
     assert_eq!(result.read_status, ServicePartitionAccessStatus::Granted);
+    // primary replicator tracks the progress of the secondaries
+    match &result.replicator_status {
+        ReplicatorStatus::Primary(status) => {
+            assert!(status.replication_queue_status.is_some());
+            assert!(
+                status
+                    .remote_replicators
+                    .iter()
+                    .any(|r| r.replica_id == s1.replica_id)
+            );
+        }
+        other => panic!("unexpected replicator status {other:?}"),
+    }
 
     // check write and read status of secondary is NOT_PRIMARY
     let deployed_replica_detail = tc
@@ -432,6 +449,10 @@ async fn test_partition_info() {
         ServicePartitionAccessStatus::NotPrimary
     );
     assert_eq!(result.read_status, ServicePartitionAccessStatus::NotPrimary);
+    assert!(matches!(
+        result.replicator_status,
+        ReplicatorStatus::ActiveSecondary(_)
+    ));
 }
 
 pub struct TestCreateUpdateClient {
@@ -466,16 +487,48 @@ impl TestCreateUpdateClient {
                 mssf_core::types::ServicePackageActivationMode::SharedProcess,
             )
             .with_min_replica_set_size(replica_count.unwrap_or(1))
-            .with_target_replica_set_size(replica_count.unwrap_or(1)),
+            .with_target_replica_set_size(replica_count.unwrap_or(1))
+            .with_load_metrics(vec![
+                ServiceLoadMetricDescription::new(
+                    WString::from("MyMetric"),
+                    ServiceLoadMetricWeight::Medium,
+                )
+                .with_default_load(2, 1),
+            ]),
         );
         // Run client operation on separate task to ensure that the api is task safe.
         println!("creating service {service_name:?}");
         let sm = self.fc.get_service_manager().clone();
         let timeout = self.timeout;
-        tokio::spawn(async move { sm.create_service(&desc, timeout, None).await })
+        let desc2 = desc.clone();
+        tokio::spawn(async move { sm.create_service(&desc2, timeout, None).await })
             .await
             .expect("task panicked")
             .expect("create failed");
+
+        // Read back the description and check the fields that were set.
+        let actual = self
+            .fc
+            .get_service_manager()
+            .get_service_description(service_name, self.timeout, None)
+            .await
+            .expect("get service description failed");
+        let (ServiceDescription::Stateful(expected), ServiceDescription::Stateful(actual)) =
+            (&desc, &actual)
+        else {
+            panic!("not stateful: {actual:?}");
+        };
+        assert_eq!(actual.service_name(), expected.service_name());
+        assert_eq!(actual.partition_scheme(), expected.partition_scheme());
+        assert_eq!(
+            actual.target_replica_set_size(),
+            expected.target_replica_set_size()
+        );
+        assert_eq!(
+            actual.min_replica_set_size(),
+            expected.min_replica_set_size()
+        );
+        assert_eq!(actual.load_metrics(), expected.load_metrics());
     }
 
     pub(crate) async fn delete_service(&self, service_name: &Uri) {
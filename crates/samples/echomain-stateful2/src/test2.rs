@@ -22,6 +22,7 @@ async fn restart_primary(uri: &Uri, fc: &FabricClient) {
     let desc = mssf_core::types::ServicePartitionQueryDescription {
         service_name: uri.clone().0,
         partition_id_filter: None,
+        continuation_token: None,
     };
 
     let ptt = q.get_partition_list(&desc, sm.timeout, None).await.unwrap();
@@ -40,6 +41,7 @@ async fn restart_primary(uri: &Uri, fc: &FabricClient) {
     let desc = mssf_core::types::ServiceReplicaQueryDescription {
         partition_id: partition.id,
         replica_id_or_instance_id_filter: None,
+        continuation_token: None,
     };
     let replicas = q
         .get_replica_list(&desc, sm.timeout, None)
@@ -195,6 +197,19 @@ async fn test_resolve_notification() {
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
     };
 
+    // Register for push based resolution changes of the partition.
+    let (change_tx, change_rx) = std::sync::mpsc::channel();
+    let change_handle = fc
+        .get_service_manager()
+        .register_service_partition_resolution_change_handler(
+            &uri,
+            &PartitionKeyType::None,
+            move |res: mssf_core::Result<ResolvedServicePartition>| {
+                let _ = change_tx.send(res);
+            },
+        )
+        .unwrap();
+
     // Trigger a failover
     tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     restart_primary(&uri, &fc).await;
@@ -209,6 +224,15 @@ async fn test_resolve_notification() {
     restart_primary(&uri, &fc).await;
     let _ = resolve_until_change(&srv, &uri, rsp4, false).await;
 
+    // The failovers are pushed to the change handler.
+    drop(change_handle);
+    let changes = change_rx
+        .try_iter()
+        .filter_map(|res| res.ok())
+        .collect::<Vec<_>>();
+    assert!(!changes.is_empty());
+    tracing::info!("Received {} resolution changes", changes.len());
+
     // Unregister the notification filter.
     fc.get_service_manager()
         .unregister_service_notification_filter(filter_id, sm.timeout, None)
@@ -15,10 +15,19 @@ use mssf_core::{
         },
     },
     types::{
+        ApplicationHealthQueryDescription, ApplicationQueryDescription, ApplicationStatus,
+        ClusterHealthChunkQueryDescription, ClusterHealthQueryDescription,
+        DeployedCodePackageQueryDescription, DeployedServicePackageQueryDescription,
+        DeployedServiceReplicaQueryDescription, DeployedServiceReplicaQueryResult, HealthState,
+        HealthStateFilter, NodeHealthQueryDescription, NodeHealthStateFilter,
+        NodeLoadInformationQueryDescription, NodeQueryDescription,
+        PagedApplicationTypeQueryDescription, PagedDeployedApplicationQueryDescription,
+        PagedQueryDescription, PropertyBatchOperation, PropertyValue, PutCustomPropertyOperation,
         QueryServiceReplicaStatus, RemoveReplicaDescription, ServiceNotificationFilterDescription,
         ServiceNotificationFilterFlags, ServicePartitionInformation,
         ServicePartitionQueryDescription, ServicePartitionQueryResult, ServicePartitionStatus,
-        ServiceReplicaQueryDescription, ServiceReplicaQueryResult, SingletonPartitionInfomation,
+        ServiceQueryDescription, ServiceQueryResult, ServiceReplicaQueryDescription,
+        ServiceReplicaQueryResult, ServiceTypeQueryDescription, SingletonPartitionInfomation,
         StatelessServiceInstanceQueryResult, StatelessServicePartitionQueryResult, Uri,
     },
 };
@@ -53,6 +62,7 @@ impl EchoTestClient {
         let desc = ServicePartitionQueryDescription {
             service_name: self.service_uri.clone(),
             partition_id_filter: None,
+            continuation_token: None,
         };
         let list = qc
             .get_partition_list(&desc, self.timeout, None)
@@ -80,6 +90,7 @@ impl EchoTestClient {
         let desc = ServiceReplicaQueryDescription {
             partition_id,
             replica_id_or_instance_id_filter: None,
+            continuation_token: None,
         };
         let replicas = qc.get_replica_list(&desc, self.timeout, None).await?;
         let replica_op = replicas.iter().next(); // only one replica
@@ -528,3 +539,467 @@ async fn test_property_client() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_property_batch() {
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let pc = fc.get_property_manager();
+    let svc_uri = Uri::from("fabric:/EchoApp/EchoAppService");
+    let property_name = WString::from("test_property_batch");
+    let timeout = Duration::from_secs(5);
+    delete_property_if_exist(pc, &svc_uri, &property_name, timeout).await;
+
+    // Create the property only if it does not exist.
+    let batch = vec![
+        PropertyBatchOperation::CheckExists {
+            property_name: property_name.clone(),
+            exists: false,
+        },
+        PropertyBatchOperation::Put {
+            property_name: property_name.clone(),
+            value: PropertyValue::WString(WString::from("owner1")),
+        },
+        PropertyBatchOperation::Get {
+            property_name: property_name.clone(),
+            include_value: true,
+        },
+    ];
+    let res = pc
+        .submit_property_batch(&svc_uri, &batch, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(res.get_failed_operation_index(), None);
+    let value = res.get_property(2).unwrap();
+    assert_eq!(
+        value.get_value_as_wstring().unwrap(),
+        WString::from("owner1")
+    );
+    let (meta, _) = value.get_named_property();
+
+    // Running the same batch again fails on the existence check.
+    let res = pc
+        .submit_property_batch(&svc_uri, &batch, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(res.get_failed_operation_index(), Some(0));
+
+    // Check and set with the sequence number.
+    let batch = vec![
+        PropertyBatchOperation::CheckSequence {
+            property_name: property_name.clone(),
+            sequence_number: meta.sequence_number,
+        },
+        PropertyBatchOperation::Put {
+            property_name: property_name.clone(),
+            value: PropertyValue::WString(WString::from("owner2")),
+        },
+    ];
+    let res = pc
+        .submit_property_batch(&svc_uri, &batch, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(res.get_failed_operation_index(), None);
+    // The sequence number has moved, so the stale check fails.
+    let res = pc
+        .submit_property_batch(&svc_uri, &batch, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(res.get_failed_operation_index(), Some(0));
+
+    // The property shows up in the enumeration of the name.
+    let properties = pc
+        .enumerate_all_properties(&svc_uri, timeout, None)
+        .await
+        .unwrap();
+    let (_, value) = properties
+        .iter()
+        .find(|(meta, _)| meta.property_name == property_name)
+        .unwrap();
+    assert_eq!(*value, PropertyValue::WString(WString::from("owner2")));
+
+    let batch = vec![
+        PropertyBatchOperation::CheckValue {
+            property_name: property_name.clone(),
+            value: PropertyValue::WString(WString::from("owner2")),
+        },
+        PropertyBatchOperation::Delete {
+            property_name: property_name.clone(),
+        },
+    ];
+    let res = pc
+        .submit_property_batch(&svc_uri, &batch, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(res.get_failed_operation_index(), None);
+}
+
+#[tokio::test]
+async fn test_custom_property() {
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let pc = fc.get_property_manager();
+    let svc_uri = Uri::from("fabric:/EchoApp/EchoAppService");
+    let property_name = WString::from("test_property_custom");
+    let timeout = Duration::from_secs(5);
+    delete_property_if_exist(pc, &svc_uri, &property_name, timeout).await;
+
+    let mut op = PutCustomPropertyOperation {
+        property_name: property_name.clone(),
+        value: PropertyValue::Binary(vec![1, 2, 3]),
+        custom_type_id: WString::from("schema_v1"),
+        sequence_number: None,
+    };
+    pc.put_custom_property(&svc_uri, &op, timeout, None)
+        .await
+        .unwrap();
+    let meta = pc
+        .get_property_metadata(&svc_uri, &property_name, timeout, None)
+        .await
+        .unwrap()
+        .get_metadata()
+        .unwrap();
+    assert_eq!(meta.custom_type_id, Some(WString::from("schema_v1")));
+
+    // Update with the current sequence number.
+    op.custom_type_id = WString::from("schema_v2");
+    op.sequence_number = Some(meta.sequence_number);
+    pc.put_custom_property(&svc_uri, &op, timeout, None)
+        .await
+        .unwrap();
+    let value = pc
+        .get_property(&svc_uri, &property_name, timeout, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        value.get_value().unwrap(),
+        PropertyValue::Binary(vec![1, 2, 3])
+    );
+    let (meta2, _) = value.get_named_property();
+    assert_eq!(meta2.custom_type_id, Some(WString::from("schema_v2")));
+
+    // The stale sequence number is rejected.
+    let err = pc
+        .put_custom_property(&svc_uri, &op, timeout, None)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.try_as_fabric_error_code().unwrap(),
+        ErrorCode::FABRIC_E_PROPERTY_CHECK_FAILED
+    );
+
+    pc.delete_property(&svc_uri, &property_name, timeout, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_health_query() {
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let hc = fc.get_health_manager();
+    let timeout = Duration::from_secs(5);
+
+    let cluster = hc
+        .get_cluster_health(
+            &ClusterHealthQueryDescription {
+                nodes_filter: Some(HealthStateFilter::All),
+                applications_filter: Some(HealthStateFilter::All),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_ne!(cluster.aggregated_health_state, HealthState::Invalid);
+    assert!(!cluster.node_health_states.is_empty());
+    let app_uri = Uri::from("fabric:/EchoApp");
+    assert!(
+        cluster
+            .application_health_states
+            .iter()
+            .any(|a| a.application_name == app_uri)
+    );
+
+    let node_name = cluster.node_health_states[0].node_name.clone();
+    let node = hc
+        .get_node_health(
+            &NodeHealthQueryDescription {
+                node_name: node_name.clone(),
+                events_filter: Some(HealthStateFilter::All),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node.node_name, node_name);
+    // System components always report on the node.
+    assert!(!node.health_events.is_empty());
+
+    let app = hc
+        .get_application_health(
+            &ApplicationHealthQueryDescription {
+                application_name: app_uri.clone(),
+                services_filter: Some(HealthStateFilter::All),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(app.application_name, app_uri);
+    let svc_uri = Uri::from(ECHO_SVC_URI);
+    assert!(
+        app.service_health_states
+            .iter()
+            .any(|s| s.service_name == svc_uri)
+    );
+
+    let chunk = hc
+        .get_cluster_health_chunk(
+            &ClusterHealthChunkQueryDescription {
+                node_filters: vec![NodeHealthStateFilter {
+                    health_state_filter: HealthStateFilter::All,
+                    node_name_filter: Some(node_name.clone()),
+                }],
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(chunk.node_health_state_chunks.items.len(), 1);
+    assert_eq!(chunk.node_health_state_chunks.items[0].node_name, node_name);
+}
+
+#[tokio::test]
+async fn test_application_queries() {
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let qc = fc.get_query_manager();
+    let timeout = Duration::from_secs(5);
+    let app_uri = Uri::from("fabric:/EchoApp");
+
+    let apps = qc
+        .get_application_list(
+            &ApplicationQueryDescription {
+                application_name_filter: Some(app_uri.clone()),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    let app = apps.iter().next().unwrap();
+    assert_eq!(app.application_name, app_uri);
+    assert_eq!(app.status, ApplicationStatus::Ready);
+
+    let app_types = qc
+        .get_application_type_list(
+            &PagedApplicationTypeQueryDescription {
+                application_type_name_filter: Some(app.application_type_name.clone()),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        app_types
+            .iter()
+            .any(|t| t.application_type_version == app.application_type_version)
+    );
+
+    let services = qc
+        .get_service_list(
+            &ServiceQueryDescription {
+                application_name: app_uri.clone(),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    let svc_uri = Uri::from(ECHO_SVC_URI);
+    let svc = services
+        .iter()
+        .find_map(|s| match s {
+            ServiceQueryResult::Stateless(s) if s.service_name == svc_uri => Some(s),
+            _ => None,
+        })
+        .unwrap();
+
+    let service_types = qc
+        .get_service_type_list(
+            &ServiceTypeQueryDescription {
+                application_type_name: app.application_type_name.clone(),
+                application_type_version: app.application_type_version.clone(),
+                service_type_name_filter: None,
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        service_types
+            .iter()
+            .any(|t| t.service_type_name == svc.service_type_name && !t.is_stateful)
+    );
+
+    // The echo app runs on every node, so the first node has it deployed.
+    let node_name = qc
+        .get_node_list(&Default::default(), timeout, None)
+        .await
+        .unwrap()
+        .iter()
+        .next()
+        .unwrap()
+        .name;
+    let deployed_apps = qc
+        .get_deployed_application_list(
+            &PagedDeployedApplicationQueryDescription {
+                node_name: node_name.clone(),
+                application_name_filter: Some(app_uri.clone()),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(deployed_apps.iter().count(), 1);
+    let packages = qc
+        .get_deployed_service_package_list(
+            &DeployedServicePackageQueryDescription {
+                node_name: node_name.clone(),
+                application_name: app_uri.clone(),
+                service_manifest_name_filter: None,
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(packages.iter().next().is_some());
+    let code_packages = qc
+        .get_deployed_code_package_list(
+            &DeployedCodePackageQueryDescription {
+                node_name: node_name.clone(),
+                application_name: app_uri.clone(),
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(code_packages.iter().all(|c| c.entry_point.is_some()));
+    let replicas = qc
+        .get_deployed_replica_list(
+            &DeployedServiceReplicaQueryDescription {
+                node_name: node_name.clone(),
+                application_name: app_uri,
+                ..Default::default()
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(
+        replicas
+            .iter()
+            .any(|r| matches!(r, DeployedServiceReplicaQueryResult::Stateless(_)))
+    );
+
+    let node_load = qc
+        .get_node_load_information(
+            &NodeLoadInformationQueryDescription {
+                node_name: node_name.clone(),
+            },
+            timeout,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node_load.get_node_name(), node_name);
+    let cluster_load = qc
+        .get_cluster_load_information(timeout, None)
+        .await
+        .unwrap();
+    // Default metrics are always present.
+    assert!(cluster_load.iter().next().is_some());
+
+    let code_versions = qc
+        .get_provisioned_fabric_code_version_list(&Default::default(), timeout, None)
+        .await
+        .unwrap();
+    assert!(code_versions.iter().next().is_some());
+}
+
+#[tokio::test]
+async fn test_query_streams() {
+    let fc = FabricClient::builder()
+        .with_connection_strings(vec![WString::from("localhost:19000")])
+        .build()
+        .unwrap();
+    let qc = fc.get_query_manager();
+    let timeout = Duration::from_secs(5);
+
+    let node_count = qc
+        .get_node_list(&Default::default(), timeout, None)
+        .await
+        .unwrap()
+        .iter()
+        .count();
+    // One node per page forces the stream to follow continuation tokens.
+    let desc = NodeQueryDescription {
+        paged_query: PagedQueryDescription {
+            max_results: Some(1),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut nodes = qc.get_node_list_stream(desc, timeout, None);
+    let mut streamed = Vec::new();
+    while let Some(node) = nodes.next().await {
+        streamed.push(node.unwrap().name);
+    }
+    assert_eq!(streamed.len(), node_count);
+
+    let mut partitions = qc.get_partition_list_stream(
+        ServicePartitionQueryDescription {
+            service_name: WString::from(ECHO_SVC_URI),
+            partition_id_filter: None,
+            continuation_token: None,
+        },
+        timeout,
+        None,
+    );
+    let mut partition_count = 0;
+    while let Some(partition) = partitions.next().await {
+        assert!(matches!(
+            partition.unwrap(),
+            ServicePartitionQueryResult::Stateless(_)
+        ));
+        partition_count += 1;
+    }
+    assert_eq!(partition_count, 1);
+}
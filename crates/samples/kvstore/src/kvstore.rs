@@ -2,7 +2,7 @@ use std::{cell::Cell, sync::Mutex};
 
 use mssf_com::{
     FabricRuntime::{
-        IFabricKeyValueStoreReplica2, IFabricStatefulServiceReplica, IFabricStoreEventHandler,
+        IFabricKeyValueStoreReplica6, IFabricStatefulServiceReplica, IFabricStoreEventHandler,
     },
     FabricTypes::FABRIC_REPLICATOR_ADDRESS,
 };
@@ -12,10 +12,15 @@ use mssf_core::{
         executor::BoxedCancelToken,
         stateful::{PrimaryReplicator, StatefulServiceFactory, StatefulServiceReplica},
         stateful_proxy::{StatefulServicePartition, StatefulServiceReplicaProxy},
-        store::{DummyStoreEventHandler, create_com_key_value_store_replica},
+        store::{
+            DummyStoreEventHandler, StorePostBackupHandler, create_com_key_value_store_replica,
+        },
         store_proxy::KVStoreProxy,
     },
-    types::{LocalStoreKind, OpenMode, ReplicaRole, ReplicatorSettings},
+    types::{
+        KeyValueStoreRestoreSettings, LocalStoreKind, OpenMode, ReplicaRole, ReplicatorSettings,
+        StoreBackupInfo, StoreBackupOption,
+    },
 };
 use mssf_util::tokio::TokioExecutor;
 use tokio::{
@@ -112,7 +117,7 @@ pub struct Service {
 }
 
 impl Service {
-    pub fn new(com: IFabricKeyValueStoreReplica2, rt: TokioExecutor) -> Service {
+    pub fn new(com: IFabricKeyValueStoreReplica6, rt: TokioExecutor) -> Service {
         Service {
             kvproxy: KVStoreProxy::new(com),
             rt,
@@ -123,9 +128,14 @@ impl Service {
     pub fn start_loop(&self) {
         let (tx, mut rx) = oneshot::channel::<()>();
         let kv = self.kvproxy.clone();
+        let rt = self.rt.clone();
         self.stop();
         self.tx.lock().unwrap().set(Some(tx));
         self.rt.get_ref().spawn(async move {
+            match Self::backup_restore(&kv, rt).await {
+                Ok(_) => info!("backup_restore success"),
+                Err(e) => info!("backup_restore error : {}", e),
+            }
             let mut counter = 0;
             loop {
                 info!("Service::run_single: {}", counter);
@@ -158,7 +168,7 @@ impl Service {
 
     async fn run_single(kv: &KVStoreProxy) -> mssf_core::Result<()> {
         // add kv
-        let seq;
+        let mut seq;
         {
             let tx = kv.create_transaction()?;
             let key = WString::from("mykey");
@@ -167,6 +177,24 @@ impl Service {
             seq = tx.commit(1000, None).await?;
         }
 
+        // update kv and scan keys by prefix
+        {
+            let tx = kv.create_transaction()?;
+            let key = WString::from("mykey");
+            let value = String::from("myvalue2");
+            kv.update(&tx, key.as_wide(), value.as_bytes(), seq)?;
+            let prefix = WString::from("my");
+            for item in kv.enumerate_by_key(&tx, prefix.as_wide(), true)? {
+                let item = item?;
+                info!(
+                    "key: {}, seq: {}",
+                    String::from_utf16_lossy(item.key()),
+                    item.sequence_number()
+                );
+            }
+            seq = tx.commit(1000, None).await?;
+        }
+
         // remove kv
         {
             let tx = kv.create_transaction()?;
@@ -178,6 +206,74 @@ impl Service {
     }
 }
 
+impl Service {
+    // Backs up a key, overwrites it, and restores the backup.
+    async fn backup_restore(kv: &KVStoreProxy, rt: TokioExecutor) -> mssf_core::Result<()> {
+        let key = WString::from("backupkey");
+        let seq;
+        {
+            let tx = kv.create_transaction()?;
+            kv.add(&tx, key.as_wide(), b"v1")?;
+            seq = tx.commit(1000, None).await?;
+        }
+
+        // A full backup needs an empty dir.
+        let dir = std::env::temp_dir().join(format!("kvstore_backup_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).map_err(|_| mssf_core::ErrorCode::E_FAIL)?;
+        let backup_dir = WString::from(dir.to_string_lossy().as_ref());
+        kv.backup_with_handler(
+            &backup_dir,
+            StoreBackupOption::Full,
+            BackupHandler,
+            rt,
+            None,
+        )
+        .await?;
+
+        {
+            let tx = kv.create_transaction()?;
+            kv.update(&tx, key.as_wide(), b"v2", seq)?;
+            tx.commit(1000, None).await?;
+        }
+
+        let settings = KeyValueStoreRestoreSettings {
+            inline_reopen: true,
+            // The store is newer than the backup.
+            enable_lsn_check: false,
+        };
+        kv.restore(&backup_dir, Some(&settings), None).await?;
+
+        // Reads back the backed up value.
+        let tx = kv.create_transaction()?;
+        let item = kv.get(&tx, key.as_wide())?;
+        info!("restored value: {}", String::from_utf8_lossy(item.val()));
+        if item.val() != b"v1" {
+            return Err(mssf_core::ErrorCode::E_UNEXPECTED.into());
+        }
+        kv.remove(&tx, key.as_wide(), item.sequence_number())?;
+        tx.commit(1000, None).await?;
+        Ok(())
+    }
+}
+
+// Post backup handler, which would typically upload the backup to remote storage.
+struct BackupHandler;
+
+impl StorePostBackupHandler for BackupHandler {
+    async fn post_backup(
+        &self,
+        info: StoreBackupInfo,
+        _: BoxedCancelToken,
+    ) -> mssf_core::Result<bool> {
+        info!(
+            "BackupHandler::post_backup {:?} in {}",
+            info.backup_option, info.backup_folder
+        );
+        Ok(true)
+    }
+}
+
 impl StatefulServiceReplica for Replica {
     async fn open(
         &self,